@@ -0,0 +1,77 @@
+#!/usr/bin/env python3
+"""Regenerate the bundled offline static data snapshot.
+
+Downloads champion, item and rune data from DDragon for the latest patch and
+writes `resources/static-snapshot.json.gz`, which is embedded in the binary
+and used until live DDragon data has been loaded.
+
+Usage: python3 scripts/update_static_snapshot.py [version]
+"""
+
+import gzip
+import json
+import sys
+import urllib.request
+from pathlib import Path
+
+DDRAGON = "https://ddragon.leagueoflegends.com"
+OUTPUT = Path(__file__).resolve().parent.parent / "resources" / "static-snapshot.json.gz"
+
+
+def fetch(path):
+    with urllib.request.urlopen(f"{DDRAGON}{path}", timeout=30) as response:
+        return json.load(response)
+
+
+def check(snapshot):
+    """Refuse to write an incomplete snapshot (truncated download, format change)."""
+    champions = snapshot["champions"]["data"].values()
+    missing = [c["id"] for c in champions if not c["name"] or not c["title"]]
+    if missing:
+        sys.exit(f"Champions without a name or title: {', '.join(missing)}")
+    if any(not entry["name"] for entry in snapshot["items"] + snapshot["runes"]):
+        sys.exit("Items or runes without a name")
+
+
+def main():
+    version = sys.argv[1] if len(sys.argv) > 1 else fetch("/api/versions.json")[0]
+    base = f"/cdn/{version}/data/en_US"
+
+    champions = fetch(f"{base}/champion.json")
+    items = fetch(f"{base}/item.json")
+    runes = fetch(f"{base}/runesReforged.json")
+
+    snapshot = {
+        "version": version,
+        "champions": {
+            "version": version,
+            "data": {
+                key: {k: champ[k] for k in ("id", "key", "name", "title")}
+                for key, champ in champions["data"].items()
+            },
+        },
+        "items": sorted(
+            (
+                {"id": int(item_id), "name": item["name"]}
+                for item_id, item in items["data"].items()
+                if item.get("gold", {}).get("purchasable", True)
+            ),
+            key=lambda item: item["id"],
+        ),
+        "runes": [
+            {"id": perk["id"], "name": perk["name"], "icon": perk["icon"]}
+            for style in runes
+            for perk in [style] + [r for slot in style["slots"] for r in slot["runes"]]
+        ],
+    }
+
+    check(snapshot)
+    OUTPUT.parent.mkdir(parents=True, exist_ok=True)
+    payload = json.dumps(snapshot, ensure_ascii=False, separators=(",", ":")).encode("utf-8")
+    with gzip.open(OUTPUT, "wb", compresslevel=9) as f:
+        f.write(payload)
+    print(f"Wrote {OUTPUT} ({version}, {len(snapshot['champions']['data'])} champions)")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,247 @@
+//! ARAM champion select: rerolls and bench swaps.
+//!
+//! In ARAM the champion is assigned, then the local player can reroll it or
+//! swap it with one from the bench. On each champion select poll the game
+//! watcher hands the session to `on_champ_select`, which sends
+//! `champion-changed` when the local player's champion was replaced. The
+//! prefetcher already drops the build of the old champion when the pick
+//! changes; the frontend re-imports for the new champion when auto-import is
+//! on, and offers to otherwise.
+//!
+//! `get_aram_bench` lists the benched champions, so the UI can show their
+//! builds and win rates before the user swaps.
+
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+use crate::events::AppEvent;
+use crate::lcu::{self, ChampionSelectSession};
+use crate::{champions, prefetch, CommandError};
+
+/// How the local player got a new champion
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ChangeReason {
+    /// Rolled a random champion
+    Reroll,
+    /// Took a champion from the bench
+    Bench,
+    /// Traded with a teammate
+    Trade,
+}
+
+/// `champion-changed` payload
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChampionChange {
+    pub previous_champion_id: i64,
+    pub champion_id: i64,
+    /// DDragon key (e.g., "MonkeyKing"), when known
+    pub champion_key: Option<String>,
+    /// Role used for the build of the new champion
+    pub role: String,
+    pub reason: ChangeReason,
+}
+
+/// A champion on the bench
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchedChampion {
+    pub champion_id: i64,
+    /// DDragon key (e.g., "MonkeyKing"), when known
+    pub champion_key: Option<String>,
+    pub is_priority: bool,
+}
+
+/// Bench of the champion select session
+#[derive(Debug, Clone, Default, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AramBench {
+    /// False outside ARAM-like modes (no bench)
+    pub enabled: bool,
+    pub champions: Vec<BenchedChampion>,
+    pub rerolls_remaining: i64,
+}
+
+/// Local player's champion and rerolls at the last poll
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct AramState {
+    champion_id: i64,
+    rerolls_remaining: i64,
+}
+
+/// State of the last poll (None outside an ARAM champion select)
+static LAST_STATE: Mutex<Option<AramState>> = Mutex::new(None);
+
+fn state_of(session: &ChampionSelectSession) -> Option<AramState> {
+    if !session.bench_enabled || session.is_spectating || session.local_champion_id <= 0 {
+        return None;
+    }
+    Some(AramState {
+        champion_id: session.local_champion_id,
+        rerolls_remaining: session.rerolls_remaining,
+    })
+}
+
+/// Change of the local player's champion since `previous` (None on the first poll)
+fn champion_change(previous: Option<AramState>, session: &ChampionSelectSession) -> Option<ChampionChange> {
+    let previous = previous?;
+    let current = state_of(session)?;
+    if current.champion_id == previous.champion_id {
+        return None;
+    }
+    let pick = prefetch::local_pick(session)?;
+
+    let traded = session
+        .my_team
+        .iter()
+        .flatten()
+        .filter(|member| Some(member.cell_id) != session.local_player_cell_id)
+        .any(|member| member.champion_id == previous.champion_id);
+    let reason = if current.rerolls_remaining < previous.rerolls_remaining {
+        ChangeReason::Reroll
+    } else if traded {
+        ChangeReason::Trade
+    } else {
+        ChangeReason::Bench
+    };
+
+    Some(ChampionChange {
+        previous_champion_id: previous.champion_id,
+        champion_id: pick.champion_id,
+        champion_key: pick.champion_key,
+        role: pick.role,
+        reason,
+    })
+}
+
+/// Benched champions, from `benchChampions` or the older `benchChampionIds`
+fn bench_of(session: &ChampionSelectSession) -> AramBench {
+    if !session.bench_enabled {
+        return AramBench::default();
+    }
+    let benched: Vec<(i64, bool)> = if session.bench_champions.is_empty() {
+        session.bench_champion_ids.iter().map(|&id| (id, false)).collect()
+    } else {
+        session
+            .bench_champions
+            .iter()
+            .map(|champion| (champion.champion_id, champion.is_priority))
+            .collect()
+    };
+    AramBench {
+        enabled: true,
+        champions: benched
+            .into_iter()
+            .filter(|&(champion_id, _)| champion_id > 0)
+            .map(|(champion_id, is_priority)| BenchedChampion {
+                champion_id,
+                champion_key: champions::ddragon_key_for_id(champion_id),
+                is_priority,
+            })
+            .collect(),
+        rerolls_remaining: session.rerolls_remaining.max(0),
+    }
+}
+
+/// Send `champion-changed` when the local player's ARAM champion was replaced
+pub fn on_champ_select(app: &AppHandle, session: &ChampionSelectSession) {
+    let Ok(mut last) = LAST_STATE.lock() else {
+        return;
+    };
+    if let Some(change) = champion_change(*last, session) {
+        log_info!(
+            "[aram] Champion {} replaced by {} ({:?})",
+            change.previous_champion_id,
+            change.champion_id,
+            change.reason
+        );
+        if let Err(e) = AppEvent::ChampionChanged(&change).emit(app) {
+            log_debug!("[aram] {}", e);
+        }
+    }
+    *last = state_of(session);
+}
+
+/// Forget the last champion (leaving champion select)
+pub fn reset() {
+    if let Ok(mut last) = LAST_STATE.lock() {
+        *last = None;
+    }
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the champions on the ARAM bench (`enabled` false in other modes).
+///
+/// # Compliance Note
+/// - Read-only: the bench is shown in the client UI
+#[tauri::command]
+pub async fn get_aram_bench() -> Result<AramBench, CommandError> {
+    let connection = lcu::find_lockfile().await.map_err(CommandError::from)?;
+    let session = lcu::get_champion_select_session(&connection)
+        .await
+        .map_err(CommandError::from)?;
+    Ok(bench_of(&session))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn session(champion_id: i64, teammate_champion_id: i64, rerolls_remaining: i64) -> ChampionSelectSession {
+        let mut session: ChampionSelectSession = serde_json::from_value(json!({
+            "localPlayerCellId": 0,
+            "benchEnabled": true,
+            "rerollsRemaining": rerolls_remaining,
+            "actions": [],
+            "myTeam": [
+                { "cellId": 0, "championId": champion_id },
+                { "cellId": 1, "championId": teammate_champion_id }
+            ]
+        }))
+        .unwrap();
+        (session.local_champion_id, session.is_locked) = session.champion_of(0);
+        session
+    }
+
+    #[test]
+    fn test_champion_change() {
+        let first = session(22, 64, 1);
+        assert_eq!(champion_change(None, &first), None);
+        let previous = state_of(&first);
+        assert_eq!(champion_change(previous, &first), None);
+
+        let reason = |session: &ChampionSelectSession| champion_change(previous, session).map(|c| c.reason);
+        assert_eq!(reason(&session(103, 64, 0)), Some(ChangeReason::Reroll));
+        assert_eq!(reason(&session(103, 64, 1)), Some(ChangeReason::Bench));
+        assert_eq!(reason(&session(64, 22, 1)), Some(ChangeReason::Trade));
+        assert_eq!(champion_change(previous, &session(103, 64, 1)).unwrap().previous_champion_id, 22);
+
+        // Not ARAM: nothing is tracked
+        let mut draft = session(103, 64, 1);
+        draft.bench_enabled = false;
+        assert_eq!(champion_change(previous, &draft), None);
+    }
+
+    #[test]
+    fn test_bench_of() {
+        let mut bench_session = session(22, 64, 2);
+        bench_session.bench_champion_ids = vec![103, 0];
+        let ids = |bench: AramBench| bench.champions.iter().map(|c| c.champion_id).collect::<Vec<_>>();
+        assert_eq!(ids(bench_of(&bench_session)), [103]);
+
+        // The newer format wins
+        bench_session.bench_champions = serde_json::from_value(json!([{ "championId": 1, "isPriority": true }])).unwrap();
+        let bench = bench_of(&bench_session);
+        assert_eq!((bench.rerolls_remaining, bench.champions[0].is_priority), (2, true));
+        assert_eq!(ids(bench), [1]);
+
+        bench_session.bench_enabled = false;
+        assert_eq!(bench_of(&bench_session), AramBench::default());
+    }
+}
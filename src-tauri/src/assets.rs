@@ -0,0 +1,140 @@
+//! Local icon cache served through the `focus-asset://` protocol.
+//!
+//! Champion and item icons are downloaded from DDragon once per patch, stored
+//! in the cache directory next to the other static data, and served to the
+//! webviews from disk. The UI and overlay no longer hit the CDN on every
+//! render, and icons already seen keep working offline.
+//!
+//! URLs are built on the frontend with `convertFileSrc("<kind>/<file>", "focus-asset")`:
+//! - `champion/<name>.png` (any name understood by `get_ddragon_key`)
+//! - `item/<id>.png`
+
+use std::time::Duration;
+use tauri::http::{Response, StatusCode};
+use tauri::AppHandle;
+
+use crate::champions::{self, DDRAGON_BASE};
+use crate::storage;
+
+/// Name of the custom protocol
+pub const ASSET_PROTOCOL: &str = "focus-asset";
+
+/// Kind of icon served by the protocol
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum AssetRequest {
+    Champion(String),
+    Item(u32),
+}
+
+impl AssetRequest {
+    /// Parse a request path: "/champion/Ahri.png", "/item%2F3031.png", ...
+    fn parse(path: &str) -> Option<Self> {
+        let path = urlencoding::decode(path).ok()?;
+        let (kind, file) = path.trim_start_matches('/').split_once('/')?;
+        let name = file.strip_suffix(".png")?;
+
+        // Only plain names: the file name ends up in a path on disk
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return None;
+        }
+
+        match kind {
+            "champion" => Some(AssetRequest::Champion(champions::get_ddragon_key(name)?)),
+            "item" => Some(AssetRequest::Item(name.parse().ok()?)),
+            _ => None,
+        }
+    }
+
+    /// Relative cache path and DDragon URL for the current patch
+    fn locate(&self, version: &str) -> (String, String) {
+        let file = match self {
+            AssetRequest::Champion(key) => format!("champion/{}.png", key),
+            AssetRequest::Item(id) => format!("item/{}.png", id),
+        };
+        (
+            format!("ddragon/{}/img/{}", version, file),
+            format!("{}/{}/img/{}", DDRAGON_BASE, version, file),
+        )
+    }
+}
+
+/// Read an icon from the cache, downloading it on first use
+async fn load_asset(app: &AppHandle, request: &AssetRequest) -> Result<Vec<u8>, String> {
+    let version = champions::ddragon_version();
+    let (relative_path, url) = request.locate(&version);
+    let path = storage::cache_file_path(app, &relative_path)?;
+
+    if let Ok(bytes) = tokio::fs::read(&path).await {
+        return Ok(bytes);
+    }
+
+    let client = crate::proxy::remote_client()
+        .timeout(Duration::from_secs(15))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let bytes = client
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?
+        .error_for_status()
+        .map_err(|e| e.to_string())?
+        .bytes()
+        .await
+        .map_err(|e| e.to_string())?
+        .to_vec();
+
+    storage::write_atomic(&path, &bytes)?;
+    Ok(bytes)
+}
+
+/// Respond to a `focus-asset://` request
+pub async fn serve_asset(app: AppHandle, path: String) -> Response<Vec<u8>> {
+    let result = match AssetRequest::parse(&path) {
+        Some(request) => load_asset(&app, &request).await.map_err(|e| (StatusCode::BAD_GATEWAY, e)),
+        None => Err((StatusCode::NOT_FOUND, format!("Unknown asset: {}", path))),
+    };
+
+    let response = match result {
+        Ok(bytes) => Response::builder()
+            .status(StatusCode::OK)
+            .header("Content-Type", "image/png")
+            .header("Cache-Control", "max-age=86400")
+            .body(bytes),
+        Err((status, message)) => {
+            log_debug!("[assets] {}", message);
+            Response::builder().status(status).body(message.into_bytes())
+        }
+    };
+    response.unwrap_or_else(|_| Response::new(Vec::new()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_asset_requests() {
+        assert_eq!(
+            AssetRequest::parse("/champion/Ahri.png"),
+            Some(AssetRequest::Champion("Ahri".to_string()))
+        );
+        // `convertFileSrc` percent-encodes the whole path
+        assert_eq!(
+            AssetRequest::parse("/champion%2Fmonkeyking.png"),
+            Some(AssetRequest::Champion("MonkeyKing".to_string()))
+        );
+        assert_eq!(AssetRequest::parse("/item/3031.png"), Some(AssetRequest::Item(3031)));
+
+        assert_eq!(AssetRequest::parse("/item/..%2F..%2Fsecret.png"), None);
+        assert_eq!(AssetRequest::parse("/profile/1.png"), None);
+        assert_eq!(AssetRequest::parse("/item/3031.jpg"), None);
+    }
+
+    #[test]
+    fn test_asset_locations() {
+        let (path, url) = AssetRequest::Item(3031).locate("15.1.1");
+        assert_eq!(path, "ddragon/15.1.1/img/item/3031.png");
+        assert_eq!(url, "https://ddragon.leagueoflegends.com/cdn/15.1.1/img/item/3031.png");
+    }
+}
@@ -0,0 +1,280 @@
+//! FocusApi account login.
+//!
+//! Users with a FocusApi account can log in to get personalized builds and
+//! higher rate limits. `login` exchanges the credentials for an access token
+//! and a refresh token; the password is not kept. Tokens stay in the backend:
+//! they are stored in the OS credential store, sent by the FocusApi client as
+//! a bearer token next to the API key, and never returned to the webview.
+//!
+//! Access tokens are short-lived. Before each FocusApi request,
+//! `refresh_if_needed` exchanges the refresh token for a new pair when the
+//! access token is about to expire; a rejected refresh token logs the user
+//! out. Requests are still made with the API key alone when nobody is logged
+//! in.
+
+use serde::{Deserialize, Serialize};
+use std::sync::RwLock;
+
+use crate::sessions::now_unix_secs;
+use crate::{credentials, focus_api, redact};
+
+/// Credential store account holding the session (JSON)
+const SESSION_ACCOUNT: &str = "focus-api-session";
+
+const LOGIN_PATH: &str = "/auth/login";
+const REFRESH_PATH: &str = "/auth/refresh";
+const LOGOUT_PATH: &str = "/auth/logout";
+
+/// Access tokens are refreshed this long before they expire
+const REFRESH_MARGIN_SECS: i64 = 60;
+
+/// Tokens of the logged-in account
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct AuthSession {
+    username: String,
+    access_token: String,
+    refresh_token: String,
+    /// Unix time the access token expires
+    expires_at: i64,
+}
+
+impl AuthSession {
+    fn needs_refresh(&self, now: i64) -> bool {
+        now >= self.expires_at - REFRESH_MARGIN_SECS
+    }
+}
+
+/// Token pair returned by the login and refresh endpoints
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TokenResponse {
+    access_token: String,
+    refresh_token: String,
+    /// Lifetime of the access token, in seconds
+    expires_in: i64,
+    #[serde(default)]
+    username: Option<String>,
+}
+
+/// Account state returned to the frontend (never the tokens)
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountStatus {
+    pub logged_in: bool,
+    pub username: Option<String>,
+}
+
+/// Session loaded from the credential store at startup
+static SESSION: RwLock<Option<AuthSession>> = RwLock::new(None);
+
+/// Held during a refresh so concurrent requests don't spend the refresh token twice
+static REFRESHING: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+/// Session from a token response; `username` is used when the response has none
+fn session_from(response: TokenResponse, username: &str, now: i64) -> Result<AuthSession, String> {
+    if response.access_token.is_empty() || response.refresh_token.is_empty() {
+        return Err("FocusApi returned an empty token".to_string());
+    }
+    Ok(AuthSession {
+        username: response.username.filter(|u| !u.is_empty()).unwrap_or_else(|| username.to_string()),
+        access_token: response.access_token,
+        refresh_token: response.refresh_token,
+        expires_at: now + response.expires_in.max(0),
+    })
+}
+
+fn current_session() -> Option<AuthSession> {
+    SESSION.read().ok().and_then(|session| session.clone())
+}
+
+fn set_session(session: Option<AuthSession>) {
+    if let Some(session) = &session {
+        redact::register_rotating_secret(&session.access_token);
+        redact::register_rotating_secret(&session.refresh_token);
+    }
+    if let Ok(mut stored) = SESSION.write() {
+        *stored = session;
+    }
+}
+
+fn account_status() -> AccountStatus {
+    let username = username();
+    AccountStatus {
+        logged_in: username.is_some(),
+        username,
+    }
+}
+
+/// Whether a FocusApi account is logged in
+pub fn is_logged_in() -> bool {
+    current_session().is_some()
+}
+
+/// Username of the logged-in account
+pub fn username() -> Option<String> {
+    current_session().map(|session| session.username)
+}
+
+/// Access token to send with FocusApi requests (None when logged out or expired)
+pub fn access_token() -> Option<String> {
+    current_session()
+        .filter(|session| session.expires_at > now_unix_secs())
+        .map(|session| session.access_token)
+}
+
+/// Store the session in the credential store, then use it
+async fn save_session(session: AuthSession) -> Result<(), String> {
+    let json = serde_json::to_string(&session).map_err(|e| e.to_string())?;
+    tokio::task::spawn_blocking(move || credentials::write_secret(SESSION_ACCOUNT, &json))
+        .await
+        .map_err(|e| e.to_string())??;
+    set_session(Some(session));
+    Ok(())
+}
+
+/// Forget the session, here and in the credential store
+pub async fn clear_session() -> Result<(), String> {
+    set_session(None);
+    tokio::task::spawn_blocking(|| credentials::delete_secret(SESSION_ACCOUNT))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Load the session from the credential store (spawned at startup)
+pub async fn load_stored_session() {
+    let result = tokio::task::spawn_blocking(|| credentials::read_secret(SESSION_ACCOUNT)).await;
+    match result {
+        Ok(Ok(Some(json))) => match serde_json::from_str::<AuthSession>(&json) {
+            Ok(session) => set_session(Some(session)),
+            Err(e) => log_warn!("[auth] Ignoring invalid stored session: {}", e),
+        },
+        Ok(Ok(None)) => {}
+        Ok(Err(e)) => log_info!("[auth] Could not read the stored session: {}", e),
+        Err(e) => log_warn!("[auth] Credential task failed: {}", e),
+    }
+}
+
+/// POST to an auth endpoint; `Ok(None)` when FocusApi rejects the credentials
+async fn post_tokens<T: Serialize>(path: &str, body: &T) -> Result<Option<TokenResponse>, String> {
+    focus_api::ensure_default_server()?;
+    let response = focus_api::send(focus_api::request_json(reqwest::Method::POST, path, body).map_err(|e| e.to_string())?)
+        .await
+        .map_err(|e| e.to_string())?;
+    let status = response.status();
+    if status == reqwest::StatusCode::UNAUTHORIZED || status == reqwest::StatusCode::FORBIDDEN {
+        return Ok(None);
+    }
+    if !status.is_success() {
+        return Err(format!("FocusApi returned error {}", status.as_u16()));
+    }
+    let tokens = response
+        .json()
+        .await
+        .map_err(|e| format!("Invalid FocusApi token response: {}", e))?;
+    Ok(Some(tokens))
+}
+
+/// Refresh the access token when it is about to expire.
+///
+/// Called before FocusApi requests; failures are logged, not returned, so a
+/// request is still made with the API key alone.
+pub async fn refresh_if_needed() {
+    if !current_session().is_some_and(|s| s.needs_refresh(now_unix_secs())) {
+        return;
+    }
+    let _refreshing = REFRESHING.lock().await;
+    // Another request may have refreshed it while we waited
+    let Some(session) = current_session().filter(|s| s.needs_refresh(now_unix_secs())) else {
+        return;
+    };
+
+    let body = serde_json::json!({ "refreshToken": session.refresh_token });
+    match post_tokens(REFRESH_PATH, &body).await {
+        Ok(Some(tokens)) => {
+            let saved = match session_from(tokens, &session.username, now_unix_secs()) {
+                Ok(refreshed) => save_session(refreshed).await,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = saved {
+                log_warn!("[auth] Could not refresh the session: {}", e);
+            }
+        }
+        Ok(None) => {
+            log_info!("[auth] Session expired, logging out {}", session.username);
+            if let Err(e) = clear_session().await {
+                log_warn!("[auth] Could not delete the stored session: {}", e);
+            }
+        }
+        // Offline: keep the session, the next request tries again
+        Err(e) => log_debug!("[auth] Refresh failed: {}", e),
+    }
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Log in to a FocusApi account.
+///
+/// The password is sent once to FocusApi and never stored.
+#[tauri::command]
+pub async fn login(username: String, password: String) -> Result<AccountStatus, String> {
+    let username = username.trim().to_string();
+    if username.is_empty() || password.is_empty() {
+        return Err("Username and password are required".to_string());
+    }
+    redact::register_secret(&password);
+
+    let body = serde_json::json!({ "username": username, "password": password });
+    let tokens = post_tokens(LOGIN_PATH, &body)
+        .await?
+        .ok_or_else(|| "Invalid username or password".to_string())?;
+    let session = session_from(tokens, &username, now_unix_secs())?;
+    log_info!("[auth] Logged in as {}", session.username);
+    save_session(session).await?;
+    Ok(account_status())
+}
+
+/// Log out, revoking the refresh token on FocusApi when it can be reached.
+#[tauri::command]
+pub async fn logout() -> Result<AccountStatus, String> {
+    if let Some(session) = current_session().filter(|_| focus_api::ensure_default_server().is_ok()) {
+        let body = serde_json::json!({ "refreshToken": session.refresh_token });
+        if let Err(e) = focus_api::post_json(LOGOUT_PATH, &body).await {
+            log_debug!("[auth] Could not revoke the session: {}", e);
+        }
+    }
+    clear_session().await?;
+    Ok(account_status())
+}
+
+/// Get whether a FocusApi account is logged in, and which one.
+#[tauri::command]
+pub async fn get_account_status() -> Result<AccountStatus, String> {
+    Ok(account_status())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_session_from_tokens() {
+        let tokens: TokenResponse = serde_json::from_value(json!({
+            "accessToken": "access-token-1",
+            "refreshToken": "refresh-token-1",
+            "expiresIn": 900
+        }))
+        .unwrap();
+        let session = session_from(tokens, "faker", 1_000).unwrap();
+        assert_eq!((session.username.as_str(), session.expires_at), ("faker", 1_900));
+        assert!(!session.needs_refresh(1_000));
+        assert!(session.needs_refresh(1_900 - REFRESH_MARGIN_SECS));
+
+        let empty: TokenResponse =
+            serde_json::from_value(json!({ "accessToken": "", "refreshToken": "x", "expiresIn": 900 })).unwrap();
+        assert!(session_from(empty, "faker", 0).is_err());
+    }
+}
@@ -0,0 +1,280 @@
+//! Safety rules of the automatic import during champion select.
+//!
+//! The frontend detects the pick, but whether the build may be imported
+//! without a click is decided here from the saved settings, not from the
+//! frontend's copy of them: `autoImport` must be on, the current queue must
+//! be in `autoImportRules.allowedQueues` when that list is set and not be in
+//! `autoImportRules.disabledQueues`, and ranked queues follow
+//! `autoImportRules.ranked` (never by default, only after the user confirms,
+//! or always); so does a queue the client does not report, which may be
+//! ranked. With `requireRoleMatch`, a build for another role than the
+//! assigned position is not imported.
+//!
+//! `delayAfterLockSecs` leaves the user time to change their mind: the import
+//! waits that long after the lock-in, and is dropped if the pick changed in
+//! the meantime.
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+use crate::cancel::InFlightImport;
+use crate::lcu::{self, ChampionSelectSession, ImportResult, QueueInfo};
+use crate::settings::{AppSettings, SettingsStore};
+use crate::{champions, prefetch, CommandError, ImportPayloadRequest};
+
+/// Longest wait after the lock-in (a pick turn lasts 30 seconds)
+const MAX_DELAY_SECS: u64 = 20;
+
+/// Automatic import in ranked queues
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum RankedPolicy {
+    /// Ranked builds are only imported with a click
+    #[default]
+    Never,
+    /// Imported once the user confirms the prompt
+    Confirm,
+    Always,
+}
+
+/// Where the automatic import applies
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AutoImportRules {
+    /// Queue IDs where builds are never imported automatically
+    pub disabled_queues: Vec<i32>,
+    /// Queue IDs where builds are imported automatically (empty = every queue)
+    pub allowed_queues: Vec<i32>,
+    pub ranked: RankedPolicy,
+    /// Seconds to wait after the lock-in before importing
+    pub delay_after_lock_secs: u64,
+    /// Only import a build for the assigned position's role
+    pub require_role_match: bool,
+}
+
+/// Whether an automatic import may go ahead
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", rename_all_fields = "camelCase", tag = "status")]
+pub enum AutoImportDecision {
+    Allowed,
+    /// `autoImport` is off
+    Disabled,
+    /// `safeMode` is on: the client is never modified
+    SafeMode,
+    /// The queue is in `disabledQueues`
+    QueueDisabled { queue_id: i32 },
+    /// `allowedQueues` is set and the queue (None when unknown) is not in it
+    QueueNotAllowed { queue_id: Option<i32> },
+    /// `requireRoleMatch` is on and the build is for another role
+    RoleMismatch { assigned_role: String, build_role: String },
+    /// The pick changed, or champion select ended, during `delayAfterLockSecs`
+    PickChanged,
+    /// Ranked or unknown queue (None) and `ranked` is `never`
+    RankedBlocked { queue_id: Option<i32> },
+    /// Ranked or unknown queue (None) and `ranked` is `confirm`: ask the user,
+    /// then retry with `confirmed`
+    NeedsConfirmation { queue_id: Option<i32> },
+}
+
+/// Result of `auto_import_build`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutoImportOutcome {
+    pub decision: AutoImportDecision,
+    /// Set when the import ran
+    pub result: Option<ImportResult>,
+}
+
+/// Build role of the local player's assigned position (None in blind pick and ARAM)
+fn assigned_role(session: &ChampionSelectSession) -> Option<&'static str> {
+    let cell_id = session.local_player_cell_id?;
+    let member = session.my_team.iter().flatten().find(|member| member.cell_id == cell_id)?;
+    member.assigned_position.as_deref().and_then(prefetch::role_for_position)
+}
+
+/// Whether a build role ("adc", or the client's "BOTTOM") is the assigned role
+fn same_role(assigned_role: &str, build_role: &str) -> bool {
+    let build_role = build_role.trim();
+    build_role.eq_ignore_ascii_case(assigned_role) || prefetch::role_for_position(build_role) == Some(assigned_role)
+}
+
+/// Whether the local player still has the build's champion locked
+fn still_picked(session: &ChampionSelectSession, payload: &ImportPayloadRequest) -> bool {
+    let champion_id = session.local_champion_id;
+    session.is_locked
+        && champion_id > 0
+        && (champion_id == payload.champion_id
+            || champions::ddragon_key_for_id(champion_id)
+                .is_some_and(|key| key.eq_ignore_ascii_case(payload.champion_key.trim())))
+}
+
+/// Apply the rules to the current queue (None when the client does not say)
+/// and to the build's role, given the assigned one (None without a position)
+fn decide(
+    settings: &AppSettings,
+    queue: Option<&QueueInfo>,
+    assigned_role: Option<&str>,
+    build_role: &str,
+    confirmed: bool,
+) -> AutoImportDecision {
+    if !settings.auto_import {
+        return AutoImportDecision::Disabled;
+    }
+    if settings.safe_mode {
+        return AutoImportDecision::SafeMode;
+    }
+    let rules = &settings.auto_import_rules;
+    if let Some(assigned_role) = assigned_role.filter(|_| rules.require_role_match) {
+        if !same_role(assigned_role, build_role) {
+            return AutoImportDecision::RoleMismatch {
+                assigned_role: assigned_role.to_string(),
+                build_role: build_role.trim().to_lowercase(),
+            };
+        }
+    }
+    let queue_id = queue.map(|q| q.id).filter(|&id| id > 0);
+    if !rules.allowed_queues.is_empty() && !queue_id.is_some_and(|id| rules.allowed_queues.contains(&id)) {
+        return AutoImportDecision::QueueNotAllowed { queue_id };
+    }
+    // An unknown queue may be ranked: it follows the ranked policy
+    if let Some(queue) = queue.filter(|q| q.id > 0) {
+        if rules.disabled_queues.contains(&queue.id) {
+            return AutoImportDecision::QueueDisabled { queue_id: queue.id };
+        }
+        if !queue.is_ranked {
+            return AutoImportDecision::Allowed;
+        }
+    }
+    match rules.ranked {
+        RankedPolicy::Always => AutoImportDecision::Allowed,
+        RankedPolicy::Confirm if confirmed => AutoImportDecision::Allowed,
+        RankedPolicy::Confirm => AutoImportDecision::NeedsConfirmation { queue_id },
+        RankedPolicy::Never => AutoImportDecision::RankedBlocked { queue_id },
+    }
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Import the build picked in champion select if the auto-import rules allow it.
+///
+/// `confirmed` is set when the user accepted the ranked confirmation prompt.
+/// Nothing is written to the client unless the decision is `allowed`; with
+/// `delayAfterLockSecs`, the command returns once the delay is over.
+#[tauri::command]
+pub async fn auto_import_build(
+    app: AppHandle,
+    in_flight: tauri::State<'_, InFlightImport>,
+    payload: ImportPayloadRequest,
+    confirmed: Option<bool>,
+) -> Result<AutoImportOutcome, CommandError> {
+    let settings = app
+        .try_state::<SettingsStore>()
+        .map(|store| store.get())
+        .unwrap_or_default();
+    let connection = lcu::find_lockfile().await.ok();
+    let (queue, champ_select) = match &connection {
+        Some(connection) => (
+            lcu::get_gameflow_session(connection)
+                .await
+                .ok()
+                .and_then(|session| session.game_data)
+                .and_then(|data| data.queue),
+            lcu::get_champion_select_session(connection).await.ok(),
+        ),
+        None => (None, None),
+    };
+    let assigned_role = champ_select.as_ref().and_then(assigned_role);
+
+    let mut decision = decide(
+        &settings,
+        queue.as_ref(),
+        assigned_role,
+        &payload.role,
+        confirmed.unwrap_or(false),
+    );
+    let delay = settings.auto_import_rules.delay_after_lock_secs.min(MAX_DELAY_SECS);
+    if decision == AutoImportDecision::Allowed && delay > 0 {
+        tokio::time::sleep(Duration::from_secs(delay)).await;
+        let session = match &connection {
+            Some(connection) => lcu::get_champion_select_session(connection).await.ok(),
+            None => None,
+        };
+        if !session.is_some_and(|session| still_picked(&session, &payload)) {
+            decision = AutoImportDecision::PickChanged;
+        }
+    }
+    if decision != AutoImportDecision::Allowed {
+        log_info!("[auto_import_build] {} not imported: {:?}", payload.champion_key, decision);
+        return Ok(AutoImportOutcome { decision, result: None });
+    }
+
+    let result = crate::import_build_to_client(app, in_flight, payload).await?;
+    Ok(AutoImportOutcome {
+        decision,
+        result: Some(result),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decide() {
+        let queue = |id, is_ranked| QueueInfo {
+            id,
+            is_ranked,
+            ..Default::default()
+        };
+        let normal = queue(400, false);
+        let ranked = queue(420, true);
+
+        let mut settings = AppSettings::default();
+        assert_eq!(decide(&settings, Some(&normal), None, "mid", false), AutoImportDecision::Disabled);
+
+        settings.auto_import = true;
+        assert_eq!(decide(&settings, Some(&normal), None, "mid", false), AutoImportDecision::Allowed);
+        // Never in ranked unless explicitly allowed
+        assert_eq!(decide(&settings, Some(&ranked), None, "mid", true), AutoImportDecision::RankedBlocked { queue_id: Some(420) });
+        // An unknown queue may be ranked
+        assert_eq!(decide(&settings, None, None, "mid", true), AutoImportDecision::RankedBlocked { queue_id: None });
+        assert_eq!(decide(&settings, Some(&queue(0, false)), None, "mid", true), AutoImportDecision::RankedBlocked { queue_id: None });
+
+        settings.auto_import_rules.ranked = RankedPolicy::Confirm;
+        assert_eq!(decide(&settings, Some(&ranked), None, "mid", false), AutoImportDecision::NeedsConfirmation { queue_id: Some(420) });
+        assert_eq!(decide(&settings, Some(&ranked), None, "mid", true), AutoImportDecision::Allowed);
+        assert_eq!(decide(&settings, None, None, "mid", false), AutoImportDecision::NeedsConfirmation { queue_id: None });
+        assert_eq!(decide(&settings, None, None, "mid", true), AutoImportDecision::Allowed);
+
+        settings.auto_import_rules.ranked = RankedPolicy::Always;
+        assert_eq!(decide(&settings, None, None, "mid", false), AutoImportDecision::Allowed);
+        settings.auto_import_rules.ranked = RankedPolicy::Confirm;
+
+        settings.auto_import_rules.disabled_queues = vec![400];
+        assert_eq!(decide(&settings, Some(&normal), None, "mid", false), AutoImportDecision::QueueDisabled { queue_id: 400 });
+
+        // Only the allowed queues, and never an unknown one
+        settings.auto_import_rules.allowed_queues = vec![420];
+        assert_eq!(decide(&settings, Some(&normal), None, "mid", false), AutoImportDecision::QueueNotAllowed { queue_id: Some(400) });
+        assert_eq!(decide(&settings, None, None, "mid", false), AutoImportDecision::QueueNotAllowed { queue_id: None });
+        assert_eq!(decide(&settings, Some(&ranked), None, "mid", true), AutoImportDecision::Allowed);
+
+        settings.auto_import_rules.require_role_match = true;
+        assert_eq!(decide(&settings, Some(&ranked), Some("adc"), "BOTTOM", true), AutoImportDecision::Allowed);
+        assert_eq!(
+            decide(&settings, Some(&ranked), Some("support"), "Mid", true),
+            AutoImportDecision::RoleMismatch {
+                assigned_role: "support".to_string(),
+                build_role: "mid".to_string()
+            }
+        );
+        // Blind pick: no position to match
+        assert_eq!(decide(&settings, Some(&ranked), None, "mid", true), AutoImportDecision::Allowed);
+
+        settings.safe_mode = true;
+        assert_eq!(decide(&settings, None, None, "mid", false), AutoImportDecision::SafeMode);
+    }
+}
@@ -0,0 +1,101 @@
+//! Launch at login.
+//!
+//! When `autostart` is enabled in the settings, FocusApp registers itself
+//! with the system through `tauri-plugin-autostart`: the `Run` registry key
+//! of the current user on Windows, a LaunchAgent on macOS and an XDG
+//! autostart entry on Linux. The registered command carries
+//! `--autostart`, so a launch at login can be told apart from the user
+//! opening the app; with `startMinimized` it stays hidden in the tray.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::plugin::TauriPlugin;
+use tauri::{AppHandle, Listener, Manager, Runtime};
+use tauri_plugin_autostart::ManagerExt;
+
+use crate::events::SETTINGS_CHANGED_EVENT;
+use crate::settings::{AppSettings, SettingsStore};
+
+/// Argument passed by the login entry
+pub const AUTOSTART_ARG: &str = "--autostart";
+
+/// Name of the login entry
+const ENTRY_NAME: &str = "FocusApp";
+
+/// Whether this process was launched at login
+pub fn launched_at_login() -> bool {
+    std::env::args().any(|arg| arg == AUTOSTART_ARG)
+}
+
+/// Whether the main window should stay hidden at startup
+pub fn should_start_hidden(settings: &AppSettings, launched_at_login: bool) -> bool {
+    launched_at_login && settings.start_minimized
+}
+
+/// Plugin managing the login entry (registered by the builder in main.rs)
+pub fn plugin<R: Runtime>() -> TauriPlugin<R> {
+    tauri_plugin_autostart::Builder::new()
+        .app_name(ENTRY_NAME)
+        .arg(AUTOSTART_ARG)
+        .build()
+}
+
+fn set_enabled(app: &AppHandle, enabled: bool) -> Result<(), tauri_plugin_autostart::Error> {
+    let launcher = app.autolaunch();
+    // Removing a missing entry fails on some systems
+    match (enabled, launcher.is_enabled()?) {
+        (true, _) => launcher.enable(),
+        (false, true) => launcher.disable(),
+        (false, false) => Ok(()),
+    }
+}
+
+/// Register or remove the login entry in the background
+fn apply(app: &AppHandle, enabled: bool) {
+    let app = app.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        if let Err(e) = set_enabled(&app, enabled) {
+            log_warn!("[autostart] Failed to update the login entry: {}", e);
+        }
+    });
+}
+
+/// Keep the login entry in line with the settings.
+///
+/// Refreshed at startup when enabled (the executable may have moved after an update)
+/// and again whenever `autostart` changes.
+pub fn init(app: &AppHandle) {
+    let Some(store) = app.try_state::<SettingsStore>() else {
+        return;
+    };
+    let enabled = AtomicBool::new(store.get().autostart);
+    if enabled.load(Ordering::Relaxed) {
+        apply(app, true);
+    }
+
+    let handle = app.clone();
+    app.listen(SETTINGS_CHANGED_EVENT, move |event| {
+        let Ok(settings) = serde_json::from_str::<AppSettings>(event.payload()) else {
+            return;
+        };
+        if enabled.swap(settings.autostart, Ordering::Relaxed) != settings.autostart {
+            apply(&handle, settings.autostart);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_start_hidden() {
+        let settings = AppSettings {
+            start_minimized: true,
+            ..AppSettings::default()
+        };
+        assert!(should_start_hidden(&settings, true));
+        // Opened by the user: always show the window
+        assert!(!should_start_hidden(&settings, false));
+        assert!(!should_start_hidden(&AppSettings::default(), true));
+    }
+}
@@ -0,0 +1,198 @@
+//! Ban suggestions during the ban phase.
+//!
+//! When the champion select actions show that the local player's ban is in
+//! progress, the game watcher hands the session to `on_champ_select`, which
+//! fetches the recommended bans of the player's assigned role from FocusApi
+//! and sends them once with the `ban-suggestions` event. Champions already
+//! banned or picked are left out. This is informational only: the user still
+//! bans in the client, nothing is sent to the LCU.
+//!
+//! Suggestions are kept in memory per role for the current patch.
+
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+use crate::events::AppEvent;
+use crate::lcu::ChampionSelectSession;
+use crate::patch_cache::PatchCache;
+use crate::{focus_api, prefetch};
+
+/// Suggestions sent for a ban turn
+const MAX_BAN_SUGGESTIONS: usize = 5;
+
+/// A recommended ban
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BanSuggestion {
+    pub champion_id: i64,
+    pub champion: String,
+    #[serde(default)]
+    pub ban_rate: Option<f64>,
+    #[serde(default)]
+    pub win_rate: Option<f64>,
+}
+
+/// `ban-suggestions` payload and result of `get_ban_suggestions`
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BanSuggestions {
+    /// Role the suggestions are for (None = every role)
+    pub role: Option<String>,
+    pub suggestions: Vec<BanSuggestion>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BanResponse {
+    bans: Vec<BanSuggestion>,
+}
+
+/// Suggestions of the current patch, by role ("" = every role)
+static CACHE: PatchCache<Vec<BanSuggestion>> = PatchCache::in_memory();
+
+/// Ban action already announced (one event per ban turn)
+static ANNOUNCED: Mutex<Option<i64>> = Mutex::new(None);
+
+/// Ban action of the local player in progress, and their role
+fn local_ban_turn(session: &ChampionSelectSession) -> Option<(i64, Option<String>)> {
+    let cell_id = session.local_player_cell_id?;
+    if session.is_spectating {
+        return None;
+    }
+    let action = session
+        .actions
+        .iter()
+        .flatten()
+        .flatten()
+        .find(|a| a.actor_cell_id == cell_id && a.action_type == "ban" && a.is_in_progress && !a.completed)?;
+    let role = session
+        .my_team
+        .iter()
+        .flatten()
+        .find(|member| member.cell_id == cell_id)
+        .and_then(|member| member.assigned_position.as_deref())
+        .and_then(prefetch::role_for_position)
+        .map(str::to_string);
+    Some((action.id, role))
+}
+
+/// Champions already banned or picked
+fn unavailable_champions(session: &ChampionSelectSession) -> Vec<i64> {
+    session
+        .actions
+        .iter()
+        .flatten()
+        .flatten()
+        .filter(|a| a.completed && a.champion_id > 0)
+        .map(|a| a.champion_id)
+        .collect()
+}
+
+/// Parse a FocusApi response, most recommended first
+fn parse_suggestions(body: serde_json::Value) -> Result<Vec<BanSuggestion>, String> {
+    let response: BanResponse = serde_json::from_value(body).map_err(|e| format!("Invalid ban suggestions: {}", e))?;
+    Ok(response.bans.into_iter().filter(|ban| ban.champion_id > 0).collect())
+}
+
+/// Best suggestions that can still be banned
+fn available(suggestions: &[BanSuggestion], unavailable: &[i64]) -> Vec<BanSuggestion> {
+    suggestions
+        .iter()
+        .filter(|ban| !unavailable.contains(&ban.champion_id))
+        .take(MAX_BAN_SUGGESTIONS)
+        .cloned()
+        .collect()
+}
+
+/// Suggestions of `role` for the current patch, fetched on first use
+async fn suggestions_for(app: &AppHandle, role: Option<&str>) -> Result<Vec<BanSuggestion>, String> {
+    let key = role.unwrap_or_default().to_string();
+    if let Some(cached) = CACHE.get(app, &key) {
+        return Ok(cached);
+    }
+
+    let suggestions = parse_suggestions(focus_api::fetch_ban_suggestions(role).await.map_err(|e| e.to_string())?)?;
+    CACHE.insert(app, key, suggestions.clone())?;
+    Ok(suggestions)
+}
+
+/// Announce the suggestions when the local player's ban turn starts
+pub fn on_champ_select(app: &AppHandle, session: &ChampionSelectSession) {
+    let Some((action_id, role)) = local_ban_turn(session) else {
+        return;
+    };
+    let Ok(mut announced) = ANNOUNCED.lock() else {
+        return;
+    };
+    if *announced == Some(action_id) {
+        return;
+    }
+    *announced = Some(action_id);
+    drop(announced);
+
+    let app = app.clone();
+    let unavailable = unavailable_champions(session);
+    tauri::async_runtime::spawn(async move {
+        match suggestions_for(&app, role.as_deref()).await {
+            Ok(suggestions) => {
+                let payload = BanSuggestions {
+                    role,
+                    suggestions: available(&suggestions, &unavailable),
+                };
+                if let Err(e) = AppEvent::BanSuggestions(&payload).emit(&app) {
+                    log_debug!("[ban_suggestions] {}", e);
+                }
+            }
+            Err(e) => log_debug!("[ban_suggestions] Not available: {}", e),
+        }
+    });
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the recommended bans of a role (every role when `role` is None) for the current patch.
+#[tauri::command]
+pub async fn get_ban_suggestions(app: AppHandle, role: Option<String>) -> Result<BanSuggestions, String> {
+    let role = role.filter(|r| !r.trim().is_empty()).map(|r| r.trim().to_lowercase());
+    let suggestions = suggestions_for(&app, role.as_deref()).await?;
+    Ok(BanSuggestions {
+        role,
+        suggestions: available(&suggestions, &[]),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_ban_turn_and_suggestions() {
+        let session: ChampionSelectSession = serde_json::from_value(json!({
+            "localPlayerCellId": 1,
+            "actions": [[
+                { "id": 10, "actorCellId": 0, "championId": 238, "completed": true, "type": "ban" },
+                { "id": 11, "actorCellId": 1, "championId": 0, "completed": false, "isInProgress": true, "type": "ban" }
+            ]],
+            "myTeam": [{ "cellId": 1, "championId": 0, "assignedPosition": "middle" }]
+        }))
+        .unwrap();
+        assert_eq!(local_ban_turn(&session), Some((11, Some("mid".to_string()))));
+
+        let suggestions = parse_suggestions(json!({
+            "bans": [
+                { "championId": 238, "champion": "Zed", "banRate": 0.4 },
+                { "championId": 157, "champion": "Yasuo" },
+                { "championId": 0, "champion": "Unknown" }
+            ]
+        }))
+        .unwrap();
+        let names: Vec<String> = available(&suggestions, &unavailable_champions(&session))
+            .into_iter()
+            .map(|ban| ban.champion)
+            .collect();
+        assert_eq!(names, ["Yasuo"]);
+    }
+}
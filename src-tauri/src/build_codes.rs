@@ -0,0 +1,184 @@
+//! Shareable build codes.
+//!
+//! A build code is a short string that can be pasted in a chat: one version
+//! byte followed by the deflate-compressed build, encoded as URL-safe base64.
+//! The build is stored as a positional JSON array rather than an object so
+//! field names don't take room in the code. Changing the layout requires a
+//! new version byte; older versions must keep decoding.
+
+use base64::Engine;
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+
+use crate::{ImportPayloadRequest, RuneTree};
+
+/// Current layout of the encoded build
+const CODE_VERSION: u8 = 1;
+
+/// Source of the builds imported from a code
+pub const SHARED_SOURCE: &str = "shared";
+
+/// Maximum decompressed size accepted when decoding
+const MAX_DECODED_LEN: u64 = 16 * 1024;
+
+const BASE64: base64::engine::GeneralPurpose = base64::engine::general_purpose::URL_SAFE_NO_PAD;
+
+/// Version 1 layout
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct BuildCodeV1(
+    i64,         // champion_id
+    String,      // champion_key
+    String,      // role
+    String,      // patch
+    String,      // title
+    i64,         // primary tree
+    Vec<i64>,    // primary runes
+    i64,         // secondary tree
+    Vec<i64>,    // secondary runes
+    Vec<i64>,    // shards
+    Vec<i64>,    // summoner spells
+    Vec<i64>,    // starting items
+    Vec<i64>,    // core items
+    Vec<i64>,    // situational items
+    Option<i64>, // boots
+);
+
+impl From<&ImportPayloadRequest> for BuildCodeV1 {
+    fn from(build: &ImportPayloadRequest) -> Self {
+        BuildCodeV1(
+            build.champion_id,
+            build.champion_key.clone(),
+            build.role.clone(),
+            build.patch.clone(),
+            build.title.clone(),
+            build.runes_primary.tree_id,
+            build.runes_primary.rune_ids.clone(),
+            build.runes_secondary.tree_id,
+            build.runes_secondary.rune_ids.clone(),
+            build.rune_shards.clone(),
+            build.summoner_spells.clone(),
+            build.items_starting.clone(),
+            build.items_core.clone(),
+            build.items_situational.clone(),
+            build.boots,
+        )
+    }
+}
+
+impl From<BuildCodeV1> for ImportPayloadRequest {
+    fn from(code: BuildCodeV1) -> Self {
+        let BuildCodeV1(
+            champion_id,
+            champion_key,
+            role,
+            patch,
+            title,
+            primary_tree,
+            primary_runes,
+            secondary_tree,
+            secondary_runes,
+            rune_shards,
+            summoner_spells,
+            items_starting,
+            items_core,
+            items_situational,
+            boots,
+        ) = code;
+        ImportPayloadRequest {
+            boots,
+            champion_id,
+            champion_key,
+            items_core,
+            items_situational,
+            items_starting,
+            patch,
+            role,
+            rune_shards,
+            runes_primary: RuneTree {
+                rune_ids: primary_runes,
+                tree_id: primary_tree,
+            },
+            runes_secondary: RuneTree {
+                rune_ids: secondary_runes,
+                tree_id: secondary_tree,
+            },
+            source: SHARED_SOURCE.to_string(),
+            summoner_spells,
+            title,
+        }
+    }
+}
+
+/// Encode a build as a shareable code
+pub fn encode(build: &ImportPayloadRequest) -> Result<String, String> {
+    let json = serde_json::to_vec(&BuildCodeV1::from(build)).map_err(|e| e.to_string())?;
+    let mut encoder = DeflateEncoder::new(vec![CODE_VERSION], Compression::best());
+    encoder.write_all(&json).map_err(|e| e.to_string())?;
+    let bytes = encoder.finish().map_err(|e| e.to_string())?;
+    Ok(BASE64.encode(bytes))
+}
+
+/// Decode a shareable code
+pub fn decode(code: &str) -> Result<ImportPayloadRequest, String> {
+    let bytes = BASE64
+        .decode(code.trim())
+        .map_err(|_| "Invalid build code".to_string())?;
+    let (&version, compressed) = bytes.split_first().ok_or("Empty build code")?;
+    if version != CODE_VERSION {
+        return Err(format!("Unsupported build code version {} (update FocusApp)", version));
+    }
+
+    let mut json = Vec::new();
+    DeflateDecoder::new(compressed)
+        .take(MAX_DECODED_LEN)
+        .read_to_end(&mut json)
+        .map_err(|_| "Corrupted build code".to_string())?;
+    let build: BuildCodeV1 = serde_json::from_slice(&json).map_err(|_| "Corrupted build code".to_string())?;
+    Ok(build.into())
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Encode a build as a short code to share in chat.
+#[tauri::command]
+pub async fn export_build_code(build: ImportPayloadRequest) -> Result<String, String> {
+    encode(&build)
+}
+
+/// Decode a shared build code into a build that can be imported.
+#[tauri::command]
+pub async fn import_build_code(code: String) -> Result<ImportPayloadRequest, String> {
+    decode(&code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_server::import_request;
+
+    #[test]
+    fn test_round_trip() {
+        let code = encode(&import_request()).unwrap();
+        assert!(code.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+
+        let decoded = decode(&code).unwrap();
+        assert_eq!(decoded.source, SHARED_SOURCE);
+        assert_eq!(BuildCodeV1::from(&decoded), BuildCodeV1::from(&import_request()));
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        assert!(decode("not a code!").is_err());
+        assert!(decode("").is_err());
+        // Unknown version byte
+        let code = encode(&import_request()).unwrap();
+        let mut bytes = BASE64.decode(code).unwrap();
+        bytes[0] = 9;
+        assert!(decode(&BASE64.encode(bytes)).unwrap_err().contains("version 9"));
+    }
+}
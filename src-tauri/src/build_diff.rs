@@ -0,0 +1,214 @@
+//! What changed in a build since the previous patch.
+//!
+//! `get_build_diff` takes the same request as an import, fetches the build of
+//! the current patch and the one of the previous patch (both through the
+//! import cache, so a build already imported last patch is not fetched
+//! again) and compares the payloads: rune trees and runes, items added or
+//! removed from the item set, and summoner spells. The League Client is not
+//! touched.
+
+use serde::Serialize;
+use std::collections::BTreeSet;
+use tauri::AppHandle;
+
+use crate::build_providers::{self, BuildProvider};
+use crate::error::AppError;
+use crate::lcu::ImportPayloadResponse;
+use crate::{champions, import_cache, CommandError, ImportPayloadRequest};
+
+/// A value that changed
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Change<T> {
+    pub from: T,
+    pub to: T,
+}
+
+/// Differences between two builds (empty lists and None mean "unchanged")
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildChanges {
+    pub primary_style: Option<Change<i32>>,
+    pub sub_style: Option<Change<i32>>,
+    /// Perk IDs, stat shards included
+    pub runes_added: Vec<i32>,
+    pub runes_removed: Vec<i32>,
+    /// Item IDs of the item set, all blocks together
+    pub items_added: Vec<String>,
+    pub items_removed: Vec<String>,
+    /// Summoner spell IDs (order ignored)
+    pub spells: Option<Change<Vec<i32>>>,
+}
+
+impl BuildChanges {
+    pub fn is_empty(&self) -> bool {
+        *self == BuildChanges::default()
+    }
+}
+
+/// Result of `get_build_diff`
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildDiff {
+    pub champion_key: String,
+    pub role: String,
+    pub from_patch: String,
+    pub to_patch: String,
+    pub changes: BuildChanges,
+    pub unchanged: bool,
+}
+
+/// Elements of `to` missing from `from`, sorted
+fn added<T: Ord + Clone>(from: &BTreeSet<T>, to: &BTreeSet<T>) -> Vec<T> {
+    to.difference(from).cloned().collect()
+}
+
+fn change<T: PartialEq>(from: T, to: T) -> Option<Change<T>> {
+    (from != to).then_some(Change { from, to })
+}
+
+/// Compare the build of the previous patch with the current one
+pub fn diff_payloads(previous: &ImportPayloadResponse, current: &ImportPayloadResponse) -> BuildChanges {
+    let perks = |payload: &ImportPayloadResponse| -> BTreeSet<i32> {
+        payload
+            .rune_page_payload
+            .iter()
+            .flat_map(|page| page.selected_perk_ids.iter().copied())
+            .collect()
+    };
+    let items = |payload: &ImportPayloadResponse| -> BTreeSet<String> {
+        payload
+            .item_set_payload
+            .iter()
+            .flat_map(|set| set.blocks.iter())
+            .flat_map(|block| block.items.iter().map(|item| item.id.clone()))
+            .collect()
+    };
+    let spells = |payload: &ImportPayloadResponse| -> Vec<i32> {
+        let mut spells: Vec<i32> = payload
+            .summoner_spells_payload
+            .iter()
+            .flat_map(|spells| [spells.spell1_id, spells.spell2_id])
+            .collect();
+        spells.sort_unstable();
+        spells
+    };
+    let style = |payload: &ImportPayloadResponse| {
+        payload
+            .rune_page_payload
+            .as_ref()
+            .map(|page| (page.primary_style_id, page.sub_style_id))
+            .unwrap_or_default()
+    };
+
+    let ((previous_primary, previous_sub), (primary, sub)) = (style(previous), style(current));
+    let (previous_perks, current_perks) = (perks(previous), perks(current));
+    let (previous_items, current_items) = (items(previous), items(current));
+    BuildChanges {
+        primary_style: change(previous_primary, primary),
+        sub_style: change(previous_sub, sub),
+        runes_added: added(&previous_perks, &current_perks),
+        runes_removed: added(&current_perks, &previous_perks),
+        items_added: added(&previous_items, &current_items),
+        items_removed: added(&current_items, &previous_items),
+        spells: change(spells(previous), spells(current)),
+    }
+}
+
+/// Payloads of `request` for `patch`, from the import cache when possible
+async fn payloads_of_patch(
+    app: &AppHandle,
+    request: &ImportPayloadRequest,
+    patch: &str,
+    max_age_secs: i64,
+) -> Result<ImportPayloadResponse, CommandError> {
+    let request = ImportPayloadRequest {
+        patch: patch.to_string(),
+        ..request.clone()
+    };
+    if let Some(cached) = import_cache::lookup(app, &request, max_age_secs) {
+        return Ok(cached);
+    }
+
+    let mut response = build_providers::configured_provider(app).fetch_payloads(&request).await?;
+    crate::validate_payloads(&mut response)?;
+    if let Err(e) = import_cache::store(app, &request, &response) {
+        log_warn!("[build_diff] Failed to cache payload: {}", e);
+    }
+    Ok(response)
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Compare a build of the current patch with the same build of the previous patch.
+///
+/// `payload.patch` is the patch to compare ("current" = the live patch).
+#[tauri::command]
+pub async fn get_build_diff(app: AppHandle, payload: ImportPayloadRequest) -> Result<BuildDiff, CommandError> {
+    if !build_providers::configured_provider(&app).cacheable() {
+        return Err(AppError::InvalidData("Build diffs need builds from FocusApi".to_string()).into());
+    }
+    let to_patch = if build_providers::is_patch(payload.patch.trim()) {
+        payload.patch.trim().to_string()
+    } else {
+        champions::patch_of(&champions::ddragon_version())
+    };
+    let from_patch = build_providers::previous_patch(&to_patch)
+        .ok_or_else(|| AppError::NotFound(format!("No patch before {}", to_patch)))?;
+
+    // The previous patch's build no longer changes: any cached copy will do
+    let previous = payloads_of_patch(&app, &payload, &from_patch, i64::MAX).await?;
+    let current = payloads_of_patch(&app, &payload, &to_patch, import_cache::FRESH_TTL_SECS).await?;
+
+    let changes = diff_payloads(&previous, &current);
+    Ok(BuildDiff {
+        champion_key: payload.champion_key,
+        role: payload.role,
+        from_patch,
+        to_patch,
+        unchanged: changes.is_empty(),
+        changes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn payload(perks: [i32; 3], items: &[&str], spells: [i32; 2]) -> ImportPayloadResponse {
+        serde_json::from_value(json!({
+            "champion": "Ahri",
+            "role": "mid",
+            "rune_page_payload": {
+                "name": "Ahri mid",
+                "primaryStyleId": 8100,
+                "subStyleId": 8200,
+                "selectedPerkIds": perks
+            },
+            "item_set_payload": {
+                "title": "Ahri mid",
+                "associatedChampions": [103],
+                "associatedMaps": [11],
+                "blocks": [{ "type": "Core", "items": items.iter().map(|id| json!({ "id": id, "count": 1 })).collect::<Vec<_>>() }]
+            },
+            "summoner_spells_payload": { "spell1Id": spells[0], "spell2Id": spells[1] }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_diff_payloads() {
+        let previous = payload([8112, 8139, 5008], &["6655", "3020"], [4, 14]);
+        let current = payload([8112, 8143, 5008], &["6655", "4645"], [14, 4]);
+
+        let changes = diff_payloads(&previous, &current);
+        assert_eq!((changes.runes_added, changes.runes_removed), (vec![8143], vec![8139]));
+        assert_eq!((changes.items_added, changes.items_removed), (vec!["4645".to_string()], vec!["3020".to_string()]));
+        assert_eq!(changes.spells, None);
+        assert_eq!(changes.primary_style, None);
+        assert!(diff_payloads(&current, &current).is_empty());
+    }
+}
@@ -0,0 +1,94 @@
+//! Ratings of imported builds, sent to FocusApi.
+//!
+//! After a game the user can rate the build they imported (1 to 5 stars).
+//! The rating goes through the backend FocusApi client so the API key never
+//! reaches the frontend. The game result is attached when the user gives it
+//! or, when they pass the game ID instead, taken from the session record.
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::focus_api;
+use crate::sessions::{self, GameResult};
+
+/// Highest rating (lowest is 1)
+const MAX_RATING: u8 = 5;
+
+/// Body of the feedback request
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct BuildFeedback {
+    rating: u8,
+    /// None when the result is unknown
+    won: Option<bool>,
+    app_version: &'static str,
+}
+
+fn check_rating(rating: u8) -> Result<u8, String> {
+    if (1..=MAX_RATING).contains(&rating) {
+        Ok(rating)
+    } else {
+        Err(format!("Rating must be between 1 and {}", MAX_RATING))
+    }
+}
+
+/// Result given by the user, else the one recorded for `game_id`
+fn game_won(won: Option<bool>, recorded: Option<GameResult>) -> Option<bool> {
+    won.or(recorded.map(|result| result == GameResult::Win))
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Rate a build imported from FocusApi.
+///
+/// `won` is the game result; when it is missing and `game_id` is given, the
+/// result recorded for that game is sent, if any.
+#[tauri::command]
+pub async fn submit_build_feedback(
+    app: AppHandle,
+    build_id: String,
+    rating: u8,
+    won: Option<bool>,
+    game_id: Option<i64>,
+) -> Result<(), String> {
+    let build_id = build_id.trim();
+    if build_id.is_empty() {
+        return Err("Missing build ID".to_string());
+    }
+    let recorded = game_id.and_then(|game_id| {
+        sessions::load_sessions(&app)
+            .into_iter()
+            .rev()
+            .find(|s| s.game_id == game_id)
+            .and_then(|s| s.result)
+    });
+    let feedback = BuildFeedback {
+        rating: check_rating(rating)?,
+        won: game_won(won, recorded),
+        app_version: env!("CARGO_PKG_VERSION"),
+    };
+
+    focus_api::post_json(&format!("/builds/{}/feedback", urlencoding::encode(build_id)), &feedback)
+        .await
+        .map_err(|e| e.to_string())?;
+    log_info!("[build_feedback] Rated build {} {}/{}", build_id, feedback.rating, MAX_RATING);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_feedback_values() {
+        assert_eq!(check_rating(5), Ok(5));
+        assert!(check_rating(0).is_err());
+        assert!(check_rating(6).is_err());
+
+        assert_eq!(game_won(Some(false), Some(GameResult::Win)), Some(false));
+        assert_eq!(game_won(None, Some(GameResult::Win)), Some(true));
+        assert_eq!(game_won(None, None), None);
+    }
+}
@@ -0,0 +1,330 @@
+//! Build providers.
+//!
+//! A provider turns the build chosen in the UI (`ImportPayloadRequest`) into
+//! the rune page, item set and summoner spell payloads sent to the client.
+//! FocusApi is the default; advanced users can select a self-hosted server
+//! speaking the same `/lol/import-payload` protocol, or a directory of JSON
+//! payload files, in the settings (`buildSource`).
+
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::path::PathBuf;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+use crate::lcu::ImportPayloadResponse;
+use crate::settings::SettingsStore;
+use crate::error::AppError;
+use crate::{auth, focus_api, CommandError, ImportPayloadRequest};
+
+/// FocusApi endpoint returning the import payloads
+const IMPORT_PAYLOAD_PATH: &str = "/lol/import-payload";
+
+/// Timeout of requests to a self-hosted provider
+const SELF_HOSTED_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Build source selected in the settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum BuildSource {
+    #[default]
+    FocusApi,
+    /// Server implementing the FocusApi import-payload endpoint (the API key is not sent)
+    SelfHosted { base_url: String },
+    /// Directory of payload files (`<champion>-<role>.json` or `<champion>.json`)
+    LocalFile { directory: String },
+}
+
+/// Patch of the builds requested at import, selected in the settings (`buildPatch`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum BuildPatch {
+    /// Patch sent by the frontend (the current one)
+    #[default]
+    Current,
+    /// Patch before the current one, while data for a new patch is still thin
+    Previous,
+    /// A given patch ("14.9")
+    Fixed { patch: String },
+}
+
+/// Patch before `version`: "14.10.1" -> "14.9" (None for the first patch of a season)
+pub(crate) fn previous_patch(version: &str) -> Option<String> {
+    let mut parts = version.split('.');
+    let major: u32 = parts.next()?.parse().ok()?;
+    let minor: u32 = parts.next()?.parse().ok()?;
+    (minor > 1).then(|| format!("{}.{}", major, minor - 1))
+}
+
+/// "major.minor" patch
+pub(crate) fn is_patch(value: &str) -> bool {
+    let parts: Vec<&str> = value.split('.').collect();
+    parts.len() == 2 && parts.iter().all(|p| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit()))
+}
+
+impl BuildPatch {
+    /// Patch to request instead of the frontend's, None to keep it
+    pub fn resolve(&self, current_version: &str) -> Option<String> {
+        match self {
+            BuildPatch::Current => None,
+            BuildPatch::Previous => previous_patch(current_version),
+            BuildPatch::Fixed { patch } => Some(patch.trim().to_string()).filter(|p| is_patch(p)),
+        }
+    }
+}
+
+/// Import request with the patch override of the settings applied.
+///
+/// The patch is part of the request and of the import cache key, so builds
+/// of another patch are fetched and cached separately.
+pub fn with_patch_override(app: &AppHandle, request: &ImportPayloadRequest) -> ImportPayloadRequest {
+    let mut request = request.clone();
+    let build_patch = app
+        .try_state::<SettingsStore>()
+        .map(|store| store.get().build_patch)
+        .unwrap_or_default();
+    if let Some(patch) = build_patch.resolve(&crate::champions::ddragon_version()) {
+        log_debug!("[build_providers] Requesting builds of patch {} (was {})", patch, request.patch);
+        request.patch = patch;
+    } else if build_patch != BuildPatch::Current {
+        log_warn!("[build_providers] Ignoring build patch override {:?}", build_patch);
+    }
+    request
+}
+
+/// Source of import payloads
+pub trait BuildProvider {
+    /// Name shown in logs and errors
+    fn name(&self) -> &'static str;
+
+    /// Whether responses go through the import cache (remote providers only)
+    fn cacheable(&self) -> bool;
+
+    /// Get the import payloads of a build
+    fn fetch_payloads(
+        &self,
+        request: &ImportPayloadRequest,
+    ) -> impl Future<Output = Result<ImportPayloadResponse, CommandError>> + Send;
+}
+
+/// Parse the response of a FocusApi-compatible server
+async fn read_payload_response(
+    provider: &str,
+    response: reqwest::Response,
+) -> Result<ImportPayloadResponse, CommandError> {
+    let status = response.status();
+    if status == reqwest::StatusCode::UNAUTHORIZED || status == reqwest::StatusCode::FORBIDDEN {
+        return Err(AppError::FocusApiUnauthorized.into());
+    }
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(AppError::FocusApi(format!("{} returned error {}: {}", provider, status, body)).into());
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| AppError::InvalidData(format!("Failed to parse {} response: {}", provider, e)).into())
+}
+
+/// Official FocusApi
+pub struct FocusApiProvider;
+
+impl BuildProvider for FocusApiProvider {
+    fn name(&self) -> &'static str {
+        "FocusApi"
+    }
+
+    fn cacheable(&self) -> bool {
+        true
+    }
+
+    async fn fetch_payloads(&self, request: &ImportPayloadRequest) -> Result<ImportPayloadResponse, CommandError> {
+        log_debug!("[build_providers] POST to FocusApi {}", IMPORT_PAYLOAD_PATH);
+
+        auth::refresh_if_needed().await;
+        let response = focus_api::send(focus_api::request_json(reqwest::Method::POST, IMPORT_PAYLOAD_PATH, request)?).await?;
+        read_payload_response(self.name(), response).await
+    }
+}
+
+/// Self-hosted FocusApi-compatible server
+pub struct SelfHostedProvider {
+    base_url: String,
+}
+
+impl BuildProvider for SelfHostedProvider {
+    fn name(&self) -> &'static str {
+        "Self-hosted build server"
+    }
+
+    fn cacheable(&self) -> bool {
+        false
+    }
+
+    async fn fetch_payloads(&self, request: &ImportPayloadRequest) -> Result<ImportPayloadResponse, CommandError> {
+        let url = format!("{}{}", self.base_url.trim_end_matches('/'), IMPORT_PAYLOAD_PATH);
+        let client = crate::proxy::remote_client().timeout(SELF_HOSTED_TIMEOUT).build()?;
+        let response = client.post(&url).json(request).send().await?;
+        read_payload_response(self.name(), response).await
+    }
+}
+
+/// Directory of payload files written by the user
+pub struct LocalFileProvider {
+    directory: PathBuf,
+}
+
+impl LocalFileProvider {
+    /// Candidate file names for a build, most specific first
+    fn candidate_files(request: &ImportPayloadRequest) -> Option<Vec<String>> {
+        let champion = request.champion_key.trim().to_lowercase();
+        let role = request.role.trim().to_lowercase();
+        // Plain names only: they end up in a path on disk
+        let plain = |value: &str| !value.is_empty() && value.chars().all(|c| c.is_ascii_alphanumeric());
+        if !plain(&champion) || !(role.is_empty() || plain(&role)) {
+            return None;
+        }
+        let mut files = Vec::new();
+        if !role.is_empty() {
+            files.push(format!("{}-{}.json", champion, role));
+        }
+        files.push(format!("{}.json", champion));
+        Some(files)
+    }
+}
+
+impl BuildProvider for LocalFileProvider {
+    fn name(&self) -> &'static str {
+        "Local build files"
+    }
+
+    fn cacheable(&self) -> bool {
+        false
+    }
+
+    async fn fetch_payloads(&self, request: &ImportPayloadRequest) -> Result<ImportPayloadResponse, CommandError> {
+        let not_found = || {
+            CommandError::from(AppError::NotFound(format!(
+                "No local build for {} ({}) in {:?}",
+                request.champion_key, request.role, self.directory
+            )))
+        };
+        let files = Self::candidate_files(request).ok_or_else(not_found)?;
+
+        for file in files {
+            let Ok(json) = tokio::fs::read_to_string(self.directory.join(&file)).await else {
+                continue;
+            };
+            return serde_json::from_str(&json)
+                .map_err(|e| AppError::InvalidData(format!("Invalid build file {}: {}", file, e)).into());
+        }
+        Err(not_found())
+    }
+}
+
+/// Provider selected in the settings
+pub enum ConfiguredProvider {
+    FocusApi(FocusApiProvider),
+    SelfHosted(SelfHostedProvider),
+    LocalFile(LocalFileProvider),
+}
+
+impl ConfiguredProvider {
+    pub fn from_source(source: &BuildSource) -> Self {
+        match source {
+            BuildSource::SelfHosted { base_url } if !base_url.trim().is_empty() => {
+                ConfiguredProvider::SelfHosted(SelfHostedProvider {
+                    base_url: base_url.trim().to_string(),
+                })
+            }
+            BuildSource::LocalFile { directory } if !directory.trim().is_empty() => {
+                ConfiguredProvider::LocalFile(LocalFileProvider {
+                    directory: PathBuf::from(directory.trim()),
+                })
+            }
+            _ => ConfiguredProvider::FocusApi(FocusApiProvider),
+        }
+    }
+}
+
+impl BuildProvider for ConfiguredProvider {
+    fn name(&self) -> &'static str {
+        match self {
+            ConfiguredProvider::FocusApi(p) => p.name(),
+            ConfiguredProvider::SelfHosted(p) => p.name(),
+            ConfiguredProvider::LocalFile(p) => p.name(),
+        }
+    }
+
+    fn cacheable(&self) -> bool {
+        match self {
+            ConfiguredProvider::FocusApi(p) => p.cacheable(),
+            ConfiguredProvider::SelfHosted(p) => p.cacheable(),
+            ConfiguredProvider::LocalFile(p) => p.cacheable(),
+        }
+    }
+
+    async fn fetch_payloads(&self, request: &ImportPayloadRequest) -> Result<ImportPayloadResponse, CommandError> {
+        match self {
+            ConfiguredProvider::FocusApi(p) => p.fetch_payloads(request).await,
+            ConfiguredProvider::SelfHosted(p) => p.fetch_payloads(request).await,
+            ConfiguredProvider::LocalFile(p) => p.fetch_payloads(request).await,
+        }
+    }
+}
+
+/// Provider selected in the settings (FocusApi when the settings are not loaded)
+pub fn configured_provider(app: &AppHandle) -> ConfiguredProvider {
+    let source = app
+        .try_state::<SettingsStore>()
+        .map(|store| store.get().build_source)
+        .unwrap_or_default();
+    ConfiguredProvider::from_source(&source)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_server::import_request;
+
+    #[test]
+    fn test_build_source_settings() {
+        let source: BuildSource =
+            serde_json::from_value(serde_json::json!({ "kind": "localFile", "directory": "C:/builds" })).unwrap();
+        assert!(matches!(ConfiguredProvider::from_source(&source), ConfiguredProvider::LocalFile(_)));
+
+        // Incomplete configuration falls back to FocusApi
+        let empty = BuildSource::SelfHosted { base_url: " ".to_string() };
+        assert!(matches!(ConfiguredProvider::from_source(&empty), ConfiguredProvider::FocusApi(_)));
+    }
+
+    #[test]
+    fn test_build_patch_override() {
+        assert_eq!(BuildPatch::Current.resolve("14.10.1"), None);
+        assert_eq!(BuildPatch::Previous.resolve("14.10.1").as_deref(), Some("14.9"));
+        assert_eq!(BuildPatch::Previous.resolve("15.1.1"), None);
+
+        let fixed: BuildPatch = serde_json::from_value(serde_json::json!({ "kind": "fixed", "patch": "14.8" })).unwrap();
+        assert_eq!(fixed.resolve("14.10.1").as_deref(), Some("14.8"));
+        assert_eq!(BuildPatch::Fixed { patch: "latest".to_string() }.resolve("14.10.1"), None);
+    }
+
+    #[test]
+    fn test_local_candidate_files() {
+        let mut request = ImportPayloadRequest {
+            champion_id: 62,
+            champion_key: "MonkeyKing".to_string(),
+            role: "Top".to_string(),
+            ..import_request()
+        };
+
+        assert_eq!(
+            LocalFileProvider::candidate_files(&request),
+            Some(vec!["monkeyking-top.json".to_string(), "monkeyking.json".to_string()])
+        );
+
+        request.champion_key = "../secret".to_string();
+        assert_eq!(LocalFileProvider::candidate_files(&request), None);
+    }
+}
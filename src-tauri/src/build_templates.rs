@@ -0,0 +1,234 @@
+//! User-authored build templates.
+//!
+//! A template is a build written by the user (runes, items and summoner
+//! spells for a champion and role), stored in the app config directory. It
+//! has the shape of the import request sent for FocusApi builds, so
+//! importing a template goes through `import_build_to_client` like any other
+//! build: same payload resolution, same offline queue, same pages in the
+//! client.
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::cancel::InFlightImport;
+use crate::lcu::ImportResult;
+use crate::sessions::now_unix_secs;
+use crate::error::AppError;
+use crate::{storage, CommandError, ImportPayloadRequest};
+
+/// File holding the templates
+const TEMPLATES_FILE: &str = "build-templates.json";
+
+/// Source sent with template builds
+pub const TEMPLATE_SOURCE: &str = "template";
+
+/// Roles a template can target
+const ROLES: &[&str] = &["top", "jungle", "mid", "adc", "bottom", "support", "aram"];
+
+/// A stored build template
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildTemplate {
+    pub id: String,
+    /// Unix timestamps (seconds)
+    pub created_at: i64,
+    pub updated_at: i64,
+    /// The build itself, in the import request format
+    pub build: ImportPayloadRequest,
+}
+
+impl BuildTemplate {
+    /// Import request for this template
+    pub fn to_request(&self) -> ImportPayloadRequest {
+        let mut request = self.build.clone();
+        request.source = TEMPLATE_SOURCE.to_string();
+        request
+    }
+}
+
+/// Check that a build can be imported
+fn validate_build(build: &ImportPayloadRequest) -> Result<(), String> {
+    if build.champion_key.trim().is_empty() {
+        return Err("A template needs a champion".to_string());
+    }
+    if !ROLES.contains(&build.role.trim().to_lowercase().as_str()) {
+        return Err(format!("Unknown role: {}", build.role));
+    }
+    if build.title.trim().is_empty() {
+        return Err("A template needs a title".to_string());
+    }
+    if build.runes_primary.rune_ids.len() > 4 || build.runes_secondary.rune_ids.len() > 2 {
+        return Err("Too many runes (4 primary and 2 secondary at most)".to_string());
+    }
+    if build.rune_shards.len() != 3 {
+        return Err("A rune page needs exactly 3 stat shards".to_string());
+    }
+    if build.summoner_spells.len() > 2 {
+        return Err("At most 2 summoner spells".to_string());
+    }
+    Ok(())
+}
+
+pub(crate) fn load_templates(app: &AppHandle) -> Vec<BuildTemplate> {
+    storage::read_json(app, TEMPLATES_FILE)
+}
+
+pub(crate) fn save_templates(app: &AppHandle, templates: &[BuildTemplate]) -> Result<(), String> {
+    storage::write_json(app, TEMPLATES_FILE, &templates)
+}
+
+/// New unique template ID
+fn new_template_id(templates: &[BuildTemplate]) -> String {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    let mut id = format!("tpl-{:x}", nanos);
+    while templates.iter().any(|t| t.id == id) {
+        id.push('0');
+    }
+    id
+}
+
+/// Create (`id` None) or replace a template
+fn upsert_template(
+    templates: &mut Vec<BuildTemplate>,
+    id: Option<String>,
+    mut build: ImportPayloadRequest,
+    now: i64,
+) -> Result<BuildTemplate, String> {
+    validate_build(&build)?;
+    build.role = build.role.trim().to_lowercase();
+    build.source = TEMPLATE_SOURCE.to_string();
+
+    let template = match id {
+        Some(id) => {
+            let existing = templates
+                .iter_mut()
+                .find(|t| t.id == id)
+                .ok_or_else(|| format!("Template not found: {}", id))?;
+            existing.build = build;
+            existing.updated_at = now;
+            existing.clone()
+        }
+        None => {
+            let template = BuildTemplate {
+                id: new_template_id(templates),
+                created_at: now,
+                updated_at: now,
+                build,
+            };
+            templates.push(template.clone());
+            template
+        }
+    };
+    Ok(template)
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// List the templates, optionally for one champion and/or role.
+#[tauri::command]
+pub async fn list_build_templates(
+    app: AppHandle,
+    champion_key: Option<String>,
+    role: Option<String>,
+) -> Result<Vec<BuildTemplate>, String> {
+    let matches = |value: &str, filter: &Option<String>| {
+        filter.as_deref().is_none_or(|f| f.trim().eq_ignore_ascii_case(value))
+    };
+    Ok(load_templates(&app)
+        .into_iter()
+        .filter(|t| matches(&t.build.champion_key, &champion_key) && matches(&t.build.role, &role))
+        .collect())
+}
+
+/// Get one template.
+#[tauri::command]
+pub async fn get_build_template(app: AppHandle, id: String) -> Result<BuildTemplate, String> {
+    load_templates(&app)
+        .into_iter()
+        .find(|t| t.id == id)
+        .ok_or_else(|| format!("Template not found: {}", id))
+}
+
+/// Create a template (`id` omitted) or update an existing one.
+#[tauri::command]
+pub async fn save_build_template(
+    app: AppHandle,
+    id: Option<String>,
+    build: ImportPayloadRequest,
+) -> Result<BuildTemplate, String> {
+    let mut templates = load_templates(&app);
+    let template = upsert_template(&mut templates, id, build, now_unix_secs())?;
+    save_templates(&app, &templates)?;
+    Ok(template)
+}
+
+/// Delete a template. Returns false if it did not exist.
+#[tauri::command]
+pub async fn delete_build_template(app: AppHandle, id: String) -> Result<bool, String> {
+    let mut templates = load_templates(&app);
+    let count = templates.len();
+    templates.retain(|t| t.id != id);
+    if templates.len() == count {
+        return Ok(false);
+    }
+    save_templates(&app, &templates)?;
+    Ok(true)
+}
+
+/// Import a template into the League Client (same path as FocusApi builds).
+#[tauri::command]
+pub async fn import_build_template(
+    app: AppHandle,
+    in_flight: tauri::State<'_, InFlightImport>,
+    id: String,
+) -> Result<ImportResult, CommandError> {
+    let template = get_build_template(app.clone(), id).await.map_err(AppError::NotFound)?;
+    crate::import_build_to_client(app, in_flight, template.to_request()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_server::import_request;
+
+    fn build(role: &str, rune_shards: Vec<i64>) -> ImportPayloadRequest {
+        ImportPayloadRequest {
+            role: role.to_string(),
+            rune_shards,
+            ..import_request()
+        }
+    }
+
+    #[test]
+    fn test_validate_build() {
+        assert!(validate_build(&build("Mid", vec![5008, 5008, 5001])).is_ok());
+        assert!(validate_build(&build("roam", vec![5008, 5008, 5001])).is_err());
+        assert!(validate_build(&build("mid", vec![5008])).is_err());
+    }
+
+    #[test]
+    fn test_upsert_template() {
+        let mut templates = Vec::new();
+        let created = upsert_template(&mut templates, None, build("MID", vec![5008, 5008, 5001]), 10).unwrap();
+        assert_eq!(created.build.role, "mid");
+        assert_eq!(created.to_request().source, TEMPLATE_SOURCE);
+
+        let updated = upsert_template(
+            &mut templates,
+            Some(created.id.clone()),
+            build("top", vec![5008, 5008, 5001]),
+            20,
+        )
+        .unwrap();
+        assert_eq!(templates.len(), 1);
+        assert_eq!((updated.created_at, updated.updated_at), (10, 20));
+        assert_eq!(templates[0].build.role, "top");
+
+        assert!(upsert_template(&mut templates, Some("missing".to_string()), build("top", vec![1, 2, 3]), 30).is_err());
+    }
+}
@@ -0,0 +1,289 @@
+//! Import of every viable role of a champion at once.
+//!
+//! Fill players do not know their role before queueing. `import_all_roles`
+//! looks up the roles the champion is played in (`roles::champion_roles`),
+//! fetches the FocusApi build of each, turns it into an import request with
+//! `import_request::from_build` (like the frontend does for a single
+//! import), resolves the payloads like a normal import, and writes one
+//! role-suffixed item set per role ("Focus: Ahri MID", "Focus: Ahri TOP") in
+//! a single LCU item set update. Rune pages are optional: the FocusApp pages
+//! are replaced by one page per role until the page limit is reached, and the
+//! player's own pages are never deleted.
+//!
+//! Like a single import, it goes through `InFlightImport` (a newer import or
+//! `cancel_import` stops it before it writes to the client) and every role is
+//! recorded in the import history.
+//!
+//! # Compliance Note
+//!
+//! Triggered by an explicit user action only. The number of FocusApi and LCU
+//! requests is bounded by the number of roles (at most five), with a single
+//! item set update.
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{AppHandle, Manager};
+
+use crate::cancel::InFlightImport;
+use crate::champions;
+use crate::error::AppError;
+use crate::import_request::{self, FLASH};
+use crate::lcu::{self, ImportResult, FOCUS_ITEM_SET_PREFIX, FOCUS_RUNE_PAGE_PREFIX};
+use crate::settings::{FlashKey, SettingsStore};
+use crate::{focus_api, import_history, roles, CommandError, ImportPayloadRequest};
+
+/// Most roles a champion can be imported for
+const MAX_ROLES: usize = 5;
+
+/// A role that could not be imported
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoleImportFailure {
+    pub role: String,
+    pub code: String,
+    pub message: String,
+}
+
+/// Result of `import_all_roles`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkImportResult {
+    pub success: bool,
+    /// Roles whose item set was written
+    pub item_sets_imported: Vec<String>,
+    /// Roles whose rune page was created
+    pub rune_pages_imported: Vec<String>,
+    pub failed: Vec<RoleImportFailure>,
+    pub message: String,
+}
+
+/// Name of a role's rune page or item set: "{prefix}{Champion} {ROLE}"
+fn role_title(prefix: &str, champion: &str, role: &str) -> String {
+    format!("{}{} {}", prefix, champion, role.to_uppercase())
+}
+
+/// Champion name as the FocusApi build endpoint expects it ("Kai'Sa" -> "kaisa")
+fn api_champion(name: &str) -> String {
+    name.chars()
+        .filter(|c| !c.is_whitespace() && *c != '\'' && *c != '.')
+        .collect::<String>()
+        .to_lowercase()
+}
+
+/// Role as the FocusApi build endpoint expects it
+fn api_role(role: &str) -> String {
+    match role.to_lowercase().as_str() {
+        "adc" => "bottom".to_string(),
+        role => role.to_string(),
+    }
+}
+
+/// Put Flash on the key chosen in the settings
+fn apply_flash_key(summoner_spells: &mut [i64], flash_key: Option<FlashKey>) {
+    let (Some(flash_key), 2) = (flash_key, summoner_spells.len()) else {
+        return;
+    };
+    let flash_slot = if flash_key == FlashKey::D { 0 } else { 1 };
+    if summoner_spells[1 - flash_slot] == FLASH && summoner_spells[flash_slot] != FLASH {
+        summoner_spells.swap(0, 1);
+    }
+}
+
+/// History entry of a role: what was written to the client for it
+fn role_result(item_set: bool, rune_page: bool, message: &str) -> ImportResult {
+    ImportResult {
+        success: item_set || rune_page,
+        runes_imported: rune_page,
+        items_imported: item_set,
+        summoners_imported: false,
+        message: message.to_string(),
+        queued: false,
+    }
+}
+
+/// Message for the rune pages of `create_rune_pages`
+fn rune_pages_message(created: &lcu::CreatedRunePages, requested: usize) -> String {
+    match &created.stopped_by {
+        None => format!("{} rune pages imported", created.created),
+        Some(e) => format!("{} of {} rune pages imported ({})", created.created, requested, e),
+    }
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Import the builds of every viable role of a champion into the League Client.
+///
+/// `champion` is a name, DDragon key or numeric ID. Item sets of all roles are
+/// written in one update and replace the existing FocusApp item sets. With
+/// `include_rune_pages`, the FocusApp rune pages are replaced by one page per
+/// role until the page limit is reached; the player's own pages are kept.
+#[tauri::command]
+pub async fn import_all_roles(
+    app: AppHandle,
+    in_flight: tauri::State<'_, InFlightImport>,
+    champion: String,
+    include_rune_pages: bool,
+) -> Result<BulkImportResult, CommandError> {
+    // A newer import (champion re-picked) cancels this one
+    let token = in_flight.begin();
+    let result = perform_bulk_import(&app, &champion, include_rune_pages, &token).await;
+    in_flight.finish(&token);
+    if token.is_cancelled() {
+        log_info!("[import_all_roles] Import of {} cancelled", champion.trim());
+        return Err(AppError::Cancelled.into());
+    }
+    result
+}
+
+async fn perform_bulk_import(
+    app: &AppHandle,
+    champion: &str,
+    include_rune_pages: bool,
+    token: &crate::cancel::CancelToken,
+) -> Result<BulkImportResult, CommandError> {
+    let champion = champions::champion_info(champion)
+        .ok_or_else(|| AppError::InvalidData(format!("Unknown champion '{}'", champion.trim())))?;
+    let mut roles = roles::champion_roles(champion.champion_id, Some(&champion.ddragon_key));
+    roles.truncate(MAX_ROLES);
+    if roles.is_empty() {
+        return Err(AppError::InvalidData(format!("No roles known for {}", champion.name)).into());
+    }
+    lcu::ensure_writable()?;
+    let connection = lcu::find_lockfile().await?;
+    let flash_key = app
+        .try_state::<SettingsStore>()
+        .and_then(|store| store.get().flash_key);
+
+    // Request, and whether its item set / rune page was built, of each role
+    let mut requests: Vec<(ImportPayloadRequest, bool, bool)> = Vec::new();
+    let mut item_sets = Vec::new();
+    let mut rune_pages = Vec::new();
+    let mut failed = Vec::new();
+
+    for role in &roles {
+        let build = focus_api::fetch_champion_build(api_champion(&champion.name), api_role(role), false, None).await;
+        let mut request = import_request::from_build(build.as_ref().unwrap_or(&Value::Null), &champion, role, None);
+        apply_flash_key(&mut request.summoner_spells, flash_key);
+        let resolved = match build {
+            Ok(_) => crate::resolve_import_payloads(app, &request).await,
+            Err(e) => Err(e),
+        };
+        let response = match resolved {
+            Ok(response) => response,
+            Err(e) => {
+                log_warn!("[import_all_roles] No payload for {} {}: {}", champion.name, role, e.message);
+                failed.push(RoleImportFailure {
+                    role: role.clone(),
+                    code: e.code.clone(),
+                    message: e.message.clone(),
+                });
+                import_history::record_result(app, &request, &Err(e));
+                continue;
+            }
+        };
+        let champion_name = response.champion.clone().unwrap_or_else(|| champion.name.clone());
+        let role_name = response.role.clone().unwrap_or_else(|| role.clone());
+
+        let has_item_set = response.item_set_payload.is_some();
+        if let Some(mut item_set) = response.item_set_payload {
+            item_set.title = role_title(FOCUS_ITEM_SET_PREFIX, &champion_name, &role_name);
+            item_sets.push(item_set);
+        }
+        let mut has_rune_page = false;
+        if include_rune_pages {
+            if let Some(mut rune_page) = response.rune_page_payload {
+                rune_page.name = role_title(FOCUS_RUNE_PAGE_PREFIX, &champion_name, &role_name);
+                rune_pages.push(rune_page);
+                has_rune_page = true;
+            }
+        }
+        requests.push((request, has_item_set, has_rune_page));
+    }
+
+    let mut messages = Vec::new();
+    let mut item_sets_written = false;
+    if !item_sets.is_empty() {
+        token.check()?;
+        match lcu::add_item_sets(&connection, &item_sets).await {
+            Ok(()) => {
+                item_sets_written = true;
+                messages.push(format!("{} item sets imported", item_sets.len()));
+            }
+            Err(e) => {
+                log_warn!("[import_all_roles] Failed to import item sets: {}", e);
+                messages.push(format!("Failed to import items: {}", e));
+            }
+        }
+    }
+    let mut rune_pages_created = 0;
+    if !rune_pages.is_empty() {
+        token.check()?;
+        match lcu::create_rune_pages(&connection, &rune_pages).await {
+            Ok(created) => {
+                messages.push(rune_pages_message(&created, rune_pages.len()));
+                rune_pages_created = created.created;
+            }
+            Err(e) => {
+                log_warn!("[import_all_roles] Failed to import rune pages: {}", e);
+                messages.push(format!("Failed to import runes: {}", e));
+            }
+        }
+    }
+    if !failed.is_empty() {
+        let roles: Vec<&str> = failed.iter().map(|f| f.role.as_str()).collect();
+        messages.push(format!("No build for {}", roles.join(", ")));
+    }
+    let message = if messages.is_empty() {
+        "No data to import".to_string()
+    } else {
+        messages.join(". ")
+    };
+
+    // Rune pages were created in role order, up to the page limit
+    let mut item_set_roles = Vec::new();
+    let mut rune_page_roles = Vec::new();
+    let mut rune_pages_left = rune_pages_created;
+    for (request, has_item_set, has_rune_page) in &requests {
+        let item_set = *has_item_set && item_sets_written;
+        let rune_page = *has_rune_page && rune_pages_left > 0;
+        if *has_rune_page {
+            rune_pages_left = rune_pages_left.saturating_sub(1);
+        }
+        if item_set {
+            item_set_roles.push(request.role.clone());
+        }
+        if rune_page {
+            rune_page_roles.push(request.role.clone());
+        }
+        import_history::record_result(app, request, &Ok(role_result(item_set, rune_page, &message)));
+    }
+
+    Ok(BulkImportResult {
+        success: !item_set_roles.is_empty() || !rune_page_roles.is_empty(),
+        item_sets_imported: item_set_roles,
+        rune_pages_imported: rune_page_roles,
+        failed,
+        message,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_flash_key() {
+        let mut spells = vec![FLASH, 7];
+        apply_flash_key(&mut spells, Some(FlashKey::F));
+        assert_eq!(spells, vec![7, FLASH]);
+        apply_flash_key(&mut spells, None);
+        assert_eq!(spells, vec![7, FLASH]);
+        apply_flash_key(&mut spells, Some(FlashKey::D));
+        assert_eq!(spells, vec![FLASH, 7]);
+
+        assert_eq!((api_champion("Kai'Sa"), api_role("ADC")), ("kaisa".to_string(), "bottom".to_string()));
+        assert_eq!(role_title(FOCUS_ITEM_SET_PREFIX, "Ahri", "mid"), format!("{}Ahri MID", FOCUS_ITEM_SET_PREFIX));
+    }
+}
@@ -0,0 +1,266 @@
+//! Cooperative cancellation of in-flight work.
+//!
+//! A `CancelToken` is checked between the steps of a long operation; once
+//! cancelled, the next `check()` fails with `CANCELLED` so the operation
+//! stops before its next write, and `run()` drops the future it wraps.
+//!
+//! `InFlightImport` holds the token of the import running now: starting a
+//! new import (the user re-picked a champion mid-import) or calling
+//! `cancel_import` cancels the previous one before it writes the wrong rune
+//! page.
+//!
+//! Other long-running commands (FocusApi fetches, build prefetches, waiting
+//! for the client) take an optional `operationId` chosen by the frontend and
+//! register their token under it for as long as they run. `cancel_operation`
+//! cancels one by id, and a new request reusing the id of one still running
+//! cancels the older one, so an orphaned future never races a newer request.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
+use tokio::sync::Notify;
+
+use crate::error::AppError;
+
+#[derive(Debug, Default)]
+struct TokenState {
+    cancelled: AtomicBool,
+    notify: Notify,
+}
+
+/// Cancellation flag shared between an operation and whoever may cancel it
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken {
+    state: Arc<TokenState>,
+}
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.state.cancelled.store(true, Ordering::Relaxed);
+        self.state.notify.notify_waiters();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.state.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Fail with `CANCELLED` once cancelled
+    pub fn check(&self) -> Result<(), AppError> {
+        if self.is_cancelled() {
+            Err(AppError::Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn same_as(&self, other: &CancelToken) -> bool {
+        Arc::ptr_eq(&self.state, &other.state)
+    }
+
+    /// Resolves once the token is cancelled
+    pub async fn cancelled(&self) {
+        let notified = self.state.notify.notified();
+        tokio::pin!(notified);
+        // Registered before checking the flag, so a cancel in between is not missed
+        notified.as_mut().enable();
+        if self.is_cancelled() {
+            return;
+        }
+        notified.await;
+    }
+
+    /// Run `future` until it completes or the token is cancelled
+    pub async fn run<F: Future>(&self, future: F) -> Result<F::Output, AppError> {
+        tokio::select! {
+            output = future => Ok(output),
+            _ = self.cancelled() => Err(AppError::Cancelled),
+        }
+    }
+}
+
+/// Operations registered by id
+static OPERATIONS: LazyLock<Mutex<HashMap<String, CancelToken>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// A cancellable operation, registered under its id until dropped
+pub struct Operation {
+    id: Option<String>,
+    token: CancelToken,
+}
+
+impl Operation {
+    /// Run `future` until it completes or the operation is cancelled
+    pub async fn run<F: Future>(&self, future: F) -> Result<F::Output, AppError> {
+        self.token.run(future).await
+    }
+}
+
+impl Drop for Operation {
+    fn drop(&mut self) {
+        let Some(id) = &self.id else {
+            return;
+        };
+        if let Ok(mut operations) = OPERATIONS.lock() {
+            if operations.get(id).is_some_and(|t| t.same_as(&self.token)) {
+                operations.remove(id);
+            }
+        }
+    }
+}
+
+/// Start an operation, registered under `id` when given.
+///
+/// An operation still running under the same id is cancelled.
+pub fn start(id: Option<String>) -> Operation {
+    let id = id.map(|id| id.trim().to_string()).filter(|id| !id.is_empty());
+    let token = CancelToken::new();
+    if let Some(id) = &id {
+        if let Ok(mut operations) = OPERATIONS.lock() {
+            if let Some(previous) = operations.insert(id.clone(), token.clone()) {
+                log_debug!("[cancel] Operation '{}' superseded by a newer request", id);
+                previous.cancel();
+            }
+        }
+    }
+    Operation { id, token }
+}
+
+/// Cancel the operation registered under `id`, false if none is running
+pub fn cancel(id: &str) -> bool {
+    let token = OPERATIONS.lock().ok().and_then(|mut operations| operations.remove(id));
+    match token {
+        Some(token) => {
+            token.cancel();
+            true
+        }
+        None => false,
+    }
+}
+
+/// Token of the build import in progress (managed state)
+#[derive(Default)]
+pub struct InFlightImport {
+    current: Mutex<Option<CancelToken>>,
+}
+
+impl InFlightImport {
+    /// Token of a new import, cancelling the one in progress
+    pub fn begin(&self) -> CancelToken {
+        let token = CancelToken::new();
+        if let Ok(mut current) = self.current.lock() {
+            if let Some(previous) = current.replace(token.clone()) {
+                previous.cancel();
+            }
+        }
+        token
+    }
+
+    /// Token of a new import, or None while another one is in progress
+    pub fn begin_if_idle(&self) -> Option<CancelToken> {
+        let mut current = self.current.lock().ok()?;
+        if current.is_some() {
+            return None;
+        }
+        let token = CancelToken::new();
+        *current = Some(token.clone());
+        Some(token)
+    }
+
+    /// Forget `token` once its import is over (a newer import keeps its own)
+    pub fn finish(&self, token: &CancelToken) {
+        if let Ok(mut current) = self.current.lock() {
+            if current.as_ref().is_some_and(|t| t.same_as(token)) {
+                *current = None;
+            }
+        }
+    }
+
+    /// Cancel the import in progress, false if there is none
+    pub fn cancel(&self) -> bool {
+        let Ok(mut current) = self.current.lock() else {
+            return false;
+        };
+        match current.take() {
+            Some(token) => {
+                token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Abort the build import in progress before its next write to the client.
+///
+/// Returns false when no import is running.
+#[tauri::command]
+pub async fn cancel_import(in_flight: tauri::State<'_, InFlightImport>) -> Result<bool, String> {
+    let cancelled = in_flight.cancel();
+    if cancelled {
+        log_info!("[cancel_import] Import in progress cancelled");
+    }
+    Ok(cancelled)
+}
+
+/// Cancel a long-running command by the `operationId` it was started with.
+///
+/// Returns false when no operation with this id is running.
+#[tauri::command]
+pub async fn cancel_operation(id: String) -> Result<bool, String> {
+    let cancelled = cancel(&id);
+    if cancelled {
+        log_debug!("[cancel_operation] Operation '{}' cancelled", id);
+    }
+    Ok(cancelled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_import_cancels_previous() {
+        let in_flight = InFlightImport::default();
+        let first = in_flight.begin();
+        let second = in_flight.begin();
+        assert!(first.check().is_err());
+        assert!(second.check().is_ok());
+
+        // The stale import finishing does not forget the newer one
+        in_flight.finish(&first);
+        assert!(in_flight.cancel());
+        assert!(second.is_cancelled());
+        assert!(!in_flight.cancel());
+
+        // A queued import waits for the one in progress instead of cancelling it
+        let user = in_flight.begin();
+        assert!(in_flight.begin_if_idle().is_none());
+        assert!(!user.is_cancelled());
+        in_flight.finish(&user);
+        assert!(in_flight.begin_if_idle().is_some());
+    }
+
+    #[test]
+    fn test_operation_registry() {
+        let first = start(Some("build".to_string()));
+        let second = start(Some("build".to_string()));
+        // Reusing an id cancels the older request
+        assert!(first.token.is_cancelled());
+        drop(first);
+        assert!(cancel("build"));
+        assert!(second.token.is_cancelled());
+        assert!(!cancel("build"));
+
+        let anonymous = start(None);
+        drop(anonymous);
+        assert!(!cancel(""));
+    }
+}
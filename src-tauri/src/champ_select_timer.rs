@@ -0,0 +1,101 @@
+//! Time left in the current champion select phase.
+//!
+//! The session's `timer` block gives the time left when the client built the
+//! session, with the client's clock at that moment. `get_champ_select_timer`
+//! takes off the time elapsed since, so the frontend can count down from an
+//! accurate value and warn when the pick will lock before the user had time
+//! to review the build.
+
+use serde::Serialize;
+
+use crate::lcu::{self, ChampSelectTimerBlock};
+use crate::CommandError;
+
+/// Below this, the build can't be reviewed comfortably before the pick locks
+const REVIEW_TIME_MS: i64 = 10_000;
+
+/// Phases where the local player may still pick or change their champion
+const PICK_PHASES: &[&str] = &["BAN_PICK", "PLANNING"];
+
+/// Countdown of the champion select phase
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChampSelectTimer {
+    /// Client phase (e.g., "BAN_PICK", "FINALIZATION")
+    pub phase: String,
+    /// Time left in the phase now (ms, 0 once elapsed)
+    pub remaining_ms: i64,
+    pub total_ms: i64,
+    /// No countdown (custom games)
+    pub is_infinite: bool,
+    /// A pick phase with less than `REVIEW_TIME_MS` left
+    pub low_time: bool,
+}
+
+/// Timer at `now_ms` (Unix ms) from the session's block
+fn timer_at(block: &ChampSelectTimerBlock, now_ms: i64) -> ChampSelectTimer {
+    // A clock of 0 means the client did not say: use the value as sent
+    let elapsed = if block.internal_now_in_epoch_ms > 0 {
+        (now_ms - block.internal_now_in_epoch_ms).max(0)
+    } else {
+        0
+    };
+    let remaining_ms = (block.adjusted_time_left_in_phase - elapsed).max(0);
+    ChampSelectTimer {
+        phase: block.phase.clone(),
+        remaining_ms,
+        total_ms: block.total_time_in_phase.max(0),
+        is_infinite: block.is_infinite,
+        low_time: !block.is_infinite && PICK_PHASES.contains(&block.phase.as_str()) && remaining_ms < REVIEW_TIME_MS,
+    }
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the current champion select phase and the time left in it.
+///
+/// # Compliance Note
+/// - Read-only: the countdown is shown in the client UI
+#[tauri::command]
+pub async fn get_champ_select_timer() -> Result<ChampSelectTimer, CommandError> {
+    let connection = lcu::find_lockfile().await.map_err(CommandError::from)?;
+    let session = lcu::get_champion_select_session(&connection)
+        .await
+        .map_err(CommandError::from)?;
+    let now_ms = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64;
+    Ok(timer_at(&session.timer.unwrap_or_default(), now_ms))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timer_at() {
+        let block: ChampSelectTimerBlock = serde_json::from_value(serde_json::json!({
+            "phase": "BAN_PICK",
+            "adjustedTimeLeftInPhase": 27_000,
+            "internalNowInEpochMs": 1_000_000,
+            "totalTimeInPhase": 30_000,
+            "isInfinite": false
+        }))
+        .unwrap();
+
+        let timer = timer_at(&block, 1_005_000);
+        assert_eq!((timer.remaining_ms, timer.total_ms, timer.low_time), (22_000, 30_000, false));
+        assert!(timer_at(&block, 1_020_000).low_time);
+        assert_eq!(timer_at(&block, 2_000_000).remaining_ms, 0);
+
+        // Finalization: nothing left to pick, no warning
+        let finalization = ChampSelectTimerBlock {
+            phase: "FINALIZATION".to_string(),
+            ..block
+        };
+        assert!(!timer_at(&finalization, 1_020_000).low_time);
+    }
+}
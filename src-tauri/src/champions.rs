@@ -1,12 +1,592 @@
 //! Champion name normalization module for League of Legends.
 //!
 //! Provides deterministic mapping from API names to display names and DDragon icon URLs.
+//!
+//! The DDragon version used in URLs is resolved at runtime from
+//! `versions.json` and refreshed periodically; until the first successful
+//! fetch, a bundled fallback version is used.
+//!
+//! Champion names and keys come from DDragon's `championFull.json`, cached on
+//! disk per patch. On patch day, DDragon may lag behind the client: champions
+//! it does not know yet are resolved from CommunityDragon's champion summary.
+//! The static tables below are only an offline fallback.
+//!
+//! When the patch changes (new DDragon version, or a client patch detected by
+//! the game watcher), caches of older patches are removed (DDragon data and
+//! icons, cached FocusApi items and import payloads), data is re-fetched and
+//! `static-data-updated` is emitted so the frontend refreshes icons.
 
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::sync::LazyLock;
+use std::sync::{LazyLock, RwLock};
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+use tokio::sync::Notify;
+
+use crate::events::AppEvent;
+use crate::{etag_cache, import_cache, queues, storage};
+
+/// Version used until the latest one has been fetched from DDragon
+const DDRAGON_FALLBACK_VERSION: &str = "14.10.1";
+pub(crate) const DDRAGON_BASE: &str = "https://ddragon.leagueoflegends.com/cdn";
+const DDRAGON_VERSIONS_URL: &str = "https://ddragon.leagueoflegends.com/api/versions.json";
+
+/// CommunityDragon game data, published straight from the client files
+const CDRAGON_GAME_DATA_BASE: &str =
+    "https://raw.communitydragon.org/latest/plugins/rcp-be-lol-game-data/global/default";
+pub(crate) const CDRAGON_CACHE_FILE: &str = "cdragon/champion-summary.json";
+
+/// How long a resolved version is considered fresh
+const DDRAGON_VERSION_TTL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// Delay before retrying after a failed version fetch
+const DDRAGON_VERSION_RETRY: Duration = Duration::from_secs(5 * 60);
+
+/// Last DDragon version resolved from the network
+struct ResolvedVersion {
+    version: String,
+    resolved_at: Instant,
+}
+
+static DDRAGON_VERSION: LazyLock<RwLock<Option<ResolvedVersion>>> = LazyLock::new(|| RwLock::new(None));
+
+/// Current DDragon version (resolved at runtime, or the bundled fallback).
+pub fn ddragon_version() -> String {
+    DDRAGON_VERSION
+        .read()
+        .ok()
+        .and_then(|v| v.as_ref().map(|r| r.version.clone()))
+        .unwrap_or_else(|| DDRAGON_FALLBACK_VERSION.to_string())
+}
+
+/// Whether the cached version is missing or older than the TTL.
+fn ddragon_version_is_stale() -> bool {
+    DDRAGON_VERSION
+        .read()
+        .ok()
+        .and_then(|v| v.as_ref().map(|r| r.resolved_at.elapsed() > DDRAGON_VERSION_TTL))
+        .unwrap_or(true)
+}
+
+/// Pick the latest game version from the `versions.json` list.
+///
+/// The list is ordered newest first but may contain non-game entries
+/// such as "lolpatch_7.17".
+fn parse_latest_version(versions: &[String]) -> Option<String> {
+    versions
+        .iter()
+        .find(|v| v.split('.').all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit())))
+        .cloned()
+}
+
+/// Fetch the latest DDragon version from `versions.json`.
+///
+/// Returns the version in use afterwards.
+pub async fn refresh_ddragon_version() -> Result<String, String> {
+    let client = crate::proxy::remote_client()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let versions: Vec<String> = client
+        .get(DDRAGON_VERSIONS_URL)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?
+        .error_for_status()
+        .map_err(|e| e.to_string())?
+        .json()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let version = parse_latest_version(&versions).ok_or("No game version in versions.json")?;
+
+    if let Ok(mut cached) = DDRAGON_VERSION.write() {
+        *cached = Some(ResolvedVersion {
+            version: version.clone(),
+            resolved_at: Instant::now(),
+        });
+    }
+
+    Ok(version)
+}
+
+/// Patch ("major.minor") of the installed client, as reported by the LCU
+static CLIENT_PATCH: LazyLock<RwLock<Option<String>>> = LazyLock::new(|| RwLock::new(None));
+
+/// Wakes the refresher up before its next scheduled refresh
+static REFRESH_REQUESTED: LazyLock<Notify> = LazyLock::new(Notify::new);
+
+/// Patch of a game or DDragon version: "14.10.584.1234" / "14.10.1" -> "14.10"
+pub fn patch_of(version: &str) -> String {
+    version.split('.').take(2).collect::<Vec<_>>().join(".")
+}
+
+/// Record the client's game version and trigger a refresh if the patch changed.
+pub fn set_client_game_version(version: &str) {
+    let patch = patch_of(version);
+    if let Ok(mut current) = CLIENT_PATCH.write() {
+        if current.as_deref() == Some(patch.as_str()) {
+            return;
+        }
+        *current = Some(patch);
+    }
+    if client_patch_ahead() {
+        log_info!("[champions] Client patch {} differs from DDragon data, refreshing", version);
+        REFRESH_REQUESTED.notify_one();
+    }
+}
+
+/// Whether the client runs a patch the static data has not caught up with yet
+fn client_patch_ahead() -> bool {
+    let client_patch = CLIENT_PATCH.read().ok().and_then(|p| p.clone());
+    client_patch.is_some_and(|patch| patch != patch_of(&ddragon_version()))
+}
+
+/// Remove cached static data of every patch other than `current_version`.
+fn invalidate_static_caches(app: &AppHandle, current_version: &str) {
+    let Ok(root) = storage::cache_file_path(app, "ddragon") else {
+        return;
+    };
+    let Ok(entries) = std::fs::read_dir(&root) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        if entry.file_name() != current_version {
+            if let Err(e) = std::fs::remove_dir_all(entry.path()) {
+                log_warn!("[champions] Failed to remove stale cache {:?}: {}", entry.path(), e);
+            }
+        }
+    }
+}
+
+/// Keep the DDragon version and champion dataset up to date for the lifetime of the app.
+pub async fn run_ddragon_version_refresher(app: AppHandle) {
+    let mut loaded_version: Option<String> = None;
+
+    loop {
+        let delay = match refresh_ddragon_version().await {
+            Ok(version) => {
+                log_info!("[champions] DDragon version: {}", version);
+                match ensure_champion_dataset(&app, &version).await {
+                    Ok(()) => {
+                        if loaded_version.as_deref() != Some(version.as_str()) {
+                            on_static_data_changed(&app, &version, loaded_version.is_some()).await;
+                            loaded_version = Some(version);
+                        }
+                        // DDragon publishes a few hours after the client: check again soon
+                        if client_patch_ahead() {
+                            load_cdragon_fallback(&app).await;
+                            DDRAGON_VERSION_RETRY
+                        } else {
+                            DDRAGON_VERSION_TTL
+                        }
+                    }
+                    Err(e) => {
+                        log_warn!("[champions] Failed to load champion dataset: {}", e);
+                        load_cdragon_fallback(&app).await;
+                        DDRAGON_VERSION_RETRY
+                    }
+                }
+            }
+            Err(e) => {
+                log_warn!("[champions] Failed to resolve DDragon version: {}", e);
+                DDRAGON_VERSION_RETRY
+            }
+        };
+        let _ = tokio::time::timeout(delay, REFRESH_REQUESTED.notified()).await;
+    }
+}
+
+/// Fill the gaps of DDragon with CommunityDragon data (patch day).
+async fn load_cdragon_fallback(app: &AppHandle) {
+    let already_loaded = CDRAGON_DATASET.read().map(|d| d.is_some()).unwrap_or(false);
+    if already_loaded {
+        return;
+    }
+    match ensure_cdragon_champions(app).await {
+        Ok(()) => emit_static_data_updated(app, &ddragon_version()),
+        Err(e) => log_warn!("[champions] CommunityDragon fallback failed: {}", e),
+    }
+}
+
+/// Drop caches of older patches and notify the frontend of new static data.
+async fn on_static_data_changed(app: &AppHandle, version: &str, patch_changed: bool) {
+    invalidate_static_caches(app, version);
+    if patch_changed {
+        // Items and rune pages of the old patch may no longer exist
+        import_cache::clear(app);
+        etag_cache::clear();
+        // Queues are loaded at startup; only refresh them on a new patch
+        queues::refresh_queue_metadata(app.clone()).await;
+    }
+
+    emit_static_data_updated(app, version);
+}
+
+fn emit_static_data_updated(app: &AppHandle, version: &str) {
+    let event = AppEvent::StaticDataUpdated { version };
+    if let Err(e) = event.emit(app) {
+        log_warn!("[champions] {}", e);
+    }
+}
+
+/// Get the DDragon version currently used for icon URLs.
+#[tauri::command]
+pub async fn get_ddragon_version() -> String {
+    // Users opening the app right after a patch should not wait for the refresher
+    if ddragon_version_is_stale() {
+        let _ = refresh_ddragon_version().await;
+    }
+    ddragon_version()
+}
+
+// =============================================================================
+// CHAMPION DATASET (championFull.json)
+// =============================================================================
+
+/// A champion as described by DDragon
+#[derive(Debug, Clone)]
+pub struct ChampionEntry {
+    /// DDragon key used in asset URLs (e.g., "MonkeyKing")
+    pub ddragon_key: String,
+    /// Numeric champion ID used by the LCU (e.g., 62)
+    pub champion_id: i64,
+    /// Display name (e.g., "Wukong")
+    pub name: String,
+    /// Icon URL when DDragon has no asset for the champion yet (CommunityDragon data)
+    pub icon_url: Option<String>,
+    /// Passive and Q/W/E/R (empty for CommunityDragon data)
+    pub abilities: Vec<ChampionAbility>,
+}
+
+/// A champion ability, for skill-order tooltips
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChampionAbility {
+    /// "P", "Q", "W", "E" or "R"
+    pub slot: String,
+    pub name: String,
+    pub description: String,
+    /// Cooldown per rank in seconds (empty for the passive)
+    pub cooldowns: Vec<f64>,
+    pub max_rank: u32,
+    pub icon_url: String,
+}
+
+/// All champions of a given patch, indexed for name lookups
+#[derive(Debug, Default, Clone)]
+pub struct ChampionDataset {
+    pub version: String,
+    champions: Vec<ChampionEntry>,
+    /// Normalized name or key -> index in `champions`
+    lookup: HashMap<String, usize>,
+}
+
+#[derive(Deserialize)]
+struct ChampionFullFile {
+    version: String,
+    data: HashMap<String, ChampionFullEntry>,
+}
+
+#[derive(Deserialize)]
+struct ChampionFullEntry {
+    id: String,
+    key: String,
+    name: String,
+    /// Only present in `championFull.json`
+    #[serde(default)]
+    spells: Vec<DDragonSpell>,
+    #[serde(default)]
+    passive: Option<DDragonPassive>,
+}
+
+#[derive(Deserialize)]
+struct DDragonImage {
+    full: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DDragonSpell {
+    name: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    cooldown: Vec<f64>,
+    #[serde(default)]
+    maxrank: u32,
+    image: DDragonImage,
+}
+
+#[derive(Deserialize)]
+struct DDragonPassive {
+    name: String,
+    #[serde(default)]
+    description: String,
+    image: DDragonImage,
+}
+
+/// Convert the DDragon passive and spells of a champion to abilities
+fn parse_abilities(version: &str, passive: Option<DDragonPassive>, spells: Vec<DDragonSpell>) -> Vec<ChampionAbility> {
+    let passive = passive.map(|passive| ChampionAbility {
+        slot: "P".to_string(),
+        name: passive.name,
+        description: passive.description,
+        cooldowns: Vec::new(),
+        max_rank: 0,
+        icon_url: format!("{}/{}/img/passive/{}", DDRAGON_BASE, version, passive.image.full),
+    });
+
+    let spells = spells.into_iter().zip(["Q", "W", "E", "R"]).map(|(spell, slot)| ChampionAbility {
+        slot: slot.to_string(),
+        name: spell.name,
+        description: spell.description,
+        cooldowns: spell.cooldown,
+        max_rank: spell.maxrank,
+        icon_url: format!("{}/{}/img/spell/{}", DDRAGON_BASE, version, spell.image.full),
+    });
+
+    passive.into_iter().chain(spells).collect()
+}
+
+/// Entry of CommunityDragon's `champion-summary.json`
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CDragonChampion {
+    id: i64,
+    name: String,
+    /// Same as the DDragon key
+    alias: String,
+}
+
+impl ChampionDataset {
+    /// Parse the contents of `championFull.json` (or `champion.json`)
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let file: ChampionFullFile = serde_json::from_str(json).map_err(|e| e.to_string())?;
+
+        let mut dataset = ChampionDataset {
+            version: file.version,
+            ..ChampionDataset::default()
+        };
+
+        for entry in file.data.into_values() {
+            dataset.insert(ChampionEntry {
+                champion_id: entry.key.parse().unwrap_or_default(),
+                abilities: parse_abilities(&dataset.version, entry.passive, entry.spells),
+                ddragon_key: entry.id,
+                name: entry.name,
+                icon_url: None,
+            });
+        }
+
+        if dataset.champions.is_empty() {
+            return Err("Champion dataset is empty".to_string());
+        }
+        Ok(dataset)
+    }
+
+    /// Parse CommunityDragon's `champion-summary.json`
+    pub fn from_cdragon_summary(json: &str) -> Result<Self, String> {
+        let champions: Vec<CDragonChampion> = serde_json::from_str(json).map_err(|e| e.to_string())?;
+
+        let mut dataset = ChampionDataset {
+            version: "cdragon".to_string(),
+            ..ChampionDataset::default()
+        };
+
+        // The summary starts with a placeholder entry (id -1, "None")
+        for champion in champions.into_iter().filter(|c| c.id > 0) {
+            dataset.insert(ChampionEntry {
+                icon_url: Some(format!(
+                    "{}/v1/champion-icons/{}.png",
+                    CDRAGON_GAME_DATA_BASE, champion.id
+                )),
+                champion_id: champion.id,
+                ddragon_key: champion.alias,
+                name: champion.name,
+                abilities: Vec::new(),
+            });
+        }
+
+        if dataset.champions.is_empty() {
+            return Err("Champion summary is empty".to_string());
+        }
+        Ok(dataset)
+    }
+
+    fn insert(&mut self, entry: ChampionEntry) {
+        let index = self.champions.len();
+        self.lookup.insert(lookup_key(&entry.ddragon_key), index);
+        self.lookup.insert(lookup_key(&entry.name), index);
+        self.champions.push(entry);
+    }
+
+    /// Find a champion by API name, display name or DDragon key (case/punctuation-insensitive)
+    pub fn find(&self, name: &str) -> Option<&ChampionEntry> {
+        self.lookup.get(&lookup_key(name)).map(|&i| &self.champions[i])
+    }
+
+    /// Find a champion by its numeric LCU ID
+    pub fn find_by_id(&self, champion_id: i64) -> Option<&ChampionEntry> {
+        self.champions.iter().find(|c| c.champion_id == champion_id)
+    }
+
+    pub fn champions(&self) -> &[ChampionEntry] {
+        &self.champions
+    }
+}
+
+/// Normalize a name for lookups: "Kai'Sa" / "kaisa" / "KaiSa" -> "kaisa"
+fn lookup_key(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
 
-const DDRAGON_VERSION: &str = "14.10.1";
-const DDRAGON_BASE: &str = "https://ddragon.leagueoflegends.com/cdn";
+/// Dataset loaded for the current patch (None until the first load)
+static CHAMPION_DATASET: LazyLock<RwLock<Option<ChampionDataset>>> = LazyLock::new(|| RwLock::new(None));
+
+/// Install the bundled offline dataset if no live dataset is loaded yet
+pub fn install_bundled_dataset(dataset: ChampionDataset) {
+    if let Ok(mut current) = CHAMPION_DATASET.write() {
+        if current.is_none() {
+            *current = Some(dataset);
+        }
+    }
+}
+
+/// CommunityDragon dataset, loaded only when DDragon lags behind the client
+static CDRAGON_DATASET: LazyLock<RwLock<Option<ChampionDataset>>> = LazyLock::new(|| RwLock::new(None));
+
+/// Look up a champion in the loaded datasets (DDragon first, then CommunityDragon)
+fn find_in_dataset(name: &str) -> Option<ChampionEntry> {
+    let find = |dataset: &RwLock<Option<ChampionDataset>>| {
+        dataset.read().ok()?.as_ref()?.find(name).cloned()
+    };
+    find(&CHAMPION_DATASET).or_else(|| find(&CDRAGON_DATASET))
+}
+
+/// DDragon key of a champion from its numeric LCU ID (requires a loaded dataset)
+pub fn ddragon_key_for_id(champion_id: i64) -> Option<String> {
+    find_in_dataset_by_id(champion_id).map(|entry| entry.ddragon_key)
+}
+
+/// Look up a champion by numeric LCU ID in the loaded datasets
+fn find_in_dataset_by_id(champion_id: i64) -> Option<ChampionEntry> {
+    let find = |dataset: &RwLock<Option<ChampionDataset>>| {
+        dataset.read().ok()?.as_ref()?.find_by_id(champion_id).cloned()
+    };
+    find(&CHAMPION_DATASET).or_else(|| find(&CDRAGON_DATASET))
+}
+
+/// Icon URL of a dataset entry
+fn entry_icon_url(entry: &ChampionEntry) -> String {
+    entry
+        .icon_url
+        .clone()
+        .unwrap_or_else(|| get_champion_icon_url(&entry.ddragon_key))
+}
+
+async fn download_text(url: &str, timeout: Duration) -> Result<String, String> {
+    let client = crate::proxy::remote_client()
+        .timeout(timeout)
+        .build()
+        .map_err(|e| e.to_string())?;
+    client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?
+        .error_for_status()
+        .map_err(|e| e.to_string())?
+        .text()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Relative path of the cached dataset for a patch
+pub(crate) fn champion_cache_path(version: &str) -> String {
+    format!("ddragon/{}/championFull.json", version)
+}
+
+/// Load the champion dataset for `version`, from disk if cached, otherwise from DDragon.
+pub async fn ensure_champion_dataset(app: &AppHandle, version: &str) -> Result<(), String> {
+    let already_loaded = CHAMPION_DATASET
+        .read()
+        .map(|d| d.as_ref().is_some_and(|d| d.version == version))
+        .unwrap_or(false);
+    let path = storage::cache_file_path(app, &champion_cache_path(version))?;
+    // A deleted cache (integrity repair) forces a re-download
+    if already_loaded && path.exists() {
+        return Ok(());
+    }
+
+    // 1. Disk cache for this patch
+    let cached = tokio::fs::read_to_string(&path)
+        .await
+        .ok()
+        .and_then(|json| ChampionDataset::from_json(&json).ok())
+        .filter(|dataset| dataset.version == version);
+
+    let dataset = match cached {
+        Some(dataset) => dataset,
+        None => {
+            // 2. Download from DDragon and cache it
+            let url = format!("{}/{}/data/en_US/championFull.json", DDRAGON_BASE, version);
+            let json = download_text(&url, Duration::from_secs(30)).await?;
+
+            let dataset = ChampionDataset::from_json(&json)?;
+            storage::write_atomic(&path, json.as_bytes())?;
+            dataset
+        }
+    };
+
+    log_info!(
+        "[champions] Loaded {} champions for patch {}",
+        dataset.champions.len(),
+        dataset.version
+    );
+    if let Ok(mut current) = CHAMPION_DATASET.write() {
+        *current = Some(dataset);
+    }
+    Ok(())
+}
+
+/// Load CommunityDragon's champion summary, falling back to the last cached copy.
+pub async fn ensure_cdragon_champions(app: &AppHandle) -> Result<(), String> {
+    let path = storage::cache_file_path(app, CDRAGON_CACHE_FILE)?;
+    let url = format!("{}/v1/champion-summary.json", CDRAGON_GAME_DATA_BASE);
+
+    let dataset = match download_text(&url, Duration::from_secs(15)).await {
+        Ok(json) => {
+            let dataset = ChampionDataset::from_cdragon_summary(&json)?;
+            storage::write_atomic(&path, json.as_bytes())?;
+            dataset
+        }
+        Err(e) => {
+            let json = tokio::fs::read_to_string(&path)
+                .await
+                .map_err(|_| format!("CommunityDragon unavailable: {}", e))?;
+            ChampionDataset::from_cdragon_summary(&json)?
+        }
+    };
+
+    log_info!(
+        "[champions] Loaded {} champions from CommunityDragon",
+        dataset.champions.len()
+    );
+    if let Ok(mut current) = CDRAGON_DATASET.write() {
+        *current = Some(dataset);
+    }
+    Ok(())
+}
+
+// =============================================================================
+// OFFLINE FALLBACK TABLES
+// =============================================================================
 
 struct ChampionData {
     display_name: &'static str,
@@ -210,33 +790,175 @@ static SIMPLE_CHAMPIONS: LazyLock<HashMap<&'static str, &'static str>> = LazyLoc
 });
 
 pub fn normalize_champion(api_name: &str) -> Option<(String, String)> {
+    // Live DDragon data first
+    if let Some(entry) = find_in_dataset(api_name) {
+        let icon_url = entry_icon_url(&entry);
+        return Some((entry.name, icon_url));
+    }
+
     let key = api_name.to_lowercase();
     let key = key.trim();
 
     // Check special cases first
     if let Some(data) = CHAMPION_MAP.get(key) {
-        let icon_url = format!("{}/{}/img/champion/{}.png", DDRAGON_BASE, DDRAGON_VERSION, data.ddragon_key);
+        let icon_url = format!("{}/{}/img/champion/{}.png", DDRAGON_BASE, ddragon_version(), data.ddragon_key);
         return Some((data.display_name.to_string(), icon_url));
     }
 
     // Check simple champions
     if let Some(ddragon_key) = SIMPLE_CHAMPIONS.get(key) {
         let display_name = capitalize_first(ddragon_key);
-        let icon_url = format!("{}/{}/img/champion/{}.png", DDRAGON_BASE, DDRAGON_VERSION, ddragon_key);
+        let icon_url = format!("{}/{}/img/champion/{}.png", DDRAGON_BASE, ddragon_version(), ddragon_key);
         return Some((display_name, icon_url));
     }
 
     // Fallback: humanize the input
     let display_name = humanize_name(api_name);
-    let icon_url = format!("{}/{}/img/champion/{}.png", DDRAGON_BASE, DDRAGON_VERSION, capitalize_first(api_name));
+    let icon_url = format!("{}/{}/img/champion/{}.png", DDRAGON_BASE, ddragon_version(), capitalize_first(api_name));
     Some((display_name, icon_url))
 }
 
 pub fn get_champion_icon_url(ddragon_key: &str) -> String {
-    format!("{}/{}/img/champion/{}.png", DDRAGON_BASE, DDRAGON_VERSION, ddragon_key)
+    format!("{}/{}/img/champion/{}.png", DDRAGON_BASE, ddragon_version(), ddragon_key)
+}
+
+/// Convert an LCU skin ID (champion ID * 1000 + skin number) to a skin number.
+pub fn skin_num_from_skin_id(skin_id: i64) -> u32 {
+    skin_id.rem_euclid(1000) as u32
+}
+
+/// Splash art URL for a champion skin (`skin_num` 0 is the base skin).
+///
+/// Splash and loading-screen art are not versioned on DDragon.
+pub fn get_champion_splash_url(ddragon_key: &str, skin_num: u32) -> String {
+    format!("{}/img/champion/splash/{}_{}.jpg", DDRAGON_BASE, ddragon_key, skin_num)
+}
+
+/// Loading-screen art URL for a champion skin (`skin_num` 0 is the base skin).
+pub fn get_loading_art_url(ddragon_key: &str, skin_num: u32) -> String {
+    format!("{}/img/champion/loading/{}_{}.jpg", DDRAGON_BASE, ddragon_key, skin_num)
+}
+
+/// Art URLs of a champion skin, for the frontend
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChampionArtUrls {
+    pub icon_url: String,
+    pub splash_url: String,
+    pub loading_url: String,
+}
+
+/// Get the icon, splash and loading-screen URLs of a champion skin.
+///
+/// `champion` accepts any name understood by `get_ddragon_key`; `skin_id` is
+/// either a skin number or a full LCU skin ID.
+#[tauri::command]
+pub async fn get_champion_art_urls(champion: String, skin_id: Option<i64>) -> Result<ChampionArtUrls, String> {
+    let ddragon_key = get_ddragon_key(&champion).ok_or("Unknown champion")?;
+    let skin_num = skin_id.map(skin_num_from_skin_id).unwrap_or(0);
+    let icon_url = find_in_dataset(&champion)
+        .map(|entry| entry_icon_url(&entry))
+        .unwrap_or_else(|| get_champion_icon_url(&ddragon_key));
+
+    Ok(ChampionArtUrls {
+        icon_url,
+        splash_url: get_champion_splash_url(&ddragon_key, skin_num),
+        loading_url: get_loading_art_url(&ddragon_key, skin_num),
+    })
+}
+
+/// Normalized champion data for the frontend
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChampionInfo {
+    /// Numeric LCU ID (0 when only the static fallback tables know the champion)
+    pub champion_id: i64,
+    pub ddragon_key: String,
+    pub name: String,
+    pub icon_url: String,
+}
+
+impl From<&ChampionEntry> for ChampionInfo {
+    fn from(entry: &ChampionEntry) -> Self {
+        ChampionInfo {
+            champion_id: entry.champion_id,
+            ddragon_key: entry.ddragon_key.clone(),
+            name: entry.name.clone(),
+            icon_url: entry_icon_url(entry),
+        }
+    }
+}
+
+/// Resolve a champion from its numeric ID or any form of its name
+pub fn champion_info(id_or_name: &str) -> Option<ChampionInfo> {
+    let id_or_name = id_or_name.trim();
+    if let Ok(champion_id) = id_or_name.parse::<i64>() {
+        return find_in_dataset_by_id(champion_id).map(|entry| ChampionInfo::from(&entry));
+    }
+
+    if let Some(entry) = find_in_dataset(id_or_name) {
+        return Some(ChampionInfo::from(&entry));
+    }
+
+    // Static fallback tables (no numeric IDs)
+    let (name, icon_url) = normalize_champion(id_or_name)?;
+    Some(ChampionInfo {
+        champion_id: 0,
+        ddragon_key: get_ddragon_key(id_or_name)?,
+        name,
+        icon_url,
+    })
+}
+
+/// All known champions sorted by name (DDragon, completed by CommunityDragon)
+pub fn list_champion_infos() -> Vec<ChampionInfo> {
+    let mut champions: Vec<ChampionInfo> = Vec::new();
+    for dataset in [&CHAMPION_DATASET, &CDRAGON_DATASET] {
+        let Ok(dataset) = dataset.read() else {
+            continue;
+        };
+        let Some(dataset) = dataset.as_ref() else {
+            continue;
+        };
+        for entry in dataset.champions() {
+            if !champions.iter().any(|c| c.champion_id == entry.champion_id) {
+                champions.push(ChampionInfo::from(entry));
+            }
+        }
+    }
+
+    champions.sort_by(|a, b| a.name.cmp(&b.name));
+    champions
+}
+
+/// Get a champion's display name, DDragon key and icon from its ID or name.
+#[tauri::command]
+pub async fn get_champion_info(id_or_name: String) -> Result<Option<ChampionInfo>, String> {
+    Ok(champion_info(&id_or_name))
+}
+
+/// List all champions with display names, DDragon keys and icons.
+#[tauri::command]
+pub async fn list_champions() -> Result<Vec<ChampionInfo>, String> {
+    Ok(list_champion_infos())
+}
+
+/// Get the passive and Q/W/E/R of a champion (name, cooldowns, icon).
+///
+/// `champion` accepts a display name, an API name or a DDragon key. Returns
+/// an empty list until the DDragon dataset has been loaded.
+#[tauri::command]
+pub async fn get_champion_abilities(champion: String) -> Result<Vec<ChampionAbility>, String> {
+    Ok(find_in_dataset(&champion)
+        .map(|entry| entry.abilities)
+        .unwrap_or_default())
 }
 
 pub fn get_ddragon_key(api_name: &str) -> Option<String> {
+    if let Some(entry) = find_in_dataset(api_name) {
+        return Some(entry.ddragon_key);
+    }
+
     let key = api_name.to_lowercase();
 
     if let Some(data) = CHAMPION_MAP.get(key.as_str()) {
@@ -392,6 +1114,129 @@ mod tests {
         assert_eq!(get_ddragon_key("jarvaniv"), Some("JarvanIV".to_string()));
     }
 
+    const CHAMPION_FULL_SAMPLE: &str = r#"{
+        "type": "champion",
+        "format": "full",
+        "version": "15.1.1",
+        "data": {
+            "MonkeyKing": { "id": "MonkeyKing", "key": "62", "name": "Wukong", "title": "the Monkey King" },
+            "Kaisa": { "id": "Kaisa", "key": "145", "name": "Kai'Sa", "title": "Daughter of the Void" },
+            "Mel": { "id": "Mel", "key": "800", "name": "Mel", "title": "the Soul's Reflection" },
+            "Ahri": {
+                "id": "Ahri", "key": "103", "name": "Ahri", "title": "the Nine-Tailed Fox",
+                "passive": { "name": "Essence Theft", "description": "", "image": { "full": "Ahri_Passive.png" } },
+                "spells": [
+                    { "id": "AhriQ", "name": "Orb of Deception", "cooldown": [7, 7, 7, 7, 7], "maxrank": 5, "image": { "full": "AhriQ.png" } },
+                    { "id": "AhriW", "name": "Fox-Fire", "cooldown": [9, 8, 7, 6, 5], "maxrank": 5, "image": { "full": "AhriW.png" } },
+                    { "id": "AhriE", "name": "Charm", "cooldown": [14, 14, 14, 14, 14], "maxrank": 5, "image": { "full": "AhriE.png" } },
+                    { "id": "AhriR", "name": "Spirit Rush", "cooldown": [130, 115, 100], "maxrank": 3, "image": { "full": "AhriR.png" } }
+                ]
+            }
+        },
+        "keys": { "62": "MonkeyKing", "145": "Kaisa", "800": "Mel" }
+    }"#;
+
+    #[test]
+    fn test_champion_dataset_lookup() {
+        let dataset = ChampionDataset::from_json(CHAMPION_FULL_SAMPLE).unwrap();
+        assert_eq!(dataset.version, "15.1.1");
+
+        let wukong = dataset.find("wukong").unwrap();
+        assert_eq!(wukong.ddragon_key, "MonkeyKing");
+        assert_eq!(wukong.champion_id, 62);
+        assert_eq!(dataset.find("MonkeyKing").unwrap().name, "Wukong");
+
+        assert_eq!(dataset.find("KAI'SA").unwrap().ddragon_key, "Kaisa");
+        // Champions missing from the static tables resolve from the dataset
+        assert_eq!(dataset.find("mel").unwrap().champion_id, 800);
+        assert_eq!(dataset.find_by_id(145).unwrap().name, "Kai'Sa");
+        assert!(dataset.find("unknown").is_none());
+    }
+
+    #[test]
+    fn test_cdragon_champion_summary() {
+        let json = r#"[
+            { "id": -1, "name": "None", "alias": "None", "squarePortraitPath": "", "roles": [] },
+            { "id": 800, "name": "Mel", "alias": "Mel", "squarePortraitPath": "/lol-game-data/assets/v1/champion-icons/800.png", "roles": ["mage"] }
+        ]"#;
+
+        let dataset = ChampionDataset::from_cdragon_summary(json).unwrap();
+        assert!(dataset.find("none").is_none());
+
+        let mel = dataset.find("Mel").unwrap();
+        assert_eq!(mel.champion_id, 800);
+        assert_eq!(mel.ddragon_key, "Mel");
+        assert!(entry_icon_url(mel).ends_with("/v1/champion-icons/800.png"));
+    }
+
+    #[test]
+    fn test_champion_abilities() {
+        let dataset = ChampionDataset::from_json(CHAMPION_FULL_SAMPLE).unwrap();
+
+        let abilities = &dataset.find("ahri").unwrap().abilities;
+        let slots: Vec<&str> = abilities.iter().map(|a| a.slot.as_str()).collect();
+        assert_eq!(slots, ["P", "Q", "W", "E", "R"]);
+        assert_eq!(abilities[4].name, "Spirit Rush");
+        assert_eq!(abilities[4].cooldowns, vec![130.0, 115.0, 100.0]);
+        assert_eq!(abilities[4].max_rank, 3);
+        assert!(abilities[0].icon_url.ends_with("/15.1.1/img/passive/Ahri_Passive.png"));
+        assert!(abilities[2].icon_url.ends_with("/15.1.1/img/spell/AhriW.png"));
+
+        // champion.json entries carry no spells
+        assert!(dataset.find("mel").unwrap().abilities.is_empty());
+    }
+
+    #[test]
+    fn test_champion_dataset_rejects_invalid_json() {
+        assert!(ChampionDataset::from_json("{").is_err());
+        assert!(ChampionDataset::from_json(r#"{ "version": "1", "data": {} }"#).is_err());
+    }
+
+    #[test]
+    fn test_parse_latest_version() {
+        let versions = vec![
+            "lolpatch_7.20".to_string(),
+            "15.1.1".to_string(),
+            "14.24.1".to_string(),
+        ];
+        assert_eq!(parse_latest_version(&versions), Some("15.1.1".to_string()));
+        assert_eq!(parse_latest_version(&[]), None);
+    }
+
+    #[test]
+    fn test_icon_url_uses_resolved_version() {
+        let url = get_champion_icon_url("Ahri");
+        assert!(url.contains(&format!("/{}/img/champion/", ddragon_version())));
+    }
+
+    #[test]
+    fn test_patch_of() {
+        assert_eq!(patch_of("14.10.584.1234"), "14.10");
+        assert_eq!(patch_of("15.1.1"), "15.1");
+        assert_eq!(patch_of("15"), "15");
+    }
+
+    #[test]
+    fn test_champion_info_from_fallback_tables() {
+        let info = champion_info(" drmundo ").unwrap();
+        assert_eq!(info.name, "Dr. Mundo");
+        assert_eq!(info.ddragon_key, "DrMundo");
+        assert!(info.icon_url.ends_with("/DrMundo.png"));
+    }
+
+    #[test]
+    fn test_splash_and_loading_urls() {
+        assert_eq!(
+            get_champion_splash_url("MonkeyKing", 0),
+            "https://ddragon.leagueoflegends.com/cdn/img/champion/splash/MonkeyKing_0.jpg"
+        );
+        assert_eq!(
+            get_loading_art_url("Ahri", skin_num_from_skin_id(103015)),
+            "https://ddragon.leagueoflegends.com/cdn/img/champion/loading/Ahri_15.jpg"
+        );
+        assert_eq!(skin_num_from_skin_id(7), 7);
+    }
+
     #[test]
     fn test_icon_url_format() {
         let (_, url) = normalize_champion("ahri").unwrap();
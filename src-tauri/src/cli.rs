@@ -0,0 +1,102 @@
+//! Startup command-line flags.
+//!
+//! Parsed once in `main()` before Tauri is built. Each flag overrides the
+//! matching setting for the current session only, nothing is saved:
+//!
+//! - `--minimized`: stay in the tray instead of showing the main window
+//! - `--no-overlay`: never show the CS overlay automatically
+//! - `--log-level=<error|warn|info|debug>`: log verbosity
+//! - `--lockfile-path=<path>`: League Client lockfile to use instead of the
+//!   default install locations
+//!
+//! Other arguments (`--autostart`, `focusapp://` links) are left to the
+//! modules that handle them.
+
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use crate::logging::LogLevel;
+
+/// Session overrides given on the command line
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CliOptions {
+    pub minimized: bool,
+    pub no_overlay: bool,
+    pub log_level: Option<LogLevel>,
+    pub lockfile_path: Option<PathBuf>,
+}
+
+static OPTIONS: OnceLock<CliOptions> = OnceLock::new();
+
+/// Parse the process arguments (the first one is the executable)
+pub fn parse(args: impl IntoIterator<Item = String>) -> CliOptions {
+    let mut options = CliOptions::default();
+
+    for arg in args.into_iter().skip(1) {
+        let (flag, value) = match arg.split_once('=') {
+            Some((flag, value)) => (flag, Some(value)),
+            None => (arg.as_str(), None),
+        };
+        match (flag, value) {
+            ("--minimized", None) => options.minimized = true,
+            ("--no-overlay", None) => options.no_overlay = true,
+            ("--log-level", Some(level)) => {
+                match serde_json::from_value(serde_json::Value::from(level.to_lowercase())) {
+                    Ok(level) => options.log_level = Some(level),
+                    Err(_) => eprintln!("[cli] Ignoring unknown log level: {}", level),
+                }
+            }
+            ("--lockfile-path", Some(path)) if !path.trim().is_empty() => {
+                options.lockfile_path = Some(PathBuf::from(path.trim()));
+            }
+            _ => {}
+        }
+    }
+
+    options
+}
+
+/// Parse the process arguments for the session (first call wins)
+pub fn init(args: impl IntoIterator<Item = String>) -> &'static CliOptions {
+    OPTIONS.get_or_init(|| parse(args))
+}
+
+/// Options of the session (none before `init`)
+pub fn options() -> &'static CliOptions {
+    OPTIONS.get_or_init(CliOptions::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        let options = parse(args(&[
+            "focusapp.exe",
+            "--minimized",
+            "--log-level=DEBUG",
+            "--lockfile-path=D:\\Games\\League of Legends\\lockfile",
+            "--autostart",
+            "focusapp://import?champion=Ahri",
+        ]));
+        assert_eq!(
+            options,
+            CliOptions {
+                minimized: true,
+                no_overlay: false,
+                log_level: Some(LogLevel::Debug),
+                lockfile_path: Some(PathBuf::from("D:\\Games\\League of Legends\\lockfile")),
+            }
+        );
+
+        let options = parse(args(&["focusapp.exe", "--no-overlay", "--log-level=loud", "--minimized=yes"]));
+        assert!(options.no_overlay);
+        assert_eq!(options.log_level, None);
+        assert!(!options.minimized);
+    }
+}
@@ -0,0 +1,217 @@
+//! Access to the League Client (LCU) and Live Client Data APIs behind traits.
+//!
+//! The import flow and the game watcher only talk to the client through
+//! `LcuApi` and `LiveClientApi`. In the app they are implemented by the
+//! `LcuConnection` read from the lockfile and by `LiveClient::local()`
+//! (https://127.0.0.1:2999); in tests both point at a `wiremock` server
+//! (`mock_server`) serving recorded fixtures, so the flows run without a
+//! League client.
+
+use crate::game_watcher::{self, LiveEvent, LiveGameData, LIVE_CLIENT_PORT};
+use crate::lcu::{
+    self, ChampionSelectSession, GameflowPhase, GameflowSession, ItemSetPayload, LcuConnection, LcuError,
+    RunePagePayload, SummonerSpellsPayload,
+};
+
+/// League Client operations used by the import flow and the game watcher
+pub trait LcuApi {
+    /// Replace the FocusApp rune page with `payload`
+    async fn create_rune_page(&self, payload: &RunePagePayload) -> Result<(), LcuError>;
+
+    /// Replace the FocusApp item set with `item_set`
+    async fn add_item_set(&self, item_set: &ItemSetPayload) -> Result<(), LcuError>;
+
+    /// Set the summoner spells (champion select only)
+    async fn set_summoner_spells(&self, payload: &SummonerSpellsPayload) -> Result<(), LcuError>;
+
+    async fn gameflow_phase(&self) -> Result<GameflowPhase, String>;
+
+    async fn gameflow_session(&self) -> Result<GameflowSession, LcuError>;
+
+    async fn champion_select_session(&self) -> Result<ChampionSelectSession, LcuError>;
+}
+
+impl LcuApi for LcuConnection {
+    async fn create_rune_page(&self, payload: &RunePagePayload) -> Result<(), LcuError> {
+        lcu::create_rune_page(self, payload).await
+    }
+
+    async fn add_item_set(&self, item_set: &ItemSetPayload) -> Result<(), LcuError> {
+        lcu::add_item_set(self, item_set).await
+    }
+
+    async fn set_summoner_spells(&self, payload: &SummonerSpellsPayload) -> Result<(), LcuError> {
+        lcu::set_summoner_spells(self, payload).await
+    }
+
+    async fn gameflow_phase(&self) -> Result<GameflowPhase, String> {
+        game_watcher::fetch_gameflow_phase(self).await
+    }
+
+    async fn gameflow_session(&self) -> Result<GameflowSession, LcuError> {
+        lcu::get_gameflow_session(self).await
+    }
+
+    async fn champion_select_session(&self) -> Result<ChampionSelectSession, LcuError> {
+        lcu::get_champion_select_session(self).await
+    }
+}
+
+/// Live Client Data API operations used during a game
+pub trait LiveClientApi {
+    /// CS, gold and stats of the local player; fails outside of a game
+    async fn game_data(&self) -> Result<LiveGameData, String>;
+
+    /// Game time in seconds
+    async fn game_time(&self) -> Result<f64, String>;
+
+    /// Events announced to every player (kills of objectives, game end, ...)
+    async fn events(&self) -> Result<Vec<LiveEvent>, String>;
+}
+
+/// Live Client Data API served by the game
+#[derive(Debug, Clone)]
+pub struct LiveClient {
+    base_url: String,
+}
+
+impl LiveClient {
+    /// API of the game running on this machine
+    pub fn local() -> Self {
+        Self {
+            base_url: format!("https://127.0.0.1:{}", LIVE_CLIENT_PORT),
+        }
+    }
+
+    /// API served at `base_url` (e.g., a mock server)
+    #[cfg(test)]
+    pub fn at(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+        }
+    }
+}
+
+impl LiveClientApi for LiveClient {
+    async fn game_data(&self) -> Result<LiveGameData, String> {
+        game_watcher::fetch_live_game_data(&self.base_url).await
+    }
+
+    async fn game_time(&self) -> Result<f64, String> {
+        game_watcher::fetch_live_game_time(&self.base_url).await
+    }
+
+    async fn events(&self) -> Result<Vec<LiveEvent>, String> {
+        game_watcher::fetch_live_events(&self.base_url).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cancel::CancelToken;
+    use crate::lcu::{ImportPayloadResponse, ItemSetBlock, ItemSetItem};
+    use crate::mock_server::*;
+
+    use super::*;
+
+    fn import_payloads() -> ImportPayloadResponse {
+        ImportPayloadResponse {
+            champion: Some("Ahri".to_string()),
+            role: Some("mid".to_string()),
+            rune_page_payload: Some(RunePagePayload {
+                name: String::new(),
+                primary_style_id: 8200,
+                sub_style_id: 8300,
+                selected_perk_ids: vec![8214, 8226, 8210, 8237, 8345, 8347, 5008, 5008, 5011],
+                current: Some(true),
+            }),
+            item_set_payload: Some(ItemSetPayload {
+                title: String::new(),
+                associated_champions: vec![103],
+                associated_maps: vec![],
+                blocks: vec![ItemSetBlock {
+                    block_type: "Core".to_string(),
+                    items: vec![ItemSetItem {
+                        id: "6655".to_string(),
+                        count: 1,
+                    }],
+                    hide_if_summoner_spell: None,
+                    show_if_summoner_spell: None,
+                    max_summoner_level: None,
+                    min_summoner_level: None,
+                    rec_math: None,
+                }],
+                uid: None,
+                champion: None,
+                is_deletable: None,
+                is_editable: None,
+                map: None,
+                mode: None,
+                priority: None,
+                sortrank: None,
+                set_type: None,
+            }),
+            summoner_spells_payload: Some(SummonerSpellsPayload {
+                spell1_id: 4,
+                spell2_id: 14,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_import_flow_against_mock_client() {
+        block_on(async {
+            let server = MockServer::start().await;
+            mount(
+                &server,
+                &[
+                    ("GET", "/lol-perks/v1/pages", 200, LCU_RUNE_PAGES),
+                    ("DELETE", "/lol-perks/v1/pages/101", 204, ""),
+                    ("POST", "/lol-perks/v1/pages", 200, "{}"),
+                    ("GET", "/lol-gameflow/v1/session", 200, LCU_GAMEFLOW_SESSION),
+                    ("GET", "/lol-summoner/v1/current-summoner", 200, LCU_CURRENT_SUMMONER),
+                    ("GET", "/lol-item-sets/v1/item-sets/42/sets", 200, LCU_ITEM_SETS),
+                    ("PUT", "/lol-item-sets/v1/item-sets/42/sets", 201, "{}"),
+                    ("PATCH", "/lol-champ-select/v1/session/my-selection", 204, ""),
+                ],
+            )
+            .await;
+
+            let connection = lcu_connection(&server);
+            let result = crate::apply_import_payloads(&connection, import_payloads(), &CancelToken::new()).await.unwrap();
+            assert!(result.success && result.runes_imported && result.items_imported && result.summoners_imported);
+
+            let requests = server.received_requests().await.unwrap();
+            let find = |method: &str, path: &str| {
+                requests
+                    .iter()
+                    .find(|r| r.method.as_str() == method && r.url.path() == path)
+                    .unwrap_or_else(|| panic!("no {} {}", method, path))
+            };
+
+            // Only the old FocusApp page is deleted before the new one is created
+            assert!(!requests.iter().any(|r| r.url.path() == "/lol-perks/v1/pages/102"));
+            let page: RunePagePayload = find("POST", "/lol-perks/v1/pages").body_json().unwrap();
+            assert_eq!(page.name, format!("{}Ahri MID", lcu::FOCUS_RUNE_PAGE_PREFIX));
+            find("DELETE", "/lol-perks/v1/pages/101");
+
+            // The user's item set is kept, the FocusApp one replaced and tied to the queue's map
+            let sets: lcu::ItemSetsResponse = find("PUT", "/lol-item-sets/v1/item-sets/42/sets").body_json().unwrap();
+            let titles: Vec<&str> = sets.item_sets.iter().map(|s| s.title.as_str()).collect();
+            assert_eq!(titles, vec!["My Lux set", "Focus: Ahri MID"]);
+            assert_eq!(sets.item_sets[1].associated_maps, vec![11]);
+
+            let spells: SummonerSpellsPayload =
+                find("PATCH", "/lol-champ-select/v1/session/my-selection").body_json().unwrap();
+            assert_eq!((spells.spell1_id, spells.spell2_id), (4, 14));
+
+            // A cancelled import writes nothing
+            let server = MockServer::start().await;
+            let token = CancelToken::new();
+            token.cancel();
+            let result = crate::apply_import_payloads(&lcu_connection(&server), import_payloads(), &token).await;
+            assert!(result.is_err());
+            assert!(server.received_requests().await.unwrap().is_empty());
+        });
+    }
+}
@@ -0,0 +1,333 @@
+//! Cloud sync of build templates and settings for logged-in users.
+//!
+//! `sync_now` keeps the user's setup on FocusApi so a reinstall or a second
+//! PC gets it back. The local and remote copies are merged against the copy
+//! of the last sync (the "base", kept in `cloud-sync.json`):
+//!
+//! - a setting changed on one side only takes that side's value; when both
+//!   sides changed it to different values, nothing is written and the
+//!   conflicts are returned so the frontend can ask the user which one to
+//!   keep, then call `sync_now` again with the choices;
+//! - templates are merged by ID, the most recently updated copy wins; a
+//!   template deleted on one side and untouched on the other is deleted.
+//!
+//! Only settings that describe the user's preferences are synced; paths,
+//! proxy and startup options stay per machine. Before the first sync the
+//! base is the default settings, so a fresh install takes the synced values
+//! without asking.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use tauri::AppHandle;
+
+use crate::build_templates::{self, BuildTemplate};
+use crate::sessions::now_unix_secs;
+use crate::settings::{AppSettings, SettingsStore};
+use crate::{auth, focus_api, storage};
+
+/// File holding the copy of the last sync
+const SYNC_STATE_FILE: &str = "cloud-sync.json";
+
+const SYNC_PATH: &str = "/sync";
+
+/// Settings synced between machines (camelCase keys)
+const SYNCED_SETTINGS: &[&str] = &[
+    "autoImport",
+    "autoImportRules",
+    "flashKey",
+    "buildSource",
+    "buildPatch",
+    "notifications",
+    "locale",
+    "csGoals",
+    "dailyGoals",
+];
+
+/// Synced data of one side
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SyncSnapshot {
+    /// Unix time of the sync that wrote it
+    pub updated_at: i64,
+    pub settings: Map<String, Value>,
+    pub templates: Vec<BuildTemplate>,
+}
+
+impl SyncSnapshot {
+    /// Same settings and templates (`updated_at` ignored)
+    fn same_content(&self, other: &SyncSnapshot) -> bool {
+        self.settings == other.settings
+            && serde_json::to_value(&self.templates).ok() == serde_json::to_value(&other.templates).ok()
+    }
+}
+
+/// Content of the sync state file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct SyncState {
+    base: Option<SyncSnapshot>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SyncResponse {
+    snapshot: Option<SyncSnapshot>,
+}
+
+/// Side kept for a conflicting setting
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum SyncChoice {
+    Local,
+    Remote,
+}
+
+/// A setting changed differently on both sides
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingConflict {
+    pub key: String,
+    pub local: Value,
+    pub remote: Value,
+}
+
+/// What `sync_now` did
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum SyncStatus {
+    UpToDate,
+    /// Local changes were sent
+    Pushed,
+    /// Remote changes were applied
+    Pulled,
+    /// Changes went both ways
+    Merged,
+    /// Nothing was written: the user must pick a side for each conflict
+    Conflict,
+}
+
+/// Result of `sync_now`
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncReport {
+    pub status: SyncStatus,
+    pub conflicts: Vec<SettingConflict>,
+}
+
+/// Synced settings of `settings`
+fn synced_settings(settings: &AppSettings) -> Map<String, Value> {
+    let Ok(Value::Object(all)) = serde_json::to_value(settings) else {
+        return Map::new();
+    };
+    all.into_iter()
+        .filter(|(key, _)| SYNCED_SETTINGS.contains(&key.as_str()))
+        .collect()
+}
+
+/// Three-way merge of the settings; `Err` lists the conflicts left without a choice
+fn merge_settings(
+    base: &Map<String, Value>,
+    local: &Map<String, Value>,
+    remote: &Map<String, Value>,
+    choices: &BTreeMap<String, SyncChoice>,
+) -> Result<Map<String, Value>, Vec<SettingConflict>> {
+    let mut merged = Map::new();
+    let mut conflicts = Vec::new();
+    for key in SYNCED_SETTINGS {
+        let (base, local, remote) = (base.get(*key), local.get(*key), remote.get(*key));
+        let value = if local == remote || remote.is_none() || remote == base {
+            local
+        } else if local == base {
+            remote
+        } else {
+            match choices.get(*key) {
+                Some(SyncChoice::Local) => local,
+                Some(SyncChoice::Remote) => remote,
+                None => {
+                    conflicts.push(SettingConflict {
+                        key: key.to_string(),
+                        local: local.cloned().unwrap_or_default(),
+                        remote: remote.cloned().unwrap_or_default(),
+                    });
+                    continue;
+                }
+            }
+        };
+        if let Some(value) = value {
+            merged.insert(key.to_string(), value.clone());
+        }
+    }
+    if conflicts.is_empty() {
+        Ok(merged)
+    } else {
+        Err(conflicts)
+    }
+}
+
+/// Merge the templates by ID, last write wins
+fn merge_templates(base: &[BuildTemplate], local: &[BuildTemplate], remote: &[BuildTemplate]) -> Vec<BuildTemplate> {
+    let updated_at = |templates: &[BuildTemplate], id: &str| templates.iter().find(|t| t.id == id).map(|t| t.updated_at);
+    let ids: BTreeSet<&str> = local.iter().chain(remote).map(|t| t.id.as_str()).collect();
+
+    let mut merged: Vec<BuildTemplate> = ids
+        .into_iter()
+        .filter_map(|id| {
+            let base = updated_at(base, id);
+            let local = local.iter().find(|t| t.id == id);
+            let remote = remote.iter().find(|t| t.id == id);
+            match (local, remote) {
+                (Some(l), Some(r)) => Some(if r.updated_at > l.updated_at { r } else { l }),
+                // Deleted on the other side unless changed here since the last sync
+                (Some(t), None) | (None, Some(t)) => base.is_none_or(|base| t.updated_at > base).then_some(t),
+                (None, None) => None,
+            }
+        })
+        .cloned()
+        .collect();
+    sort_templates(&mut merged);
+    merged
+}
+
+/// Oldest first, so both sides list the same templates in the same order
+fn sort_templates(templates: &mut [BuildTemplate]) {
+    templates.sort_by(|a, b| (a.created_at, &a.id).cmp(&(b.created_at, &b.id)));
+}
+
+/// Merge both sides against `base`
+fn merge(
+    base: &SyncSnapshot,
+    local: &SyncSnapshot,
+    remote: &SyncSnapshot,
+    choices: &BTreeMap<String, SyncChoice>,
+) -> Result<SyncSnapshot, Vec<SettingConflict>> {
+    Ok(SyncSnapshot {
+        updated_at: now_unix_secs(),
+        settings: merge_settings(&base.settings, &local.settings, &remote.settings, choices)?,
+        templates: merge_templates(&base.templates, &local.templates, &remote.templates),
+    })
+}
+
+/// Base of a first sync: default settings, no templates
+fn default_base() -> SyncSnapshot {
+    SyncSnapshot {
+        settings: synced_settings(&AppSettings::default()),
+        ..SyncSnapshot::default()
+    }
+}
+
+async fn fetch_remote() -> Result<Option<SyncSnapshot>, String> {
+    let body = focus_api::get_json(SYNC_PATH).await.map_err(|e| e.to_string())?;
+    let response: SyncResponse = serde_json::from_value(body).map_err(|e| format!("Invalid sync data: {}", e))?;
+    Ok(response.snapshot)
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Sync the build templates and settings with the FocusApi account.
+///
+/// When a setting was changed differently on both sides, nothing is written
+/// and the report lists the conflicts; call again with a `choices` entry
+/// (`"local"` or `"remote"`) for each conflicting key.
+#[tauri::command]
+pub async fn sync_now(
+    app: AppHandle,
+    store: tauri::State<'_, SettingsStore>,
+    choices: Option<BTreeMap<String, SyncChoice>>,
+) -> Result<SyncReport, String> {
+    if !auth::is_logged_in() {
+        return Err("Log in to a FocusApi account to sync".to_string());
+    }
+    focus_api::ensure_default_server()?;
+
+    let state: SyncState = storage::read_json(&app, SYNC_STATE_FILE);
+    let base = state.base.unwrap_or_else(default_base);
+    let mut local = SyncSnapshot {
+        updated_at: now_unix_secs(),
+        settings: synced_settings(&store.get()),
+        templates: build_templates::load_templates(&app),
+    };
+    sort_templates(&mut local.templates);
+    let remote = fetch_remote().await?;
+    let remote_or_base = remote.clone().unwrap_or_else(|| base.clone());
+
+    let merged = match merge(&base, &local, &remote_or_base, &choices.unwrap_or_default()) {
+        Ok(merged) => merged,
+        Err(conflicts) => {
+            log_info!("[cloud_sync] {} conflicting settings, waiting for the user", conflicts.len());
+            return Ok(SyncReport {
+                status: SyncStatus::Conflict,
+                conflicts,
+            });
+        }
+    };
+
+    let pushed = !remote.as_ref().is_some_and(|remote| remote.same_content(&merged));
+    let pulled = !local.same_content(&merged);
+    if pushed {
+        focus_api::put_json(SYNC_PATH, &merged).await.map_err(|e| e.to_string())?;
+    }
+    if pulled {
+        store.update(&app, Value::Object(merged.settings.clone()))?;
+        build_templates::save_templates(&app, &merged.templates)?;
+    }
+    storage::write_json(&app, SYNC_STATE_FILE, &SyncState { base: Some(merged) })?;
+
+    let status = match (pushed, pulled) {
+        (false, false) => SyncStatus::UpToDate,
+        (true, false) => SyncStatus::Pushed,
+        (false, true) => SyncStatus::Pulled,
+        (true, true) => SyncStatus::Merged,
+    };
+    log_info!("[cloud_sync] Sync done: {:?}", status);
+    Ok(SyncReport {
+        status,
+        conflicts: Vec::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_server::import_request;
+    use serde_json::json;
+
+    fn template(id: &str, updated_at: i64) -> BuildTemplate {
+        BuildTemplate {
+            id: id.to_string(),
+            created_at: 0,
+            updated_at,
+            build: import_request(),
+        }
+    }
+
+    fn settings(pairs: &[(&str, Value)]) -> Map<String, Value> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.clone())).collect()
+    }
+
+    #[test]
+    fn test_merge() {
+        let base = settings(&[("autoImport", json!(false)), ("locale", json!("en")), ("flashKey", json!("D"))]);
+        let local = settings(&[("autoImport", json!(true)), ("locale", json!("fr")), ("flashKey", json!("D"))]);
+        let remote = settings(&[("autoImport", json!(false)), ("locale", json!("de")), ("flashKey", json!("F"))]);
+
+        // Each side's changes are kept, the locale changed on both
+        let conflicts = merge_settings(&base, &local, &remote, &BTreeMap::new()).unwrap_err();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!((conflicts[0].key.as_str(), &conflicts[0].remote), ("locale", &json!("de")));
+        let choices = BTreeMap::from([("locale".to_string(), SyncChoice::Remote)]);
+        let merged = merge_settings(&base, &local, &remote, &choices).unwrap();
+        assert_eq!(merged, settings(&[("autoImport", json!(true)), ("locale", json!("de")), ("flashKey", json!("F"))]));
+
+        // Newest copy wins; deleted remotely and untouched locally is deleted
+        let base = [template("a", 10), template("b", 10)];
+        let local = [template("a", 20), template("b", 10), template("c", 30)];
+        let remote = [template("a", 15), template("d", 30)];
+        let merged: Vec<(String, i64)> = merge_templates(&base, &local, &remote)
+            .into_iter()
+            .map(|t| (t.id, t.updated_at))
+            .collect();
+        assert_eq!(merged, [("a".to_string(), 20), ("c".to_string(), 30), ("d".to_string(), 30)]);
+    }
+}
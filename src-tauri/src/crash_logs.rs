@@ -0,0 +1,196 @@
+//! Crash logs written by the panic hook.
+//!
+//! Each panic writes `crash-<unix time>-<pid>.log` in the `crashes` folder of
+//! the app log directory, with the app version, the panic message and the
+//! last lines of the log kept in memory. Only the `MAX_CRASH_LOGS` newest
+//! files are kept. `get_recent_crashes` lists them so the UI can offer to
+//! report the crash on the next launch.
+//!
+//! The hook is installed before Tauri starts, when the log directory is not
+//! known yet: until `init`, crash logs go to a `crashes` folder next to the
+//! executable.
+
+use serde::Serialize;
+use std::panic::PanicHookInfo;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+use crate::redact;
+use crate::sessions::now_unix_secs;
+
+/// Folder of the crash logs, in the app log directory
+const CRASH_DIR: &str = "crashes";
+
+/// Crash logs kept (oldest deleted first)
+const MAX_CRASH_LOGS: usize = 10;
+
+/// Line of a crash log holding the panic message
+const MESSAGE_PREFIX: &str = "Message: ";
+
+/// Folder found by `init` (None before)
+static CRASH_DIR_PATH: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// A crash log, for the UI
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashReport {
+    pub file_name: String,
+    /// Unix timestamp (seconds) of the crash
+    pub crashed_at: i64,
+    /// Panic message (first line)
+    pub message: String,
+    pub path: String,
+}
+
+/// Folder the hook writes to
+fn crash_dir() -> Option<PathBuf> {
+    if let Some(dir) = CRASH_DIR_PATH.try_lock().ok().and_then(|dir| dir.clone()) {
+        return Some(dir);
+    }
+    std::env::current_exe()
+        .ok()
+        .map(|exe| exe.with_file_name(CRASH_DIR))
+}
+
+/// Content of a crash log
+fn format_crash_log(crashed_at: i64, message: &str, details: &str, recent_lines: &[String]) -> String {
+    let mut log = format!(
+        "=== FOCUSAPP PANIC ===\nVersion: {}\nOS: {}\nTime: {}\n{}{}\n\n{}\n",
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        crashed_at,
+        MESSAGE_PREFIX,
+        message.lines().next().unwrap_or_default(),
+        details
+    );
+    log.push_str("\n--- Last log lines ---\n");
+    for line in recent_lines {
+        log.push_str(line);
+        log.push('\n');
+    }
+    redact::redact(&log)
+}
+
+/// Write a crash log in `dir` and delete the oldest ones beyond the limit
+fn write_crash_log(dir: &Path, crashed_at: i64, contents: &str) -> std::io::Result<PathBuf> {
+    std::fs::create_dir_all(dir)?;
+    let path = dir.join(format!("crash-{}-{}.log", crashed_at, std::process::id()));
+    std::fs::write(&path, contents)?;
+
+    let reports = list_crashes(dir);
+    for report in reports.iter().skip(MAX_CRASH_LOGS) {
+        let _ = std::fs::remove_file(&report.path);
+    }
+    Ok(path)
+}
+
+/// Crash logs in `dir`, newest first
+fn list_crashes(dir: &Path) -> Vec<CrashReport> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut reports: Vec<CrashReport> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let file_name = entry.file_name().to_string_lossy().into_owned();
+            let crashed_at = file_name
+                .strip_prefix("crash-")?
+                .strip_suffix(".log")?
+                .split('-')
+                .next()?
+                .parse()
+                .ok()?;
+            let contents = std::fs::read_to_string(entry.path()).unwrap_or_default();
+            let message = contents
+                .lines()
+                .find_map(|line| line.strip_prefix(MESSAGE_PREFIX))
+                .unwrap_or_default()
+                .to_string();
+            Some(CrashReport {
+                file_name,
+                crashed_at,
+                message,
+                path: entry.path().to_string_lossy().into_owned(),
+            })
+        })
+        .collect();
+    reports.sort_by(|a, b| b.crashed_at.cmp(&a.crashed_at).then_with(|| b.file_name.cmp(&a.file_name)));
+    reports
+}
+
+/// Panic message without the location
+fn panic_message(info: &PanicHookInfo) -> String {
+    if let Some(message) = info.payload().downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = info.payload().downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Unknown panic".to_string()
+    }
+}
+
+/// Install the panic hook (in all builds: release builds are silent otherwise)
+pub fn install_hook() {
+    std::panic::set_hook(Box::new(|info| {
+        let details = info.to_string();
+        log_error!("=== FOCUSAPP PANIC ===\n{}\n", details);
+
+        let crashed_at = now_unix_secs();
+        let contents = format_crash_log(crashed_at, &panic_message(info), &details, &crate::logging::recent_lines());
+        if let Some(dir) = crash_dir() {
+            match write_crash_log(&dir, crashed_at, &contents) {
+                Ok(path) => eprintln!("Crash log written to {:?}", path),
+                Err(e) => eprintln!("Failed to write the crash log: {}", e),
+            }
+        }
+    }));
+}
+
+/// Write crash logs to the app log directory from now on
+pub fn init(app: &AppHandle) {
+    match app.path().app_log_dir() {
+        Ok(dir) => {
+            if let Ok(mut current) = CRASH_DIR_PATH.lock() {
+                *current = Some(dir.join(CRASH_DIR));
+            }
+        }
+        Err(e) => log_warn!("[crash_logs] No log directory, crash logs stay next to the executable: {}", e),
+    }
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// List the crash logs, newest first.
+#[tauri::command]
+pub async fn get_recent_crashes() -> Result<Vec<CrashReport>, String> {
+    Ok(crash_dir().map(|dir| list_crashes(&dir)).unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crash_logs_are_capped() {
+        let dir = std::env::temp_dir().join(format!("focusapp-crashes-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let contents = format_crash_log(100, "index out of bounds\nmore", "panicked at src/main.rs:1:1", &["1 INFO ready".to_string()]);
+        assert!(contents.contains("Message: index out of bounds\n"));
+        assert!(contents.ends_with("--- Last log lines ---\n1 INFO ready\n"));
+
+        for crashed_at in 0..(MAX_CRASH_LOGS as i64 + 3) {
+            write_crash_log(&dir, 1_000 + crashed_at, &contents).unwrap();
+        }
+        std::fs::write(dir.join("notes.txt"), "not a crash").unwrap();
+
+        let reports = list_crashes(&dir);
+        let _ = std::fs::remove_dir_all(&dir);
+        assert_eq!(reports.len(), MAX_CRASH_LOGS);
+        assert_eq!(reports[0].crashed_at, 1_000 + MAX_CRASH_LOGS as i64 + 2);
+        assert_eq!(reports[0].message, "index out of bounds");
+    }
+}
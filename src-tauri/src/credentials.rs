@@ -0,0 +1,65 @@
+//! Secrets stored in the OS credential store.
+//!
+//! Goes through the `keyring` crate: the Windows Credential Manager, the
+//! macOS Keychain, and the Secret Service on Linux. Secrets are stored as
+//! UTF-8 bytes; the Credential Manager holds at most 2560 bytes per secret.
+
+use keyring::Entry;
+
+/// Service name under which the secrets are stored
+const SERVICE: &str = "com.focusapp.frontend";
+
+fn entry(account: &str) -> Result<Entry, String> {
+    Entry::new(SERVICE, account).map_err(|e| format!("Credential store unavailable: {}", e))
+}
+
+/// Turn a credential store lookup into the stored secret (None when missing or empty)
+fn stored_secret(result: keyring::Result<Vec<u8>>) -> Result<Option<String>, String> {
+    let bytes = match result {
+        Ok(bytes) => bytes,
+        Err(keyring::Error::NoEntry) => return Ok(None),
+        Err(e) => return Err(format!("Credential store error: {}", e)),
+    };
+    let secret = String::from_utf8(bytes).map_err(|e| format!("Invalid stored secret: {}", e))?;
+    Ok(Some(secret).filter(|s| !s.is_empty()))
+}
+
+/// Read a secret; `Ok(None)` when nothing is stored for `account`.
+///
+/// Blocking: call from `spawn_blocking` in async code.
+pub fn read_secret(account: &str) -> Result<Option<String>, String> {
+    stored_secret(entry(account)?.get_secret())
+}
+
+/// Store a secret, replacing the previous one. Blocking.
+pub fn write_secret(account: &str, secret: &str) -> Result<(), String> {
+    entry(account)?
+        .set_secret(secret.as_bytes())
+        .map_err(|e| format!("Credential store error: {}", e))
+}
+
+/// Delete a secret (no error if nothing is stored). Blocking.
+pub fn delete_secret(account: &str) -> Result<(), String> {
+    match entry(account)?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(format!("Credential store error: {}", e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stored_secret() {
+        let secret = r#"{"accessToken":"a1b2","refreshToken":"c3d4"}"#;
+        assert_eq!(stored_secret(Ok(secret.as_bytes().to_vec())).unwrap().as_deref(), Some(secret));
+
+        // Nothing stored, or stored empty
+        assert_eq!(stored_secret(Err(keyring::Error::NoEntry)).unwrap(), None);
+        assert_eq!(stored_secret(Ok(Vec::new())).unwrap(), None);
+
+        assert!(stored_secret(Ok(vec![0xff, 0xfe])).is_err());
+        assert!(stored_secret(Err(keyring::Error::TooLong("secret".to_string(), 2560))).is_err());
+    }
+}
@@ -0,0 +1,157 @@
+//! CS targets per game stage.
+//!
+//! Instead of a single CS/min number, users can set targets such as 80 CS at
+//! 10 minutes and 160 at 20, globally or for a given role (`csGoals` in the
+//! settings). The goals of the role played are sent to the overlay with its
+//! mode, which colors the CS/min delta against the target interpolated
+//! between stages, and the game watcher emits a `cs-milestone` event each
+//! time a stage is passed.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Longest game a stage can target (minutes)
+const MAX_GOAL_MINUTE: u32 = 90;
+
+/// A CS target at a game minute
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CsGoal {
+    pub minute: u32,
+    pub cs: u32,
+}
+
+/// `csGoals` setting
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CsGoalSettings {
+    /// Goals of every role (empty = the overlay's CS/min target)
+    pub global: Vec<CsGoal>,
+    /// Goals replacing the global ones for a role ("top", "jungle", "mid", "adc", "support")
+    pub roles: BTreeMap<String, Vec<CsGoal>>,
+}
+
+impl CsGoalSettings {
+    /// Goals of `role`, by increasing minute
+    pub fn goals_for(&self, role: Option<&str>) -> Vec<CsGoal> {
+        let goals = role
+            .and_then(|role| self.roles.get(&role.trim().to_lowercase()))
+            .filter(|goals| !goals.is_empty())
+            .unwrap_or(&self.global);
+
+        let mut goals: Vec<CsGoal> = goals
+            .iter()
+            .copied()
+            .filter(|goal| (1..=MAX_GOAL_MINUTE).contains(&goal.minute))
+            .collect();
+        goals.sort_by_key(|goal| goal.minute);
+        goals.dedup_by_key(|goal| goal.minute);
+        goals
+    }
+}
+
+/// Target CS at `game_time`, interpolated between stages and extended at
+/// the rate of the last one (None without goals). Same curve as the overlay.
+pub fn target_cs_at(goals: &[CsGoal], game_time: f64) -> Option<f64> {
+    let minutes = game_time / 60.0;
+    let mut previous = CsGoal::default();
+    for goal in goals {
+        if minutes <= f64::from(goal.minute) {
+            let ratio = (minutes - f64::from(previous.minute)) / f64::from(goal.minute - previous.minute);
+            return Some(f64::from(previous.cs) + ratio * (f64::from(goal.cs) - f64::from(previous.cs)));
+        }
+        previous = *goal;
+    }
+    (previous.minute > 0).then(|| f64::from(previous.cs) * minutes / f64::from(previous.minute))
+}
+
+/// A stage passed during the game (`cs-milestone` payload)
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CsMilestone {
+    pub minute: u32,
+    pub target_cs: u32,
+    pub cs: i32,
+    pub reached: bool,
+}
+
+/// Detects the stages passed between two polls of the Live Client
+#[derive(Debug, Clone, Default)]
+pub struct MilestoneTracker {
+    goals: Vec<CsGoal>,
+    /// Index of the next stage
+    next: usize,
+    started: bool,
+}
+
+impl MilestoneTracker {
+    pub fn new(goals: Vec<CsGoal>) -> Self {
+        Self {
+            goals,
+            ..Self::default()
+        }
+    }
+
+    /// Goals followed, by increasing minute
+    pub fn goals(&self) -> &[CsGoal] {
+        &self.goals
+    }
+
+    /// Stages passed by `game_time`.
+    ///
+    /// Stages already passed at the first poll (app started mid-game) are
+    /// skipped: the CS at that minute is unknown.
+    pub fn observe(&mut self, game_time: f64, cs: i32) -> Vec<CsMilestone> {
+        let passed = |goal: &CsGoal| game_time >= f64::from(goal.minute) * 60.0;
+        let mut milestones = Vec::new();
+        while let Some(goal) = self.goals.get(self.next).filter(|goal| passed(goal)) {
+            if self.started {
+                milestones.push(CsMilestone {
+                    minute: goal.minute,
+                    target_cs: goal.cs,
+                    cs,
+                    reached: cs >= goal.cs as i32,
+                });
+            }
+            self.next += 1;
+        }
+        self.started = true;
+        milestones
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_goals_and_milestones() {
+        let goal = |minute, cs| CsGoal { minute, cs };
+        let settings = CsGoalSettings {
+            global: vec![goal(20, 160), goal(10, 80), goal(0, 5)],
+            roles: BTreeMap::from([("support".to_string(), vec![goal(10, 20)])]),
+        };
+        assert_eq!(settings.goals_for(None), vec![goal(10, 80), goal(20, 160)]);
+        assert_eq!(settings.goals_for(Some("mid")), settings.goals_for(None));
+        assert_eq!(settings.goals_for(Some("Support")), vec![goal(10, 20)]);
+
+        let goals = settings.goals_for(None);
+        assert_eq!(target_cs_at(&goals, 300.0), Some(40.0));
+        assert_eq!(target_cs_at(&goals, 900.0), Some(120.0));
+        assert_eq!(target_cs_at(&goals, 1_800.0), Some(240.0));
+        assert_eq!(target_cs_at(&[], 600.0), None);
+
+        let mut tracker = MilestoneTracker::new(settings.goals_for(None));
+        assert!(tracker.observe(30.0, 0).is_empty());
+        assert!(tracker.observe(599.5, 79).is_empty());
+        let milestones = tracker.observe(600.0, 79);
+        assert_eq!(milestones.len(), 1);
+        assert_eq!((milestones[0].minute, milestones[0].reached), (10, false));
+        assert!(tracker.observe(601.0, 80).is_empty());
+
+        // Started after 10 minutes: only the 20 minute stage is reported
+        let mut late = MilestoneTracker::new(settings.goals_for(None));
+        assert!(late.observe(700.0, 90).is_empty());
+        assert!(late.observe(1_200.0, 170)[0].reached);
+    }
+}
@@ -0,0 +1,311 @@
+//! Daily goals and their streaks.
+//!
+//! Users set daily goals in the settings (`dailyGoals`), such as "average 7
+//! CS/min over 3 games or more". Each time a game is recorded, the goals are
+//! checked against the games of the current day; the first time a goal is
+//! met on a day its streak grows (or restarts when the previous day was
+//! missed) and a `goal-achieved` event is sent for the UI to celebrate.
+//!
+//! Streaks are kept in their own database document so they survive the
+//! trimming of the session history. Days are local days: the frontend sends its UTC offset
+//! with `get_streaks` and the last one is stored for the checks made at game
+//! end.
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+use crate::events::AppEvent;
+use crate::sessions::{self, local_day, now_unix_secs, GameSession};
+use crate::db;
+use crate::settings::SettingsStore;
+
+/// Value measured by a daily goal
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum GoalMetric {
+    /// Average CS/min of the day's games
+    #[default]
+    CsPerMinute,
+    /// Average gold/min of the day's games (games without gold data are skipped)
+    GoldPerMinute,
+    /// Number of games played
+    Games,
+}
+
+/// A goal to meet every day
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DailyGoal {
+    /// Identifies the streak of the goal (goals without one are ignored)
+    pub id: String,
+    pub metric: GoalMetric,
+    pub target: f64,
+    /// Games to play before the goal counts
+    pub min_games: usize,
+}
+
+impl Default for DailyGoal {
+    fn default() -> Self {
+        Self {
+            id: String::new(),
+            metric: GoalMetric::CsPerMinute,
+            target: 7.0,
+            min_games: 3,
+        }
+    }
+}
+
+impl DailyGoal {
+    /// Value of the metric over `games` (None when it cannot be measured)
+    fn value(&self, games: &[&GameSession]) -> Option<f64> {
+        let values: Vec<f64> = match self.metric {
+            GoalMetric::Games => return Some(games.len() as f64),
+            GoalMetric::CsPerMinute => games.iter().map(|s| s.cs_per_minute).collect(),
+            GoalMetric::GoldPerMinute => games.iter().filter_map(|s| s.gold_per_minute).collect(),
+        };
+        (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+    }
+
+    fn is_met(&self, games: &[&GameSession]) -> bool {
+        games.len() >= self.min_games && self.value(games).is_some_and(|value| value >= self.target)
+    }
+}
+
+/// Stored streak of a goal
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+struct GoalStreak {
+    goal_id: String,
+    /// Consecutive days met, up to `last_met_day`
+    current: u32,
+    best: u32,
+    /// Local day (days since 1970-01-01) the goal was last met
+    last_met_day: Option<i64>,
+}
+
+impl GoalStreak {
+    /// Current streak as seen on `today` (a missed day resets it)
+    fn current_on(&self, today: i64) -> u32 {
+        if self.last_met_day.is_some_and(|day| day >= today - 1) {
+            self.current
+        } else {
+            0
+        }
+    }
+
+    /// Count `day` as met; returns false if it already was
+    fn meet(&mut self, day: i64) -> bool {
+        if self.last_met_day.is_some_and(|last| last >= day) {
+            return false;
+        }
+        self.current = self.current_on(day) + 1;
+        self.best = self.best.max(self.current);
+        self.last_met_day = Some(day);
+        true
+    }
+}
+
+/// Streaks document
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+struct StreakStore {
+    /// Last UTC offset reported by the frontend
+    utc_offset_secs: i64,
+    streaks: Vec<GoalStreak>,
+}
+
+impl StreakStore {
+    fn streak_mut(&mut self, goal_id: &str) -> &mut GoalStreak {
+        let index = match self.streaks.iter().position(|s| s.goal_id == goal_id) {
+            Some(index) => index,
+            None => {
+                self.streaks.push(GoalStreak {
+                    goal_id: goal_id.to_string(),
+                    ..GoalStreak::default()
+                });
+                self.streaks.len() - 1
+            }
+        };
+        &mut self.streaks[index]
+    }
+}
+
+/// A goal met for the first time today (`goal-achieved` payload)
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GoalAchieved {
+    pub goal: DailyGoal,
+    pub value: f64,
+    pub games: usize,
+    pub streak: u32,
+    pub best_streak: u32,
+}
+
+/// State of a goal, returned by `get_streaks`
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GoalStatus {
+    pub goal: DailyGoal,
+    pub current_streak: u32,
+    pub best_streak: u32,
+    pub met_today: bool,
+    /// Games and value of the metric so far today
+    pub today_games: usize,
+    pub today_value: Option<f64>,
+}
+
+/// Games of the local day `day`
+fn games_of_day(sessions: &[GameSession], day: i64, utc_offset_secs: i64) -> Vec<&GameSession> {
+    sessions
+        .iter()
+        .filter(|s| local_day(s.ended_at, utc_offset_secs) == day)
+        .collect()
+}
+
+/// Meet the goals reached today, returning the ones met for the first time
+fn update_streaks(store: &mut StreakStore, goals: &[DailyGoal], sessions: &[GameSession], now: i64) -> Vec<GoalAchieved> {
+    let today = local_day(now, store.utc_offset_secs);
+    let games = games_of_day(sessions, today, store.utc_offset_secs);
+
+    let mut achieved = Vec::new();
+    for goal in goals.iter().filter(|goal| !goal.id.is_empty()) {
+        if !goal.is_met(&games) {
+            continue;
+        }
+        let streak = store.streak_mut(&goal.id);
+        if streak.meet(today) {
+            achieved.push(GoalAchieved {
+                goal: goal.clone(),
+                value: goal.value(&games).unwrap_or_default(),
+                games: games.len(),
+                streak: streak.current,
+                best_streak: streak.best,
+            });
+        }
+    }
+    achieved
+}
+
+/// State of each goal on the current day
+fn goal_statuses(store: &StreakStore, goals: &[DailyGoal], sessions: &[GameSession], now: i64) -> Vec<GoalStatus> {
+    let today = local_day(now, store.utc_offset_secs);
+    let games = games_of_day(sessions, today, store.utc_offset_secs);
+
+    goals
+        .iter()
+        .filter(|goal| !goal.id.is_empty())
+        .map(|goal| {
+            let streak = store.streaks.iter().find(|s| s.goal_id == goal.id);
+            GoalStatus {
+                goal: goal.clone(),
+                current_streak: streak.map(|s| s.current_on(today)).unwrap_or_default(),
+                best_streak: streak.map(|s| s.best).unwrap_or_default(),
+                met_today: streak.is_some_and(|s| s.last_met_day == Some(today)),
+                today_games: games.len(),
+                today_value: goal.value(&games),
+            }
+        })
+        .collect()
+}
+
+fn daily_goals(app: &AppHandle) -> Vec<DailyGoal> {
+    app.try_state::<SettingsStore>()
+        .map(|store| store.get().daily_goals)
+        .unwrap_or_default()
+}
+
+/// Check the goals after a game was recorded and celebrate the ones met
+pub fn record_game(app: &AppHandle) -> Result<(), String> {
+    let goals = daily_goals(app);
+    if goals.is_empty() {
+        return Ok(());
+    }
+    let sessions = sessions::load_competitive_sessions(app);
+    let achieved = db::with(app, |connection| {
+        let mut store: StreakStore = db::load_document(connection, db::STREAKS_DOCUMENT)?;
+        let achieved = update_streaks(&mut store, &goals, &sessions, now_unix_secs());
+        if !achieved.is_empty() {
+            db::save_document(connection, db::STREAKS_DOCUMENT, &store)?;
+        }
+        Ok(achieved)
+    })?;
+
+    for goal in &achieved {
+        log_info!("[daily_goals] Goal {} met ({} day streak)", goal.goal.id, goal.streak);
+        if let Err(e) = AppEvent::GoalAchieved(goal).emit(app) {
+            log_debug!("[daily_goals] {}", e);
+        }
+    }
+    Ok(())
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the streak and today's progress of each daily goal.
+///
+/// `utc_offset_minutes` is the local offset from UTC (e.g. 120 for UTC+2);
+/// it is stored for the checks made when a game ends.
+#[tauri::command]
+pub async fn get_streaks(app: AppHandle, utc_offset_minutes: Option<i32>) -> Result<Vec<GoalStatus>, String> {
+    let goals = daily_goals(&app);
+    let sessions = sessions::load_competitive_sessions(&app);
+    let now = now_unix_secs();
+
+    let store = db::with(&app, |connection| {
+        let mut store: StreakStore = db::load_document(connection, db::STREAKS_DOCUMENT)?;
+        let offset = utc_offset_minutes.map(|minutes| i64::from(minutes) * 60);
+        let offset_changed = offset.is_some_and(|offset| offset != store.utc_offset_secs);
+        store.utc_offset_secs = offset.unwrap_or(store.utc_offset_secs);
+        // Games recorded while no goal was set yet still count for today
+        let met = update_streaks(&mut store, &goals, &sessions, now);
+        if offset_changed || !met.is_empty() {
+            db::save_document(connection, db::STREAKS_DOCUMENT, &store)?;
+        }
+        Ok(store)
+    })?;
+
+    Ok(goal_statuses(&store, &goals, &sessions, now))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY_SECS: i64 = 86_400;
+
+    #[test]
+    fn test_update_streaks() {
+        let goals = [DailyGoal {
+            id: "farm".to_string(),
+            ..DailyGoal::default()
+        }];
+        let game = |ended_at, cs_per_minute| GameSession {
+            ended_at,
+            cs_per_minute,
+            ..GameSession::default()
+        };
+        let mut store = StreakStore::default();
+        let mut sessions = vec![game(10, 8.0), game(20, 7.0)];
+
+        // Two games only: not met yet
+        assert!(update_streaks(&mut store, &goals, &sessions, 30).is_empty());
+        sessions.push(game(30, 6.5));
+        let achieved = update_streaks(&mut store, &goals, &sessions, 40);
+        assert_eq!(achieved.len(), 1);
+        assert_eq!((achieved[0].games, achieved[0].streak), (3, 1));
+        // Celebrated once per day
+        assert!(update_streaks(&mut store, &goals, &sessions, 50).is_empty());
+
+        // Next day continues the streak, a missed day resets it
+        sessions.extend([game(DAY_SECS, 9.0), game(DAY_SECS + 1, 9.0), game(DAY_SECS + 2, 9.0)]);
+        assert_eq!(update_streaks(&mut store, &goals, &sessions, DAY_SECS + 3)[0].streak, 2);
+        let statuses = goal_statuses(&store, &goals, &sessions, 3 * DAY_SECS);
+        assert_eq!((statuses[0].current_streak, statuses[0].best_streak), (0, 2));
+        assert!(!statuses[0].met_today);
+
+        // Local days follow the UTC offset
+        assert_eq!(games_of_day(&sessions, -1, -3_600).len(), 3);
+    }
+}
@@ -0,0 +1,266 @@
+//! Export and deletion of everything the app stores locally.
+//!
+//! `export_all_data` packs every file of the app config directory (settings,
+//! overlay config, build templates, queued imports...) and a copy of the
+//! database (game sessions, import history, personal bests...) into a single
+//! zip, so the user can move to another machine. `delete_all_data` wipes the
+//! config, data, cache and log directories, the stored API key and the
+//! account session, then restarts the app on a clean state.
+//!
+//! The archive is only written where the user chose in a save dialog, never
+//! to a path sent by the frontend.
+
+use serde::Serialize;
+use std::io::{Cursor, Write};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_dialog::DialogExt;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::db::{self, Database};
+use crate::sessions::now_unix_secs;
+use crate::{auth, focus_api, logging, storage};
+
+/// Name of the manifest added at the root of the archive
+const MANIFEST_FILE: &str = "focusapp-export.json";
+
+/// Name of the database copy in the archive
+const DATABASE_ENTRY: &str = db::DATABASE_FILE;
+
+/// Contents of the manifest
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ExportManifest<'a> {
+    app_version: &'a str,
+    exported_at: i64,
+    files: Vec<&'a str>,
+}
+
+/// Civil (year, month, day) of a day counted from 1970-01-01 (Howard Hinnant's algorithm)
+pub(crate) fn civil_date(days: i64) -> (i64, i64, i64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    (yoe + era * 400 + i64::from(month <= 2), month, day)
+}
+
+/// Modification time of the archive entries (zip dates can't be before 1980)
+fn zip_date_time(timestamp: i64) -> zip::DateTime {
+    let secs = timestamp.rem_euclid(86_400);
+    let (year, month, day) = civil_date(timestamp.div_euclid(86_400));
+    zip::DateTime::from_date_and_time(
+        year.clamp(1980, 2107) as u16,
+        month as u8,
+        day as u8,
+        (secs / 3600) as u8,
+        ((secs % 3600) / 60) as u8,
+        (secs % 60) as u8,
+    )
+    .unwrap_or_default()
+}
+
+/// Build a zip archive (deflate) from `(name, contents)` entries
+fn write_zip(entries: &[(String, Vec<u8>)], timestamp: i64) -> Result<Vec<u8>, String> {
+    let options = SimpleFileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .last_modified_time(zip_date_time(timestamp));
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    for (name, contents) in entries {
+        writer.start_file(name.as_str(), options).map_err(|e| e.to_string())?;
+        writer.write_all(contents).map_err(|e| e.to_string())?;
+    }
+    Ok(writer.finish().map_err(|e| e.to_string())?.into_inner())
+}
+
+/// Whether `name` is the live database or one of its journal files. The
+/// archive gets a consistent copy instead; they are in the config directory
+/// wherever it is also the data directory (Windows, macOS).
+fn is_database_file(name: &str) -> bool {
+    name.strip_prefix(DATABASE_ENTRY)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('-'))
+}
+
+/// Every file under `dir`, with its path relative to `root` ("/" separated)
+fn collect_files(root: &Path, dir: &Path, files: &mut Vec<(String, Vec<u8>)>) -> Result<(), String> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Ok(());
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files(root, &path, files)?;
+            continue;
+        }
+        // Leftovers of an interrupted atomic write
+        if path.extension().is_some_and(|ext| ext == "tmp") {
+            continue;
+        }
+        let Ok(relative) = path.strip_prefix(root) else {
+            continue;
+        };
+        let name = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        if is_database_file(&name) {
+            continue;
+        }
+        let contents = std::fs::read(&path).map_err(|e| format!("Failed to read {}: {}", name, e))?;
+        files.push((name, contents));
+    }
+    Ok(())
+}
+
+/// Consistent copy of the database, taken through a temporary file
+fn database_copy(app: &AppHandle) -> Result<Vec<u8>, String> {
+    let database = app.try_state::<Database>().ok_or("Database not opened")?;
+    let path = storage::cache_file_path(app, "export.db")?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    // VACUUM INTO refuses to overwrite a leftover copy
+    let _ = std::fs::remove_file(&path);
+    database.backup_to(&path)?;
+    let contents = std::fs::read(&path).map_err(|e| e.to_string());
+    let _ = std::fs::remove_file(&path);
+    contents
+}
+
+/// Folder of the exports when the user gives no path (Downloads, else home)
+pub(crate) fn export_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .download_dir()
+        .or_else(|_| app.path().home_dir())
+        .map_err(|e| e.to_string())
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Write all the user data to a zip archive and return its path.
+///
+/// The destination is chosen in a save dialog, opened in the Downloads
+/// folder; returns None when the user cancels it.
+#[tauri::command]
+pub async fn export_all_data(app: AppHandle) -> Result<Option<String>, String> {
+    let timestamp = now_unix_secs();
+    let dialog = app
+        .dialog()
+        .file()
+        .set_title("Export FocusApp data")
+        .add_filter("Zip archive", &["zip"])
+        .set_file_name(format!("focusapp-export-{}.zip", timestamp));
+    let dialog = match export_dir(&app) {
+        Ok(dir) => dialog.set_directory(dir),
+        Err(_) => dialog,
+    };
+    let chosen = tokio::task::spawn_blocking(move || dialog.blocking_save_file())
+        .await
+        .map_err(|e| e.to_string())?;
+    let Some(chosen) = chosen else {
+        return Ok(None);
+    };
+    let path = chosen.into_path().map_err(|e| e.to_string())?;
+
+    let root = app.path().app_config_dir().map_err(|e| e.to_string())?;
+    let mut files = Vec::new();
+    collect_files(&root, &root, &mut files)?;
+    files.push((DATABASE_ENTRY.to_string(), database_copy(&app)?));
+    files.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let manifest = ExportManifest {
+        app_version: env!("CARGO_PKG_VERSION"),
+        exported_at: timestamp,
+        files: files.iter().map(|(name, _)| name.as_str()).collect(),
+    };
+    let manifest = serde_json::to_vec_pretty(&manifest).map_err(|e| e.to_string())?;
+    files.insert(0, (MANIFEST_FILE.to_string(), manifest));
+
+    let archive = write_zip(&files, timestamp)?;
+    storage::write_atomic(&path, &archive)?;
+
+    log_info!("[data_export] Exported {} file(s) to {:?}", files.len() - 1, path);
+    Ok(Some(path.to_string_lossy().into_owned()))
+}
+
+/// Delete everything FocusApp stored on this machine, then restart.
+#[tauri::command]
+pub async fn delete_all_data(app: AppHandle) -> Result<(), String> {
+    focus_api::clear_api_key().await?;
+    auth::clear_session().await?;
+    logging::close_file();
+    if let Some(database) = app.try_state::<Database>() {
+        database.close()?;
+    }
+
+    let directories = [
+        app.path().app_config_dir(),
+        app.path().app_data_dir(),
+        app.path().app_cache_dir(),
+        app.path().app_log_dir(),
+    ];
+    for dir in directories.into_iter().flatten() {
+        if dir.exists() {
+            std::fs::remove_dir_all(&dir).map_err(|e| format!("Failed to delete {:?}: {}", dir, e))?;
+        }
+    }
+
+    log_info!("[data_export] All local data deleted, restarting");
+    app.restart()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn test_write_zip() {
+        let entries = vec![
+            ("settings.json".to_string(), b"{\"autoImport\":true}".to_vec()),
+            ("static/queues.json".to_string(), vec![b'a'; 1000]),
+        ];
+        let archive = write_zip(&entries, 1_700_000_000).unwrap();
+
+        let mut zip = zip::ZipArchive::new(Cursor::new(archive)).unwrap();
+        assert_eq!(zip.len(), 2);
+        let mut entry = zip.by_name("static/queues.json").unwrap();
+        assert_eq!(entry.compression(), CompressionMethod::Deflated);
+        // 2023-11-14 22:13:20 UTC
+        let modified = entry.last_modified().unwrap();
+        assert_eq!((modified.year(), modified.month(), modified.day()), (2023, 11, 14));
+        assert_eq!((modified.hour(), modified.minute(), modified.second()), (22, 13, 20));
+
+        let mut contents = Vec::new();
+        entry.read_to_end(&mut contents).unwrap();
+        assert_eq!(contents, entries[1].1);
+    }
+
+    #[test]
+    fn test_collect_files_skips_database() {
+        // Config and data directories are the same on Windows and macOS
+        let root = std::env::temp_dir().join(format!("focusapp-export-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("static")).unwrap();
+        for name in ["settings.json", "static/queues.json", "focusapp.db", "focusapp.db-journal", "focusapp.db-wal"] {
+            std::fs::write(root.join(name), b"{}").unwrap();
+        }
+
+        let mut files = Vec::new();
+        collect_files(&root, &root, &mut files).unwrap();
+        files.push((DATABASE_ENTRY.to_string(), Vec::new()));
+        files.sort_by(|a, b| a.0.cmp(&b.0));
+        let names: Vec<&str> = files.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["focusapp.db", "settings.json", "static/queues.json"]);
+        assert!(write_zip(&files, 1_700_000_000).is_ok());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
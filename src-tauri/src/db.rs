@@ -0,0 +1,243 @@
+//! Local SQLite database.
+//!
+//! Game sessions, CS reports and timelines, the import history, favorite
+//! champions, champion notes, the personal bests and the daily goal streaks
+//! live in `focusapp.db` in the app data
+//! directory. The connection is opened once in the setup and shared through
+//! the Tauri state; every access goes through `with` or `read`, which hold the
+//! connection's lock, so two read-modify-writes never interleave.
+//!
+//! Records are stored as JSON in a `data` column next to the keys they are
+//! looked up by, so a new field with a serde default needs no migration. The
+//! schema itself is created and upgraded by `migrations`.
+
+mod migrations;
+
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::path::Path;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+/// File of the database in the app data directory
+pub const DATABASE_FILE: &str = "focusapp.db";
+
+/// Recorded game sessions (`sessions::GameSession`)
+pub const GAME_SESSIONS: &str = "game_sessions";
+
+/// CS reports of finished games (`game_reports::GameReport`)
+pub const GAME_REPORTS: &str = "game_reports";
+
+/// CS timelines of finished games (`game_reports::GameTimeline`)
+pub const CS_TIMELINES: &str = "cs_timelines";
+
+/// Applied imports (`import_history::ImportRecord`)
+pub const IMPORT_HISTORY: &str = "import_history";
+
+/// Notes written about a champion (`favorites::ChampionNote`)
+pub const CHAMPION_NOTES: &str = "champion_notes";
+
+/// Document holding the personal bests
+pub const PERSONAL_BESTS_DOCUMENT: &str = "personal_bests";
+
+/// Document holding the daily goal streaks
+pub const STREAKS_DOCUMENT: &str = "streaks";
+
+/// Connection shared through the Tauri state
+pub struct Database {
+    connection: Mutex<Connection>,
+}
+
+impl Database {
+    /// Open the database and bring its schema up to date.
+    ///
+    /// Falls back to an in-memory database when the file can't be opened, so a
+    /// locked or corrupted file never prevents the app from starting.
+    pub fn open(app: &AppHandle) -> Self {
+        let opened = app
+            .path()
+            .app_data_dir()
+            .map_err(|e| e.to_string())
+            .and_then(|dir| open_file(&dir.join(DATABASE_FILE)));
+
+        let connection = opened.unwrap_or_else(|e| {
+            log_warn!("[db] Failed to open the database, history kept in memory: {}", e);
+            let mut connection = Connection::open_in_memory().expect("in-memory SQLite database");
+            if let Err(e) = migrations::apply(&mut connection) {
+                log_warn!("[db] {}", e);
+            }
+            connection
+        });
+
+        Self {
+            connection: Mutex::new(connection),
+        }
+    }
+
+    /// Copy the whole database to `path` (consistent even while it is written)
+    pub fn backup_to(&self, path: &Path) -> Result<(), String> {
+        let connection = self.connection.lock().map_err(|_| "Database lock poisoned")?;
+        connection
+            .execute("VACUUM INTO ?1", [path.to_string_lossy()])
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+
+    /// Close the database file (before it is deleted); later accesses use an
+    /// empty in-memory database until the app restarts
+    pub fn close(&self) -> Result<(), String> {
+        let mut connection = self.connection.lock().map_err(|_| "Database lock poisoned")?;
+        let mut memory = Connection::open_in_memory().map_err(|e| e.to_string())?;
+        migrations::apply(&mut memory)?;
+        *connection = memory;
+        Ok(())
+    }
+}
+
+/// Empty in-memory database with the current schema
+#[cfg(test)]
+pub fn open_in_memory() -> Connection {
+    let mut connection = Connection::open_in_memory().expect("in-memory SQLite database");
+    migrations::apply(&mut connection).expect("database schema");
+    connection
+}
+
+fn open_file(path: &Path) -> Result<Connection, String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let mut connection = Connection::open(path).map_err(|e| e.to_string())?;
+    migrations::apply(&mut connection)?;
+    Ok(connection)
+}
+
+/// Run `f` on the connection, holding its lock
+pub fn with<T>(app: &AppHandle, f: impl FnOnce(&mut Connection) -> rusqlite::Result<T>) -> Result<T, String> {
+    let database = app.try_state::<Database>().ok_or("Database not opened")?;
+    let mut connection = database.connection.lock().map_err(|_| "Database lock poisoned")?;
+    f(&mut connection).map_err(|e| e.to_string())
+}
+
+/// Like `with`, for reads: returns `T::default()` when the query fails, so an
+/// unreadable database never breaks a screen
+pub fn read<T: Default>(app: &AppHandle, f: impl FnOnce(&mut Connection) -> rusqlite::Result<T>) -> T {
+    with(app, f).unwrap_or_else(|e| {
+        log_warn!("[db] Read failed: {}", e);
+        T::default()
+    })
+}
+
+fn to_json<T: Serialize>(value: &T) -> rusqlite::Result<String> {
+    serde_json::to_string(value).map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))
+}
+
+/// Parse a `data` column, skipping (and logging) rows written by an incompatible version
+fn from_json<T: DeserializeOwned>(table: &str, data: &str) -> Option<T> {
+    serde_json::from_str(data)
+        .map_err(|e| log_warn!("[db] Ignoring unreadable row of {}: {}", table, e))
+        .ok()
+}
+
+/// Every record of a table, oldest first
+pub fn load_rows<T: DeserializeOwned>(connection: &Connection, table: &str) -> rusqlite::Result<Vec<T>> {
+    let mut statement = connection.prepare(&format!("SELECT data FROM {} ORDER BY id", table))?;
+    let rows = statement.query_map([], |row| row.get::<_, String>(0))?;
+    let mut records = Vec::new();
+    for data in rows {
+        records.extend(from_json(table, &data?));
+    }
+    Ok(records)
+}
+
+/// Drop the oldest records of a table beyond `max_rows`
+pub fn trim(connection: &Connection, table: &str, max_rows: usize) -> rusqlite::Result<()> {
+    connection.execute(
+        &format!(
+            "DELETE FROM {0} WHERE id NOT IN (SELECT id FROM {0} ORDER BY id DESC LIMIT ?1)",
+            table
+        ),
+        [max_rows as i64],
+    )?;
+    Ok(())
+}
+
+/// Latest record of a game (None if it has none)
+pub fn load_game<T: DeserializeOwned>(connection: &Connection, table: &str, game_id: i64) -> rusqlite::Result<Option<T>> {
+    let data: Option<String> = connection
+        .query_row(
+            &format!("SELECT data FROM {} WHERE game_id = ?1 ORDER BY id DESC LIMIT 1", table),
+            [game_id],
+            |row| row.get(0),
+        )
+        .optional()?;
+    Ok(data.and_then(|data| from_json(table, &data)))
+}
+
+/// Store the record of a finished game and keep the last `max_rows`.
+///
+/// If a record with the same non-zero game ID exists, it is replaced.
+pub fn save_game<T: Serialize>(
+    connection: &mut Connection,
+    table: &str,
+    game_id: i64,
+    record: &T,
+    max_rows: usize,
+) -> rusqlite::Result<()> {
+    let data = to_json(record)?;
+    let transaction = connection.transaction()?;
+    if game_id != 0 {
+        transaction.execute(&format!("DELETE FROM {} WHERE game_id = ?1", table), [game_id])?;
+    }
+    transaction.execute(
+        &format!("INSERT INTO {} (game_id, data) VALUES (?1, ?2)", table),
+        params![game_id, data],
+    )?;
+    trim(&transaction, table, max_rows)?;
+    transaction.commit()
+}
+
+/// Change the latest record of a game in place.
+///
+/// Returns `Ok(false)` if the game has no record.
+pub fn update_game<T: Serialize + DeserializeOwned>(
+    connection: &Connection,
+    table: &str,
+    game_id: i64,
+    update: impl FnOnce(&mut T),
+) -> rusqlite::Result<bool> {
+    let row: Option<(i64, String)> = connection
+        .query_row(
+            &format!("SELECT id, data FROM {} WHERE game_id = ?1 ORDER BY id DESC LIMIT 1", table),
+            [game_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()?;
+    let Some((id, mut record)) = row.and_then(|(id, data)| Some((id, from_json::<T>(table, &data)?))) else {
+        return Ok(false);
+    };
+    update(&mut record);
+    connection.execute(
+        &format!("UPDATE {} SET data = ?1 WHERE id = ?2", table),
+        params![to_json(&record)?, id],
+    )?;
+    Ok(true)
+}
+
+/// Load a document (`T::default()` when it was never saved or can't be read)
+pub fn load_document<T: DeserializeOwned + Default>(connection: &Connection, name: &str) -> rusqlite::Result<T> {
+    let data: Option<String> = connection
+        .query_row("SELECT data FROM documents WHERE name = ?1", [name], |row| row.get(0))
+        .optional()?;
+    Ok(data.and_then(|data| from_json("documents", &data)).unwrap_or_default())
+}
+
+/// Save a document, replacing the previous version
+pub fn save_document<T: Serialize>(connection: &Connection, name: &str, value: &T) -> rusqlite::Result<()> {
+    connection.execute(
+        "INSERT INTO documents (name, data) VALUES (?1, ?2)
+         ON CONFLICT (name) DO UPDATE SET data = excluded.data",
+        params![name, to_json(value)?],
+    )?;
+    Ok(())
+}
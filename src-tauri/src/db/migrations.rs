@@ -0,0 +1,103 @@
+//! Schema of the database and its upgrades.
+//!
+//! `MIGRATIONS[n]` upgrades a version `n` database to `n + 1`; the version is
+//! kept in SQLite's `user_version`.
+
+use rusqlite::Connection;
+
+/// Schema of version 1: game records, import history, favorites, notes and documents
+const SCHEMA_V1: &str = "
+CREATE TABLE game_sessions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    game_id INTEGER NOT NULL,
+    data TEXT NOT NULL
+);
+CREATE INDEX game_sessions_game_id ON game_sessions (game_id);
+
+CREATE TABLE game_reports (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    game_id INTEGER NOT NULL,
+    data TEXT NOT NULL
+);
+CREATE INDEX game_reports_game_id ON game_reports (game_id);
+
+CREATE TABLE cs_timelines (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    game_id INTEGER NOT NULL,
+    data TEXT NOT NULL
+);
+CREATE INDEX cs_timelines_game_id ON cs_timelines (game_id);
+
+CREATE TABLE import_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    imported_at INTEGER NOT NULL,
+    data TEXT NOT NULL
+);
+
+CREATE TABLE favorites (
+    champion_key TEXT PRIMARY KEY COLLATE NOCASE,
+    added_at INTEGER NOT NULL
+);
+
+CREATE TABLE champion_notes (
+    champion_key TEXT NOT NULL COLLATE NOCASE,
+    role TEXT NOT NULL COLLATE NOCASE,
+    data TEXT NOT NULL,
+    PRIMARY KEY (champion_key, role)
+);
+
+CREATE TABLE documents (
+    name TEXT PRIMARY KEY,
+    data TEXT NOT NULL
+);
+";
+
+/// Migration steps, in order
+const MIGRATIONS: &[&str] = &[SCHEMA_V1];
+
+fn schema_version(connection: &Connection) -> rusqlite::Result<usize> {
+    connection
+        .query_row("PRAGMA user_version", [], |row| row.get::<_, i64>(0))
+        .map(|version| version.max(0) as usize)
+}
+
+/// Bring the schema up to date
+pub fn apply(connection: &mut Connection) -> Result<(), String> {
+    let version = schema_version(connection).map_err(|e| e.to_string())?;
+    if version >= MIGRATIONS.len() {
+        return Ok(());
+    }
+
+    let transaction = connection.transaction().map_err(|e| e.to_string())?;
+    for migration in &MIGRATIONS[version..] {
+        transaction.execute_batch(migration).map_err(|e| e.to_string())?;
+    }
+    transaction
+        .pragma_update(None, "user_version", MIGRATIONS.len() as i64)
+        .map_err(|e| e.to_string())?;
+    transaction.commit().map_err(|e| e.to_string())?;
+    log_info!("[db] Schema upgraded from version {} to {}", version, MIGRATIONS.len());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply() {
+        let mut connection = Connection::open_in_memory().unwrap();
+        apply(&mut connection).unwrap();
+        assert_eq!(schema_version(&connection).unwrap(), MIGRATIONS.len());
+        connection
+            .execute("INSERT INTO favorites (champion_key, added_at) VALUES ('Ahri', 1)", [])
+            .unwrap();
+
+        // An up-to-date database is left untouched
+        apply(&mut connection).unwrap();
+        let favorites: i64 = connection
+            .query_row("SELECT COUNT(*) FROM favorites", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(favorites, 1);
+    }
+}
@@ -0,0 +1,150 @@
+//! `focusapp://` deep links.
+//!
+//! The FocusApi website offers "Open in FocusApp" buttons pointing to
+//! `focusapp://import?champion=Ahri&role=mid`. The scheme is declared in
+//! `tauri.conf.json` (`plugins.deep-link`) and registered by the installer;
+//! `tauri-plugin-deep-link` delivers the links, and
+//! `tauri-plugin-single-instance` hands the links of later launches to the
+//! running instance. The link is emitted to the frontend as
+//! `deep-link-import`, which asks the user for confirmation before opening
+//! the build in the import flow. Nothing is imported without the user's click.
+
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, Url};
+use tauri_plugin_deep_link::DeepLinkExt;
+
+use crate::events::AppEvent;
+
+/// URL scheme of the deep links
+pub const SCHEME: &str = "focusapp";
+
+/// Roles accepted in a link
+const ROLES: &[&str] = &["top", "jungle", "mid", "adc", "support", "aram"];
+
+/// Build import requested by a deep link
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DeepLinkImport {
+    /// DDragon key or name (e.g., "MonkeyKing")
+    pub champion: String,
+    pub role: Option<String>,
+}
+
+/// Parse a `focusapp://import?...` link
+pub fn parse_link(link: &str) -> Result<DeepLinkImport, String> {
+    let url = Url::parse(link.trim()).map_err(|e| format!("Invalid link: {}", e))?;
+    if url.scheme() != SCHEME {
+        return Err(format!("Not a {} link", SCHEME));
+    }
+    // "focusapp://import?..." (host) or "focusapp:import?..." (path)
+    let action = url.host_str().unwrap_or_else(|| url.path()).trim_matches('/');
+    if action != "import" {
+        return Err(format!("Unknown link action: {}", action));
+    }
+
+    let query = |name: &str| {
+        url.query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+
+    let champion = query("champion").ok_or("The link has no champion")?;
+    if champion.len() > 32 || !champion.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(format!("Invalid champion: {}", champion));
+    }
+    let role = match query("role").map(|role| role.to_lowercase()) {
+        Some(role) if !ROLES.contains(&role.as_str()) => return Err(format!("Unknown role: {}", role)),
+        role => role,
+    };
+
+    Ok(DeepLinkImport { champion, role })
+}
+
+/// Deep link state shared in the Tauri state
+#[derive(Default)]
+pub struct DeepLinkState {
+    /// Last link not yet picked up by the frontend
+    pending: Mutex<Option<DeepLinkImport>>,
+}
+
+/// Handle a received link: keep it for the frontend and bring the app to front
+pub fn handle_link(app: &AppHandle, link: &str) {
+    let import = match parse_link(link) {
+        Ok(import) => import,
+        Err(e) => {
+            log_warn!("[deep_link] Ignoring link: {}", e);
+            return;
+        }
+    };
+
+    if let Some(state) = app.try_state::<DeepLinkState>() {
+        if let Ok(mut pending) = state.pending.lock() {
+            *pending = Some(import.clone());
+        }
+    }
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+    let _ = AppEvent::DeepLinkImport(&import).emit(app);
+}
+
+/// Receive the links that launched the app or were opened while it runs
+pub fn init(app: &AppHandle) {
+    // Installed builds are registered by the installer; this covers dev builds and AppImages
+    #[cfg(any(windows, target_os = "linux"))]
+    if let Err(e) = app.deep_link().register_all() {
+        log_warn!("[deep_link] Failed to register the {}:// scheme: {}", SCHEME, e);
+    }
+
+    let handle = app.clone();
+    app.deep_link().on_open_url(move |event| {
+        for url in event.urls() {
+            handle_link(&handle, url.as_str());
+        }
+    });
+
+    match app.deep_link().get_current() {
+        Ok(urls) => {
+            for url in urls.into_iter().flatten() {
+                handle_link(app, url.as_str());
+            }
+        }
+        Err(e) => log_warn!("[deep_link] {}", e),
+    }
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Take the deep link received before the frontend was listening (e.g., the link that launched the app).
+#[tauri::command]
+pub async fn take_pending_deep_link(state: tauri::State<'_, DeepLinkState>) -> Result<Option<DeepLinkImport>, String> {
+    Ok(state.pending.lock().map_err(|e| e.to_string())?.take())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_link() {
+        assert_eq!(
+            parse_link("focusapp://import?champion=MonkeyKing&role=Top"),
+            Ok(DeepLinkImport {
+                champion: "MonkeyKing".to_string(),
+                role: Some("top".to_string()),
+            })
+        );
+        assert_eq!(parse_link("focusapp://import/?champion=Ahri").unwrap().role, None);
+
+        assert!(parse_link("https://import?champion=Ahri").is_err());
+        assert!(parse_link("focusapp://delete?champion=Ahri").is_err());
+        assert!(parse_link("focusapp://import?champion=..%2Fsecret").is_err());
+        assert!(parse_link("focusapp://import?champion=Ahri&role=roam").is_err());
+    }
+}
@@ -0,0 +1,230 @@
+//! Crate-wide error taxonomy.
+//!
+//! Every failure surfaced to the frontend as a `CommandError` goes through
+//! `AppError`, whose `code()` is the stable, machine-readable code the UI,
+//! the offline import queue, the message catalog and the usage counters rely
+//! on. Module errors (`LcuError`, `reqwest`, IO, JSON) convert into it with
+//! `From`, so two modules failing the same way always report the same code.
+
+use thiserror::Error;
+
+use crate::lcu::LcuError;
+use crate::CommandError;
+
+#[derive(Debug, Error)]
+pub enum AppError {
+    // League Client (LCU)
+    #[error("League Client is not running")]
+    ClientNotRunning,
+
+    #[error("Lockfile not found at: {0}")]
+    LockfileNotFound(String),
+
+    #[error("Failed to parse lockfile: {0}")]
+    LockfileInvalid(String),
+
+    /// The client is known to run but its API cannot be reached
+    #[error("League Client unreachable: {0}")]
+    LcuUnreachable(String),
+
+    /// The client answered with an error
+    #[error("League Client API error: {0}")]
+    Lcu(String),
+
+    // Live Client Data API (in game)
+    #[error("Live Client API error: {0}")]
+    LiveClient(String),
+
+    // FocusApi and other build sources
+    #[error("FocusApi rejected the API key")]
+    FocusApiUnauthorized,
+
+    #[error("{0}")]
+    FocusApi(String),
+
+    /// FocusApi quota used up; requests resume after `retry_after_secs`
+    #[error("FocusApi rate limit reached, retry in {retry_after_secs}s")]
+    RateLimited { retry_after_secs: u64 },
+
+    /// Remote server unreachable (offline, DNS, timeout)
+    #[error("HTTP request failed: {0}")]
+    Network(String),
+
+    /// A response or file does not have the expected format
+    #[error("{0}")]
+    InvalidData(String),
+
+    /// A build response the League Client would reject
+    #[error("Invalid build data: {field}: {reason}")]
+    PayloadInvalid { field: String, reason: String },
+
+    /// A rune page written by the user the League Client would reject
+    #[error("Invalid rune page: {field}: {reason}")]
+    RunePageInvalid { field: String, reason: String },
+
+    #[error("{0}")]
+    NotFound(String),
+
+    // Local data
+    #[error("Storage error: {0}")]
+    Storage(String),
+
+    // CS overlay window
+    #[error("Overlay error: {0}")]
+    Overlay(String),
+
+    /// Stopped by the user or by a newer request
+    #[error("Operation cancelled")]
+    Cancelled,
+
+    /// Writes to the client are disabled in the settings
+    #[error("Safe mode is on: the League Client is not modified")]
+    SafeMode,
+}
+
+impl AppError {
+    /// Stable error code sent to the frontend
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::ClientNotRunning => "CLIENT_NOT_RUNNING",
+            AppError::LockfileNotFound(_) => "LOCKFILE_NOT_FOUND",
+            AppError::LockfileInvalid(_) => "LOCKFILE_PARSE_ERROR",
+            AppError::LcuUnreachable(_) => "LCU_UNREACHABLE",
+            AppError::Lcu(_) => "LCU_ERROR",
+            AppError::LiveClient(_) => "LIVE_CLIENT_ERROR",
+            AppError::FocusApiUnauthorized => "FOCUS_API_UNAUTHORIZED",
+            AppError::FocusApi(_) => "FOCUS_API_ERROR",
+            AppError::RateLimited { .. } => "RATE_LIMITED",
+            AppError::Network(_) => "NETWORK_ERROR",
+            AppError::InvalidData(_) => "PARSE_ERROR",
+            AppError::PayloadInvalid { .. } => "PAYLOAD_INVALID",
+            AppError::RunePageInvalid { .. } => "RUNE_PAGE_INVALID",
+            AppError::NotFound(_) => "NOT_FOUND",
+            AppError::Storage(_) => "STORAGE_ERROR",
+            AppError::Overlay(_) => "OVERLAY_ERROR",
+            AppError::Cancelled => "CANCELLED",
+            AppError::SafeMode => "SAFE_MODE",
+        }
+    }
+
+    /// Whether the error means the client or the network is unavailable
+    /// (the import can be retried later)
+    pub fn is_offline_code(code: &str) -> bool {
+        matches!(
+            code,
+            "CLIENT_NOT_RUNNING" | "LOCKFILE_NOT_FOUND" | "LCU_UNREACHABLE" | "NETWORK_ERROR"
+        )
+    }
+}
+
+impl From<LcuError> for AppError {
+    fn from(err: LcuError) -> Self {
+        match err {
+            LcuError::ClientNotRunning => AppError::ClientNotRunning,
+            LcuError::LockfileNotFound(path) => AppError::LockfileNotFound(path.display().to_string()),
+            LcuError::LockfileParseError(e) => AppError::LockfileInvalid(e),
+            LcuError::HttpError(e) => AppError::LcuUnreachable(e.to_string()),
+            LcuError::ApiError(e) => AppError::Lcu(e),
+            LcuError::IoError(e) => AppError::Storage(e.to_string()),
+            LcuError::SafeMode => AppError::SafeMode,
+        }
+    }
+}
+
+impl From<reqwest::Error> for AppError {
+    fn from(err: reqwest::Error) -> Self {
+        if err.is_decode() {
+            AppError::InvalidData(err.to_string())
+        } else {
+            AppError::Network(err.to_string())
+        }
+    }
+}
+
+impl From<std::io::Error> for AppError {
+    fn from(err: std::io::Error) -> Self {
+        AppError::Storage(err.to_string())
+    }
+}
+
+impl From<serde_json::Error> for AppError {
+    fn from(err: serde_json::Error) -> Self {
+        AppError::InvalidData(err.to_string())
+    }
+}
+
+impl From<AppError> for CommandError {
+    fn from(err: AppError) -> Self {
+        CommandError {
+            code: err.code().to_string(),
+            message: err.to_string(),
+            retry_after_secs: match err {
+                AppError::RateLimited { retry_after_secs } => Some(retry_after_secs),
+                _ => None,
+            },
+        }
+    }
+}
+
+impl From<LcuError> for CommandError {
+    fn from(err: LcuError) -> Self {
+        AppError::from(err).into()
+    }
+}
+
+impl From<reqwest::Error> for CommandError {
+    fn from(err: reqwest::Error) -> Self {
+        AppError::from(err).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::{message_for, Locale};
+
+    #[test]
+    fn test_codes_have_messages() {
+        let errors = [
+            AppError::ClientNotRunning,
+            AppError::LockfileNotFound(String::new()),
+            AppError::LockfileInvalid(String::new()),
+            AppError::LcuUnreachable(String::new()),
+            AppError::Lcu(String::new()),
+            AppError::LiveClient(String::new()),
+            AppError::FocusApiUnauthorized,
+            AppError::FocusApi(String::new()),
+            AppError::RateLimited { retry_after_secs: 0 },
+            AppError::Network(String::new()),
+            AppError::InvalidData(String::new()),
+            AppError::PayloadInvalid {
+                field: String::new(),
+                reason: String::new(),
+            },
+            AppError::RunePageInvalid {
+                field: String::new(),
+                reason: String::new(),
+            },
+            AppError::NotFound(String::new()),
+            AppError::Storage(String::new()),
+            AppError::Overlay(String::new()),
+            AppError::Cancelled,
+            AppError::SafeMode,
+        ];
+        // Unknown codes get the generic message
+        let generic = message_for("", Locale::En);
+        for error in &errors {
+            assert_ne!(message_for(error.code(), Locale::En), generic, "{}", error.code());
+        }
+
+        let command_error = CommandError::from(LcuError::LockfileParseError("bad".to_string()));
+        assert_eq!(command_error.code, "LOCKFILE_PARSE_ERROR");
+        assert_eq!(command_error.message, "Failed to parse lockfile: bad");
+        assert_eq!(serde_json::to_value(&command_error).unwrap().get("retryAfterSecs"), None);
+
+        // The frontend gets the delay of a rate limit
+        let command_error = CommandError::from(AppError::RateLimited { retry_after_secs: 42 });
+        let json = serde_json::to_value(&command_error).unwrap();
+        assert_eq!((json["code"].as_str(), json["retryAfterSecs"].as_u64()), (Some("RATE_LIMITED"), Some(42)));
+    }
+}
@@ -0,0 +1,115 @@
+//! ETag cache of FocusApi GET responses.
+//!
+//! Each cached response is kept on disk with its ETag. The next GET of the
+//! same URL sends it back in `If-None-Match`; a `304 Not Modified` answer
+//! is served from the cache, so refreshing tier lists, benchmarks and
+//! champion data after a restart costs a round trip and no download.
+//!
+//! Responses are keyed by URL and FocusApi account, so a personalized
+//! response is never served to another account, and switching to another
+//! server never serves the previous server's copy.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+use crate::storage;
+
+/// Folder of the cached responses, relative to the cache directory
+const ETAG_CACHE_DIR: &str = "focus-api/etags";
+
+/// Folder found by `init` (None before: nothing is cached)
+static CACHE_DIR: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// A cached response body and its ETag
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CachedResponse {
+    pub etag: String,
+    pub body: String,
+}
+
+/// File of the response of `url` for `account`
+fn file_name(url: &str, account: Option<&str>) -> String {
+    let key = format!("{}\n{}", account.unwrap_or_default(), url);
+    let hash = Sha256::digest(key.as_bytes());
+    format!("{}.json", hash.iter().take(16).map(|b| format!("{:02x}", b)).collect::<String>())
+}
+
+fn read_in(dir: &Path, url: &str, account: Option<&str>) -> Option<CachedResponse> {
+    let json = std::fs::read_to_string(dir.join(file_name(url, account))).ok()?;
+    serde_json::from_str(&json).ok()
+}
+
+fn write_in(dir: &Path, url: &str, account: Option<&str>, response: &CachedResponse) -> Result<(), String> {
+    let json = serde_json::to_string(response).map_err(|e| e.to_string())?;
+    storage::write_atomic(&dir.join(file_name(url, account)), json.as_bytes())
+}
+
+fn cache_dir() -> Option<PathBuf> {
+    CACHE_DIR.lock().ok().and_then(|dir| dir.clone())
+}
+
+/// Cached response of `url`, if any
+pub fn lookup(url: &str, account: Option<&str>) -> Option<CachedResponse> {
+    read_in(&cache_dir()?, url, account)
+}
+
+/// Cache the response of `url` (failures are logged: the cache is optional)
+pub fn store(url: &str, account: Option<&str>, response: &CachedResponse) {
+    let Some(dir) = cache_dir() else {
+        return;
+    };
+    if let Err(e) = write_in(&dir, url, account, response) {
+        log_debug!("[etag_cache] Failed to cache {}: {}", url, e);
+    }
+}
+
+/// Drop every cached response (new patch)
+pub fn clear() {
+    let Some(dir) = cache_dir() else {
+        return;
+    };
+    match std::fs::remove_dir_all(&dir) {
+        Ok(()) => log_info!("[etag_cache] Cleared cached FocusApi responses"),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => log_warn!("[etag_cache] Failed to clear {:?}: {}", dir, e),
+    }
+}
+
+/// Find the cache folder (called from the setup)
+pub fn init(app: &AppHandle) {
+    match app.path().app_cache_dir() {
+        Ok(dir) => {
+            if let Ok(mut current) = CACHE_DIR.lock() {
+                *current = Some(dir.join(ETAG_CACHE_DIR));
+            }
+        }
+        Err(e) => log_warn!("[etag_cache] No cache directory, FocusApi responses are not cached: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cached_responses() {
+        let dir = std::env::temp_dir().join(format!("focusapp-etags-{}", std::process::id()));
+        let response = CachedResponse {
+            etag: "\"abc\"".to_string(),
+            body: "{\"tiers\":[]}".to_string(),
+        };
+        let url = "https://api.hommet.ch/api/v1/tierlist?role=top";
+        write_in(&dir, url, None, &response).unwrap();
+
+        assert_eq!(read_in(&dir, url, None), Some(response));
+        assert_eq!(read_in(&dir, "https://api.hommet.ch/api/v1/tierlist?role=mid", None), None);
+        // Another account or server does not share the response
+        assert_eq!(read_in(&dir, url, Some("faker")), None);
+        assert_eq!(read_in(&dir, "https://builds.example.com/api/v1/tierlist?role=top", None), None);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,218 @@
+//! Events sent to the frontend.
+//!
+//! Every event the backend emits is an `AppEvent` variant: `name()` is the
+//! stable event name the frontend listens to, and the payload is the
+//! variant's content serialized as-is. Emitting through `AppEvent` instead of
+//! calling `app.emit` with a string keeps names and payload shapes in one
+//! place.
+//!
+//! | Event | Payload | Sent to |
+//! |-------|---------|---------|
+//! | `game-state-changed` | `GameState` | all windows |
+//! | `objective-timers-updated` | `ObjectiveTimers` | all windows |
+//! | `spell-timers-update` | `SpellTimerStatus[]` | all windows |
+//! | `cs-overlay-update` | changed fields of `LiveCsStats` | overlay |
+//! | `cs-overlay-mode` | `OverlayMode` | all windows / overlay |
+//! | `cs-overlay-config` | `OverlayConfig` | overlay |
+//! | `cs-overlay-style` | `OverlayStyle` | overlay |
+//! | `cs-milestone` | `CsMilestone` | all windows |
+//! | `goal-achieved` | `GoalAchieved` | all windows |
+//! | `pending-imports-changed` | `PendingImportsState` | all windows |
+//! | `champ-select-pick-changed` | `ChampSelectPick` | all windows |
+//! | `ban-suggestions` | `BanSuggestions` | all windows |
+//! | `team-picks-changed` | `TeamPicks` | all windows |
+//! | `assigned-position` | `AssignedPosition` | all windows |
+//! | `champion-changed` | `ChampionChange` | all windows |
+//! | `reimport-recommended` | `ReimportRecommended` | all windows |
+//! | `deep-link-import` | `DeepLinkImport` | all windows |
+//! | `static-data-updated` | `{ version }` | all windows |
+//! | `settings-changed` | `AppSettings` | all windows |
+//! | `update-available` | `LatestVersion` | all windows |
+//! | `update-download-progress` | `UpdateProgress` | all windows |
+//!
+//! `EVENTS_VERSION` is bumped whenever an event is renamed or a payload
+//! changes shape; the frontend can compare it with `get_event_catalog`.
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use crate::aram::ChampionChange;
+use crate::ban_suggestions::BanSuggestions;
+use crate::cs_goals::CsMilestone;
+use crate::daily_goals::GoalAchieved;
+use crate::deep_link::DeepLinkImport;
+use crate::game_watcher::GameState;
+use crate::objectives::ObjectiveTimers;
+use crate::overlay::{OverlayConfig, OverlayMode, OverlayStyle};
+use crate::pending_imports::PendingImportsState;
+use crate::prefetch::ChampSelectPick;
+use crate::reimport::ReimportRecommended;
+use crate::settings::AppSettings;
+use crate::spell_timers::SpellTimerStatus;
+use crate::team_picks::{AssignedPosition, TeamPicks};
+use crate::updater::{LatestVersion, UpdateProgress};
+
+/// Version of the event names and payloads
+pub const EVENTS_VERSION: u32 = 1;
+
+pub const GAME_STATE_CHANGED_EVENT: &str = "game-state-changed";
+pub const OBJECTIVE_TIMERS_UPDATED_EVENT: &str = "objective-timers-updated";
+pub const SPELL_TIMERS_UPDATE_EVENT: &str = "spell-timers-update";
+pub const CS_OVERLAY_UPDATE_EVENT: &str = "cs-overlay-update";
+pub const CS_OVERLAY_MODE_EVENT: &str = "cs-overlay-mode";
+pub const CS_OVERLAY_CONFIG_EVENT: &str = "cs-overlay-config";
+pub const CS_OVERLAY_STYLE_EVENT: &str = "cs-overlay-style";
+pub const CS_MILESTONE_EVENT: &str = "cs-milestone";
+pub const GOAL_ACHIEVED_EVENT: &str = "goal-achieved";
+pub const PENDING_IMPORTS_EVENT: &str = "pending-imports-changed";
+pub const PICK_CHANGED_EVENT: &str = "champ-select-pick-changed";
+pub const BAN_SUGGESTIONS_EVENT: &str = "ban-suggestions";
+pub const TEAM_PICKS_CHANGED_EVENT: &str = "team-picks-changed";
+pub const ASSIGNED_POSITION_EVENT: &str = "assigned-position";
+pub const CHAMPION_CHANGED_EVENT: &str = "champion-changed";
+pub const REIMPORT_RECOMMENDED_EVENT: &str = "reimport-recommended";
+pub const DEEP_LINK_EVENT: &str = "deep-link-import";
+pub const STATIC_DATA_UPDATED_EVENT: &str = "static-data-updated";
+pub const SETTINGS_CHANGED_EVENT: &str = "settings-changed";
+pub const UPDATE_AVAILABLE_EVENT: &str = "update-available";
+pub const UPDATE_PROGRESS_EVENT: &str = "update-download-progress";
+
+/// Every event name, in the order of the table above
+const EVENT_NAMES: &[&str] = &[
+    GAME_STATE_CHANGED_EVENT,
+    OBJECTIVE_TIMERS_UPDATED_EVENT,
+    SPELL_TIMERS_UPDATE_EVENT,
+    CS_OVERLAY_UPDATE_EVENT,
+    CS_OVERLAY_MODE_EVENT,
+    CS_OVERLAY_CONFIG_EVENT,
+    CS_OVERLAY_STYLE_EVENT,
+    CS_MILESTONE_EVENT,
+    GOAL_ACHIEVED_EVENT,
+    PENDING_IMPORTS_EVENT,
+    PICK_CHANGED_EVENT,
+    BAN_SUGGESTIONS_EVENT,
+    TEAM_PICKS_CHANGED_EVENT,
+    ASSIGNED_POSITION_EVENT,
+    CHAMPION_CHANGED_EVENT,
+    REIMPORT_RECOMMENDED_EVENT,
+    DEEP_LINK_EVENT,
+    STATIC_DATA_UPDATED_EVENT,
+    SETTINGS_CHANGED_EVENT,
+    UPDATE_AVAILABLE_EVENT,
+    UPDATE_PROGRESS_EVENT,
+];
+
+/// An event and its payload
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum AppEvent<'a> {
+    GameStateChanged(&'a GameState),
+    ObjectiveTimersUpdated(&'a ObjectiveTimers),
+    SpellTimersUpdate(&'a [SpellTimerStatus]),
+    /// Fields of the live CS stats that changed since the last update
+    CsOverlayUpdate(&'a serde_json::Value),
+    CsOverlayMode(&'a OverlayMode),
+    CsOverlayConfig(&'a OverlayConfig),
+    CsOverlayStyle(&'a OverlayStyle),
+    CsMilestone(&'a CsMilestone),
+    GoalAchieved(&'a GoalAchieved),
+    PendingImportsChanged(&'a PendingImportsState),
+    ChampSelectPickChanged(&'a ChampSelectPick),
+    BanSuggestions(&'a BanSuggestions),
+    TeamPicksChanged(&'a TeamPicks),
+    AssignedPosition(&'a AssignedPosition),
+    ChampionChanged(&'a ChampionChange),
+    ReimportRecommended(&'a ReimportRecommended),
+    DeepLinkImport(&'a DeepLinkImport),
+    StaticDataUpdated { version: &'a str },
+    SettingsChanged(&'a AppSettings),
+    UpdateAvailable(&'a LatestVersion),
+    UpdateDownloadProgress(&'a UpdateProgress),
+}
+
+impl AppEvent<'_> {
+    /// Stable event name
+    pub fn name(&self) -> &'static str {
+        match self {
+            AppEvent::GameStateChanged(_) => GAME_STATE_CHANGED_EVENT,
+            AppEvent::ObjectiveTimersUpdated(_) => OBJECTIVE_TIMERS_UPDATED_EVENT,
+            AppEvent::SpellTimersUpdate(_) => SPELL_TIMERS_UPDATE_EVENT,
+            AppEvent::CsOverlayUpdate(_) => CS_OVERLAY_UPDATE_EVENT,
+            AppEvent::CsOverlayMode(_) => CS_OVERLAY_MODE_EVENT,
+            AppEvent::CsOverlayConfig(_) => CS_OVERLAY_CONFIG_EVENT,
+            AppEvent::CsOverlayStyle(_) => CS_OVERLAY_STYLE_EVENT,
+            AppEvent::CsMilestone(_) => CS_MILESTONE_EVENT,
+            AppEvent::GoalAchieved(_) => GOAL_ACHIEVED_EVENT,
+            AppEvent::PendingImportsChanged(_) => PENDING_IMPORTS_EVENT,
+            AppEvent::ChampSelectPickChanged(_) => PICK_CHANGED_EVENT,
+            AppEvent::BanSuggestions(_) => BAN_SUGGESTIONS_EVENT,
+            AppEvent::TeamPicksChanged(_) => TEAM_PICKS_CHANGED_EVENT,
+            AppEvent::AssignedPosition(_) => ASSIGNED_POSITION_EVENT,
+            AppEvent::ChampionChanged(_) => CHAMPION_CHANGED_EVENT,
+            AppEvent::ReimportRecommended(_) => REIMPORT_RECOMMENDED_EVENT,
+            AppEvent::DeepLinkImport(_) => DEEP_LINK_EVENT,
+            AppEvent::StaticDataUpdated { .. } => STATIC_DATA_UPDATED_EVENT,
+            AppEvent::SettingsChanged(_) => SETTINGS_CHANGED_EVENT,
+            AppEvent::UpdateAvailable(_) => UPDATE_AVAILABLE_EVENT,
+            AppEvent::UpdateDownloadProgress(_) => UPDATE_PROGRESS_EVENT,
+        }
+    }
+
+    /// Send to every window
+    pub fn emit(&self, app: &AppHandle) -> Result<(), String> {
+        app.emit(self.name(), self)
+            .map_err(|e| format!("Failed to emit {}: {}", self.name(), e))
+    }
+
+    /// Send to the window `label` only
+    pub fn emit_to(&self, app: &AppHandle, label: &str) -> Result<(), String> {
+        app.emit_to(label, self.name(), self)
+            .map_err(|e| format!("Failed to emit {}: {}", self.name(), e))
+    }
+}
+
+/// Event names and payload version, for the frontend to check against its own
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventCatalog {
+    pub version: u32,
+    pub events: Vec<&'static str>,
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the version and names of the events the backend emits.
+#[tauri::command]
+pub async fn get_event_catalog() -> Result<EventCatalog, String> {
+    Ok(EventCatalog {
+        version: EVENTS_VERSION,
+        events: EVENT_NAMES.to_vec(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_names_and_payloads() {
+        // Names are unique
+        for (i, name) in EVENT_NAMES.iter().enumerate() {
+            assert!(!EVENT_NAMES[..i].contains(name), "{}", name);
+        }
+
+        // The payload is the variant's content, without a tag
+        let event = AppEvent::StaticDataUpdated { version: "14.10.1" };
+        assert_eq!(event.name(), "static-data-updated");
+        assert_eq!(serde_json::to_value(&event).unwrap(), serde_json::json!({ "version": "14.10.1" }));
+
+        let progress = UpdateProgress { downloaded: 1, total: 2 };
+        let event = AppEvent::UpdateDownloadProgress(&progress);
+        assert_eq!(
+            serde_json::to_value(&event).unwrap(),
+            serde_json::to_value(&progress).unwrap()
+        );
+    }
+}
@@ -0,0 +1,172 @@
+//! Favorite champions and champion notes.
+//!
+//! Both live in the database: favorites are a set of champion keys, notes
+//! are free text kept per champion and role (an empty role is a note about
+//! the champion in general). Keys are matched case-insensitively, so "ahri"
+//! and "Ahri" are the same champion.
+
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::db;
+use crate::sessions::now_unix_secs;
+
+/// Longest note kept, in characters
+const MAX_NOTE_CHARS: usize = 4000;
+
+/// A note about a champion
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChampionNote {
+    pub champion_key: String,
+    /// Empty for a note about every role
+    pub role: String,
+    pub text: String,
+    /// Unix timestamp (seconds)
+    pub updated_at: i64,
+}
+
+/// Favorite champion keys, in the order they were added
+fn load_favorites(connection: &Connection) -> rusqlite::Result<Vec<String>> {
+    let mut statement = connection.prepare("SELECT champion_key FROM favorites ORDER BY added_at, rowid")?;
+    let rows = statement.query_map([], |row| row.get(0))?;
+    rows.collect()
+}
+
+fn set_favorite_in(connection: &Connection, champion_key: &str, favorite: bool, now: i64) -> rusqlite::Result<()> {
+    if favorite {
+        connection.execute(
+            "INSERT OR IGNORE INTO favorites (champion_key, added_at) VALUES (?1, ?2)",
+            params![champion_key, now],
+        )?;
+    } else {
+        connection.execute("DELETE FROM favorites WHERE champion_key = ?1", [champion_key])?;
+    }
+    Ok(())
+}
+
+/// Notes of a champion, general note first
+fn load_notes(connection: &Connection, champion_key: &str) -> rusqlite::Result<Vec<ChampionNote>> {
+    let mut statement = connection.prepare(&format!(
+        "SELECT data FROM {} WHERE champion_key = ?1 ORDER BY role",
+        db::CHAMPION_NOTES
+    ))?;
+    let rows = statement.query_map([champion_key], |row| row.get::<_, String>(0))?;
+    let mut notes = Vec::new();
+    for data in rows {
+        notes.extend(serde_json::from_str(&data?).ok());
+    }
+    Ok(notes)
+}
+
+/// Save a note, or delete it when its text is empty
+fn save_note_in(connection: &Connection, note: &ChampionNote) -> rusqlite::Result<()> {
+    if note.text.is_empty() {
+        connection.execute(
+            &format!("DELETE FROM {} WHERE champion_key = ?1 AND role = ?2", db::CHAMPION_NOTES),
+            params![note.champion_key, note.role],
+        )?;
+        return Ok(());
+    }
+    let data = serde_json::to_string(note).map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+    connection.execute(
+        &format!(
+            "INSERT INTO {} (champion_key, role, data) VALUES (?1, ?2, ?3)
+             ON CONFLICT (champion_key, role) DO UPDATE SET data = excluded.data",
+            db::CHAMPION_NOTES
+        ),
+        params![note.champion_key, note.role, data],
+    )?;
+    Ok(())
+}
+
+fn champion_key(champion_key: &str) -> Result<String, String> {
+    let champion_key = champion_key.trim();
+    if champion_key.is_empty() {
+        return Err("A champion is required".to_string());
+    }
+    Ok(champion_key.to_string())
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the favorite champion keys, in the order they were added.
+#[tauri::command]
+pub async fn get_favorite_champions(app: AppHandle) -> Result<Vec<String>, String> {
+    Ok(db::read(&app, |connection| load_favorites(connection)))
+}
+
+/// Add a champion to the favorites (or remove it); returns the new list.
+#[tauri::command]
+pub async fn set_favorite_champion(app: AppHandle, champion_key: String, favorite: bool) -> Result<Vec<String>, String> {
+    let champion_key = self::champion_key(&champion_key)?;
+    db::with(&app, |connection| {
+        set_favorite_in(connection, &champion_key, favorite, now_unix_secs())?;
+        load_favorites(connection)
+    })
+}
+
+/// Get the notes written about a champion.
+#[tauri::command]
+pub async fn get_champion_notes(app: AppHandle, champion_key: String) -> Result<Vec<ChampionNote>, String> {
+    let champion_key = self::champion_key(&champion_key)?;
+    Ok(db::read(&app, |connection| load_notes(connection, &champion_key)))
+}
+
+/// Save the note of a champion for a role ("" for every role); an empty
+/// text deletes it.
+#[tauri::command]
+pub async fn save_champion_note(
+    app: AppHandle,
+    champion_key: String,
+    role: Option<String>,
+    text: String,
+) -> Result<Option<ChampionNote>, String> {
+    let note = ChampionNote {
+        champion_key: self::champion_key(&champion_key)?,
+        role: role.unwrap_or_default().trim().to_lowercase(),
+        text: text.trim().chars().take(MAX_NOTE_CHARS).collect(),
+        updated_at: now_unix_secs(),
+    };
+    db::with(&app, |connection| save_note_in(connection, &note))?;
+    Ok(Some(note).filter(|note| !note.text.is_empty()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(champion_key: &str, role: &str, text: &str) -> ChampionNote {
+        ChampionNote {
+            champion_key: champion_key.to_string(),
+            role: role.to_string(),
+            text: text.to_string(),
+            updated_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_favorites_and_notes() {
+        let connection = db::open_in_memory();
+
+        set_favorite_in(&connection, "Ahri", true, 1).unwrap();
+        set_favorite_in(&connection, "Zed", true, 2).unwrap();
+        set_favorite_in(&connection, "ahri", true, 3).unwrap();
+        assert_eq!(load_favorites(&connection).unwrap(), ["Ahri", "Zed"]);
+        set_favorite_in(&connection, "AHRI", false, 4).unwrap();
+        assert_eq!(load_favorites(&connection).unwrap(), ["Zed"]);
+
+        save_note_in(&connection, &note("Ahri", "mid", "Trade after her E")).unwrap();
+        save_note_in(&connection, &note("Ahri", "", "Charm has a long cooldown")).unwrap();
+        save_note_in(&connection, &note("ahri", "mid", "Roam after 6")).unwrap();
+        let notes = load_notes(&connection, "Ahri").unwrap();
+        assert_eq!(notes, [note("Ahri", "", "Charm has a long cooldown"), note("ahri", "mid", "Roam after 6")]);
+
+        // An empty note is deleted
+        save_note_in(&connection, &note("Ahri", "", "")).unwrap();
+        assert_eq!(load_notes(&connection, "AHRI").unwrap().len(), 1);
+    }
+}
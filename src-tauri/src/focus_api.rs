@@ -0,0 +1,905 @@
+//! FocusApi client.
+//!
+//! Every request to api.hommet.ch goes through this module so the API key
+//! stays in the backend: the webview calls the commands below and never sees
+//! the key. Responses are passed through as JSON; formatting stays in the
+//! frontend.
+//!
+//! The key is configured at runtime (`set_api_key`, `validate_api_key`,
+//! `clear_api_key`) and kept in the OS credential store; a key embedded at
+//! compile time is only used when none is stored.
+//!
+//! When a signing secret is embedded at build time, every request also
+//! carries an HMAC-SHA256 signature of its timestamp, method, path and body
+//! hash, so the server can reject forged or replayed requests made with a
+//! stolen key alone.
+//!
+//! FocusApi announces the request quota in `X-RateLimit-*` headers. The
+//! client keeps the last values: once the quota is used up, requests wait
+//! for the reset when it is close, otherwise they fail with `RATE_LIMITED`
+//! and the delay, without calling FocusApi.
+//!
+//! The API URL is the default server or a custom URL chosen in the settings
+//! (`focusApiUrl`); the `FOCUS_API_BASE_URL` environment
+//! variable overrides them all, for self-hosters and development. Custom URLs
+//! must use https, except on localhost. The API key, the request signature,
+//! the account tokens and the synced data are only sent to the default
+//! server: another server gets anonymous requests.
+//!
+//! GET responses are cached with their ETag (see etag_cache.rs) and
+//! revalidated with `If-None-Match`.
+//!
+//! When a FocusApi account is logged in (see `auth`), its access token is
+//! sent as a bearer token next to the API key.
+
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::sync::{Mutex, RwLock};
+use std::time::Duration;
+use tauri::{AppHandle, Listener, Manager};
+
+use crate::error::AppError;
+use crate::events::SETTINGS_CHANGED_EVENT;
+use crate::sessions::now_unix_secs;
+use crate::settings::{AppSettings, SettingsStore};
+use crate::{auth, cancel, credentials, etag_cache, redact, CommandError};
+
+/// Default API URL
+const FOCUS_API_BASE_URL: &str = "https://api.hommet.ch/api/v1";
+
+/// Host of the default API URL, the only one credentials are sent to
+const FOCUS_API_HOST: &str = "api.hommet.ch";
+
+/// Environment variable overriding the API URL of the settings
+const BASE_URL_ENV: &str = "FOCUS_API_BASE_URL";
+
+/// Fallback API key embedded at compile time via `FOCUS_API_KEY` env var.
+/// Build with: FOCUS_API_KEY=your_key cargo tauri build
+const FOCUS_API_KEY: &str = match option_env!("FOCUS_API_KEY") {
+    Some(key) => key,
+    None => "",
+};
+
+/// Secret used to sign requests, embedded at compile time via `FOCUS_API_SIGNING_SECRET`.
+/// Requests are sent unsigned when it is missing (development builds).
+const FOCUS_API_SIGNING_SECRET: &str = match option_env!("FOCUS_API_SIGNING_SECRET") {
+    Some(secret) => secret,
+    None => "",
+};
+
+/// Credential store account holding the API key
+const API_KEY_ACCOUNT: &str = "focus-api-key";
+
+/// Default timeout of FocusApi requests
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Authenticated endpoint pinged to test a key (rejected with 401 before any work)
+const KEY_CHECK_PATH: &str = "/tierlist?role=top";
+const KEY_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Status endpoint of FocusApi (servers without one are probed with HEAD on the API URL)
+const STATUS_PATH: &str = "/status";
+const STATUS_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A status check is reused this long
+const STATUS_TTL_SECS: i64 = 60;
+
+/// Slower status answers mean the service is degraded
+const SLOW_STATUS_MS: u64 = 3_000;
+
+/// Requests wait for the quota reset when it is this close, otherwise fail
+const MAX_QUOTA_WAIT_SECS: u64 = 5;
+
+/// Retry delay when a 429 response has no `Retry-After` header
+const DEFAULT_RETRY_AFTER_SECS: u64 = 60;
+
+/// API key read from the credential store (loaded once at startup)
+static STORED_API_KEY: RwLock<Option<String>> = RwLock::new(None);
+
+/// API URL in use (None = the default one)
+static BASE_URL: RwLock<Option<String>> = RwLock::new(None);
+
+/// Last status check
+static API_STATUS: Mutex<Option<ApiStatus>> = Mutex::new(None);
+
+/// Request quota, as announced by the last FocusApi response
+static QUOTA: Mutex<Quota> = Mutex::new(Quota {
+    remaining: None,
+    reset_at: 0,
+});
+
+/// Where the API key in use comes from
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ApiKeySource {
+    Keyring,
+    Embedded,
+    None,
+}
+
+/// Check a custom API URL and drop its trailing slash
+pub fn check_base_url(url: &str) -> Result<String, String> {
+    let parsed = tauri::Url::parse(url.trim()).map_err(|e| format!("Invalid API URL: {}", e))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(format!("Unsupported API URL scheme '{}' (expected http or https)", parsed.scheme()));
+    }
+    let host = parsed.host_str().unwrap_or_default();
+    if host.is_empty() {
+        return Err("The API URL has no host".to_string());
+    }
+    if parsed.scheme() == "http" && !matches!(host, "localhost" | "127.0.0.1" | "[::1]") {
+        return Err("The API URL must use https (http is only allowed on localhost)".to_string());
+    }
+    Ok(url.trim().trim_end_matches('/').to_string())
+}
+
+/// Whether `url` is on the default FocusApi server
+fn is_default_server(url: &str) -> bool {
+    tauri::Url::parse(url).is_ok_and(|url| url.scheme() == "https" && url.host_str() == Some(FOCUS_API_HOST))
+}
+
+/// Fail unless the API URL in use is the default server, the only one the
+/// account tokens and synced data are sent to
+pub fn ensure_default_server() -> Result<(), String> {
+    if is_default_server(&base_url()) {
+        Ok(())
+    } else {
+        Err(format!("FocusApi accounts are only available on {}", FOCUS_API_HOST))
+    }
+}
+
+/// API URL from the environment override, the custom URL or the default server, in that order
+fn resolve_base_url(env: Option<&str>, custom: Option<&str>) -> String {
+    [env, custom]
+        .into_iter()
+        .flatten()
+        .filter(|url| !url.trim().is_empty())
+        .find_map(|url| {
+            check_base_url(url)
+                .inspect_err(|e| log_warn!("[focus_api] Ignoring API URL {}: {}", url, e))
+                .ok()
+        })
+        .unwrap_or_else(|| FOCUS_API_BASE_URL.to_string())
+}
+
+fn set_base_url(settings: &AppSettings) {
+    let env = std::env::var(BASE_URL_ENV).ok();
+    let url = resolve_base_url(env.as_deref(), settings.focus_api_url.as_deref());
+    if let Ok(mut current) = BASE_URL.write() {
+        if current.as_deref() != Some(url.as_str()) {
+            log_info!("[focus_api] Using {}", url);
+            *current = Some(url);
+        }
+    }
+}
+
+fn base_url() -> String {
+    BASE_URL
+        .read()
+        .ok()
+        .and_then(|url| url.clone())
+        .unwrap_or_else(|| FOCUS_API_BASE_URL.to_string())
+}
+
+/// Follow the API URL saved in the settings
+pub fn init(app: &AppHandle) {
+    if let Some(store) = app.try_state::<SettingsStore>() {
+        set_base_url(&store.get());
+    }
+    app.listen(SETTINGS_CHANGED_EVENT, |event| {
+        if let Ok(settings) = serde_json::from_str::<AppSettings>(event.payload()) {
+            set_base_url(&settings);
+        }
+    });
+}
+
+/// Whether a fallback API key was embedded at build time (never the key itself)
+pub fn has_embedded_api_key() -> bool {
+    !FOCUS_API_KEY.is_empty()
+}
+
+/// API key used for requests: stored key first, then the embedded one
+fn api_key() -> String {
+    STORED_API_KEY
+        .read()
+        .ok()
+        .and_then(|key| key.clone())
+        .unwrap_or_else(|| FOCUS_API_KEY.to_string())
+}
+
+fn api_key_source() -> ApiKeySource {
+    if STORED_API_KEY.read().map(|key| key.is_some()).unwrap_or(false) {
+        ApiKeySource::Keyring
+    } else if has_embedded_api_key() {
+        ApiKeySource::Embedded
+    } else {
+        ApiKeySource::None
+    }
+}
+
+fn set_stored_api_key(key: Option<String>) {
+    if let Some(key) = &key {
+        redact::register_secret(key);
+    }
+    if let Ok(mut stored) = STORED_API_KEY.write() {
+        *stored = key;
+    }
+}
+
+/// Load the API key from the credential store (spawned at startup)
+pub async fn load_stored_api_key() {
+    redact::register_secret(FOCUS_API_KEY);
+    redact::register_secret(FOCUS_API_SIGNING_SECRET);
+    let result = tokio::task::spawn_blocking(|| credentials::read_secret(API_KEY_ACCOUNT)).await;
+    match result {
+        Ok(Ok(key)) => set_stored_api_key(key),
+        Ok(Err(e)) => log_info!("[focus_api] Could not read the stored API key: {}", e),
+        Err(e) => log_warn!("[focus_api] Credential task failed: {}", e),
+    }
+}
+
+/// Check that a key looks like a FocusApi key: 1 to 256 ASCII letters,
+/// digits, `-` or `_` (e.g. "fk_live_0123abcd")
+fn check_api_key_format(key: &str) -> Result<(), String> {
+    if key.is_empty() || key.len() > 256 {
+        return Err("The API key must be between 1 and 256 characters".to_string());
+    }
+    if !key.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_')) {
+        return Err("The API key may only contain letters, digits, '-' and '_'".to_string());
+    }
+    Ok(())
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// HMAC-SHA256 (RFC 2104)
+fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(message);
+    mac.finalize().into_bytes().into()
+}
+
+/// Signature of a request: HMAC of "timestamp\nMETHOD\npath\nsha256(body)"
+fn request_signature(secret: &str, timestamp: i64, method: &reqwest::Method, path: &str, body: &[u8]) -> String {
+    let canonical = format!(
+        "{}\n{}\n{}\n{}",
+        timestamp,
+        method.as_str(),
+        path,
+        to_hex(&Sha256::digest(body))
+    );
+    to_hex(&hmac_sha256(secret.as_bytes(), canonical.as_bytes()))
+}
+
+/// Build a request to a FocusApi endpoint with the API key attached.
+///
+/// `path` is relative to the API base URL (e.g., "/lol/import-payload").
+pub fn request(method: reqwest::Method, path: &str) -> Result<reqwest::RequestBuilder, reqwest::Error> {
+    request_with_key(method, path, &api_key(), REQUEST_TIMEOUT, Vec::new())
+}
+
+/// Build a request with a JSON body (the body is part of the signature).
+pub fn request_json<T: Serialize>(
+    method: reqwest::Method,
+    path: &str,
+    body: &T,
+) -> Result<reqwest::RequestBuilder, AppError> {
+    let body = serde_json::to_vec(body)?;
+    Ok(request_with_key(method, path, &api_key(), REQUEST_TIMEOUT, body)?.header("Content-Type", "application/json"))
+}
+
+fn request_with_key(
+    method: reqwest::Method,
+    path: &str,
+    key: &str,
+    timeout: Duration,
+    body: Vec<u8>,
+) -> Result<reqwest::RequestBuilder, reqwest::Error> {
+    let base_url = base_url();
+    let mut builder = crate::http_clients::remote()?
+        .request(method.clone(), format!("{}{}", base_url, path))
+        .timeout(timeout)
+        .header("Accept", "application/json");
+
+    // Credentials never leave for another server
+    if is_default_server(&base_url) {
+        builder = builder.header("X-API-Key", key);
+        if let Some(token) = auth::access_token() {
+            builder = builder.bearer_auth(token);
+        }
+        if !FOCUS_API_SIGNING_SECRET.is_empty() {
+            let timestamp = now_unix_secs();
+            builder = builder
+                .header("X-Focus-Timestamp", timestamp.to_string())
+                .header(
+                    "X-Focus-Signature",
+                    request_signature(FOCUS_API_SIGNING_SECRET, timestamp, &method, path, &body),
+                );
+        }
+    }
+    if !body.is_empty() {
+        builder = builder.body(body);
+    }
+    Ok(builder)
+}
+
+/// Request quota left
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Quota {
+    /// None until a response announced it
+    remaining: Option<u64>,
+    /// Unix time the quota is reset
+    reset_at: i64,
+}
+
+impl Quota {
+    /// Seconds to wait before the next request, when the quota is used up
+    fn retry_after(&self, now: i64) -> Option<u64> {
+        (self.remaining == Some(0) && self.reset_at > now).then(|| (self.reset_at - now) as u64)
+    }
+}
+
+fn header_u64(headers: &reqwest::header::HeaderMap, name: &str) -> Option<u64> {
+    headers.get(name)?.to_str().ok()?.trim().parse().ok()
+}
+
+/// Quota announced by a response (None when it has no rate limit headers)
+fn quota_from_headers(status: reqwest::StatusCode, headers: &reqwest::header::HeaderMap, now: i64) -> Option<Quota> {
+    if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        let retry_after = header_u64(headers, "Retry-After").unwrap_or(DEFAULT_RETRY_AFTER_SECS);
+        return Some(Quota {
+            remaining: Some(0),
+            reset_at: now + retry_after as i64,
+        });
+    }
+    let remaining = header_u64(headers, "X-RateLimit-Remaining")?;
+    // Either a Unix time or a delay in seconds
+    let reset_at = match header_u64(headers, "X-RateLimit-Reset").unwrap_or(0) as i64 {
+        reset if reset > now / 2 => reset,
+        delay => now + delay,
+    };
+    Some(Quota {
+        remaining: Some(remaining),
+        reset_at,
+    })
+}
+
+/// Send a FocusApi request within the quota.
+///
+/// Waits for the quota reset when it is close, otherwise fails with
+/// `RateLimited` without sending; a 429 response also becomes `RateLimited`.
+pub async fn send(builder: reqwest::RequestBuilder) -> Result<reqwest::Response, AppError> {
+    let retry_after = QUOTA.lock().ok().and_then(|quota| quota.retry_after(now_unix_secs()));
+    match retry_after {
+        Some(secs) if secs <= MAX_QUOTA_WAIT_SECS => tokio::time::sleep(Duration::from_secs(secs)).await,
+        Some(secs) => return Err(AppError::RateLimited { retry_after_secs: secs }),
+        None => {}
+    }
+
+    let response = builder.send().await?;
+    let now = now_unix_secs();
+    let quota = quota_from_headers(response.status(), response.headers(), now);
+    if let (Some(quota), Ok(mut current)) = (quota, QUOTA.lock()) {
+        *current = quota;
+    }
+    if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        let retry_after_secs = quota.and_then(|q| q.retry_after(now)).unwrap_or(DEFAULT_RETRY_AFTER_SECS);
+        log_info!("[focus_api] Rate limited, retry in {}s", retry_after_secs);
+        return Err(AppError::RateLimited { retry_after_secs });
+    }
+    Ok(response)
+}
+
+/// GET a FocusApi endpoint and return the JSON body
+pub async fn get_json(path: &str) -> Result<Value, AppError> {
+    log_debug!("[focus_api] GET {}", path);
+
+    auth::refresh_if_needed().await;
+    let account = auth::username();
+    // Keyed by full URL: another server never answers with this one's copy
+    let url = format!("{}{}", base_url(), path);
+    let cached = etag_cache::lookup(&url, account.as_deref());
+    let mut builder = request(reqwest::Method::GET, path)?;
+    if let Some(cached) = &cached {
+        builder = builder.header(reqwest::header::IF_NONE_MATCH, &cached.etag);
+    }
+    let response = send(builder).await?;
+    if let (reqwest::StatusCode::NOT_MODIFIED, Some(cached)) = (response.status(), &cached) {
+        log_debug!("[focus_api] {} not modified", path);
+        return parse_body(&cached.body);
+    }
+
+    let etag = response
+        .headers()
+        .get(reqwest::header::ETAG)
+        .and_then(|etag| etag.to_str().ok())
+        .map(str::to_string);
+    let body = read_text(response).await?;
+    let value = parse_body(&body)?;
+    if let Some(etag) = etag {
+        etag_cache::store(&url, account.as_deref(), &etag_cache::CachedResponse { etag, body });
+    }
+    Ok(value)
+}
+
+/// POST a JSON body to a FocusApi endpoint and return the JSON response (null if empty)
+pub async fn post_json<T: Serialize>(path: &str, body: &T) -> Result<Value, AppError> {
+    send_json(reqwest::Method::POST, path, body).await
+}
+
+/// PUT a JSON body to a FocusApi endpoint and return the JSON response (null if empty)
+pub async fn put_json<T: Serialize>(path: &str, body: &T) -> Result<Value, AppError> {
+    send_json(reqwest::Method::PUT, path, body).await
+}
+
+async fn send_json<T: Serialize>(method: reqwest::Method, path: &str, body: &T) -> Result<Value, AppError> {
+    log_debug!("[focus_api] {} {}", method, path);
+
+    auth::refresh_if_needed().await;
+    let response = send(request_json(method, path, body)?).await?;
+    let body = read_text(response).await?;
+    if body.trim().is_empty() {
+        return Ok(Value::Null);
+    }
+    parse_body(&body)
+}
+
+fn parse_body(body: &str) -> Result<Value, AppError> {
+    serde_json::from_str(body).map_err(|e| AppError::InvalidData(format!("Failed to parse FocusApi response: {}", e)))
+}
+
+/// Check the status of a FocusApi response and return its body
+async fn read_text(response: reqwest::Response) -> Result<String, AppError> {
+    let status = response.status();
+    if status == reqwest::StatusCode::UNAUTHORIZED {
+        return Err(AppError::FocusApiUnauthorized);
+    }
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        // The body may echo the request headers back
+        return Err(AppError::FocusApi(redact::redact(&format!("API Error {}: {}", status.as_u16(), body))));
+    }
+
+    Ok(response.text().await?)
+}
+
+/// Encode one path segment (champion names, Riot IDs)
+fn segment(value: &str) -> String {
+    urlencoding::encode(value.trim()).into_owned()
+}
+
+/// Append `?{flag}=true` when `enabled`
+fn flag(path: String, name: &str, enabled: bool) -> String {
+    if enabled {
+        format!("{}?{}=true", path, name)
+    } else {
+        path
+    }
+}
+
+/// Fetch the average CS per minute of a rank and role (see rank_benchmarks.rs).
+pub async fn fetch_cs_benchmark(rank: &str, role: &str) -> Result<Value, AppError> {
+    let path = format!(
+        "/benchmarks/cs-curve?rank={}&role={}",
+        segment(&rank.to_lowercase()),
+        segment(&role.to_lowercase())
+    );
+    get_json(&path).await
+}
+
+/// Fetch the recommended bans of a role, or of every role (see ban_suggestions.rs).
+pub async fn fetch_ban_suggestions(role: Option<&str>) -> Result<Value, AppError> {
+    let path = match role {
+        Some(role) => format!("/bans?role={}", segment(&role.to_lowercase())),
+        None => "/bans".to_string(),
+    };
+    get_json(&path).await
+}
+
+/// Fetch the win rates of a champion and role against each opponent (see matchups.rs).
+pub async fn fetch_matchups(champion: &str, role: &str) -> Result<Value, AppError> {
+    let path = format!(
+        "/matchups?champion={}&role={}",
+        segment(champion),
+        segment(&role.to_lowercase())
+    );
+    get_json(&path).await
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Select the FocusApi server: a custom URL, or the default server when None.
+///
+/// Returns the API URL now in use (the `FOCUS_API_BASE_URL` environment
+/// variable, when set, still wins).
+#[tauri::command]
+pub async fn set_api_endpoint(
+    app: AppHandle,
+    store: tauri::State<'_, SettingsStore>,
+    url: Option<String>,
+) -> Result<String, String> {
+    let url = url.filter(|u| !u.trim().is_empty()).map(|u| check_base_url(&u)).transpose()?;
+    let settings = store.update(&app, serde_json::json!({ "focusApiUrl": url }))?;
+    set_base_url(&settings);
+    Ok(base_url())
+}
+
+/// State of the build service
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ServiceState {
+    Operational,
+    /// Slow, rate limited or reported degraded: requests may fail
+    Degraded,
+    Down,
+}
+
+/// Result of `get_api_status`
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiStatus {
+    pub state: ServiceState,
+    pub message: String,
+    /// None when FocusApi was not reached
+    pub latency_ms: Option<u64>,
+    /// Unix timestamp (seconds) of the check
+    pub checked_at: i64,
+}
+
+/// Body of the status endpoint
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct StatusResponse {
+    /// "ok", "degraded", "down"...
+    status: String,
+    message: Option<String>,
+}
+
+/// State from the HTTP status, the state reported by FocusApi and the answer time
+fn service_state(http_status: u16, reported: Option<&str>, latency_ms: u64) -> ServiceState {
+    let state = match reported.map(str::to_lowercase).as_deref() {
+        Some("down" | "outage" | "maintenance") => ServiceState::Down,
+        Some("degraded") => ServiceState::Degraded,
+        _ if http_status >= 500 => ServiceState::Down,
+        _ if http_status == 429 => ServiceState::Degraded,
+        _ => ServiceState::Operational,
+    };
+    if state == ServiceState::Operational && latency_ms > SLOW_STATUS_MS {
+        ServiceState::Degraded
+    } else {
+        state
+    }
+}
+
+/// Ask FocusApi for its status
+async fn check_api_status() -> ApiStatus {
+    let checked_at = now_unix_secs();
+    if let Some(secs) = QUOTA.lock().ok().and_then(|quota| quota.retry_after(checked_at)) {
+        return ApiStatus {
+            state: ServiceState::Degraded,
+            message: format!("Rate limited, requests resume in {}s", secs),
+            latency_ms: None,
+            checked_at,
+        };
+    }
+
+    let start = std::time::Instant::now();
+    let probe = |method, path| request_with_key(method, path, &api_key(), STATUS_TIMEOUT, Vec::new());
+    let response = match probe(reqwest::Method::GET, STATUS_PATH) {
+        Ok(builder) => builder.send().await,
+        Err(e) => Err(e),
+    };
+    let response = match response {
+        Ok(response) if response.status() == reqwest::StatusCode::NOT_FOUND => match probe(reqwest::Method::HEAD, "") {
+            Ok(builder) => builder.send().await,
+            Err(e) => Err(e),
+        },
+        response => response,
+    };
+    let latency_ms = start.elapsed().as_millis() as u64;
+
+    let response = match response {
+        Ok(response) => response,
+        Err(e) => {
+            return ApiStatus {
+                state: ServiceState::Down,
+                message: format!("FocusApi unreachable: {}", e),
+                latency_ms: None,
+                checked_at,
+            }
+        }
+    };
+    let http_status = response.status().as_u16();
+    let body: StatusResponse = response.json().await.unwrap_or_default();
+    let reported = Some(body.status.as_str()).filter(|s| !s.is_empty());
+    let state = service_state(http_status, reported, latency_ms);
+    let message = body.message.filter(|m| !m.is_empty()).unwrap_or_else(|| match state {
+        ServiceState::Operational => "Build service operational".to_string(),
+        ServiceState::Degraded => "Build service degraded".to_string(),
+        ServiceState::Down => format!("Build service down (HTTP {})", http_status),
+    });
+    ApiStatus {
+        state,
+        message,
+        latency_ms: Some(latency_ms),
+        checked_at,
+    }
+}
+
+/// Get the state of the build service, checked at most once a minute unless `refresh`.
+#[tauri::command]
+pub async fn get_api_status(refresh: bool) -> Result<ApiStatus, String> {
+    let cached = API_STATUS.lock().ok().and_then(|status| status.clone());
+    if let Some(cached) = cached.filter(|s| !refresh && now_unix_secs() - s.checked_at < STATUS_TTL_SECS) {
+        return Ok(cached);
+    }
+
+    let status = check_api_status().await;
+    if status.state != ServiceState::Operational {
+        log_info!("[focus_api] {}", status.message);
+    }
+    if let Ok(mut cached) = API_STATUS.lock() {
+        *cached = Some(status.clone());
+    }
+    Ok(status)
+}
+
+/// Store the API key in the OS credential store.
+///
+/// An empty key deletes the stored one (the embedded key, if any, is used
+/// again). Returns the source of the key now in use.
+#[tauri::command]
+pub async fn set_api_key(key: String) -> Result<ApiKeySource, String> {
+    let key = key.trim().to_string();
+    if key.is_empty() {
+        tokio::task::spawn_blocking(|| credentials::delete_secret(API_KEY_ACCOUNT))
+            .await
+            .map_err(|e| e.to_string())??;
+        set_stored_api_key(None);
+    } else {
+        check_api_key_format(&key)?;
+        let secret = key.clone();
+        tokio::task::spawn_blocking(move || credentials::write_secret(API_KEY_ACCOUNT, &secret))
+            .await
+            .map_err(|e| e.to_string())??;
+        set_stored_api_key(Some(key));
+    }
+    Ok(api_key_source())
+}
+
+/// Result of `validate_api_key`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiKeyValidation {
+    /// True when FocusApi accepted the key
+    pub valid: bool,
+    /// HTTP status returned by FocusApi (None when it could not be reached)
+    pub status: Option<u16>,
+    pub message: String,
+}
+
+/// Test an API key against FocusApi.
+///
+/// Tests `key` without storing it when given, otherwise the key in use.
+#[tauri::command]
+pub async fn validate_api_key(key: Option<String>) -> Result<ApiKeyValidation, String> {
+    let key = match key.map(|k| k.trim().to_string()).filter(|k| !k.is_empty()) {
+        Some(key) => {
+            check_api_key_format(&key)?;
+            redact::register_secret(&key);
+            key
+        }
+        None if api_key_source() == ApiKeySource::None => {
+            return Ok(ApiKeyValidation {
+                valid: false,
+                status: None,
+                message: "No API key configured".to_string(),
+            });
+        }
+        None => api_key(),
+    };
+
+    if !is_default_server(&base_url()) {
+        return Ok(ApiKeyValidation {
+            valid: false,
+            status: None,
+            message: format!("API keys are only sent to {}", FOCUS_API_HOST),
+        });
+    }
+
+    let response = request_with_key(reqwest::Method::GET, KEY_CHECK_PATH, &key, KEY_CHECK_TIMEOUT, Vec::new())
+        .map_err(|e| e.to_string())?
+        .send()
+        .await;
+
+    Ok(match response {
+        Ok(response) => {
+            let status = response.status();
+            let (valid, message) = match status {
+                reqwest::StatusCode::UNAUTHORIZED | reqwest::StatusCode::FORBIDDEN => {
+                    (false, "API key rejected by FocusApi".to_string())
+                }
+                s if s.is_success() => (true, "API key accepted".to_string()),
+                s => (false, format!("FocusApi returned error {}", s.as_u16())),
+            };
+            ApiKeyValidation {
+                valid,
+                status: Some(status.as_u16()),
+                message,
+            }
+        }
+        Err(e) => ApiKeyValidation {
+            valid: false,
+            status: None,
+            message: format!("FocusApi unreachable: {}", e),
+        },
+    })
+}
+
+/// Remove the stored API key (the embedded key, if any, is used again).
+#[tauri::command]
+pub async fn clear_api_key() -> Result<ApiKeySource, String> {
+    set_api_key(String::new()).await
+}
+
+/// Get the source of the API key in use (the key itself is never returned).
+#[tauri::command]
+pub async fn get_api_key_source() -> Result<ApiKeySource, String> {
+    Ok(api_key_source())
+}
+
+/// `get_json`, cancellable with `cancel_operation(operation_id)`
+async fn get_json_cancellable(path: &str, operation_id: Option<String>) -> Result<Value, CommandError> {
+    Ok(cancel::start(operation_id).run(get_json(path)).await??)
+}
+
+/// Fetch the tier list, optionally filtered by role.
+///
+/// Every fetch command takes an optional `operation_id` (see cancel.rs).
+#[tauri::command]
+pub async fn fetch_tierlist(role: Option<String>, operation_id: Option<String>) -> Result<Value, CommandError> {
+    let path = match role.filter(|r| !r.trim().is_empty()) {
+        Some(role) => format!("/tierlist?role={}", segment(&role.to_lowercase())),
+        None => "/tierlist".to_string(),
+    };
+    get_json_cancellable(&path, operation_id).await
+}
+
+/// Fetch the build of a champion for a role.
+///
+/// `champion` and `role` are expected in the API format ("leesin", "bottom").
+#[tauri::command]
+pub async fn fetch_champion_build(
+    champion: String,
+    role: String,
+    force_refresh: bool,
+    operation_id: Option<String>,
+) -> Result<Value, CommandError> {
+    let path = format!("/build/{}/{}", segment(&champion), segment(&role));
+    get_json_cancellable(&flag(path, "force_refresh", force_refresh), operation_id).await
+}
+
+/// Fetch the item list.
+#[tauri::command]
+pub async fn fetch_items(refresh: bool, operation_id: Option<String>) -> Result<Value, CommandError> {
+    get_json_cancellable(&flag("/items".to_string(), "refresh", refresh), operation_id).await
+}
+
+/// Fetch a player profile by Riot ID.
+#[tauri::command]
+pub async fn fetch_player_profile(
+    game_name: String,
+    tag_line: String,
+    refresh: bool,
+    operation_id: Option<String>,
+) -> Result<Value, CommandError> {
+    let path = format!("/player/{}/{}", segment(&game_name), segment(&tag_line));
+    get_json_cancellable(&flag(path, "refresh", refresh), operation_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endpoint_paths() {
+        assert_eq!(segment(" Faker "), "Faker");
+        assert_eq!(segment("Hide on bush"), "Hide%20on%20bush");
+        assert_eq!(segment("a/b"), "a%2Fb");
+
+        assert_eq!(flag("/items".to_string(), "refresh", true), "/items?refresh=true");
+        assert_eq!(flag("/items".to_string(), "refresh", false), "/items");
+    }
+
+    #[test]
+    fn test_request_signature() {
+        // RFC 4231 test case 2
+        assert_eq!(
+            to_hex(&hmac_sha256(b"Jefe", b"what do ya want for nothing?")),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+
+        let signature = request_signature("secret", 1_700_000_000, &reqwest::Method::POST, "/lol/import-payload", b"{}");
+        assert_eq!(signature.len(), 64);
+        // Any change of the body or timestamp changes the signature
+        assert_ne!(
+            signature,
+            request_signature("secret", 1_700_000_000, &reqwest::Method::POST, "/lol/import-payload", b"{ }")
+        );
+        assert_ne!(
+            signature,
+            request_signature("secret", 1_700_000_001, &reqwest::Method::POST, "/lol/import-payload", b"{}")
+        );
+    }
+
+    #[test]
+    fn test_resolve_base_url() {
+        assert_eq!(resolve_base_url(None, None), FOCUS_API_BASE_URL);
+        assert_eq!(resolve_base_url(None, Some(" ")), FOCUS_API_BASE_URL);
+        assert_eq!(
+            resolve_base_url(None, Some("https://builds.example.com/api/v1/")),
+            "https://builds.example.com/api/v1"
+        );
+        assert_eq!(
+            resolve_base_url(Some("http://localhost:8080"), Some("https://builds.example.com")),
+            "http://localhost:8080"
+        );
+        // Invalid URLs are skipped; plain http only on localhost
+        assert_eq!(resolve_base_url(Some("ftp://x"), None), FOCUS_API_BASE_URL);
+        assert_eq!(resolve_base_url(None, Some("http://builds.example.com")), FOCUS_API_BASE_URL);
+
+        assert!(is_default_server(FOCUS_API_BASE_URL));
+        assert!(!is_default_server("http://api.hommet.ch/api/v1"));
+        assert!(!is_default_server("https://api.hommet.ch.example.com/api/v1"));
+    }
+
+    #[test]
+    fn test_service_state() {
+        assert_eq!(service_state(200, Some("ok"), 120), ServiceState::Operational);
+        assert_eq!(service_state(200, None, SLOW_STATUS_MS + 1), ServiceState::Degraded);
+        assert_eq!(service_state(200, Some("Degraded"), 120), ServiceState::Degraded);
+        assert_eq!(service_state(503, None, 120), ServiceState::Down);
+        assert_eq!(service_state(200, Some("maintenance"), 120), ServiceState::Down);
+        assert_eq!(service_state(429, None, 120), ServiceState::Degraded);
+    }
+
+    #[test]
+    fn test_quota_from_headers() {
+        use reqwest::header::{HeaderMap, HeaderValue};
+        use reqwest::StatusCode;
+
+        let now = 1_700_000_000;
+        let mut headers = HeaderMap::new();
+        assert_eq!(quota_from_headers(StatusCode::OK, &headers, now), None);
+
+        headers.insert("X-RateLimit-Remaining", HeaderValue::from_static("0"));
+        headers.insert("X-RateLimit-Reset", HeaderValue::from_static("30"));
+        let quota = quota_from_headers(StatusCode::OK, &headers, now).unwrap();
+        assert_eq!(quota.retry_after(now), Some(30));
+        assert_eq!(quota.retry_after(now + 30), None);
+
+        headers.insert("X-RateLimit-Reset", HeaderValue::from_static("1700000012"));
+        assert_eq!(quota_from_headers(StatusCode::OK, &headers, now).unwrap().retry_after(now), Some(12));
+
+        let mut headers = HeaderMap::new();
+        headers.insert("Retry-After", HeaderValue::from_static("7"));
+        let quota = quota_from_headers(StatusCode::TOO_MANY_REQUESTS, &headers, now).unwrap();
+        assert_eq!(quota.retry_after(now), Some(7));
+    }
+
+    #[test]
+    fn test_check_api_key_format() {
+        assert!(check_api_key_format("fk_live_0123abcd-EF").is_ok());
+        assert!(check_api_key_format("").is_err());
+        assert!(check_api_key_format("with space").is_err());
+        assert!(check_api_key_format("fk_live_0123/abcd").is_err());
+        assert!(check_api_key_format("fk_live_0123abcd\n").is_err());
+        assert!(check_api_key_format(&"a".repeat(300)).is_err());
+    }
+}
@@ -0,0 +1,465 @@
+//! Post-game CS reports.
+//!
+//! While a game is live the watcher feeds a `CsTimeline` (CS every 30
+//! seconds and death times, a death being the health dropping to 0). When
+//! the game ends, `build_report` turns it into a report: the CS curve against
+//! the stage goals, the 2-minute windows with the biggest farming gaps and
+//! the deaths followed by a CS drought. Reports are kept next to the session
+//! history, keyed by game ID, and returned by `get_game_report`.
+//!
+//! Once the end-of-game stats arrive, the report is completed with the
+//! player's combat figures (KDA, damage share, kill participation).
+//!
+//! Without stage goals, the watcher passes the rank benchmark curve instead
+//! (see rank_benchmarks.rs); without either, the expected farm of a window
+//! is the game's own average rate.
+//!
+//! The timeline itself is kept too, for a playback view: `get_game_timeline`
+//! returns the samples and markers for the deaths, the objectives taken by
+//! either team (from the live events) and the recalls. The Live Client does
+//! not report recalls, so a drop of the current gold (items bought at the
+//! shop) is taken as one.
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::cs_goals::{target_cs_at, CsGoal};
+use crate::db;
+use crate::game_watcher::LiveEvent;
+use crate::lcu::EogStatsBlock;
+
+/// Reports kept (oldest are dropped first)
+const MAX_REPORTS: usize = 100;
+
+/// Interval between two CS samples (seconds)
+const SAMPLE_INTERVAL_SECS: f64 = 30.0;
+
+/// Length of a farming window (seconds)
+const WINDOW_SECS: f64 = 120.0;
+
+/// Farming windows start after the first minion waves reached the lanes
+const FIRST_WINDOW_SECS: f64 = 120.0;
+
+/// Farming gaps listed in a report
+const MAX_GAPS: usize = 3;
+
+/// Share of the expected CS under which the 2 minutes after a death are a drought
+const DROUGHT_RATIO: f64 = 0.5;
+
+/// Timelines kept (a few hundred samples each)
+const MAX_TIMELINES: usize = 20;
+
+/// Gold drop between two polls taken as a shop visit
+const RECALL_SPENT_GOLD: f64 = 300.0;
+
+/// Purchases within this time of a recall belong to it (seconds)
+const RECALL_MERGE_SECS: f64 = 30.0;
+
+/// Live events marked on the timeline
+const OBJECTIVE_EVENTS: &[&str] = &["DragonKill", "HeraldKill", "BaronKill", "TurretKilled", "InhibKilled"];
+
+/// CS at a game time
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CsSample {
+    pub game_time: f64,
+    pub cs: i32,
+    /// Current (unspent) gold
+    #[serde(default)]
+    pub gold: f64,
+}
+
+/// Kind of a timeline marker
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum MarkerKind {
+    Death,
+    Objective,
+    Recall,
+}
+
+/// An event on the timeline
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TimelineMarker {
+    pub game_time: f64,
+    pub kind: MarkerKind,
+    /// Live event name of an objective ("DragonKill", "TurretKilled", ...)
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Dragon type of a DragonKill
+    #[serde(default)]
+    pub detail: Option<String>,
+}
+
+/// CS curve, deaths and recalls of a live game
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CsTimeline {
+    samples: Vec<CsSample>,
+    deaths: Vec<f64>,
+    alive: bool,
+    recalls: Vec<f64>,
+    last_gold: Option<f64>,
+    objectives: Vec<TimelineMarker>,
+}
+
+impl CsTimeline {
+    /// Take into account one poll of the Live Client
+    pub fn observe(&mut self, game_time: f64, cs: i32, current_gold: f64, current_health: f64) {
+        let due = self
+            .samples
+            .last()
+            .is_none_or(|last| game_time - last.game_time >= SAMPLE_INTERVAL_SECS);
+        if due {
+            self.samples.push(CsSample {
+                game_time,
+                cs,
+                gold: current_gold,
+            });
+        }
+
+        let alive = current_health > 0.0;
+        if self.alive && !alive {
+            self.deaths.push(game_time);
+        }
+        self.alive = alive;
+
+        let spent = self.last_gold.is_some_and(|last| last - current_gold >= RECALL_SPENT_GOLD);
+        let merged = self.recalls.last().is_some_and(|&last| game_time - last < RECALL_MERGE_SECS);
+        if spent && !merged {
+            self.recalls.push(game_time);
+        }
+        self.last_gold = Some(current_gold);
+    }
+
+    /// Take into account the live events (the Live Client always sends all of them)
+    pub fn observe_events(&mut self, events: &[LiveEvent]) {
+        self.objectives = events
+            .iter()
+            .filter(|e| OBJECTIVE_EVENTS.contains(&e.event_name.as_str()))
+            .map(|e| TimelineMarker {
+                game_time: e.event_time,
+                kind: MarkerKind::Objective,
+                name: Some(e.event_name.clone()),
+                detail: e.dragon_type.clone(),
+            })
+            .collect();
+    }
+
+    /// Samples and markers of the finished game, markers by game time
+    pub fn to_game_timeline(&self, game_id: i64, duration_secs: f64) -> GameTimeline {
+        let marker = |kind| {
+            move |&game_time: &f64| TimelineMarker {
+                game_time,
+                kind,
+                name: None,
+                detail: None,
+            }
+        };
+        let mut markers: Vec<TimelineMarker> = self
+            .deaths
+            .iter()
+            .map(marker(MarkerKind::Death))
+            .chain(self.recalls.iter().map(marker(MarkerKind::Recall)))
+            .chain(self.objectives.iter().cloned())
+            .collect();
+        markers.sort_by(|a, b| a.game_time.total_cmp(&b.game_time));
+
+        GameTimeline {
+            game_id,
+            duration_secs,
+            samples: self.samples.clone(),
+            markers,
+        }
+    }
+
+    /// CS at `game_time`, interpolated between samples
+    fn cs_at(&self, game_time: f64) -> Option<f64> {
+        let after = self.samples.iter().position(|s| s.game_time >= game_time)?;
+        let next = &self.samples[after];
+        let Some(previous) = after.checked_sub(1).map(|i| &self.samples[i]) else {
+            return (next.game_time - game_time < SAMPLE_INTERVAL_SECS).then_some(f64::from(next.cs));
+        };
+        let ratio = (game_time - previous.game_time) / (next.game_time - previous.game_time);
+        Some(f64::from(previous.cs) + ratio * f64::from(next.cs - previous.cs))
+    }
+}
+
+/// Recorded timeline of a finished game, for the playback view
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GameTimeline {
+    pub game_id: i64,
+    pub duration_secs: f64,
+    /// Every `SAMPLE_INTERVAL_SECS`
+    pub samples: Vec<CsSample>,
+    pub markers: Vec<TimelineMarker>,
+}
+
+/// A point of the CS curve
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CurvePoint {
+    pub minute: u32,
+    pub cs: i32,
+    /// Stage goal at that minute (None without goals)
+    pub target_cs: Option<f64>,
+}
+
+/// A 2-minute window farmed below expectations
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FarmingGap {
+    pub start_minute: u32,
+    pub end_minute: u32,
+    pub cs: f64,
+    pub expected_cs: f64,
+}
+
+/// A death followed by a CS drought
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DeathDrought {
+    /// Game time of the death (seconds)
+    pub game_time: f64,
+    /// CS in the 2 minutes after the death
+    pub cs_after: f64,
+    pub expected_cs: f64,
+}
+
+/// Combat figures of the local player, from the end-of-game stats
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CombatStats {
+    pub kills: u32,
+    pub deaths: u32,
+    pub assists: u32,
+    pub damage_to_champions: f64,
+    /// Share of the team's damage to champions
+    pub damage_share: Option<f64>,
+    /// Share of the team's kills the player took part in
+    pub kill_participation: Option<f64>,
+}
+
+impl CombatStats {
+    /// Combat figures of the local player (None without their stats)
+    pub fn from_eog(block: &EogStatsBlock) -> Option<Self> {
+        let player = block.local_player.as_ref()?;
+        let count = |name: &str| player.stat(name).unwrap_or_default().max(0.0) as u32;
+        let (kills, assists) = (count("CHAMPIONS_KILLED"), count("ASSISTS"));
+        let kill_participation = block
+            .local_team_total("CHAMPIONS_KILLED")
+            .filter(|&team_kills| team_kills > 0.0)
+            .map(|team_kills| f64::from(kills + assists) / team_kills);
+
+        Some(Self {
+            kills,
+            deaths: count("NUM_DEATHS"),
+            assists,
+            damage_to_champions: player.stat("TOTAL_DAMAGE_DEALT_TO_CHAMPIONS")?,
+            damage_share: block.local_share("TOTAL_DAMAGE_DEALT_TO_CHAMPIONS"),
+            kill_participation,
+        })
+    }
+}
+
+/// Report of a finished game
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GameReport {
+    pub game_id: i64,
+    pub champion_name: String,
+    pub duration_secs: f64,
+    pub final_cs: i32,
+    pub curve: Vec<CurvePoint>,
+    /// Biggest gaps first
+    pub farming_gaps: Vec<FarmingGap>,
+    pub death_droughts: Vec<DeathDrought>,
+    /// Set from the end-of-game stats (None until they are received)
+    #[serde(default)]
+    pub combat: Option<CombatStats>,
+}
+
+/// Build the report of a finished game
+pub fn build_report(
+    game_id: i64,
+    champion_name: &str,
+    duration_secs: f64,
+    final_cs: i32,
+    timeline: &CsTimeline,
+    goals: &[CsGoal],
+) -> GameReport {
+    let curve = (1..=(duration_secs / 60.0) as u32)
+        .filter_map(|minute| {
+            let game_time = f64::from(minute) * 60.0;
+            Some(CurvePoint {
+                minute,
+                cs: timeline.cs_at(game_time)?.round() as i32,
+                target_cs: target_cs_at(goals, game_time),
+            })
+        })
+        .collect();
+
+    // CS expected between two game times: the goals, else the game's average rate
+    let average_rate = if duration_secs > 0.0 { f64::from(final_cs) / duration_secs } else { 0.0 };
+    let expected = |from: f64, to: f64| match (target_cs_at(goals, from), target_cs_at(goals, to)) {
+        (Some(start), Some(end)) => end - start,
+        _ => average_rate * (to - from),
+    };
+
+    let mut farming_gaps: Vec<FarmingGap> = std::iter::successors(Some(FIRST_WINDOW_SECS), |start| Some(start + WINDOW_SECS))
+        .take_while(|start| start + WINDOW_SECS <= duration_secs)
+        .filter_map(|start| {
+            let end = start + WINDOW_SECS;
+            let cs = timeline.cs_at(end)? - timeline.cs_at(start)?;
+            Some(FarmingGap {
+                start_minute: (start / 60.0) as u32,
+                end_minute: (end / 60.0) as u32,
+                cs,
+                expected_cs: expected(start, end),
+            })
+        })
+        .filter(|gap| gap.cs < gap.expected_cs)
+        .collect();
+    farming_gaps.sort_by(|a, b| (b.expected_cs - b.cs).total_cmp(&(a.expected_cs - a.cs)));
+    farming_gaps.truncate(MAX_GAPS);
+
+    let death_droughts = timeline
+        .deaths
+        .iter()
+        .filter(|&&death| death + WINDOW_SECS <= duration_secs)
+        .filter_map(|&death| {
+            let cs_after = timeline.cs_at(death + WINDOW_SECS)? - timeline.cs_at(death)?;
+            let expected_cs = expected(death, death + WINDOW_SECS);
+            (cs_after < expected_cs * DROUGHT_RATIO).then_some(DeathDrought {
+                game_time: death,
+                cs_after,
+                expected_cs,
+            })
+        })
+        .collect();
+
+    GameReport {
+        game_id,
+        champion_name: champion_name.to_string(),
+        duration_secs,
+        final_cs,
+        curve,
+        farming_gaps,
+        death_droughts,
+        combat: None,
+    }
+}
+
+/// Store the report of a finished game.
+///
+/// If a report with the same non-zero game ID exists, it is replaced.
+pub fn record_report(app: &AppHandle, report: GameReport) -> Result<(), String> {
+    db::with(app, |connection| {
+        db::save_game(connection, db::GAME_REPORTS, report.game_id, &report, MAX_REPORTS)
+    })
+}
+
+/// Store the timeline of a finished game (same rules as the reports).
+pub fn record_timeline(app: &AppHandle, timeline: GameTimeline) -> Result<(), String> {
+    db::with(app, |connection| {
+        db::save_game(connection, db::CS_TIMELINES, timeline.game_id, &timeline, MAX_TIMELINES)
+    })
+}
+
+/// Attach the combat figures to an already stored report.
+///
+/// Returns `Ok(false)` if no report matches the game ID.
+pub fn set_report_combat(app: &AppHandle, game_id: i64, combat: CombatStats) -> Result<bool, String> {
+    db::with(app, |connection| {
+        db::update_game(connection, db::GAME_REPORTS, game_id, |report: &mut GameReport| {
+            report.combat = Some(combat)
+        })
+    })
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the CS report of a finished game (None if it has none).
+#[tauri::command]
+pub async fn get_game_report(app: AppHandle, game_id: i64) -> Result<Option<GameReport>, String> {
+    db::with(&app, |connection| db::load_game(connection, db::GAME_REPORTS, game_id))
+}
+
+/// Get the recorded samples and markers of a finished game (None if it has none).
+///
+/// Only the last `MAX_TIMELINES` games keep their timeline.
+#[tauri::command]
+pub async fn get_game_timeline(app: AppHandle, game_id: i64) -> Result<Option<GameTimeline>, String> {
+    db::with(&app, |connection| db::load_game(connection, db::CS_TIMELINES, game_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_report() {
+        // 8 CS/min, nothing between 6:00 and 8:00 after a death at 6:00
+        let mut timeline = CsTimeline::default();
+        let mut cs = 0.0;
+        for tick in 0..=1_200 {
+            let game_time = f64::from(tick);
+            let dead = (360.0..380.0).contains(&game_time);
+            if !(360.0..480.0).contains(&game_time) {
+                cs += 8.0 / 60.0;
+            }
+            // Shop visit after the death: 1300 gold spent over a few seconds
+            let gold = if game_time < 380.0 { 1_500.0 } else { 200.0 + (game_time - 380.0).min(5.0) * 10.0 };
+            let gold = if (380.0..383.0).contains(&game_time) { 900.0 } else { gold };
+            timeline.observe(game_time, cs as i32, gold, if dead { 0.0 } else { 500.0 });
+        }
+        assert_eq!(timeline.deaths, vec![360.0]);
+        assert_eq!(timeline.recalls, vec![380.0]);
+        assert_eq!(timeline.samples.len(), 41);
+
+        let goals = [CsGoal { minute: 10, cs: 80 }, CsGoal { minute: 20, cs: 160 }];
+        let report = build_report(7, "Ahri", 1_200.0, cs as i32, &timeline, &goals);
+        assert_eq!(report.curve.len(), 20);
+        assert_eq!(report.curve[9].target_cs, Some(80.0));
+
+        let gap = &report.farming_gaps[0];
+        assert_eq!((gap.start_minute, gap.end_minute), (6, 8));
+        assert!(gap.cs <= 1.0);
+        assert_eq!(report.death_droughts.len(), 1);
+        assert_eq!(report.death_droughts[0].game_time, 360.0);
+
+        let objective: LiveEvent = serde_json::from_value(serde_json::json!({
+            "EventName": "DragonKill", "EventTime": 370.0, "DragonType": "Fire"
+        }))
+        .unwrap();
+        timeline.observe_events(&[objective]);
+        let kinds: Vec<MarkerKind> = timeline.to_game_timeline(7, 1_200.0).markers.iter().map(|m| m.kind).collect();
+        assert_eq!(kinds, [MarkerKind::Death, MarkerKind::Objective, MarkerKind::Recall]);
+    }
+
+    #[test]
+    fn test_combat_stats_from_eog() {
+        let block: EogStatsBlock = serde_json::from_value(serde_json::json!({
+            "localPlayer": {
+                "teamId": 100,
+                "stats": { "CHAMPIONS_KILLED": 4, "NUM_DEATHS": 2, "ASSISTS": 6, "TOTAL_DAMAGE_DEALT_TO_CHAMPIONS": 15000 }
+            },
+            "teams": [{
+                "teamId": 100,
+                "players": [
+                    { "stats": { "CHAMPIONS_KILLED": 4, "TOTAL_DAMAGE_DEALT_TO_CHAMPIONS": 15000 } },
+                    { "stats": { "CHAMPIONS_KILLED": 16, "TOTAL_DAMAGE_DEALT_TO_CHAMPIONS": 45000 } }
+                ]
+            }]
+        }))
+        .unwrap();
+        let combat = CombatStats::from_eog(&block).unwrap();
+        assert_eq!((combat.kills, combat.deaths, combat.assists), (4, 2, 6));
+        assert_eq!(combat.damage_share, Some(0.25));
+        assert_eq!(combat.kill_participation, Some(0.5));
+        assert!(CombatStats::from_eog(&EogStatsBlock::default()).is_none());
+    }
+}
@@ -30,11 +30,32 @@
 
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
-use tauri::{AppHandle, Emitter};
+use std::time::SystemTime;
+use tauri::{AppHandle, Manager};
 use tokio::sync::{Mutex, RwLock};
 use tokio::time::{interval, Duration};
 
-use crate::lcu::{find_lockfile, GameflowPhase, LcuConnection};
+use crate::aram;
+use crate::ban_suggestions;
+use crate::champions;
+use crate::client_api::{LcuApi, LiveClient, LiveClientApi};
+use crate::cs_goals::{CsGoal, CsMilestone, MilestoneTracker};
+use crate::daily_goals;
+use crate::events::AppEvent;
+use crate::game_reports::{self, CombatStats, CsTimeline};
+use crate::lcu_metrics::TimedSend;
+use crate::lcu::{find_lockfile, get_client_game_version, get_eog_stats_block, GameflowPhase, LcuConnection};
+use crate::notifications::{self, NotificationEvent};
+use crate::objectives::{compute_objective_timers, ObjectiveTimer, ObjectiveTimers};
+use crate::overlay::{apply_queue_mode, OverlayConfigStore, OverlayEmitter, OverlayMode};
+use crate::personal_bests::{self, GameProgress, GameStats};
+use crate::prefetch::{self, BuildPrefetcher};
+use crate::queues;
+use crate::rank_benchmarks;
+use crate::reimport;
+use crate::sessions::{self, GameResult, GameSession};
+use crate::spell_timers::SpellTimers;
+use crate::team_picks;
 
 // =============================================================================
 // CONSTANTES
@@ -42,7 +63,7 @@ use crate::lcu::{find_lockfile, GameflowPhase, LcuConnection};
 
 /// Port fixe de l'API Live Client Data (In-Game)
 /// ATTENTION : C'est bien 2999, PAS 29990!
-const LIVE_CLIENT_PORT: u16 = 2999;
+pub(crate) const LIVE_CLIENT_PORT: u16 = 2999;
 
 /// Intervalle de polling pour le LCU (Client)
 const LCU_POLL_INTERVAL_MS: u64 = 1000;
@@ -50,9 +71,15 @@ const LCU_POLL_INTERVAL_MS: u64 = 1000;
 /// Intervalle de polling pour le Live Client (In-Game) - plus rapide
 const INGAME_POLL_INTERVAL_MS: u64 = 500;
 
-/// Timeout pour les requêtes HTTP vers les APIs locales
+/// Timeout des requêtes de polling vers le LCU
 const REQUEST_TIMEOUT_SECS: u64 = 3;
 
+/// Écart d'horloge entre deux tours de boucle au-delà duquel la machine sortait de veille
+const RESUME_GAP_SECS: u64 = 30;
+
+/// Nombre d'échecs de polling consécutifs déclenchant une reconnexion complète
+const FAILURE_BURST: u32 = 5;
+
 // =============================================================================
 // STRUCTURES DE DONNÉES
 // =============================================================================
@@ -115,6 +142,34 @@ pub struct LiveGameData {
     pub max_mana: f64,
 }
 
+/// Événement du flux `/liveclientdata/eventdata` (DragonKill, BaronKill, ...)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct LiveEvent {
+    #[serde(rename = "EventID", default)]
+    pub event_id: i64,
+    #[serde(rename = "EventName")]
+    pub event_name: String,
+    /// Temps de jeu de l'événement en secondes
+    #[serde(rename = "EventTime", default)]
+    pub event_time: f64,
+    /// Type de dragon pour les DragonKill ("Fire", "Elder", ...)
+    #[serde(rename = "DragonType", default)]
+    pub dragon_type: Option<String>,
+    /// Résultat pour l'événement GameEnd ("Win" / "Lose")
+    #[serde(rename = "Result", default)]
+    pub result: Option<String>,
+}
+
+/// Partie en cours telle que connue par le LCU (gameflow session)
+#[derive(Debug, Clone, Default)]
+struct ActiveGame {
+    game_id: i64,
+    queue_id: i32,
+    game_mode: String,
+    /// Rôle attribué en sélection de champion
+    role: Option<String>,
+}
+
 /// État interne du watcher (partagé entre threads)
 #[derive(Debug, Default)]
 struct WatcherState {
@@ -126,6 +181,24 @@ struct WatcherState {
     current_game_id: Option<String>,
     /// Indique si on est en mode "jeu en cours"
     in_live_game: bool,
+    /// Derniers timers d'objectifs émis (pour n'émettre qu'en cas de changement)
+    last_objective_timers: Vec<ObjectiveTimer>,
+    /// Partie en cours (ID et file) récupérée au passage en InProgress
+    active_game: Option<ActiveGame>,
+    /// Dernières données live reçues (pour l'enregistrement de fin de partie)
+    last_live_data: Option<LiveGameData>,
+    /// Résultat annoncé par l'événement GameEnd
+    live_result: Option<GameResult>,
+    /// Rôle du joueur local en sélection de champion
+    champ_select_role: Option<String>,
+    /// Mesures de la partie en cours (CS à 10/20 min, or gagné) pour les records
+    progress: GameProgress,
+    /// Étapes CS de la partie en cours (événements `cs-milestone`)
+    milestones: MilestoneTracker,
+    /// Courbe de CS et morts de la partie en cours (rapport de fin de partie)
+    timeline: CsTimeline,
+    /// CS moyen par minute du rang, référence du rapport sans cibles par étape
+    benchmark_curve: Vec<CsGoal>,
 }
 
 // =============================================================================
@@ -182,16 +255,14 @@ impl GameWatcher {
             polling_loop(state, app_handle, running).await;
         });
 
-        #[cfg(debug_assertions)]
-        eprintln!("[GameWatcher] Démarré");
+        log_debug!("[GameWatcher] Démarré");
     }
 
     /// Arrête le watcher
     pub async fn stop(&self) {
         let mut running = self.running.write().await;
         *running = false;
-        #[cfg(debug_assertions)]
-        eprintln!("[GameWatcher] Arrêté");
+        log_debug!("[GameWatcher] Arrêté");
     }
 
     /// Retourne l'état actuel du jeu
@@ -226,7 +297,7 @@ impl GameWatcher {
                 GameflowPhase::InProgress => {
                     if state.in_live_game {
                         // Récupère les données live si disponibles
-                        match fetch_live_game_data().await {
+                        match LiveClient::local().game_data().await {
                             Ok(data) => GameState::InProgress {
                                 game_data: Some(data),
                             },
@@ -279,6 +350,9 @@ async fn polling_loop(
     // Désactive le tick immédiat
     lcu_interval.tick().await;
 
+    let mut last_wake = SystemTime::now();
+    let mut consecutive_failures = 0;
+
     loop {
         // Vérifie si on doit s'arrêter
         {
@@ -288,6 +362,26 @@ async fn polling_loop(
             }
         }
 
+        // Sortie de veille (horloge murale en avance) ou série d'échecs :
+        // connexion et timers sont périmés, on repart de zéro immédiatement
+        let now = SystemTime::now();
+        let resumed = resume_detected(last_wake, now);
+        last_wake = now;
+        if resumed || consecutive_failures >= FAILURE_BURST {
+            log_info!(
+                "[GameWatcher] {}, forcing a full refresh",
+                if resumed { "System resumed from sleep" } else { "Repeated poll failures" }
+            );
+            consecutive_failures = 0;
+            force_refresh(&state, &app_handle).await;
+            lcu_interval.reset();
+            ingame_interval.reset();
+            if let Err(e) = poll_lcu(&state, &app_handle).await {
+                log_debug!("[GameWatcher] LCU poll error: {}", e);
+            }
+            continue;
+        }
+
         // Récupère l'état actuel
         let current_mode = {
             let state_guard = state.read().await;
@@ -302,18 +396,24 @@ async fn polling_loop(
         if current_mode == "lcu" {
             tokio::select! {
                 _ = lcu_interval.tick() => {
-                    if let Err(e) = poll_lcu(&state, &app_handle).await {
-                        #[cfg(debug_assertions)]
-                        eprintln!("[GameWatcher] LCU poll error: {}", e);
+                    match poll_lcu(&state, &app_handle).await {
+                        Ok(()) => consecutive_failures = 0,
+                        Err(e) => {
+                            consecutive_failures += 1;
+                            log_debug!("[GameWatcher] LCU poll error: {}", e);
+                        }
                     }
                 }
             }
         } else {
             tokio::select! {
                 _ = ingame_interval.tick() => {
-                    if let Err(e) = poll_ingame(&state, &app_handle).await {
-                        #[cfg(debug_assertions)]
-                        eprintln!("[GameWatcher] InGame poll error: {}", e);
+                    match poll_ingame(&state, &app_handle).await {
+                        Ok(()) => consecutive_failures = 0,
+                        Err(e) => {
+                            consecutive_failures += 1;
+                            log_debug!("[GameWatcher] InGame poll error: {}", e);
+                        }
                     }
                 }
             }
@@ -321,6 +421,34 @@ async fn polling_loop(
     }
 }
 
+/// L'horloge murale a avancé bien plus que l'intervalle de polling : la
+/// machine était en veille (l'horloge monotone de Tokio, elle, s'arrête)
+fn resume_detected(last_wake: SystemTime, now: SystemTime) -> bool {
+    now.duration_since(last_wake)
+        .is_ok_and(|gap| gap >= Duration::from_secs(RESUME_GAP_SECS))
+}
+
+/// Oublie la connexion et la phase connues pour que le prochain poll
+/// redétecte le client et réémette l'état complet
+async fn force_refresh(state: &Arc<RwLock<WatcherState>>, app_handle: &Arc<Mutex<Option<AppHandle>>>) {
+    {
+        let mut state_guard = state.write().await;
+        state_guard.last_phase = None;
+        state_guard.last_connection = None;
+        state_guard.last_objective_timers.clear();
+    }
+
+    // Les connexions gardées ouvertes par les clients HTTP sont mortes
+    crate::http_clients::reset();
+
+    if let Some(app) = app_handle.lock().await.as_ref() {
+        // Le prochain envoi à l'overlay sera complet
+        if let Some(emitter) = app.try_state::<OverlayEmitter>() {
+            emitter.reset();
+        }
+    }
+}
+
 // =============================================================================
 // POLLING LCU (LEAGUE CLIENT)
 // =============================================================================
@@ -344,19 +472,16 @@ async fn poll_lcu(
             if changed {
                 drop(state_guard);
                 emit_state_change(app_handle, GameState::ClientClosed).await;
+                set_champ_select_pick(app_handle, None).await;
             }
             return Ok(());
         }
     };
 
     // 2. Récupère la phase actuelle
-    let phase = match fetch_gameflow_phase(&connection).await {
+    let phase = match connection.gameflow_phase().await {
         Ok(p) => p,
-        Err(e) => {
-            #[cfg(debug_assertions)]
-            eprintln!("[GameWatcher] Failed to fetch gameflow phase: {}", e);
-            return Ok(());
-        }
+        Err(e) => return Err(format!("Failed to fetch gameflow phase: {}", e)),
     };
 
     // 3. Met à jour l'état si changement
@@ -364,15 +489,14 @@ async fn poll_lcu(
     let previous_phase = state_guard.last_phase.clone();
 
     if previous_phase.as_ref() != Some(&phase) {
-        #[cfg(debug_assertions)]
-        eprintln!(
+        log_debug!(
             "[GameWatcher] Phase changed: {:?} -> {:?}",
             previous_phase,
             phase
         );
 
         state_guard.last_phase = Some(phase.clone());
-        state_guard.last_connection = Some(connection);
+        state_guard.last_connection = Some(connection.clone());
 
         // Détecte le passage en mode "In Game"
         if phase == GameflowPhase::InProgress {
@@ -380,28 +504,180 @@ async fn poll_lcu(
         }
 
         // Construit et émet le nouvel état
-        let game_state = match phase {
-            GameflowPhase::None => GameState::None,
-            GameflowPhase::Lobby => GameState::Lobby,
-            GameflowPhase::ChampSelect => GameState::ChampSelect { champion_id: None },
-            GameflowPhase::GameStart => GameState::GameStart,
-            GameflowPhase::InProgress => GameState::InProgress { game_data: None },
-            GameflowPhase::EndOfGame => GameState::EndOfGame,
-            _ => GameState::None,
-        };
+        let game_state = game_state_for_phase(&phase);
 
         drop(state_guard);
         emit_state_change(app_handle, game_state).await;
+
+        // Client (re)détecté : vérifie s'il a été mis à jour vers un nouveau patch
+        if previous_phase.is_none() {
+            if let Ok(version) = get_client_game_version(&connection).await {
+                champions::set_client_game_version(&version);
+            }
+        }
+
+        // Actions liées à la nouvelle phase (hors verrou)
+        on_phase_entered(state, app_handle, &phase, &connection).await;
+
+        if previous_phase == Some(GameflowPhase::ChampSelect) {
+            set_champ_select_pick(app_handle, None).await;
+            team_picks::reset();
+            aram::reset();
+        }
+        // Les imports d'avant la sélection ne concernent pas ce champion
+        if previous_phase == Some(GameflowPhase::ChampSelect) || phase == GameflowPhase::ChampSelect {
+            reimport::reset();
+        }
     } else {
         // Même phase, met juste à jour la connexion
-        state_guard.last_connection = Some(connection);
+        state_guard.last_connection = Some(connection.clone());
+        drop(state_guard);
+    }
+
+    // Suit le champion du joueur local pour précharger son build
+    if phase == GameflowPhase::ChampSelect {
+        let session = connection.champion_select_session().await.ok();
+        if let (Some(session), Some(app)) = (&session, app_handle.lock().await.as_ref()) {
+            // Suggestions de bans pendant le tour de ban du joueur (affichage seulement)
+            ban_suggestions::on_champ_select(app, session);
+            // Composition de l'équipe (survols et verrouillages des coéquipiers)
+            team_picks::on_champ_select(app, session);
+            // ARAM : reroll ou échange avec le banc
+            aram::on_champ_select(app, session);
+            // Build importé pour un autre champion (échange, reroll)
+            reimport::on_champ_select(app, session);
+        }
+        let pick = session.as_ref().and_then(prefetch::local_pick);
+        if let Some(pick) = &pick {
+            state.write().await.champ_select_role = Some(pick.role.clone());
+            prefetch_cs_benchmark(app_handle, &pick.role).await;
+        }
+        set_champ_select_pick(app_handle, pick).await;
     }
 
     Ok(())
 }
 
+/// État émis à l'entrée dans une phase (données détaillées récupérées ensuite)
+fn game_state_for_phase(phase: &GameflowPhase) -> GameState {
+    match phase {
+        GameflowPhase::None => GameState::None,
+        GameflowPhase::Lobby => GameState::Lobby,
+        GameflowPhase::ChampSelect => GameState::ChampSelect { champion_id: None },
+        GameflowPhase::GameStart => GameState::GameStart,
+        GameflowPhase::InProgress => GameState::InProgress { game_data: None },
+        GameflowPhase::EndOfGame => GameState::EndOfGame,
+        _ => GameState::None,
+    }
+}
+
+/// Télécharge à l'avance la courbe CS du rang de l'overlay pour le rôle choisi
+async fn prefetch_cs_benchmark(app_handle: &Arc<Mutex<Option<AppHandle>>>, role: &str) {
+    if let Some(app) = app_handle.lock().await.as_ref() {
+        let rank = app
+            .try_state::<OverlayConfigStore>()
+            .map(|store| store.get().rank)
+            .unwrap_or_default();
+        rank_benchmarks::prefetch(app, &rank, role);
+    }
+}
+
+/// Transmet le champion survolé/verrouillé au préchargement des builds
+async fn set_champ_select_pick(app_handle: &Arc<Mutex<Option<AppHandle>>>, pick: Option<prefetch::ChampSelectPick>) {
+    let handle_guard = app_handle.lock().await;
+    if let Some(app) = handle_guard.as_ref() {
+        if let Some(prefetcher) = app.try_state::<BuildPrefetcher>() {
+            prefetcher.set_pick(app, pick.as_ref());
+        }
+    }
+}
+
+/// Actions déclenchées à l'entrée dans une nouvelle phase
+async fn on_phase_entered(
+    state: &Arc<RwLock<WatcherState>>,
+    app_handle: &Arc<Mutex<Option<AppHandle>>>,
+    phase: &GameflowPhase,
+    connection: &LcuConnection,
+) {
+    match phase {
+        GameflowPhase::ReadyCheck | GameflowPhase::ChampSelect => {
+            let event = if *phase == GameflowPhase::ReadyCheck {
+                NotificationEvent::ReadyCheck
+            } else {
+                NotificationEvent::ChampSelect
+            };
+            if let Some(app) = app_handle.lock().await.as_ref() {
+                notifications::notify(app, event);
+            }
+        }
+        GameflowPhase::InProgress => {
+            // Mémorise l'ID de partie, la file et le rôle pour l'historique
+            let role = state.write().await.champ_select_role.take();
+            let active_game = match connection.gameflow_session().await {
+                Ok(session) => {
+                    let game_data = session.game_data.unwrap_or_default();
+                    let queue = game_data.queue.unwrap_or_default();
+                    ActiveGame {
+                        game_id: game_data.game_id,
+                        queue_id: queue.id,
+                        game_mode: queue.game_mode,
+                        role: role.clone(),
+                    }
+                }
+                Err(_) => ActiveGame {
+                    role: role.clone(),
+                    ..ActiveGame::default()
+                },
+            };
+
+            // Adapte l'overlay à la file (ARAM, ...) et au rôle (cibles CS par étape)
+            let mode = match app_handle.lock().await.as_ref() {
+                Some(app) => apply_queue_mode(app, active_game.queue_id, &active_game.game_mode, role.as_deref()),
+                None => OverlayMode::default(),
+            };
+
+            let mut state_guard = state.write().await;
+            state_guard.active_game = Some(active_game);
+            state_guard.live_result = None;
+            state_guard.progress = GameProgress::default();
+            state_guard.milestones = MilestoneTracker::new(mode.cs_goals);
+            state_guard.benchmark_curve = mode.benchmark_curve;
+            state_guard.timeline = CsTimeline::default();
+        }
+        GameflowPhase::EndOfGame => {
+            // Le bloc de stats de fin de partie confirme le résultat et donne l'or exact
+            let Ok(block) = get_eog_stats_block(connection).await else {
+                return;
+            };
+
+            if let Some(app) = app_handle.lock().await.as_ref() {
+                if let Some(won) = block.local_player_won() {
+                    let result = if won { GameResult::Win } else { GameResult::Loss };
+                    if let Err(e) = sessions::set_session_result(app, block.game_id, result) {
+                        log_warn!("[GameWatcher] Failed to record game result: {}", e);
+                    }
+                }
+                if let Some(gold) = block.local_player.as_ref().and_then(|p| p.gold_earned()) {
+                    if let Err(e) = sessions::set_session_gold(app, block.game_id, gold, block.local_gold_share()) {
+                        log_warn!("[GameWatcher] Failed to record game gold: {}", e);
+                    }
+                }
+                if let Some(combat) = CombatStats::from_eog(&block) {
+                    if let Err(e) = game_reports::set_report_combat(app, block.game_id, combat.clone()) {
+                        log_warn!("[GameWatcher] Failed to add combat stats to the report: {}", e);
+                    }
+                    if let Err(e) = sessions::set_session_combat(app, block.game_id, combat) {
+                        log_warn!("[GameWatcher] Failed to record combat stats: {}", e);
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
 /// Récupère la phase actuelle depuis le LCU
-async fn fetch_gameflow_phase(
+pub(crate) async fn fetch_gameflow_phase(
     connection: &LcuConnection,
 ) -> Result<GameflowPhase, String> {
     let client = create_lcu_http_client()?;
@@ -410,7 +686,8 @@ async fn fetch_gameflow_phase(
     let response = client
         .get(&url)
         .header("Authorization", connection.auth_header())
-        .send()
+        .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .send_timed()
         .await
         .map_err(|e| e.to_string())?;
 
@@ -455,7 +732,8 @@ async fn get_selected_champion(
     let response = client
         .get(&url)
         .header("Authorization", connection.auth_header())
-        .send()
+        .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .send_timed()
         .await
         .map_err(|e| e.to_string())?;
 
@@ -501,8 +779,10 @@ async fn poll_ingame(
     state: &Arc<RwLock<WatcherState>>,
     app_handle: &Arc<Mutex<Option<AppHandle>>>,
 ) -> Result<(), String> {
+    let live = LiveClient::local();
+
     // Vérifie si le jeu est toujours actif
-    match fetch_live_game_data().await {
+    match live.game_data().await {
         Ok(data) => {
             // Jeu toujours actif, émet les données mises à jour
             let game_state = GameState::InProgress {
@@ -510,19 +790,149 @@ async fn poll_ingame(
             };
             emit_state_change(app_handle, game_state).await;
 
+            // Récupère les événements avant de verrouiller l'état
+            let events = live.events().await;
+
             // Met à jour l'ID de partie
             let mut state_guard = state.write().await;
-            state_guard.current_game_id = Some(data.game_id);
+            state_guard.current_game_id = Some(data.game_id.clone());
+            state_guard.last_live_data = Some(data.clone());
+            state_guard
+                .progress
+                .observe(data.game_time, data.current_cs, data.current_gold);
+            state_guard
+                .timeline
+                .observe(data.game_time, data.current_cs, data.current_gold, data.current_health);
+            let milestones = state_guard.milestones.observe(data.game_time, data.current_cs);
+            if !milestones.is_empty() {
+                emit_cs_milestones(app_handle, &milestones).await;
+            }
+
+            // Timers d'objectifs : émis uniquement quand ils changent
+            if let Ok(events) = events {
+                state_guard.timeline.observe_events(&events);
+                if let Some(result) = events
+                    .iter()
+                    .find(|e| e.event_name == "GameEnd")
+                    .and_then(|e| e.result.as_deref())
+                    .and_then(GameResult::from_live_event)
+                {
+                    state_guard.live_result = Some(result);
+                }
+
+                let timers = compute_objective_timers(&events, data.game_time);
+                if timers != state_guard.last_objective_timers {
+                    state_guard.last_objective_timers = timers.clone();
+                    drop(state_guard);
+                    emit_objective_timers(
+                        app_handle,
+                        ObjectiveTimers {
+                            game_time: data.game_time,
+                            timers,
+                        },
+                    )
+                    .await;
+                }
+            }
         }
         Err(_) => {
             // Le jeu n'est plus accessible
             let mut state_guard = state.write().await;
 
             if state_guard.in_live_game {
-                #[cfg(debug_assertions)]
-            eprintln!("[GameWatcher] Game ended, switching back to LCU mode");
+                log_debug!("[GameWatcher] Game ended, switching back to LCU mode");
                 state_guard.in_live_game = false;
                 state_guard.current_game_id = None;
+                state_guard.last_objective_timers.clear();
+
+                let progress = std::mem::take(&mut state_guard.progress);
+                let timeline = std::mem::take(&mut state_guard.timeline);
+                let finished = state_guard.last_live_data.take().map(|data| {
+                    let active_game = state_guard.active_game.take().unwrap_or_default();
+                    let ended_at = sessions::now_unix_secs();
+                    let stats = GameStats {
+                        game_id: active_game.game_id,
+                        ended_at,
+                        champion_name: data.champion_name.clone(),
+                        role: active_game.role,
+                        duration_secs: data.game_time,
+                        cs_at_10: progress.cs_at_10,
+                        cs_at_20: progress.cs_at_20,
+                        final_cs: data.current_cs,
+                        gold_earned: progress.gold_earned(),
+                    };
+                    let report = game_reports::build_report(
+                        active_game.game_id,
+                        &data.champion_name,
+                        data.game_time,
+                        data.current_cs,
+                        &timeline,
+                        if state_guard.milestones.goals().is_empty() {
+                            &state_guard.benchmark_curve
+                        } else {
+                            state_guard.milestones.goals()
+                        },
+                    );
+                    let session = GameSession {
+                        game_id: active_game.game_id,
+                        queue_id: active_game.queue_id,
+                        champion_name: data.champion_name,
+                        role: stats.role.clone(),
+                        ended_at,
+                        duration_secs: data.game_time,
+                        final_cs: data.current_cs,
+                        cs_per_minute: data.cs_per_minute,
+                        laning_cs_per_minute: progress.laning_cs_per_minute(),
+                        result: state_guard.live_result.take(),
+                        gold_per_minute: stats
+                            .gold_earned
+                            .filter(|_| data.game_time > 0.0)
+                            .map(|gold| gold / (data.game_time / 60.0)),
+                        gold_share: None,
+                        combat: None,
+                        training: queues::is_practice_tool(&active_game.game_mode),
+                    };
+                    let game_timeline = timeline.to_game_timeline(active_game.game_id, data.game_time);
+                    (session, stats, report, game_timeline)
+                });
+
+                if let Some(app) = app_handle.lock().await.as_ref() {
+                    // Enregistre la partie terminée dans l'historique et met à jour les records
+                    if let Some((session, stats, report, game_timeline)) = finished {
+                        notifications::notify(app, NotificationEvent::GameEnded(session.clone()));
+                        let training = session.training;
+                        if let Err(e) = sessions::record_session(app, session) {
+                            log_warn!("[GameWatcher] Failed to record session: {}", e);
+                        }
+                        // Les parties d'entraînement ne comptent pas pour les records
+                        let bests = if training {
+                            Ok(Vec::new())
+                        } else {
+                            if let Err(e) = daily_goals::record_game(app) {
+                                log_warn!("[GameWatcher] Failed to update daily goals: {}", e);
+                            }
+                            personal_bests::record_game(app, &stats)
+                        };
+                        match bests {
+                            Ok(improved) if !improved.is_empty() => {
+                                log_info!("[GameWatcher] New personal bests on {}: {:?}", stats.champion_name, improved);
+                            }
+                            Ok(_) => {}
+                            Err(e) => log_warn!("[GameWatcher] Failed to record personal bests: {}", e),
+                        }
+                        if let Err(e) = game_reports::record_report(app, report) {
+                            log_warn!("[GameWatcher] Failed to record game report: {}", e);
+                        }
+                        if let Err(e) = game_reports::record_timeline(app, game_timeline) {
+                            log_warn!("[GameWatcher] Failed to record game timeline: {}", e);
+                        }
+                    }
+
+                    // Les timers de sorts ne concernent que la partie terminée
+                    if let Some(timers) = app.try_state::<SpellTimers>() {
+                        timers.clear();
+                    }
+                }
 
                 // Revérifie le LCU pour voir la nouvelle phase
                 drop(state_guard);
@@ -539,14 +949,11 @@ async fn poll_ingame(
 /// # Compliance Note
 /// Cette fonction utilise l'endpoint officiel /liveclientdata/activeplayer
 /// fourni par Riot Games. C'est une API documentée et autorisée.
-async fn fetch_live_game_data() -> Result<LiveGameData, String> {
+pub(crate) async fn fetch_live_game_data(base_url: &str) -> Result<LiveGameData, String> {
     let client = create_ingame_http_client()?;
 
     // Récupère les données du joueur actif
-    let active_player_url = format!(
-        "https://127.0.0.1:{}/liveclientdata/activeplayer",
-        LIVE_CLIENT_PORT
-    );
+    let active_player_url = format!("{}/liveclientdata/activeplayer", base_url);
 
     let active_player_response = client
         .get(&active_player_url)
@@ -589,10 +996,7 @@ async fn fetch_live_game_data() -> Result<LiveGameData, String> {
     let stats = active_player.champion_stats;
 
     // Récupère les stats de la partie
-    let game_stats_url = format!(
-        "https://127.0.0.1:{}/liveclientdata/gamestats",
-        LIVE_CLIENT_PORT
-    );
+    let game_stats_url = format!("{}/liveclientdata/gamestats", base_url);
 
     let game_stats_response = client.get(&game_stats_url).send().await.map_err(|e| e.to_string())?;
 
@@ -631,6 +1035,50 @@ async fn fetch_live_game_data() -> Result<LiveGameData, String> {
     })
 }
 
+/// Récupère le temps de jeu actuel (secondes) depuis le Live Client Data API
+pub(crate) async fn fetch_live_game_time(base_url: &str) -> Result<f64, String> {
+    let client = create_ingame_http_client()?;
+    let url = format!("{}/liveclientdata/gamestats", base_url);
+
+    let response = client.get(&url).send().await.map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err("Live Client API not available".to_string());
+    }
+
+    #[derive(Deserialize)]
+    struct GameStats {
+        #[serde(rename = "gameTime")]
+        game_time: f64,
+    }
+
+    let stats: GameStats = response.json().await.map_err(|e| e.to_string())?;
+    Ok(stats.game_time)
+}
+
+/// Récupère le flux d'événements de la partie en cours
+///
+/// # Compliance Note
+/// Endpoint officiel /liveclientdata/eventdata : ce sont les mêmes
+/// événements que ceux annoncés à tous les joueurs dans le jeu.
+pub(crate) async fn fetch_live_events(base_url: &str) -> Result<Vec<LiveEvent>, String> {
+    let client = create_ingame_http_client()?;
+    let url = format!("{}/liveclientdata/eventdata", base_url);
+
+    let response = client.get(&url).send().await.map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err("Live Client API not available".to_string());
+    }
+
+    #[derive(Deserialize)]
+    struct EventData {
+        #[serde(rename = "Events", default)]
+        events: Vec<LiveEvent>,
+    }
+
+    let data: EventData = response.json().await.map_err(|e| e.to_string())?;
+    Ok(data.events)
+}
+
 // =============================================================================
 // ÉMISSION D'ÉVÉNEMENTS TAURI
 // =============================================================================
@@ -641,16 +1089,52 @@ async fn emit_state_change(app_handle: &Arc<Mutex<Option<AppHandle>>>, state: Ga
 
     if let Some(app) = handle_guard.as_ref() {
         // Émet vers la fenêtre principale
-        if let Err(e) = app.emit("game-state-changed", &state) {
-            #[cfg(debug_assertions)]
-            eprintln!("[GameWatcher] Failed to emit state change: {}", e);
+        if let Err(e) = AppEvent::GameStateChanged(&state).emit(app) {
+            log_debug!("[GameWatcher] {}", e);
         }
 
-        // Émet également vers l'overlay s'il existe
-        let _ = app.emit("cs-overlay-update", &state);
+        // État de connexion affiché dans le tray
+        crate::tray::set_status(app, &state);
+
+        // Émet également vers l'overlay (uniquement les champs modifiés)
+        if let Some(emitter) = app.try_state::<OverlayEmitter>() {
+            match &state {
+                GameState::InProgress {
+                    game_data: Some(data),
+                } => {
+                    let _ = emitter.emit_delta(app, data);
+                }
+                GameState::InProgress { game_data: None } => {}
+                // Hors partie : le prochain envoi à l'overlay sera complet
+                _ => emitter.reset(),
+            }
+        }
 
-        #[cfg(debug_assertions)]
-        eprintln!("[GameWatcher] Emitted state: {:?}", state);
+        log_debug!("[GameWatcher] Emitted state: {:?}", state);
+    }
+}
+
+/// Émet un événement `objective-timers-updated` (fenêtre principale et overlay)
+async fn emit_objective_timers(app_handle: &Arc<Mutex<Option<AppHandle>>>, timers: ObjectiveTimers) {
+    let handle_guard = app_handle.lock().await;
+
+    if let Some(app) = handle_guard.as_ref() {
+        if let Err(e) = AppEvent::ObjectiveTimersUpdated(&timers).emit(app) {
+            log_debug!("[GameWatcher] {}", e);
+        }
+    }
+}
+
+/// Émet les étapes CS franchies (`cs-milestone`)
+async fn emit_cs_milestones(app_handle: &Arc<Mutex<Option<AppHandle>>>, milestones: &[CsMilestone]) {
+    let handle_guard = app_handle.lock().await;
+
+    if let Some(app) = handle_guard.as_ref() {
+        for milestone in milestones {
+            if let Err(e) = AppEvent::CsMilestone(milestone).emit(app) {
+                log_debug!("[GameWatcher] {}", e);
+            }
+        }
     }
 }
 
@@ -658,7 +1142,7 @@ async fn emit_state_change(app_handle: &Arc<Mutex<Option<AppHandle>>>, state: Ga
 // CLIENTS HTTP AVEC GESTION DES CERTIFICATS
 // =============================================================================
 
-/// Crée un client HTTP configuré pour le LCU (League Client)
+/// Client HTTP partagé du LCU (League Client)
 ///
 /// # Security Note
 /// `danger_accept_invalid_certs(true)` est nécessaire car le League Client
@@ -667,24 +1151,16 @@ async fn emit_state_change(app_handle: &Arc<Mutex<Option<AppHandle>>>, state: Ga
 /// - Le lockfile est lu depuis le système de fichiers local
 /// - Aucune donnée ne quitte la machine
 fn create_lcu_http_client() -> Result<reqwest::Client, String> {
-    reqwest::Client::builder()
-        .danger_accept_invalid_certs(true)
-        .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
-        .build()
-        .map_err(|e| e.to_string())
+    crate::http_clients::lcu().map_err(|e| e.to_string())
 }
 
-/// Crée un client HTTP configuré pour le Live Client Data API (In-Game)
+/// Client HTTP partagé du Live Client Data API (In-Game)
 ///
 /// # Security Note
 /// Même configuration que le LCU - le jeu utilise un certificat auto-signé.
 /// C'est sécurisé car c'est une connexion localhost uniquement.
-fn create_ingame_http_client() -> Result<reqwest::Client, String> {
-    reqwest::Client::builder()
-        .danger_accept_invalid_certs(true)
-        .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
-        .build()
-        .map_err(|e| e.to_string())
+pub(crate) fn create_ingame_http_client() -> Result<reqwest::Client, String> {
+    crate::http_clients::live_client().map_err(|e| e.to_string())
 }
 
 // =============================================================================
@@ -733,6 +1209,71 @@ pub async fn refresh_game_state(
 ) -> Result<GameState, String> {
     // Réémet l'état actuel
     let state = watcher.get_current_state().await;
-    let _ = app.emit("game-state-changed", &state);
+    let _ = AppEvent::GameStateChanged(&state).emit(&app);
     Ok(state)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_server::*;
+
+    #[test]
+    fn test_resume_detected() {
+        let before = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+        assert!(!resume_detected(before, before + Duration::from_millis(LCU_POLL_INTERVAL_MS)));
+        assert!(resume_detected(before, before + Duration::from_secs(2 * 60 * 60)));
+        // Horloge reculée (changement d'heure manuel) : pas une sortie de veille
+        assert!(!resume_detected(before, before - Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn test_watcher_transitions_against_mock_client() {
+        block_on(async {
+            let server = MockServer::start().await;
+            let connection = lcu_connection(&server);
+            let live = live_client(&server);
+
+            // Sélection des champions : le pick local est suivi
+            mount(
+                &server,
+                &[
+                    ("GET", "/lol-gameflow/v1/gameflow-phase", 200, "\"ChampSelect\""),
+                    ("GET", "/lol-champ-select/v1/session", 200, LCU_CHAMP_SELECT_SESSION),
+                ],
+            )
+            .await;
+            let phase = connection.gameflow_phase().await.unwrap();
+            assert_eq!(game_state_for_phase(&phase), GameState::ChampSelect { champion_id: None });
+            assert_eq!(get_selected_champion(&connection).await.unwrap(), 103);
+
+            // Partie lancée : les données live sont lues depuis le Live Client
+            server.reset().await;
+            mount(
+                &server,
+                &[
+                    ("GET", "/lol-gameflow/v1/gameflow-phase", 200, "\"InProgress\""),
+                    ("GET", "/liveclientdata/activeplayer", 200, LIVE_ACTIVE_PLAYER),
+                    ("GET", "/liveclientdata/gamestats", 200, LIVE_GAME_STATS),
+                    ("GET", "/liveclientdata/eventdata", 200, LIVE_EVENT_DATA),
+                ],
+            )
+            .await;
+            let phase = connection.gameflow_phase().await.unwrap();
+            assert_eq!(game_state_for_phase(&phase), GameState::InProgress { game_data: None });
+            let data = live.game_data().await.unwrap();
+            assert_eq!((data.current_cs, data.level, data.champion_name.as_str()), (120, 11, "Ahri"));
+            assert_eq!(live.game_time().await.unwrap(), 900.0);
+            let events = live.events().await.unwrap();
+            assert_eq!(events.len(), 3);
+            assert_eq!(events[2].dragon_type.as_deref(), Some("Fire"));
+
+            // Fin de partie : le Live Client ne répond plus, le LCU passe en EndOfGame
+            server.reset().await;
+            mount(&server, &[("GET", "/lol-gameflow/v1/gameflow-phase", 200, "\"EndOfGame\"")]).await;
+            assert!(live.game_data().await.is_err());
+            let phase = connection.gameflow_phase().await.unwrap();
+            assert_eq!(game_state_for_phase(&phase), GameState::EndOfGame);
+        });
+    }
+}
@@ -0,0 +1,170 @@
+//! Health check of every integration.
+//!
+//! `run_health_check` tests, in parallel, the League Client API (LCU), the
+//! Live Client Data API, FocusApi and the static data cache, and returns one
+//! entry per integration for the status panel. A client or game that is
+//! simply not running is reported as `unavailable`, not as an error.
+
+use serde::Serialize;
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+use crate::game_watcher::LIVE_CLIENT_PORT;
+use crate::lcu::{self, LcuError};
+use crate::sessions::now_unix_secs;
+use crate::{focus_api, static_data};
+
+/// Timeout of the Live Client Data API probe
+const LIVE_CLIENT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// State of an integration
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum HealthStatus {
+    Ok,
+    /// Not running (client closed, no game in progress)
+    Unavailable,
+    Error,
+}
+
+/// Result of the check of one integration
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntegrationHealth {
+    pub status: HealthStatus,
+    /// Time taken by the check
+    pub latency_ms: u64,
+    pub message: String,
+}
+
+/// Report returned by `run_health_check`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthReport {
+    /// Unix timestamp (seconds) of the check
+    pub checked_at: i64,
+    /// True when no integration is in error
+    pub healthy: bool,
+    pub lcu: IntegrationHealth,
+    pub live_client: IntegrationHealth,
+    pub focus_api: IntegrationHealth,
+    pub static_data: IntegrationHealth,
+}
+
+/// Run a check and measure it
+async fn timed<F>(check: F) -> IntegrationHealth
+where
+    F: std::future::Future<Output = (HealthStatus, String)>,
+{
+    let start = Instant::now();
+    let (status, message) = check.await;
+    IntegrationHealth {
+        status,
+        latency_ms: start.elapsed().as_millis() as u64,
+        message,
+    }
+}
+
+async fn check_lcu() -> (HealthStatus, String) {
+    let connection = match lcu::find_lockfile().await {
+        Ok(connection) => connection,
+        Err(LcuError::ClientNotRunning) => {
+            return (HealthStatus::Unavailable, "League Client is not running".to_string())
+        }
+        Err(e) => return (HealthStatus::Error, e.to_string()),
+    };
+    match lcu::get_gameflow_phase(&connection).await {
+        Ok(phase) => (HealthStatus::Ok, format!("Connected (phase: {})", phase)),
+        Err(e) => (HealthStatus::Error, format!("Lockfile found but the client does not answer: {}", e)),
+    }
+}
+
+async fn check_live_client() -> (HealthStatus, String) {
+    let client = match crate::http_clients::live_client() {
+        Ok(client) => client,
+        Err(e) => return (HealthStatus::Error, e.to_string()),
+    };
+    let url = format!("https://127.0.0.1:{}/liveclientdata/gamestats", LIVE_CLIENT_PORT);
+
+    match client.get(&url).timeout(LIVE_CLIENT_TIMEOUT).send().await {
+        Ok(response) if response.status().is_success() => (HealthStatus::Ok, "Game in progress".to_string()),
+        // The API answers 404 while the game is loading
+        Ok(response) => (
+            HealthStatus::Unavailable,
+            format!("No game data yet (HTTP {})", response.status().as_u16()),
+        ),
+        Err(_) => (HealthStatus::Unavailable, "No game in progress".to_string()),
+    }
+}
+
+async fn check_focus_api() -> (HealthStatus, String) {
+    match focus_api::validate_api_key(None).await {
+        Ok(validation) if validation.valid => (HealthStatus::Ok, validation.message),
+        Ok(validation) => (HealthStatus::Error, validation.message),
+        Err(e) => (HealthStatus::Error, e),
+    }
+}
+
+async fn check_static_data(app: &AppHandle) -> (HealthStatus, String) {
+    let report = static_data::inspect_static_data(app);
+    if report.healthy {
+        return (HealthStatus::Ok, format!("Cache valid for patch {}", report.version));
+    }
+    let broken: Vec<String> = report
+        .checks
+        .iter()
+        .filter(|c| c.status != static_data::CacheStatus::Ok)
+        .map(|c| c.file.clone())
+        .collect();
+    (HealthStatus::Error, format!("Invalid cache files: {}", broken.join(", ")))
+}
+
+/// No integration is in error
+fn all_healthy(checks: &[&IntegrationHealth]) -> bool {
+    checks.iter().all(|check| check.status != HealthStatus::Error)
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Check every integration in parallel.
+#[tauri::command]
+pub async fn run_health_check(app: AppHandle) -> Result<HealthReport, String> {
+    let (lcu, live_client, focus_api, static_data) = tokio::join!(
+        timed(check_lcu()),
+        timed(check_live_client()),
+        timed(check_focus_api()),
+        timed(check_static_data(&app)),
+    );
+
+    Ok(HealthReport {
+        checked_at: now_unix_secs(),
+        healthy: all_healthy(&[&lcu, &live_client, &focus_api, &static_data]),
+        lcu,
+        live_client,
+        focus_api,
+        static_data,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_all_healthy() {
+        let check = |status| IntegrationHealth {
+            status,
+            latency_ms: 0,
+            message: String::new(),
+        };
+        let ok = check(HealthStatus::Ok);
+        let unavailable = check(HealthStatus::Unavailable);
+        let error = check(HealthStatus::Error);
+
+        // A closed client is not an error
+        assert!(all_healthy(&[&ok, &unavailable]));
+        assert!(!all_healthy(&[&ok, &error]));
+    }
+}
@@ -0,0 +1,87 @@
+//! HTTP clients shared by the whole application.
+//!
+//! Building a reqwest client sets up a TLS configuration and a connection
+//! pool, which used to happen on almost every command and every poll of the
+//! game watcher. The League Client, Live Client Data API and FocusApi
+//! clients are now built on first use and reused (a `reqwest::Client` is a
+//! cheap handle to the shared pool).
+//!
+//! They live in process-wide slots rather than the Tauri state because the
+//! LCU and Live Client functions are called without an `AppHandle`.
+//! `reset()` drops them so the next request builds fresh ones: the proxy
+//! setting changed, or the machine woke up and the pooled connections are
+//! dead.
+
+use reqwest::Client;
+use std::sync::RwLock;
+use std::time::Duration;
+
+/// Timeout of League Client requests
+const LCU_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Timeout of Live Client Data API requests (polled every 500 ms in game)
+const LIVE_CLIENT_TIMEOUT: Duration = Duration::from_secs(3);
+
+static LCU: RwLock<Option<Client>> = RwLock::new(None);
+static LIVE_CLIENT: RwLock<Option<Client>> = RwLock::new(None);
+static REMOTE: RwLock<Option<Client>> = RwLock::new(None);
+
+/// The client in `slot`, built with `build` the first time
+fn cached(slot: &RwLock<Option<Client>>, build: fn() -> reqwest::ClientBuilder) -> Result<Client, reqwest::Error> {
+    if let Some(client) = slot.read().ok().and_then(|client| client.clone()) {
+        return Ok(client);
+    }
+    let client = build().build()?;
+    if let Ok(mut current) = slot.write() {
+        *current = Some(client.clone());
+    }
+    Ok(client)
+}
+
+/// Client of the League Client API (localhost, self-signed certificate)
+pub fn lcu() -> Result<Client, reqwest::Error> {
+    cached(&LCU, || {
+        crate::proxy::local_client()
+            .danger_accept_invalid_certs(true)
+            .timeout(LCU_TIMEOUT)
+    })
+}
+
+/// Client of the Live Client Data API (localhost, self-signed certificate)
+pub fn live_client() -> Result<Client, reqwest::Error> {
+    cached(&LIVE_CLIENT, || {
+        crate::proxy::local_client()
+            .danger_accept_invalid_certs(true)
+            .timeout(LIVE_CLIENT_TIMEOUT)
+    })
+}
+
+/// Client of FocusApi, following the proxy settings.
+///
+/// Has no timeout of its own: each request sets one.
+pub fn remote() -> Result<Client, reqwest::Error> {
+    cached(&REMOTE, crate::proxy::remote_client)
+}
+
+/// Drop the shared clients and their pooled connections
+pub fn reset() {
+    for slot in [&LCU, &LIVE_CLIENT, &REMOTE] {
+        if let Ok(mut client) = slot.write() {
+            *client = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clients_are_reused_until_reset() {
+        lcu().unwrap();
+        assert!(LCU.read().unwrap().is_some());
+        reset();
+        assert!(LCU.read().unwrap().is_none());
+        assert!(live_client().is_ok());
+    }
+}
@@ -0,0 +1,170 @@
+//! On-disk cache of FocusApi import payloads.
+//!
+//! Entries are keyed by patch, champion, role and build source, and also
+//! keep the request they answer so a different build for the same key never
+//! returns a stale payload. Fresh entries make re-importing the same build
+//! instant; older ones are only used when FocusApi cannot be reached.
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::lcu::ImportPayloadResponse;
+use crate::sessions::now_unix_secs;
+use crate::{storage, ImportPayloadRequest};
+
+/// Cache file, relative to the cache directory
+const IMPORT_CACHE_FILE: &str = "focus-api/import-payloads.json";
+
+/// Age under which a cached payload is used without calling FocusApi
+pub const FRESH_TTL_SECS: i64 = 6 * 60 * 60;
+
+/// Maximum age of a cached payload used when FocusApi is unreachable
+pub const STALE_TTL_SECS: i64 = 24 * 60 * 60;
+
+/// Maximum number of cached payloads (oldest are dropped first)
+const MAX_ENTRIES: usize = 100;
+
+/// Cache key of an import payload
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct PayloadKey {
+    patch: String,
+    champion: String,
+    role: String,
+    source: String,
+}
+
+impl PayloadKey {
+    fn of(request: &ImportPayloadRequest) -> Self {
+        let normalize = |value: &str| value.trim().to_lowercase();
+        Self {
+            patch: normalize(&request.patch),
+            champion: normalize(&request.champion_key),
+            role: normalize(&request.role),
+            source: normalize(&request.source),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CachedPayload {
+    key: PayloadKey,
+    /// Request the payload answers
+    request: serde_json::Value,
+    /// Unix timestamp (seconds) of the FocusApi response
+    fetched_at: i64,
+    response: ImportPayloadResponse,
+}
+
+fn load_entries(app: &AppHandle) -> Vec<CachedPayload> {
+    let Ok(path) = storage::cache_file_path(app, IMPORT_CACHE_FILE) else {
+        return Vec::new();
+    };
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+fn save_entries(app: &AppHandle, entries: &[CachedPayload]) -> Result<(), String> {
+    let path = storage::cache_file_path(app, IMPORT_CACHE_FILE)?;
+    let json = serde_json::to_string(entries).map_err(|e| e.to_string())?;
+    storage::write_atomic(&path, json.as_bytes())
+}
+
+/// Find the payload answering `request` if it is younger than `max_age_secs`
+fn find_entry<'a>(
+    entries: &'a [CachedPayload],
+    request: &ImportPayloadRequest,
+    max_age_secs: i64,
+    now: i64,
+) -> Option<&'a CachedPayload> {
+    let key = PayloadKey::of(request);
+    let request = serde_json::to_value(request).ok()?;
+    entries
+        .iter()
+        .find(|entry| entry.key == key && entry.request == request)
+        .filter(|entry| now - entry.fetched_at <= max_age_secs)
+}
+
+/// Cached payload for `request`, if younger than `max_age_secs`
+pub fn lookup(app: &AppHandle, request: &ImportPayloadRequest, max_age_secs: i64) -> Option<ImportPayloadResponse> {
+    find_entry(&load_entries(app), request, max_age_secs, now_unix_secs()).map(|entry| entry.response.clone())
+}
+
+/// Save the FocusApi response for `request`, replacing the entry of the same key
+pub fn store(app: &AppHandle, request: &ImportPayloadRequest, response: &ImportPayloadResponse) -> Result<(), String> {
+    let key = PayloadKey::of(request);
+    let mut entries = load_entries(app);
+    entries.retain(|entry| entry.key != key);
+    entries.push(CachedPayload {
+        key,
+        request: serde_json::to_value(request).map_err(|e| e.to_string())?,
+        fetched_at: now_unix_secs(),
+        response: response.clone(),
+    });
+
+    if entries.len() > MAX_ENTRIES {
+        let excess = entries.len() - MAX_ENTRIES;
+        entries.drain(..excess);
+    }
+
+    save_entries(app, &entries)
+}
+
+/// Drop every cached payload (new patch)
+pub fn clear(app: &AppHandle) {
+    let Ok(path) = storage::cache_file_path(app, IMPORT_CACHE_FILE) else {
+        return;
+    };
+    match std::fs::remove_file(&path) {
+        Ok(()) => log_info!("[import_cache] Cleared cached import payloads"),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => log_warn!("[import_cache] Failed to clear {:?}: {}", path, e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_server::import_request;
+
+    fn request(champion_key: &str, items_core: Vec<i64>) -> ImportPayloadRequest {
+        ImportPayloadRequest {
+            champion_key: champion_key.to_string(),
+            items_core,
+            ..import_request()
+        }
+    }
+
+    fn entry(request: &ImportPayloadRequest, fetched_at: i64) -> CachedPayload {
+        CachedPayload {
+            key: PayloadKey::of(request),
+            request: serde_json::to_value(request).unwrap(),
+            fetched_at,
+            response: ImportPayloadResponse {
+                champion: Some("Ahri".to_string()),
+                role: Some("mid".to_string()),
+                rune_page_payload: None,
+                item_set_payload: None,
+                summoner_spells_payload: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_find_entry() {
+        let cached = request("Ahri", vec![6655]);
+        let entries = vec![entry(&cached, 1_000)];
+
+        // Same key regardless of case or build
+        assert_eq!(PayloadKey::of(&request("ahri", vec![])), PayloadKey::of(&cached));
+
+        assert!(find_entry(&entries, &cached, FRESH_TTL_SECS, 2_000).is_some());
+        // Too old
+        assert!(find_entry(&entries, &cached, FRESH_TTL_SECS, 1_000 + FRESH_TTL_SECS + 1).is_none());
+        // Same key, different build
+        assert!(find_entry(&entries, &request("Ahri", vec![3089]), FRESH_TTL_SECS, 2_000).is_none());
+    }
+}
@@ -0,0 +1,209 @@
+//! History of build imports.
+//!
+//! Every import the app applies (or tries to apply) to the League Client is
+//! recorded here with its outcome, so the user can see what was written to
+//! their client and how often imports fail. Imports queued while offline are
+//! recorded as queued, then again with their final outcome once the queue is
+//! processed.
+
+use serde::{Deserialize, Serialize};
+use rusqlite::params;
+use std::collections::HashMap;
+use tauri::AppHandle;
+
+use crate::lcu::ImportResult;
+use crate::sessions::now_unix_secs;
+use crate::{db, CommandError, ImportPayloadRequest};
+
+/// Maximum number of records kept (oldest are dropped first)
+const MAX_RECORDS: usize = 500;
+
+/// Number of champions listed in the statistics
+const TOP_CHAMPIONS: usize = 5;
+
+/// Outcome of an import
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ImportOutcome {
+    Success,
+    Failed,
+    /// Client or FocusApi unreachable, import waiting in the offline queue
+    Queued,
+}
+
+/// One recorded import
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportRecord {
+    /// Unix timestamp (seconds)
+    pub timestamp: i64,
+    pub champion_key: String,
+    pub role: String,
+    pub patch: String,
+    pub source: String,
+    pub outcome: ImportOutcome,
+    pub message: String,
+}
+
+/// Number of imports of a champion
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChampionImportCount {
+    pub champion_key: String,
+    pub count: usize,
+}
+
+/// Aggregate statistics of the import history
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportStats {
+    pub total: usize,
+    pub successes: usize,
+    pub failures: usize,
+    pub queued: usize,
+    /// Failures among finished imports (0.0 - 1.0)
+    pub failure_rate: f64,
+    /// Most imported champions, successful imports only
+    pub most_imported: Vec<ChampionImportCount>,
+}
+
+fn load_history(app: &AppHandle) -> Vec<ImportRecord> {
+    db::read(app, |connection| db::load_rows(connection, db::IMPORT_HISTORY))
+}
+
+/// Append an import to the history
+fn record(app: &AppHandle, payload: &ImportPayloadRequest, outcome: ImportOutcome, message: &str) {
+    let record = ImportRecord {
+        timestamp: now_unix_secs(),
+        champion_key: payload.champion_key.clone(),
+        role: payload.role.clone(),
+        patch: payload.patch.clone(),
+        source: payload.source.clone(),
+        outcome,
+        message: message.to_string(),
+    };
+    let Ok(data) = serde_json::to_string(&record) else {
+        return;
+    };
+
+    let saved = db::with(app, |connection| {
+        let transaction = connection.transaction()?;
+        transaction.execute(
+            "INSERT INTO import_history (imported_at, data) VALUES (?1, ?2)",
+            params![record.timestamp, data],
+        )?;
+        db::trim(&transaction, db::IMPORT_HISTORY, MAX_RECORDS)?;
+        transaction.commit()
+    });
+    if let Err(e) = saved {
+        log_warn!("[import_history] Failed to save history: {}", e);
+    }
+}
+
+/// Record the result of an import
+pub fn record_result(app: &AppHandle, payload: &ImportPayloadRequest, result: &Result<ImportResult, CommandError>) {
+    let (outcome, message) = match result {
+        Ok(r) if r.queued => (ImportOutcome::Queued, &r.message),
+        Ok(r) if r.success => (ImportOutcome::Success, &r.message),
+        Ok(r) => (ImportOutcome::Failed, &r.message),
+        Err(e) => (ImportOutcome::Failed, &e.message),
+    };
+    record(app, payload, outcome, message);
+}
+
+/// Compute the statistics of a history
+pub fn compute_stats(history: &[ImportRecord]) -> ImportStats {
+    let count = |outcome: ImportOutcome| history.iter().filter(|r| r.outcome == outcome).count();
+    let successes = count(ImportOutcome::Success);
+    let failures = count(ImportOutcome::Failed);
+    let finished = successes + failures;
+
+    let mut per_champion: HashMap<String, ChampionImportCount> = HashMap::new();
+    for record in history.iter().filter(|r| r.outcome == ImportOutcome::Success) {
+        per_champion
+            .entry(record.champion_key.to_lowercase())
+            .or_insert_with(|| ChampionImportCount {
+                champion_key: record.champion_key.clone(),
+                count: 0,
+            })
+            .count += 1;
+    }
+    let mut most_imported: Vec<ChampionImportCount> = per_champion.into_values().collect();
+    most_imported.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.champion_key.cmp(&b.champion_key)));
+    most_imported.truncate(TOP_CHAMPIONS);
+
+    ImportStats {
+        total: history.len(),
+        successes,
+        failures,
+        queued: count(ImportOutcome::Queued),
+        failure_rate: if finished == 0 {
+            0.0
+        } else {
+            failures as f64 / finished as f64
+        },
+        most_imported,
+    }
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the recorded imports, most recent first.
+#[tauri::command]
+pub async fn get_import_history(app: AppHandle, limit: Option<usize>) -> Result<Vec<ImportRecord>, String> {
+    let mut history = load_history(&app);
+    history.reverse();
+    if let Some(limit) = limit {
+        history.truncate(limit);
+    }
+    Ok(history)
+}
+
+/// Get aggregate statistics of the import history.
+#[tauri::command]
+pub async fn get_import_stats(app: AppHandle) -> Result<ImportStats, String> {
+    Ok(compute_stats(&load_history(&app)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(champion_key: &str, outcome: ImportOutcome) -> ImportRecord {
+        ImportRecord {
+            timestamp: 0,
+            champion_key: champion_key.to_string(),
+            role: "mid".to_string(),
+            patch: "15.1".to_string(),
+            source: "focusapi".to_string(),
+            outcome,
+            message: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_compute_stats() {
+        let history = vec![
+            record("Ahri", ImportOutcome::Success),
+            record("Jinx", ImportOutcome::Success),
+            record("ahri", ImportOutcome::Success),
+            record("Jinx", ImportOutcome::Failed),
+            record("Lux", ImportOutcome::Queued),
+        ];
+        let stats = compute_stats(&history);
+
+        assert_eq!((stats.total, stats.successes, stats.failures, stats.queued), (5, 3, 1, 1));
+        // Queued imports are not finished yet
+        assert_eq!(stats.failure_rate, 0.25);
+        assert_eq!(
+            stats.most_imported,
+            vec![
+                ChampionImportCount { champion_key: "Ahri".to_string(), count: 2 },
+                ChampionImportCount { champion_key: "Jinx".to_string(), count: 1 },
+            ]
+        );
+        assert_eq!(compute_stats(&[]).failure_rate, 0.0);
+    }
+}
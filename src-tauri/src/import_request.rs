@@ -0,0 +1,191 @@
+//! Import requests built from FocusApi builds.
+//!
+//! A FocusApi build lists every rune, shard, item and summoner spell seen
+//! with its pick count; an import needs one choice of each. `from_build`
+//! makes that choice (most played keystone and spells, API order for the
+//! primary runes, first three items as the core) and is the only place it is
+//! made: the frontend gets its import requests from `build_import_request`,
+//! and `import_all_roles` calls it directly, so both hit the same import
+//! cache entries.
+
+use serde_json::Value;
+
+use crate::champions::{self, ChampionInfo};
+use crate::error::AppError;
+use crate::{lcu, spell_timers, CommandError, ImportPayloadRequest, RuneTree};
+
+/// Secondary tree used when the build has no secondary rune (Sorcery)
+const DEFAULT_SECONDARY_TREE: i64 = 8200;
+
+/// Summoner spell ID of Flash (used for unknown spell names)
+pub const FLASH: i64 = 4;
+
+fn ids(values: Option<&Value>) -> Vec<i64> {
+    values
+        .and_then(Value::as_array)
+        .map(|values| values.iter().filter_map(|v| v.get("id")?.as_i64()).collect())
+        .unwrap_or_default()
+}
+
+fn count(value: &Value) -> i64 {
+    value.get("count").and_then(Value::as_i64).unwrap_or_default()
+}
+
+fn non_empty<'a>(tree: &'a Value, field: &str) -> Option<&'a Vec<Value>> {
+    tree.get(field).and_then(Value::as_array).filter(|values| !values.is_empty())
+}
+
+/// Tree of a rune ID (8010 -> 8000)
+fn tree_of(rune_id: i64) -> i64 {
+    rune_id / 100 * 100
+}
+
+/// Import request of a FocusApi build (`fetch_champion_build` response) for
+/// `role`, on `patch` ("current" when None)
+pub fn from_build(data: &Value, champion: &ChampionInfo, role: &str, patch: Option<&str>) -> ImportPayloadRequest {
+    let build = &data["build"];
+    let trees = build["runes"].as_array().map(Vec::as_slice).unwrap_or_default();
+
+    // Primary tree: the one with keystones; secondary: another tree with runes only
+    let primary = trees.iter().find(|t| non_empty(t, "keystones").is_some());
+    let secondary = trees.iter().find(|t| {
+        non_empty(t, "runes").is_some()
+            && non_empty(t, "keystones").is_none()
+            && t.get("path") != primary.and_then(|p| p.get("path"))
+    });
+
+    let keystone = primary
+        .and_then(|t| non_empty(t, "keystones"))
+        .and_then(|keystones| keystones.iter().reduce(|best, k| if count(k) > count(best) { k } else { best }))
+        .and_then(|k| k["id"].as_i64())
+        .unwrap_or_default();
+    let mut primary_runes: Vec<i64> = vec![keystone];
+    if let Some(runes) = primary.and_then(|t| non_empty(t, "runes")).filter(|runes| runes.len() >= 3) {
+        primary_runes.extend(runes[..3].iter().filter_map(|r| r["id"].as_i64()));
+    }
+    primary_runes.retain(|&id| id != 0);
+
+    let mut secondary_runes: Vec<&Value> = secondary.and_then(|t| non_empty(t, "runes")).into_iter().flatten().collect();
+    secondary_runes.sort_by_key(|r| std::cmp::Reverse(count(r)));
+    let secondary_runes: Vec<i64> = secondary_runes.iter().take(2).filter_map(|r| r["id"].as_i64()).collect();
+
+    // Stat shards, by row: `stat_shards` list, or the older `shards` object
+    let rows = ["offense", "flex", "defense"];
+    let shards: Vec<Option<i64>> = match non_empty(build, "stat_shards") {
+        Some(stat_shards) => rows
+            .iter()
+            .map(|row| stat_shards.iter().find(|s| s["row"] == *row)?["id"].as_i64())
+            .collect(),
+        None => rows.iter().map(|row| build["shards"][row].as_i64()).collect(),
+    };
+    let rune_shards = lcu::default_stat_shards()
+        .into_iter()
+        .zip(shards)
+        .map(|(default, id)| id.filter(|&id| id != 0).unwrap_or(default))
+        .collect();
+
+    // First three build items are the core, the rest situational
+    let mut items_core = ids(build.get("items"));
+    let items_situational = items_core.split_off(items_core.len().min(3));
+
+    let mut spells: Vec<&Value> = build["summoner_spells"].as_array().into_iter().flatten().collect();
+    spells.sort_by_key(|s| std::cmp::Reverse(count(s)));
+    let summoner_spells = spells
+        .iter()
+        .take(2)
+        .map(|s| spell_timers::spell_id_by_name(s["spell"].as_str().unwrap_or_default()).unwrap_or(FLASH))
+        .collect();
+
+    ImportPayloadRequest {
+        boots: build["boots"]["id"].as_i64(),
+        champion_id: champion.champion_id,
+        champion_key: champion.ddragon_key.clone(),
+        items_core,
+        items_situational,
+        items_starting: ids(data.get("starting_items")),
+        patch: patch.unwrap_or("current").to_string(),
+        role: role.to_string(),
+        rune_shards,
+        runes_primary: RuneTree {
+            rune_ids: primary_runes,
+            tree_id: tree_of(keystone),
+        },
+        runes_secondary: RuneTree {
+            tree_id: secondary_runes.first().map_or(DEFAULT_SECONDARY_TREE, |&id| tree_of(id)),
+            rune_ids: secondary_runes,
+        },
+        source: "FocusApp".to_string(),
+        summoner_spells,
+        title: format!("{} {}", champion.name, role.to_uppercase()),
+    }
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Turn a FocusApi build into the request sent to `import_build_to_client`.
+///
+/// `champion` is a name, DDragon key or numeric ID. `summoner_spells`
+/// replaces the spells of the build when the user reordered them.
+#[tauri::command]
+pub async fn build_import_request(
+    build: Value,
+    champion: String,
+    role: String,
+    patch: Option<String>,
+    summoner_spells: Option<Vec<i64>>,
+) -> Result<ImportPayloadRequest, CommandError> {
+    let info = champions::champion_info(&champion)
+        .ok_or_else(|| AppError::InvalidData(format!("Unknown champion '{}'", champion.trim())))?;
+    let mut request = from_build(&build, &info, &role, patch.as_deref().filter(|p| !p.is_empty()));
+    if let Some(summoner_spells) = summoner_spells.filter(|spells| !spells.is_empty()) {
+        request.summoner_spells = summoner_spells;
+    }
+    Ok(request)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_build() {
+        let champion = ChampionInfo {
+            champion_id: 145,
+            ddragon_key: "Kaisa".to_string(),
+            name: "Kai'Sa".to_string(),
+            icon_url: String::new(),
+        };
+        let build = serde_json::json!({
+            "starting_items": [{ "id": 1055 }, { "id": 2003 }],
+            "build": {
+                "runes": [
+                    { "path": "Precision", "keystones": [{ "id": 8005, "count": 3 }, { "id": 8008, "count": 9 }],
+                      "runes": [{ "id": 9101 }, { "id": 9104 }, { "id": 8014 }] },
+                    { "path": "Domination", "keystones": [],
+                      "runes": [{ "id": 8139, "count": 2 }, { "id": 8135, "count": 7 }, { "id": 8126, "count": 5 }] },
+                ],
+                "stat_shards": [{ "id": 5001, "row": "defense" }, { "id": 5005, "row": "offense" }],
+                "items": [{ "id": 6672 }, { "id": 3124 }, { "id": 3085 }, { "id": 3031 }],
+                "boots": { "id": 3006 },
+                "summoner_spells": [{ "spell": "Heal", "count": 12 }, { "spell": "Flash", "count": 20 }],
+            },
+        });
+
+        let request = from_build(&build, &champion, "adc", Some("14.10"));
+        assert_eq!((request.runes_primary.tree_id, request.runes_primary.rune_ids), (8000, vec![8008, 9101, 9104, 8014]));
+        assert_eq!((request.runes_secondary.tree_id, request.runes_secondary.rune_ids), (8100, vec![8135, 8126]));
+        // The missing flex shard takes the row default
+        assert_eq!(request.rune_shards, vec![5005, 5008, 5001]);
+        assert_eq!((request.items_core, request.items_situational), (vec![6672, 3124, 3085], vec![3031]));
+        assert_eq!((request.items_starting, request.boots), (vec![1055, 2003], Some(3006)));
+        assert_eq!(request.summoner_spells, vec![4, 7]);
+        assert_eq!((request.title.as_str(), request.patch.as_str()), ("Kai'Sa ADC", "14.10"));
+
+        // An empty build still gives a complete request
+        let request = from_build(&serde_json::json!({}), &champion, "mid", None);
+        assert_eq!(request.runes_secondary.tree_id, DEFAULT_SECONDARY_TREE);
+        assert_eq!((request.rune_shards, request.patch), (lcu::default_stat_shards().to_vec(), "current".to_string()));
+    }
+}
@@ -18,8 +18,15 @@ use base64::Engine;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::RwLock;
+use tauri::{AppHandle, Listener, Manager};
 use thiserror::Error;
 
+use crate::events::SETTINGS_CHANGED_EVENT;
+use crate::lcu_metrics::TimedSend;
+use crate::settings::{AppSettings, SettingsStore};
+
 /// Errors that can occur when interacting with the League Client
 #[derive(Error, Debug)]
 pub enum LcuError {
@@ -40,16 +47,30 @@ pub enum LcuError {
 
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
+
+    #[error("Safe mode is on: FocusApp does not modify the League Client")]
+    SafeMode,
 }
 
 /// Connection info extracted from the League Client lockfile
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct LcuConnection {
     pub port: u16,
     pub password: String,
     pub protocol: String,
 }
 
+/// Never prints the password
+impl std::fmt::Debug for LcuConnection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LcuConnection")
+            .field("port", &self.port)
+            .field("password", &crate::redact::REDACTED)
+            .field("protocol", &self.protocol)
+            .finish()
+    }
+}
+
 impl LcuConnection {
     /// Build the base URL for League Client API requests
     pub fn base_url(&self) -> String {
@@ -151,6 +172,164 @@ pub struct ImportPayloadResponse {
     pub summoner_spells_payload: Option<SummonerSpellsPayload>,
 }
 
+/// Rune trees (Precision, Domination, Sorcery, Resolve, Inspiration)
+const RUNE_STYLE_IDS: &[i32] = &[8000, 8100, 8200, 8300, 8400];
+
+/// Perks of a full rune page: 4 primary, 2 secondary and 3 stat shards
+const RUNE_PAGE_PERK_COUNT: usize = 9;
+
+/// Runes of a page before the stat shards (4 primary, 2 secondary)
+const RUNE_COUNT: usize = 6;
+
+/// Stat shard perk IDs
+const STAT_SHARD_IDS: std::ops::Range<i32> = 5000..6000;
+
+/// Stat shards allowed in each row (offense, flex, defense), the default first
+const STAT_SHARD_ROWS: [&[i32]; 3] = [
+    // Adaptive force, attack speed, ability haste
+    &[5008, 5005, 5007],
+    // Adaptive force, move speed, scaling health
+    &[5008, 5010, 5001],
+    // Health, tenacity and slow resist, scaling health
+    &[5011, 5013, 5001],
+];
+
+/// Stat shards of a page whose build has none: the first shard of each row
+pub fn default_stat_shards() -> [i64; 3] {
+    STAT_SHARD_ROWS.map(|row| i64::from(row[0]))
+}
+
+impl RunePagePayload {
+    /// Check what the League Client would reject in the page.
+    ///
+    /// Returns the field (as in the JSON payload) and the reason.
+    pub fn validate(&self) -> Result<(), (String, String)> {
+        let invalid = |field: &str, reason: String| Err((field.to_string(), reason));
+
+        if !RUNE_STYLE_IDS.contains(&self.primary_style_id) {
+            return invalid("primaryStyleId", format!("unknown rune tree {}", self.primary_style_id));
+        }
+        if !RUNE_STYLE_IDS.contains(&self.sub_style_id) {
+            return invalid("subStyleId", format!("unknown rune tree {}", self.sub_style_id));
+        }
+        if self.sub_style_id == self.primary_style_id {
+            return invalid("subStyleId", "same tree as primaryStyleId".to_string());
+        }
+        if self.selected_perk_ids.len() != RUNE_PAGE_PERK_COUNT {
+            return invalid(
+                "selectedPerkIds",
+                format!(
+                    "expected {} perks, got {}",
+                    RUNE_PAGE_PERK_COUNT,
+                    self.selected_perk_ids.len()
+                ),
+            );
+        }
+        if let Some(index) = self.selected_perk_ids.iter().position(|id| *id <= 0) {
+            return invalid(
+                &format!("selectedPerkIds[{}]", index),
+                format!("invalid perk id {}", self.selected_perk_ids[index]),
+            );
+        }
+        Ok(())
+    }
+
+    /// Make the last three perks one valid stat shard per row.
+    ///
+    /// FocusApi sometimes sends the shards missing, doubled or in the wrong
+    /// row, which the client rejects with a bare 400. Each row takes the first
+    /// shard sent that fits it, or its default. Pages without exactly six
+    /// runes are left for `validate` to report. Returns whether the page changed.
+    pub fn normalize_stat_shards(&mut self) -> bool {
+        let (runes, mut shards): (Vec<i32>, Vec<i32>) =
+            self.selected_perk_ids.iter().partition(|id| !STAT_SHARD_IDS.contains(id));
+        if runes.len() != RUNE_COUNT {
+            return false;
+        }
+        let in_rows = shards.len() == STAT_SHARD_ROWS.len()
+            && shards.iter().zip(STAT_SHARD_ROWS).all(|(id, row)| row.contains(id));
+        if !in_rows {
+            shards = STAT_SHARD_ROWS
+                .iter()
+                .map(|row| match shards.iter().position(|id| row.contains(id)) {
+                    Some(index) => shards.remove(index),
+                    None => row[0],
+                })
+                .collect();
+        }
+        let perks: Vec<i32> = runes.into_iter().chain(shards).collect();
+        if perks == self.selected_perk_ids {
+            return false;
+        }
+        self.selected_perk_ids = perks;
+        true
+    }
+
+    /// `validate`, plus the name of a page written by the user.
+    ///
+    /// FocusApp replaces the pages named with its prefix on every import, so
+    /// a user page can't use it.
+    pub fn validate_custom(&self) -> Result<(), (String, String)> {
+        let name = self.name.trim();
+        if name.is_empty() {
+            return Err(("name".to_string(), "a rune page needs a name".to_string()));
+        }
+        if name.starts_with(FOCUS_RUNE_PAGE_PREFIX) {
+            return Err((
+                "name".to_string(),
+                format!("names starting with {} are reserved for FocusApp imports", FOCUS_RUNE_PAGE_PREFIX),
+            ));
+        }
+        self.validate()
+    }
+}
+
+impl ImportPayloadResponse {
+    /// Check what the League Client would reject, so a bad server response
+    /// is reported with the offending field instead of an LCU 400.
+    ///
+    /// Returns the field path (as in the JSON response) and the reason.
+    pub fn validate(&self) -> Result<(), (String, String)> {
+        let invalid = |field: String, reason: String| Err((field, reason));
+
+        if let Some(rune_page) = &self.rune_page_payload {
+            rune_page
+                .validate()
+                .map_err(|(field, reason)| (format!("rune_page_payload.{}", field), reason))?;
+        }
+
+        if let Some(item_set) = &self.item_set_payload {
+            for (block_index, block) in item_set.blocks.iter().enumerate() {
+                for (item_index, item) in block.items.iter().enumerate() {
+                    let field = format!("item_set_payload.blocks[{}].items[{}]", block_index, item_index);
+                    let numeric = !item.id.is_empty() && item.id.chars().all(|c| c.is_ascii_digit());
+                    if !numeric {
+                        return invalid(format!("{}.id", field), format!("'{}' is not an item id", item.id));
+                    }
+                    if item.count < 1 {
+                        return invalid(format!("{}.count", field), format!("invalid count {}", item.count));
+                    }
+                }
+            }
+        }
+
+        if let Some(spells) = &self.summoner_spells_payload {
+            let field = "summoner_spells_payload";
+            if spells.spell1_id <= 0 {
+                return invalid(format!("{}.spell1Id", field), format!("invalid spell id {}", spells.spell1_id));
+            }
+            if spells.spell2_id <= 0 {
+                return invalid(format!("{}.spell2Id", field), format!("invalid spell id {}", spells.spell2_id));
+            }
+            if spells.spell1_id == spells.spell2_id {
+                return invalid(format!("{}.spell2Id", field), "same spell as spell1Id".to_string());
+            }
+        }
+
+        Ok(())
+    }
+}
+
 /// Existing rune page from the client (for listing/deletion)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -171,40 +350,92 @@ pub struct ImportResult {
     pub items_imported: bool,
     pub summoners_imported: bool,
     pub message: String,
+    /// The import could not reach the client or FocusApi and was queued
+    #[serde(default)]
+    pub queued: bool,
 }
 
-/// Find and parse the League Client lockfile to get connection info
-///
-/// The lockfile is located at:
-/// - Windows: C:\Riot Games\League of Legends\lockfile
-/// - macOS: /Applications/League of Legends.app/Contents/LoL/lockfile
+/// Environment variable pointing at the lockfile to use
+pub const LOCKFILE_PATH_ENV: &str = "FOCUS_LOCKFILE_PATH";
+
+/// Lockfile path from the settings (None = default install locations)
+static LOCKFILE_PATH_SETTING: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+fn set_lockfile_path(path: Option<String>) {
+    let path = path
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+        .map(PathBuf::from);
+    if let Ok(mut current) = LOCKFILE_PATH_SETTING.write() {
+        *current = path;
+    }
+}
+
+/// Read-only mode: every write to the client is refused (`safeMode` setting)
+static SAFE_MODE: AtomicBool = AtomicBool::new(false);
+
+/// Fail with `SafeMode` when writes to the client are disabled.
 ///
-/// Format: processname:pid:port:password:protocol
-pub async fn find_lockfile() -> Result<LcuConnection, LcuError> {
-    // Common installation paths for the lockfile
-    let possible_paths = if cfg!(target_os = "windows") {
-        vec![
+/// Checked by every function changing the client (rune pages, item sets,
+/// summoner spells), whoever calls it.
+pub fn ensure_writable() -> Result<(), LcuError> {
+    if SAFE_MODE.load(Ordering::Relaxed) {
+        Err(LcuError::SafeMode)
+    } else {
+        Ok(())
+    }
+}
+
+/// Follow the lockfile path and safe mode saved in the settings
+pub fn init(app: &AppHandle) {
+    if let Some(store) = app.try_state::<SettingsStore>() {
+        let settings = store.get();
+        set_lockfile_path(settings.lockfile_path);
+        SAFE_MODE.store(settings.safe_mode, Ordering::Relaxed);
+    }
+    app.listen(SETTINGS_CHANGED_EVENT, |event| {
+        if let Ok(settings) = serde_json::from_str::<AppSettings>(event.payload()) {
+            set_lockfile_path(settings.lockfile_path);
+            SAFE_MODE.store(settings.safe_mode, Ordering::Relaxed);
+        }
+    });
+}
+
+/// Lockfile to use instead of the default locations: `--lockfile-path`
+/// first, then `FOCUS_LOCKFILE_PATH`, then the setting
+fn lockfile_override(cli: &Option<PathBuf>, env: Option<PathBuf>, setting: Option<PathBuf>) -> Option<PathBuf> {
+    cli.clone()
+        .or(env.filter(|path| !path.as_os_str().is_empty()))
+        .or(setting)
+}
+
+/// Lockfiles to try, in order: the override first, then the default locations
+fn lockfile_candidates(override_path: Option<PathBuf>) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = override_path.into_iter().collect();
+    if cfg!(target_os = "windows") {
+        paths.extend([
             PathBuf::from(r"C:\Riot Games\League of Legends\lockfile"),
             PathBuf::from(r"D:\Riot Games\League of Legends\lockfile"),
             // Also check via LOCALAPPDATA for Riot Client installed games
             std::env::var("LOCALAPPDATA")
                 .map(|p| PathBuf::from(p).join(r"Riot Games\League of Legends\lockfile"))
                 .unwrap_or_default(),
-        ]
+        ]);
     } else if cfg!(target_os = "macos") {
-        vec![PathBuf::from(
+        paths.push(PathBuf::from(
             "/Applications/League of Legends.app/Contents/LoL/lockfile",
-        )]
-    } else {
-        vec![]
-    };
+        ));
+    }
+    paths
+}
 
-    // Try each possible path
-    for path in possible_paths {
+/// Connection info of the first readable lockfile of `paths`
+async fn read_first_lockfile(paths: &[PathBuf]) -> Result<LcuConnection, LcuError> {
+    for path in paths {
         if path.as_os_str().is_empty() {
             continue;
         }
-        if let Ok(contents) = tokio::fs::read_to_string(&path).await {
+        if let Ok(contents) = tokio::fs::read_to_string(path).await {
             return parse_lockfile(&contents);
         }
     }
@@ -212,6 +443,25 @@ pub async fn find_lockfile() -> Result<LcuConnection, LcuError> {
     Err(LcuError::ClientNotRunning)
 }
 
+/// Find and parse the League Client lockfile to get connection info
+///
+/// The lockfile is located at:
+/// - Windows: C:\Riot Games\League of Legends\lockfile
+/// - macOS: /Applications/League of Legends.app/Contents/LoL/lockfile
+///
+/// Format: processname:pid:port:password:protocol
+///
+/// `--lockfile-path`, the `FOCUS_LOCKFILE_PATH` environment variable or the
+/// `lockfilePath` setting is tried before these locations (e.g., a fake
+/// lockfile in tests, or an unusual install layout); when it can't be read,
+/// the default locations are still tried.
+pub async fn find_lockfile() -> Result<LcuConnection, LcuError> {
+    let setting = LOCKFILE_PATH_SETTING.read().ok().and_then(|path| path.clone());
+    let env = std::env::var_os(LOCKFILE_PATH_ENV).map(PathBuf::from);
+    let override_path = lockfile_override(&crate::cli::options().lockfile_path, env, setting);
+    read_first_lockfile(&lockfile_candidates(override_path)).await
+}
+
 /// Parse the lockfile content into connection info
 fn parse_lockfile(contents: &str) -> Result<LcuConnection, LcuError> {
     let parts: Vec<&str> = contents.trim().split(':').collect();
@@ -227,6 +477,8 @@ fn parse_lockfile(contents: &str) -> Result<LcuConnection, LcuError> {
         .parse::<u16>()
         .map_err(|_| LcuError::LockfileParseError("Invalid port number".to_string()))?;
 
+    crate::redact::register_lcu_password(parts[3]);
+
     Ok(LcuConnection {
         port,
         password: parts[3].to_string(),
@@ -239,13 +491,8 @@ fn parse_lockfile(contents: &str) -> Result<LcuConnection, LcuError> {
 /// The client ignores certificate validation because the League Client
 /// uses a self-signed certificate for its local HTTPS server.
 fn create_lcu_client() -> Result<Client, LcuError> {
-    Client::builder()
-        // Accept self-signed certificates from League Client
-        .danger_accept_invalid_certs(true)
-        // Reasonable timeout for local requests
-        .timeout(std::time::Duration::from_secs(10))
-        .build()
-        .map_err(LcuError::HttpError)
+    // Shared client, built once (see http_clients)
+    crate::http_clients::lcu().map_err(LcuError::HttpError)
 }
 
 /// Get all existing rune pages from the League Client
@@ -256,7 +503,7 @@ pub async fn get_rune_pages(connection: &LcuConnection) -> Result<Vec<ExistingRu
     let response = client
         .get(&url)
         .header("Authorization", connection.auth_header())
-        .send()
+        .send_timed()
         .await?;
 
     if !response.status().is_success() {
@@ -273,13 +520,14 @@ pub async fn get_rune_pages(connection: &LcuConnection) -> Result<Vec<ExistingRu
 
 /// Delete a rune page by ID
 pub async fn delete_rune_page(connection: &LcuConnection, page_id: i64) -> Result<(), LcuError> {
+    ensure_writable()?;
     let client = create_lcu_client()?;
     let url = format!("{}/lol-perks/v1/pages/{}", connection.base_url(), page_id);
 
     let response = client
         .delete(&url)
         .header("Authorization", connection.auth_header())
-        .send()
+        .send_timed()
         .await?;
 
     if !response.status().is_success() {
@@ -311,34 +559,128 @@ pub async fn create_rune_page(
     connection: &LcuConnection,
     payload: &RunePagePayload,
 ) -> Result<(), LcuError> {
-    // Step 1: Get all existing rune pages
-    let pages = get_rune_pages(connection).await?;
+    let mut pages = delete_focus_rune_pages(connection).await?;
+    post_rune_page(connection, payload, &mut pages).await
+}
+
+/// Result of `create_rune_pages`
+#[derive(Debug)]
+pub struct CreatedRunePages {
+    /// Number of pages created: the first `created` payloads
+    pub created: usize,
+    /// Why the next page was not created (page limit or client error)
+    pub stopped_by: Option<LcuError>,
+}
+
+/// Replace the FocusApp rune pages with `payloads` (one page per role)
+///
+/// Existing FocusApp pages are deleted first. Other pages are never deleted:
+/// at the page limit (or on the first error), the remaining payloads are
+/// skipped and the result tells how many pages were created.
+pub async fn create_rune_pages(
+    connection: &LcuConnection,
+    payloads: &[RunePagePayload],
+) -> Result<CreatedRunePages, LcuError> {
+    delete_focus_rune_pages(connection).await?;
+
+    let mut created = 0;
+    for payload in payloads {
+        match post_new_rune_page(connection, payload).await {
+            Ok(()) => created += 1,
+            Err(e) => {
+                log_debug!("[create_rune_pages] Stopped after {} pages: {}", created, e);
+                return Ok(CreatedRunePages {
+                    created,
+                    stopped_by: Some(e),
+                });
+            }
+        }
+    }
+    Ok(CreatedRunePages {
+        created,
+        stopped_by: None,
+    })
+}
+
+/// Delete the existing FocusApp pages (pages starting with "⚡"), returning
+/// the remaining ones
+async fn delete_focus_rune_pages(connection: &LcuConnection) -> Result<Vec<ExistingRunePage>, LcuError> {
+    ensure_writable()?;
+    let mut pages = get_rune_pages(connection).await?;
 
-    // Step 2: Delete any existing FocusApp pages (pages starting with "⚡ FOCUS")
     for page in pages.iter() {
         if page.name.starts_with(FOCUS_RUNE_PAGE_PREFIX) && page.is_deletable {
-            #[cfg(debug_assertions)]
-            eprintln!(
+            log_debug!(
                 "[create_rune_page] Deleting existing FocusApp page: '{}' (id: {})",
                 page.name, page.id
             );
             delete_rune_page(connection, page.id).await?;
         }
     }
+    pages.retain(|p| !(p.name.starts_with(FOCUS_RUNE_PAGE_PREFIX) && p.is_deletable));
+    Ok(pages)
+}
+
+/// Create a rune page written by the user, as it is.
+///
+/// Unlike `create_rune_page`, the name is kept and no page is deleted: at the
+/// page limit, the client's error is returned.
+pub async fn create_custom_rune_page(
+    connection: &LcuConnection,
+    payload: &RunePagePayload,
+) -> Result<(), LcuError> {
+    ensure_writable()?;
+    log_debug!("[create_custom_rune_page] Creating page: '{}'", payload.name);
+    post_new_rune_page(connection, payload).await
+}
 
-    // Step 3: Create the new rune page
+/// Create one rune page without making room for it
+async fn post_new_rune_page(connection: &LcuConnection, payload: &RunePagePayload) -> Result<(), LcuError> {
     let client = create_lcu_client()?;
     let url = format!("{}/lol-perks/v1/pages", connection.base_url());
 
-    #[cfg(debug_assertions)]
-    eprintln!("[create_rune_page] Creating new page: '{}'", payload.name);
+    let response = client
+        .post(&url)
+        .header("Authorization", connection.auth_header())
+        .header("Content-Type", "application/json")
+        .json(payload)
+        .send_timed()
+        .await?;
+
+    if response.status().is_success() {
+        return Ok(());
+    }
+    let status = response.status();
+    let body = response.text().await.unwrap_or_default();
+    if body.contains("Max pages reached") {
+        return Err(LcuError::ApiError(
+            "Max rune pages reached: delete a page in the client first".to_string(),
+        ));
+    }
+    Err(LcuError::ApiError(format!(
+        "Failed to create rune page: {} - {}",
+        status, body
+    )))
+}
+
+/// Create one rune page, deleting an old editable page if the limit is reached
+async fn post_rune_page(
+    connection: &LcuConnection,
+    payload: &RunePagePayload,
+    pages: &mut Vec<ExistingRunePage>,
+) -> Result<(), LcuError> {
+    ensure_writable()?;
+    let client = create_lcu_client()?;
+    let url = format!("{}/lol-perks/v1/pages", connection.base_url());
+
+    log_debug!("[create_rune_page] Creating new page: '{}'", payload.name);
 
     let response = client
         .post(&url)
         .header("Authorization", connection.auth_header())
         .header("Content-Type", "application/json")
         .json(payload)
-        .send()
+        .send_timed()
         .await?;
 
     if response.status().is_success() {
@@ -351,11 +693,11 @@ pub async fn create_rune_page(
 
     if body.contains("Max pages reached") || status.as_u16() == 400 {
         // Try to delete any editable page (not a FocusApp one, since we already deleted those)
-        if let Some(deletable_page) = pages.iter().find(|p| {
+        if let Some(index) = pages.iter().position(|p| {
             p.is_deletable && p.is_editable && !p.name.starts_with(FOCUS_RUNE_PAGE_PREFIX)
         }) {
-            #[cfg(debug_assertions)]
-            eprintln!(
+            let deletable_page = pages.remove(index);
+            log_debug!(
                 "[create_rune_page] Max pages reached, deleting: '{}' (id: {})",
                 deletable_page.name, deletable_page.id
             );
@@ -367,7 +709,7 @@ pub async fn create_rune_page(
                 .header("Authorization", connection.auth_header())
                 .header("Content-Type", "application/json")
                 .json(payload)
-                .send()
+                .send_timed()
                 .await?;
 
             if retry_response.status().is_success() {
@@ -400,21 +742,21 @@ pub async fn set_summoner_spells(
     connection: &LcuConnection,
     payload: &SummonerSpellsPayload,
 ) -> Result<(), LcuError> {
+    ensure_writable()?;
     let client = create_lcu_client()?;
     let url = format!(
         "{}/lol-champ-select/v1/session/my-selection",
         connection.base_url()
     );
 
-    #[cfg(debug_assertions)]
-    eprintln!("[set_summoner_spells] PATCH {} with {:?}", url, payload);
+    log_debug!("[set_summoner_spells] PATCH {} with {:?}", url, payload);
 
     let response = client
         .patch(&url)
         .header("Authorization", connection.auth_header())
         .header("Content-Type", "application/json")
         .json(payload)
-        .send()
+        .send_timed()
         .await?;
 
     if response.status().is_success() {
@@ -446,7 +788,7 @@ pub async fn get_current_summoner_id(connection: &LcuConnection) -> Result<i64,
     let response = client
         .get(&url)
         .header("Authorization", connection.auth_header())
-        .send()
+        .send_timed()
         .await?;
 
     if !response.status().is_success() {
@@ -491,7 +833,7 @@ pub async fn get_item_sets(
     let response = client
         .get(&url)
         .header("Authorization", connection.auth_header())
-        .send()
+        .send_timed()
         .await?;
 
     if !response.status().is_success() {
@@ -512,6 +854,7 @@ pub async fn update_item_sets(
     summoner_id: i64,
     item_sets: &ItemSetsResponse,
 ) -> Result<(), LcuError> {
+    ensure_writable()?;
     let client = create_lcu_client()?;
     let url = format!(
         "{}/lol-item-sets/v1/item-sets/{}/sets",
@@ -524,7 +867,7 @@ pub async fn update_item_sets(
         .header("Authorization", connection.auth_header())
         .header("Content-Type", "application/json")
         .json(item_sets)
-        .send()
+        .send_timed()
         .await?;
 
     if !response.status().is_success() {
@@ -547,16 +890,25 @@ pub async fn add_item_set(
     connection: &LcuConnection,
     item_set: &ItemSetPayload,
 ) -> Result<(), LcuError> {
+    add_item_sets(connection, std::slice::from_ref(item_set)).await
+}
+
+/// Replace the FocusApp item sets with `item_sets` in a single update
+pub async fn add_item_sets(
+    connection: &LcuConnection,
+    item_sets: &[ItemSetPayload],
+) -> Result<(), LcuError> {
+    ensure_writable()?;
     let summoner_id = get_current_summoner_id(connection).await?;
     let mut sets_response = get_item_sets(connection, summoner_id).await?;
 
-    // Remove ALL existing FocusApp item sets (singleton pattern - only keep one)
+    // Remove ALL existing FocusApp item sets (only the new ones are kept)
     sets_response
         .item_sets
         .retain(|s| !s.title.starts_with(FOCUS_ITEM_SET_PREFIX));
 
-    // Add the new item set
-    sets_response.item_sets.push(item_set.clone());
+    // Add the new item sets
+    sets_response.item_sets.extend_from_slice(item_sets);
 
     // Update timestamp
     sets_response.timestamp = std::time::SystemTime::now()
@@ -586,6 +938,94 @@ pub struct ChampionSelectSession {
     /// Is spectating
     #[serde(default)]
     pub is_spectating: bool,
+    /// Bench of rerolled champions (ARAM)
+    #[serde(default)]
+    pub bench_enabled: bool,
+    /// Champions on the bench (ARAM)
+    #[serde(default)]
+    pub bench_champions: Vec<BenchChampion>,
+    /// Bench of older clients, IDs only
+    #[serde(default)]
+    pub bench_champion_ids: Vec<i64>,
+    /// Rerolls the local player has left (ARAM)
+    #[serde(default)]
+    pub rerolls_remaining: i64,
+    /// Countdown of the current phase
+    #[serde(default)]
+    pub timer: Option<ChampSelectTimerBlock>,
+    /// Champion hovered or locked by the local player (0 if none), from the actions
+    #[serde(default, skip_deserializing)]
+    pub local_champion_id: i64,
+    /// Whether the local player's pick is completed (false while only hovered)
+    #[serde(default, skip_deserializing)]
+    pub is_locked: bool,
+}
+
+impl ChampionSelectSession {
+    /// Champion of the player in `cell_id` (0 if none) and whether their pick is completed.
+    ///
+    /// `myTeam` holds the champion without telling a hover from a lock, so
+    /// the pick actions of the cell decide. Before the player's turn, the
+    /// hover is on their pick action (not in progress yet) or in their pick
+    /// intent.
+    pub fn champion_of(&self, cell_id: i64) -> (i64, bool) {
+        let member = self.my_team.iter().flatten().find(|member| member.cell_id == cell_id);
+        let member_champion = member.map(|m| m.champion_id.max(0)).unwrap_or_default();
+        let pick_actions: Vec<&ChampSelectAction> = self
+            .actions
+            .iter()
+            .flatten()
+            .flatten()
+            .filter(|action| action.actor_cell_id == cell_id && action.action_type == "pick")
+            .collect();
+        if pick_actions.is_empty() {
+            // No pick turn (e.g., ARAM): the champion is already assigned
+            return (member_champion, member_champion > 0);
+        }
+        if let Some(locked) = pick_actions.iter().find(|a| a.completed && a.champion_id > 0) {
+            return (locked.champion_id, true);
+        }
+        let hovered = pick_actions
+            .iter()
+            .find(|a| a.is_in_progress && a.champion_id > 0)
+            .or_else(|| pick_actions.iter().find(|a| !a.completed && a.champion_id > 0))
+            .map(|a| a.champion_id)
+            .or_else(|| member.map(|m| m.champion_pick_intent).filter(|&id| id > 0))
+            .unwrap_or(member_champion);
+        (hovered, false)
+    }
+
+    /// Fill `local_champion_id` and `is_locked`
+    fn with_local_pick(mut self) -> Self {
+        if let Some(cell_id) = self.local_player_cell_id.filter(|_| !self.is_spectating) {
+            (self.local_champion_id, self.is_locked) = self.champion_of(cell_id);
+        }
+        self
+    }
+}
+
+/// A champion on the ARAM bench
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchChampion {
+    pub champion_id: i64,
+    /// Flagged as a priority pick by the client
+    #[serde(default)]
+    pub is_priority: bool,
+}
+
+/// `timer` block of the champion select session
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ChampSelectTimerBlock {
+    /// "PLANNING", "BAN_PICK", "FINALIZATION" or "GAME_STARTING"
+    pub phase: String,
+    /// Time left in the phase when the client sent the session (ms)
+    pub adjusted_time_left_in_phase: i64,
+    /// Client clock when it sent the session (Unix ms)
+    pub internal_now_in_epoch_ms: i64,
+    pub total_time_in_phase: i64,
+    pub is_infinite: bool,
 }
 
 /// An action in champion select (pick or ban)
@@ -616,6 +1056,9 @@ pub struct ChampSelectTeamMember {
     pub cell_id: i64,
     /// Champion ID
     pub champion_id: i64,
+    /// Champion hovered before the member's pick turn (0 if none)
+    #[serde(default)]
+    pub champion_pick_intent: i64,
     /// Assigned position (e.g., "TOP", "JUNGLE", "MIDDLE", "BOTTOM", "UTILITY")
     pub assigned_position: Option<String>,
     /// Summoner ID
@@ -642,7 +1085,7 @@ pub async fn get_champion_select_session(
     let response = client
         .get(&url)
         .header("Authorization", connection.auth_header())
-        .send()
+        .send_timed()
         .await?;
 
     if !response.status().is_success() {
@@ -654,7 +1097,11 @@ pub async fn get_champion_select_session(
         )));
     }
 
-    response.json().await.map_err(LcuError::HttpError)
+    response
+        .json()
+        .await
+        .map(ChampionSelectSession::with_local_pick)
+        .map_err(LcuError::HttpError)
 }
 
 // =============================================================================
@@ -731,6 +1178,9 @@ pub struct QueueInfo {
     pub is_ranked: bool,
     #[serde(rename = "type", default)]
     pub queue_type: String,
+    /// Game mode of the queue (e.g., "CLASSIC", "ARAM", "PRACTICETOOL")
+    #[serde(default)]
+    pub game_mode: String,
 }
 
 /// Current summoner data from the LCU API.
@@ -745,6 +1195,33 @@ pub struct CurrentSummoner {
     pub summoner_level: i64,
     #[serde(default)]
     pub profile_icon_id: i32,
+    /// Riot ID game name (e.g., "Faker" in "Faker#KR1")
+    #[serde(default)]
+    pub game_name: String,
+    /// Riot ID tag line (e.g., "KR1" in "Faker#KR1")
+    #[serde(default)]
+    pub tag_line: String,
+}
+
+impl CurrentSummoner {
+    /// Check whether a player name from the Live Client API designates this summoner.
+    ///
+    /// The Live Client API reports either the full Riot ID ("Name#TAG") or,
+    /// on older clients, the legacy summoner name. Comparison is case-insensitive.
+    pub fn matches_player_name(&self, player_name: &str) -> bool {
+        let player_name = player_name.trim();
+        if player_name.is_empty() {
+            return false;
+        }
+
+        let riot_id = format!("{}#{}", self.game_name, self.tag_line);
+        let candidates = [riot_id.as_str(), self.game_name.as_str(), self.display_name.as_str()];
+
+        candidates
+            .iter()
+            .filter(|c| !c.is_empty() && **c != "#")
+            .any(|c| c.eq_ignore_ascii_case(player_name))
+    }
 }
 
 /// Get the current gameflow session from the League Client.
@@ -765,7 +1242,7 @@ pub async fn get_gameflow_session(
     let response = client
         .get(&url)
         .header("Authorization", connection.auth_header())
-        .send()
+        .send_timed()
         .await?;
 
     if !response.status().is_success() {
@@ -780,6 +1257,38 @@ pub async fn get_gameflow_session(
     response.json().await.map_err(LcuError::HttpError)
 }
 
+/// Get the game version installed with the League Client (e.g., "14.10.584.1234").
+///
+/// # Compliance Note
+/// - Uses official LCU endpoint /lol-patch/v1/game-version
+/// - Read-only operation, used to detect new patches
+pub async fn get_client_game_version(
+    connection: &LcuConnection,
+) -> Result<String, LcuError> {
+    let client = create_lcu_client()?;
+    let url = format!(
+        "{}/lol-patch/v1/game-version",
+        connection.base_url()
+    );
+
+    let response = client
+        .get(&url)
+        .header("Authorization", connection.auth_header())
+        .send_timed()
+        .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(LcuError::ApiError(format!(
+            "Game version error: {} - {}",
+            status, body
+        )));
+    }
+
+    response.json().await.map_err(LcuError::HttpError)
+}
+
 /// Get the current summoner (logged-in user) from the League Client.
 ///
 /// # Compliance Note
@@ -798,7 +1307,7 @@ pub async fn get_current_summoner(
     let response = client
         .get(&url)
         .header("Authorization", connection.auth_header())
-        .send()
+        .send_timed()
         .await?;
 
     if !response.status().is_success() {
@@ -813,6 +1322,163 @@ pub async fn get_current_summoner(
     response.json().await.map_err(LcuError::HttpError)
 }
 
+// =============================================================================
+// END OF GAME - Post-game stats block
+// =============================================================================
+
+/// End-of-game stats block from the LCU API (subset used by FocusApp).
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct EogStatsBlock {
+    #[serde(default)]
+    pub game_id: i64,
+    /// Game length in seconds
+    #[serde(default)]
+    pub game_length: i64,
+    #[serde(default)]
+    pub local_player: Option<EogPlayer>,
+    #[serde(default)]
+    pub teams: Vec<EogTeam>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct EogPlayer {
+    #[serde(default)]
+    pub team_id: i64,
+    #[serde(default)]
+    pub champion_id: i64,
+    /// Raw stat values keyed by name (e.g., "WIN", "LOSE", "MINIONS_KILLED")
+    #[serde(default)]
+    pub stats: std::collections::HashMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct EogTeam {
+    #[serde(default)]
+    pub team_id: i64,
+    #[serde(default)]
+    pub is_winning_team: bool,
+    #[serde(default)]
+    pub players: Vec<EogPlayer>,
+}
+
+impl EogPlayer {
+    /// Numeric value of a stat
+    pub fn stat(&self, name: &str) -> Option<f64> {
+        self.stats.get(name).and_then(|v| v.as_f64())
+    }
+
+    /// Gold earned during the game (GOLD_EARNED stat)
+    pub fn gold_earned(&self) -> Option<f64> {
+        self.stat("GOLD_EARNED")
+    }
+}
+
+impl EogStatsBlock {
+    /// Whether the local player won, if it can be determined.
+    ///
+    /// Uses the team flags first and falls back to the WIN/LOSE stats.
+    pub fn local_player_won(&self) -> Option<bool> {
+        let player = self.local_player.as_ref()?;
+
+        if let Some(team) = self.teams.iter().find(|t| t.team_id == player.team_id) {
+            return Some(team.is_winning_team);
+        }
+
+        let stat = |name: &str| player.stats.get(name).and_then(|v| v.as_i64()).unwrap_or(0);
+        match (stat("WIN"), stat("LOSE")) {
+            (w, _) if w > 0 => Some(true),
+            (_, l) if l > 0 => Some(false),
+            _ => None,
+        }
+    }
+
+    /// Total of a stat over the local player's team.
+    pub fn local_team_total(&self, name: &str) -> Option<f64> {
+        let player = self.local_player.as_ref()?;
+        let team = self.teams.iter().find(|t| t.team_id == player.team_id)?;
+        Some(team.players.iter().filter_map(|p| p.stat(name)).sum())
+    }
+
+    /// Share of a stat of their team held by the local player.
+    pub fn local_share(&self, name: &str) -> Option<f64> {
+        let value = self.local_player.as_ref()?.stat(name)?;
+        let team_total = self.local_team_total(name)?;
+        (team_total > 0.0).then(|| value / team_total)
+    }
+
+    /// Share of the gold of their team earned by the local player.
+    pub fn local_gold_share(&self) -> Option<f64> {
+        self.local_share("GOLD_EARNED")
+    }
+}
+
+/// Get the end-of-game stats block of the last game.
+///
+/// # Compliance Note
+/// - Uses official LCU endpoint /lol-end-of-game/v1/eog-stats-block
+/// - Read-only, only available after the game has ended
+pub async fn get_eog_stats_block(connection: &LcuConnection) -> Result<EogStatsBlock, LcuError> {
+    let client = create_lcu_client()?;
+    let url = format!(
+        "{}/lol-end-of-game/v1/eog-stats-block",
+        connection.base_url()
+    );
+
+    let response = client
+        .get(&url)
+        .header("Authorization", connection.auth_header())
+        .send_timed()
+        .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(LcuError::ApiError(format!(
+            "End of game stats error: {} - {}",
+            status, body
+        )));
+    }
+
+    response.json().await.map_err(LcuError::HttpError)
+}
+
+/// Look up a summoner by Riot ID ("Name#TAG") or legacy summoner name.
+///
+/// # Compliance Note
+/// - Uses official LCU endpoint /lol-summoner/v1/summoners?name=
+/// - Returns public profile information only
+pub async fn get_summoner_by_name(
+    connection: &LcuConnection,
+    name: &str,
+) -> Result<CurrentSummoner, LcuError> {
+    let client = create_lcu_client()?;
+    let url = format!(
+        "{}/lol-summoner/v1/summoners?name={}",
+        connection.base_url(),
+        urlencoding::encode(name)
+    );
+
+    let response = client
+        .get(&url)
+        .header("Authorization", connection.auth_header())
+        .send_timed()
+        .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(LcuError::ApiError(format!(
+            "Summoner lookup error: {} - {}",
+            status, body
+        )));
+    }
+
+    response.json().await.map_err(LcuError::HttpError)
+}
+
 /// Get only the gameflow phase (lightweight check).
 /// Returns the phase as a string for simpler frontend handling.
 pub async fn get_gameflow_phase(
@@ -836,6 +1502,27 @@ mod tests {
         assert_eq!(connection.protocol, "https");
     }
 
+    #[test]
+    fn test_lockfile_override() {
+        let cli = Some(PathBuf::from("cli/lockfile"));
+        let env = || Some(PathBuf::from("env/lockfile"));
+        let setting = || Some(PathBuf::from("setting/lockfile"));
+        assert_eq!(lockfile_override(&cli, env(), setting()), cli);
+        assert_eq!(lockfile_override(&None, env(), setting()), env());
+        assert_eq!(lockfile_override(&None, Some(PathBuf::new()), setting()), setting());
+        assert_eq!(lockfile_override(&None, None, None), None);
+
+        // The override is read first, the default locations tried after it
+        let path = std::env::temp_dir().join(format!("focusapp-lockfile-{}", std::process::id()));
+        std::fs::write(&path, "LeagueClient:1:4242:fakepassword:http").unwrap();
+        let missing = path.with_extension("missing");
+        assert_eq!(lockfile_candidates(Some(path.clone()))[0], path);
+        assert_eq!(lockfile_candidates(Some(path.clone())).len(), lockfile_candidates(None).len() + 1);
+        let connection = crate::mock_server::block_on(read_first_lockfile(&[missing, path.clone()]));
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(connection.unwrap().port, 4242);
+    }
+
     #[test]
     fn test_auth_header() {
         let connection = LcuConnection {
@@ -847,4 +1534,148 @@ mod tests {
         let auth = connection.auth_header();
         assert!(auth.starts_with("Basic "));
     }
+
+    #[test]
+    fn test_eog_local_player_won() {
+        let block: EogStatsBlock = serde_json::from_str(
+            r#"{
+                "gameId": 42,
+                "localPlayer": { "teamId": 200, "stats": { "LOSE": 1 } },
+                "teams": [
+                    { "teamId": 100, "isWinningTeam": true },
+                    { "teamId": 200, "isWinningTeam": false }
+                ]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(block.local_player_won(), Some(false));
+
+        let block: EogStatsBlock = serde_json::from_str(
+            r#"{ "gameId": 42, "localPlayer": { "teamId": 100, "stats": { "WIN": 1 } } }"#,
+        )
+        .unwrap();
+        assert_eq!(block.local_player_won(), Some(true));
+        assert_eq!(block.local_gold_share(), None);
+
+        let block: EogStatsBlock = serde_json::from_str(
+            r#"{
+                "localPlayer": { "teamId": 100, "stats": { "GOLD_EARNED": 12000 } },
+                "teams": [{
+                    "teamId": 100,
+                    "players": [
+                        { "stats": { "GOLD_EARNED": 12000 } },
+                        { "stats": { "GOLD_EARNED": 8000 } },
+                        { "stats": { "GOLD_EARNED": 10000 } }
+                    ]
+                }]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(block.local_gold_share(), Some(0.4));
+    }
+
+    #[test]
+    fn test_validate_import_payload() {
+        let mut response: ImportPayloadResponse = serde_json::from_str(
+            r#"{
+                "champion": "Ahri",
+                "role": "mid",
+                "rune_page_payload": {
+                    "name": "Ahri mid",
+                    "primaryStyleId": 8200,
+                    "subStyleId": 8300,
+                    "selectedPerkIds": [8112, 8143, 8138, 8106, 8304, 8347, 5008, 5008, 5001]
+                },
+                "item_set_payload": {
+                    "title": "Ahri mid",
+                    "associatedChampions": [103],
+                    "associatedMaps": [11],
+                    "blocks": [{ "type": "Core", "items": [{ "id": "6655", "count": 1 }] }]
+                },
+                "summoner_spells_payload": { "spell1Id": 4, "spell2Id": 14 }
+            }"#,
+        )
+        .unwrap();
+        assert!(response.validate().is_ok());
+
+        response.item_set_payload.as_mut().unwrap().blocks[0].items[0].id = "Luden".to_string();
+        let (field, _) = response.validate().unwrap_err();
+        assert_eq!(field, "item_set_payload.blocks[0].items[0].id");
+
+        response.rune_page_payload.as_mut().unwrap().selected_perk_ids.truncate(7);
+        let (field, reason) = response.validate().unwrap_err();
+        assert_eq!(field, "rune_page_payload.selectedPerkIds");
+        assert_eq!(reason, "expected 9 perks, got 7");
+    }
+
+    #[test]
+    fn test_normalize_stat_shards() {
+        let runes = [8112, 8139, 8138, 8135, 8226, 8237];
+        let mut page = RunePagePayload {
+            name: "Ahri mid".to_string(),
+            primary_style_id: 8100,
+            sub_style_id: 8200,
+            selected_perk_ids: runes.iter().copied().chain([5008, 5008, 5001]).collect(),
+            current: None,
+        };
+        let shards = |page: &RunePagePayload| page.selected_perk_ids[RUNE_COUNT..].to_vec();
+        assert!(!page.normalize_stat_shards());
+
+        // Missing: defaults
+        page.selected_perk_ids.truncate(7);
+        assert!(page.normalize_stat_shards());
+        assert_eq!(shards(&page), [5008, 5008, 5011]);
+
+        // Doubled and out of order: one per row
+        page.selected_perk_ids = runes.iter().copied().chain([5013, 5007, 5007, 5010, 5013]).collect();
+        assert!(page.normalize_stat_shards());
+        assert_eq!(shards(&page), [5007, 5010, 5013]);
+        assert!(page.validate().is_ok());
+
+        // A rune missing: nothing to fix here
+        page.selected_perk_ids.remove(0);
+        assert!(!page.normalize_stat_shards());
+    }
+
+    #[test]
+    fn test_validate_custom_rune_page() {
+        let mut page = RunePagePayload {
+            name: "My Ahri".to_string(),
+            primary_style_id: 8100,
+            sub_style_id: 8200,
+            selected_perk_ids: vec![8112, 8139, 8138, 8135, 8226, 8237, 5008, 5008, 5001],
+            current: Some(true),
+        };
+        assert!(page.validate_custom().is_ok());
+
+        page.name = format!("{}Ahri MID", FOCUS_RUNE_PAGE_PREFIX);
+        assert_eq!(page.validate_custom().unwrap_err().0, "name");
+        page.name = "  ".to_string();
+        assert_eq!(page.validate_custom().unwrap_err().0, "name");
+
+        page.name = "My Ahri".to_string();
+        page.sub_style_id = 8100;
+        assert_eq!(page.validate_custom().unwrap_err().0, "subStyleId");
+    }
+
+    #[test]
+    fn test_matches_player_name() {
+        let summoner: CurrentSummoner = serde_json::from_str(
+            r#"{
+                "summonerId": 1,
+                "accountId": 2,
+                "puuid": "abc",
+                "displayName": "OldName",
+                "gameName": "Focus",
+                "tagLine": "EUW"
+            }"#,
+        )
+        .unwrap();
+
+        assert!(summoner.matches_player_name("Focus#EUW"));
+        assert!(summoner.matches_player_name("focus#euw"));
+        assert!(summoner.matches_player_name("OldName"));
+        assert!(!summoner.matches_player_name("Someone#EUW"));
+        assert!(!summoner.matches_player_name(""));
+    }
 }
@@ -0,0 +1,163 @@
+//! Latency and failure counters of the League Client API.
+//!
+//! Every LCU request is sent with `send_timed()` instead of `send()`, which
+//! records, per endpoint ("GET /lol-perks/v1/pages/{id}"), the number of
+//! requests, transport failures (timeout, connection refused), non-2xx
+//! answers and the response times. `get_lcu_metrics` returns the counters
+//! since launch, to tell "FocusApp is slow" from "the client itself is
+//! lagging" in bug reports. Counters stay in memory and are never sent.
+
+use reqwest::{Method, RequestBuilder, Response};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::sessions::now_unix_secs;
+
+/// Counters of one endpoint
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EndpointMetrics {
+    pub endpoint: String,
+    pub requests: u64,
+    /// No answer (timeout, connection refused, ...)
+    pub failures: u64,
+    /// Answered with a non-2xx status
+    pub http_errors: u64,
+    pub avg_ms: f64,
+    pub max_ms: u64,
+    pub last_ms: u64,
+    #[serde(skip)]
+    total_ms: u64,
+}
+
+/// Result of `get_lcu_metrics`
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LcuMetrics {
+    /// Unix timestamp (seconds) of the first request counted
+    pub since: i64,
+    pub endpoints: Vec<EndpointMetrics>,
+}
+
+/// How a request ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    Success,
+    HttpError,
+    Failure,
+}
+
+#[derive(Debug, Default)]
+struct Metrics {
+    since: i64,
+    endpoints: BTreeMap<String, EndpointMetrics>,
+}
+
+impl Metrics {
+    fn record(&mut self, endpoint: &str, elapsed: Duration, outcome: Outcome, now: i64) {
+        if self.endpoints.is_empty() {
+            self.since = now;
+        }
+        let stats = self.endpoints.entry(endpoint.to_string()).or_insert_with(|| EndpointMetrics {
+            endpoint: endpoint.to_string(),
+            ..Default::default()
+        });
+        let ms = elapsed.as_millis() as u64;
+        stats.requests += 1;
+        stats.total_ms += ms;
+        stats.avg_ms = stats.total_ms as f64 / stats.requests as f64;
+        stats.max_ms = stats.max_ms.max(ms);
+        stats.last_ms = ms;
+        match outcome {
+            Outcome::Success => {}
+            Outcome::HttpError => stats.http_errors += 1,
+            Outcome::Failure => stats.failures += 1,
+        }
+    }
+}
+
+static METRICS: Mutex<Metrics> = Mutex::new(Metrics {
+    since: 0,
+    endpoints: BTreeMap::new(),
+});
+
+/// Endpoint of a request: IDs in the path are replaced with `{id}`
+fn endpoint_key(method: &Method, path: &str) -> String {
+    let path: Vec<&str> = path
+        .split('/')
+        .map(|segment| {
+            if !segment.is_empty() && segment.chars().all(|c| c.is_ascii_digit()) {
+                "{id}"
+            } else {
+                segment
+            }
+        })
+        .collect();
+    format!("{} {}", method, path.join("/"))
+}
+
+/// `send()` that records the latency and outcome of the request
+pub trait TimedSend {
+    async fn send_timed(self) -> Result<Response, reqwest::Error>;
+}
+
+impl TimedSend for RequestBuilder {
+    async fn send_timed(self) -> Result<Response, reqwest::Error> {
+        let (client, request) = self.build_split();
+        let request = request?;
+        let endpoint = endpoint_key(request.method(), request.url().path());
+
+        let start = Instant::now();
+        let result = client.execute(request).await;
+        let outcome = match &result {
+            Ok(response) if response.status().is_success() => Outcome::Success,
+            Ok(_) => Outcome::HttpError,
+            Err(_) => Outcome::Failure,
+        };
+        if let Ok(mut metrics) = METRICS.lock() {
+            metrics.record(&endpoint, start.elapsed(), outcome, now_unix_secs());
+        }
+        result
+    }
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the latency and failure counters of each LCU endpoint since launch.
+#[tauri::command]
+pub async fn get_lcu_metrics() -> Result<LcuMetrics, String> {
+    let metrics = METRICS.lock().map_err(|e| e.to_string())?;
+    Ok(LcuMetrics {
+        since: metrics.since,
+        endpoints: metrics.endpoints.values().cloned().collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endpoint_metrics() {
+        assert_eq!(endpoint_key(&Method::DELETE, "/lol-perks/v1/pages/1234"), "DELETE /lol-perks/v1/pages/{id}");
+        assert_eq!(
+            endpoint_key(&Method::GET, "/lol-item-sets/v1/item-sets/42/sets"),
+            "GET /lol-item-sets/v1/item-sets/{id}/sets"
+        );
+
+        let mut metrics = Metrics::default();
+        let endpoint = "GET /lol-perks/v1/pages";
+        metrics.record(endpoint, Duration::from_millis(10), Outcome::Success, 100);
+        metrics.record(endpoint, Duration::from_millis(30), Outcome::HttpError, 200);
+        metrics.record(endpoint, Duration::from_millis(20), Outcome::Failure, 300);
+
+        let stats = &metrics.endpoints[endpoint];
+        assert_eq!(metrics.since, 100);
+        assert_eq!((stats.requests, stats.http_errors, stats.failures), (3, 1, 1));
+        assert_eq!((stats.avg_ms, stats.max_ms, stats.last_ms), (20.0, 30, 20));
+    }
+}
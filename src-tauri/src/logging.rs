@@ -0,0 +1,246 @@
+//! Runtime log level and log file.
+//!
+//! Log lines go to stderr and to `focusapp.log` in the app log directory,
+//! filtered by the level saved in the settings (`logLevel`). Support can ask
+//! a user to switch to `debug`, reproduce the issue and send the file,
+//! without a special build. The file is rotated to `focusapp.log.old` once
+//! it grows past `MAX_LOG_SIZE`. The last `RECENT_LINE_COUNT` lines are also
+//! kept in memory for the crash logs.
+//!
+//! The `log_error!`, `log_warn!`, `log_info!` and `log_debug!` macros take
+//! the same arguments as `eprintln!` and are available in every module.
+//! Lines are passed through `redact::redact` before being written.
+
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use tauri::{AppHandle, Listener, Manager};
+
+use crate::events::SETTINGS_CHANGED_EVENT;
+use crate::settings::{AppSettings, SettingsStore};
+
+/// Log file name, in the app log directory
+const LOG_FILE: &str = "focusapp.log";
+
+/// Size above which the log file is rotated
+const MAX_LOG_SIZE: u64 = 5 * 1024 * 1024;
+
+/// Lines kept in memory for the crash logs
+const RECENT_LINE_COUNT: usize = 200;
+
+/// Verbosity of the logs
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Error = 0,
+    Warn = 1,
+    Info = 2,
+    Debug = 3,
+}
+
+impl Default for LogLevel {
+    /// Debug builds keep logging everything
+    fn default() -> Self {
+        if cfg!(debug_assertions) {
+            LogLevel::Debug
+        } else {
+            LogLevel::Info
+        }
+    }
+}
+
+impl LogLevel {
+    fn from_u8(value: u8) -> Self {
+        match value {
+            0 => LogLevel::Error,
+            1 => LogLevel::Warn,
+            2 => LogLevel::Info,
+            _ => LogLevel::Debug,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            LogLevel::Error => "ERROR",
+            LogLevel::Warn => "WARN",
+            LogLevel::Info => "INFO",
+            LogLevel::Debug => "DEBUG",
+        }
+    }
+}
+
+/// Current level
+static LEVEL: AtomicU8 = AtomicU8::new(if cfg!(debug_assertions) { 3 } else { 2 });
+
+/// Open log file (None until `init`, or if it cannot be written)
+static LOG_FILE_HANDLE: Mutex<Option<(PathBuf, File)>> = Mutex::new(None);
+
+/// Last lines written, oldest first
+static RECENT_LINES: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+pub fn set_level(level: LogLevel) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+pub fn level() -> LogLevel {
+    LogLevel::from_u8(LEVEL.load(Ordering::Relaxed))
+}
+
+/// Whether lines of `level` are written
+pub fn enabled(level: LogLevel) -> bool {
+    level <= self::level()
+}
+
+/// Rotate the file if it is too big, then append to it
+fn append_to_file(line: &str) {
+    let Ok(mut handle) = LOG_FILE_HANDLE.lock() else {
+        return;
+    };
+    let Some((path, file)) = handle.as_mut() else {
+        return;
+    };
+
+    if file.metadata().map(|m| m.len()).unwrap_or(0) > MAX_LOG_SIZE {
+        let _ = std::fs::rename(&*path, path.with_extension("log.old"));
+        match OpenOptions::new().create(true).append(true).open(&*path) {
+            Ok(new_file) => *file = new_file,
+            Err(_) => {
+                *handle = None;
+                return;
+            }
+        }
+    }
+    let _ = writeln!(file, "{}", line);
+}
+
+/// Write a log line (use the `log_*!` macros)
+pub fn write(level: LogLevel, args: std::fmt::Arguments) {
+    if !enabled(level) {
+        return;
+    }
+    let line = format!(
+        "{} {:<5} {}",
+        crate::sessions::now_unix_secs(),
+        level.label(),
+        crate::redact::redact(&args.to_string())
+    );
+    eprintln!("{}", line);
+    append_to_file(&line);
+    if let Ok(mut recent) = RECENT_LINES.lock() {
+        if recent.len() >= RECENT_LINE_COUNT {
+            recent.pop_front();
+        }
+        recent.push_back(line);
+    }
+}
+
+/// Last lines written, oldest first.
+///
+/// Empty if another thread is writing a line: called from the panic hook,
+/// which must not wait on a lock the panicking thread may hold.
+pub fn recent_lines() -> Vec<String> {
+    RECENT_LINES
+        .try_lock()
+        .map(|recent| recent.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Stop writing to the log file (before its directory is deleted)
+pub fn close_file() {
+    if let Ok(mut handle) = LOG_FILE_HANDLE.lock() {
+        *handle = None;
+    }
+}
+
+/// Path of the log file
+fn log_file_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app.path().app_log_dir().map_err(|e| e.to_string())?;
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir.join(LOG_FILE))
+}
+
+/// Open the log file and follow the level saved in the settings
+pub fn init(app: &AppHandle) {
+    match log_file_path(app).and_then(|path| {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| e.to_string())?;
+        Ok((path, file))
+    }) {
+        Ok(log_file) => {
+            if let Ok(mut handle) = LOG_FILE_HANDLE.lock() {
+                *handle = Some(log_file);
+            }
+        }
+        Err(e) => eprintln!("[logging] Cannot open the log file: {}", e),
+    }
+
+    // `--log-level` wins over the saved level for the whole session
+    let cli_level = crate::cli::options().log_level;
+    if let Some(store) = app.try_state::<SettingsStore>() {
+        set_level(cli_level.unwrap_or(store.get().log_level));
+    }
+    app.listen(SETTINGS_CHANGED_EVENT, move |event| {
+        if let Ok(settings) = serde_json::from_str::<AppSettings>(event.payload()) {
+            set_level(cli_level.unwrap_or(settings.log_level));
+        }
+    });
+}
+
+macro_rules! log_error {
+    ($($arg:tt)*) => { $crate::logging::write($crate::logging::LogLevel::Error, format_args!($($arg)*)) };
+}
+
+macro_rules! log_warn {
+    ($($arg:tt)*) => { $crate::logging::write($crate::logging::LogLevel::Warn, format_args!($($arg)*)) };
+}
+
+macro_rules! log_info {
+    ($($arg:tt)*) => { $crate::logging::write($crate::logging::LogLevel::Info, format_args!($($arg)*)) };
+}
+
+macro_rules! log_debug {
+    ($($arg:tt)*) => { $crate::logging::write($crate::logging::LogLevel::Debug, format_args!($($arg)*)) };
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Change the log level (saved in the settings).
+#[tauri::command]
+pub async fn set_log_level(
+    app: AppHandle,
+    store: tauri::State<'_, SettingsStore>,
+    level: LogLevel,
+) -> Result<LogLevel, String> {
+    store.update(&app, serde_json::json!({ "logLevel": level }))?;
+    set_level(level);
+    Ok(level)
+}
+
+/// Path of the log file, to attach to a bug report.
+#[tauri::command]
+pub async fn get_log_file_path(app: AppHandle) -> Result<String, String> {
+    Ok(log_file_path(&app)?.to_string_lossy().into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_level_order() {
+        assert!(LogLevel::Error < LogLevel::Debug);
+        assert_eq!(serde_json::to_value(LogLevel::Warn).unwrap(), serde_json::json!("warn"));
+        for level in [LogLevel::Error, LogLevel::Warn, LogLevel::Info, LogLevel::Debug] {
+            assert_eq!(LogLevel::from_u8(level as u8), level);
+        }
+    }
+}
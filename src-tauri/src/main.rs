@@ -10,7 +10,8 @@
 //! - Does NOT automate gameplay or provide unfair competitive advantages
 //! - Does NOT send keyboard/mouse inputs to the game client
 //! - ONLY uses the officially supported local HTTP API endpoints
-//! - All actions require EXPLICIT user interaction (button clicks)
+//! - All actions require EXPLICIT user interaction (button clicks); a build
+//!   the user imported while offline is applied once the client is back
 //! - The same configurations can be set manually in the client
 //!
 //! Reference: https://support-leagueoflegends.riotgames.com/hc/en-us/articles/225266848-Third-Party-Applications
@@ -26,35 +27,91 @@
     windows_subsystem = "windows"
 )]
 
+// Déclaré en premier : les macros log_*! sont utilisables dans tous les modules
+#[macro_use]
+mod logging;
+mod aram;
+mod assets;
+mod auth;
+mod auto_import;
+mod autostart;
+mod ban_suggestions;
+mod build_codes;
+mod build_diff;
+mod build_feedback;
+mod build_providers;
+mod build_templates;
+mod bulk_import;
+mod cancel;
+mod champ_select_timer;
 mod champions;
+mod cli;
+mod client_api;
+mod cloud_sync;
+mod crash_logs;
+mod credentials;
+mod cs_goals;
+mod daily_goals;
+mod data_export;
+mod db;
+mod deep_link;
+mod error;
+mod etag_cache;
+mod events;
+mod favorites;
+mod focus_api;
+mod game_reports;
 mod game_watcher;
+mod health;
+mod http_clients;
+mod import_cache;
+mod import_history;
+mod import_request;
 mod lcu;
+mod lcu_metrics;
+mod matchups;
+mod messages;
+#[cfg(test)]
+mod mock_server;
+mod notifications;
+mod objectives;
 mod overlay;
+mod patch_cache;
+mod pending_imports;
+mod personal_bests;
+mod prefetch;
+mod proxy;
+mod queues;
+mod rank_benchmarks;
+mod redact;
+mod reimport;
+mod roles;
+mod scoreboard;
+mod sessions;
+mod settings;
+mod snapshot;
+mod spell_timers;
+mod static_data;
+mod stats_export;
+mod storage;
+mod team_picks;
+mod telemetry;
+mod tray;
+mod trends;
+mod updater;
 
 use game_watcher::{get_game_state, refresh_game_state, start_game_watcher, stop_game_watcher, GameWatcher};
 use lcu::{
-    add_item_set, create_rune_page, find_lockfile, get_champion_select_session,
+    find_lockfile, get_champion_select_session,
     get_current_summoner, get_gameflow_session, set_summoner_spells,
     ChampionSelectSession, CurrentSummoner, GameflowSession, ImportPayloadResponse,
-    ImportResult, LcuError, SummonerSpellsPayload, FOCUS_ITEM_SET_PREFIX, FOCUS_RUNE_PAGE_PREFIX,
+    ImportResult, RunePagePayload, SummonerSpellsPayload, FOCUS_ITEM_SET_PREFIX, FOCUS_RUNE_PAGE_PREFIX,
 };
+use build_providers::BuildProvider;
+use client_api::LcuApi;
+use error::AppError;
 use serde::{Deserialize, Serialize};
-use std::panic;
-
-/// API configuration
-const FOCUS_API_BASE_URL: &str = "https://api.hommet.ch/api/v1";
-
-/// API key embedded at compile time via `FOCUS_API_KEY` env var.
-/// Build with: FOCUS_API_KEY=your_key cargo tauri build
-const FOCUS_API_KEY: &str = match option_env!("FOCUS_API_KEY") {
-    Some(key) => key,
-    None => "",
-};
-
-#[tauri::command]
-fn get_env_api_key() -> String {
-    FOCUS_API_KEY.to_string()
-}
+use tauri::Manager;
 
 /// Runes primary/secondary structure for API request
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -85,35 +142,28 @@ pub struct ImportPayloadRequest {
 }
 
 /// Error type for Tauri commands
-#[derive(Debug, Serialize)]
+#[derive(Debug)]
 pub struct CommandError {
     pub code: String,
     pub message: String,
+    /// Seconds before FocusApi accepts requests again (`RATE_LIMITED` only)
+    pub retry_after_secs: Option<u64>,
 }
 
-impl From<LcuError> for CommandError {
-    fn from(err: LcuError) -> Self {
-        let code = match &err {
-            LcuError::ClientNotRunning => "CLIENT_NOT_RUNNING",
-            LcuError::LockfileNotFound(_) => "LOCKFILE_NOT_FOUND",
-            LcuError::LockfileParseError(_) => "LOCKFILE_PARSE_ERROR",
-            LcuError::HttpError(_) => "HTTP_ERROR",
-            LcuError::ApiError(_) => "API_ERROR",
-            LcuError::IoError(_) => "IO_ERROR",
-        };
-        CommandError {
-            code: code.to_string(),
-            message: err.to_string(),
-        }
-    }
-}
-
-impl From<reqwest::Error> for CommandError {
-    fn from(err: reqwest::Error) -> Self {
-        CommandError {
-            code: "HTTP_ERROR".to_string(),
-            message: format!("HTTP request failed: {}", err),
+/// Sent to the frontend as `{ code, message, detail }`: `message` comes from
+/// the localized catalog, `detail` is the redacted technical message.
+/// `RATE_LIMITED` errors also carry `retryAfterSecs`.
+impl Serialize for CommandError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+        let mut state = serializer.serialize_struct("CommandError", 4)?;
+        state.serialize_field("code", &self.code)?;
+        state.serialize_field("message", messages::localized(&self.code))?;
+        state.serialize_field("detail", &redact::redact(&self.message))?;
+        if let Some(retry_after_secs) = self.retry_after_secs {
+            state.serialize_field("retryAfterSecs", &retry_after_secs)?;
         }
+        state.end()
     }
 }
 
@@ -124,6 +174,10 @@ impl From<reqwest::Error> for CommandError {
 /// This command is designed to comply with Riot Games' third-party application policy:
 /// - It is ONLY triggered by explicit user action (clicking the "Import Build" button)
 /// - It makes a SINGLE request per user action (no loops, no spam, no automation)
+/// - A build queued because the client or FocusApi was unreachable is applied
+///   later by `pending_imports`, in the background, once the client is
+///   reachable again; it is still the one import the user asked for, and it
+///   goes through `InFlightImport` so `cancel_import` can stop it
 /// - It only uses the official League Client local API endpoints
 /// - It does NOT send any keyboard or mouse inputs
 /// - The data imported can be manually configured in the client
@@ -136,39 +190,103 @@ impl From<reqwest::Error> for CommandError {
 ///
 /// * `Ok(ImportResult)` - Success with details of what was imported
 /// * `Err(CommandError)` - Error with code and message for the frontend to display
+///
+/// When the client or FocusApi is unreachable, the build is queued (see
+/// `pending_imports`) and the result has `queued` set.
 #[tauri::command]
 async fn import_build_to_client(
+    app: tauri::AppHandle,
+    in_flight: tauri::State<'_, cancel::InFlightImport>,
     payload: ImportPayloadRequest,
 ) -> Result<ImportResult, CommandError> {
-    #[cfg(debug_assertions)]
-    eprintln!(
+    // A newer import (champion re-picked) cancels this one
+    let token = in_flight.begin();
+    let result = perform_import(&app, &payload, &token).await;
+    in_flight.finish(&token);
+    if token.is_cancelled() {
+        log_info!("[import_build_to_client] Import of {} cancelled", payload.champion_key);
+        return Err(AppError::Cancelled.into());
+    }
+
+    let result = match result {
+        Err(err) if pending_imports::is_offline_error(&err) => {
+            pending_imports::enqueue(&app, payload.clone(), &err)
+                .await
+                .map_err(AppError::Storage)?;
+            Ok(pending_imports::queued_result(&err))
+        }
+        result => result,
+    };
+    import_history::record_result(&app, &payload, &result);
+    telemetry::record(
+        &app,
+        match &result {
+            Ok(r) if r.queued => telemetry::Metric::ImportQueued,
+            Ok(r) if r.success => telemetry::Metric::ImportSucceeded,
+            Ok(_) => telemetry::Metric::ImportFailed,
+            Err(e) => telemetry::Metric::Error(&e.code),
+        },
+    );
+    if result.as_ref().is_ok_and(|r| r.success) {
+        reimport::record(&payload);
+        notifications::notify(
+            &app,
+            notifications::NotificationEvent::BuildImported {
+                champion: payload.champion_key.clone(),
+                role: payload.role.clone(),
+            },
+        );
+    }
+    result
+}
+
+/// Import a build: resolve the FocusApi payloads and apply them to the client.
+///
+/// `token` is checked before every write to the client, so a cancelled
+/// import stops with `CANCELLED` instead of applying a stale build.
+async fn perform_import(
+    app: &tauri::AppHandle,
+    payload: &ImportPayloadRequest,
+    token: &cancel::CancelToken,
+) -> Result<ImportResult, CommandError> {
+    log_debug!(
         "[import_build_to_client] Starting import for {} ({})",
         payload.champion_key, payload.role
     );
 
+    // Nothing to resolve when the client cannot be written to
+    lcu::ensure_writable()?;
+
     // Step 1: Find and connect to the League Client
     let connection = find_lockfile().await.map_err(|e| {
-        #[cfg(debug_assertions)]
-        eprintln!("[import_build_to_client] Failed to find lockfile: {}", e);
+        log_debug!("[import_build_to_client] Failed to find lockfile: {}", e);
         CommandError::from(e)
     })?;
 
-    #[cfg(debug_assertions)]
-    eprintln!(
+    log_debug!(
         "[import_build_to_client] Connected to League Client on port {}",
         connection.port
     );
 
-    // Step 2: Call FocusApi to get the import payloads (POST request)
-    let payload_response = fetch_import_payloads(&payload).await?;
+    // Step 2: Get the import payloads from the cache or FocusApi (POST request)
+    let payload_response = resolve_import_payloads(app, payload).await?;
+    token.check()?;
 
-    #[cfg(debug_assertions)]
-    eprintln!(
+    log_debug!(
         "[import_build_to_client] Got payload response for {} ({})",
         payload_response.champion.as_deref().unwrap_or("unknown"),
         payload_response.role.as_deref().unwrap_or("unknown")
     );
 
+    apply_import_payloads(&connection, payload_response, token).await
+}
+
+/// Write resolved import payloads (rune page, item set, summoner spells) to the client.
+async fn apply_import_payloads<C: LcuApi>(
+    client: &C,
+    payload_response: ImportPayloadResponse,
+    token: &cancel::CancelToken,
+) -> Result<ImportResult, CommandError> {
     let mut runes_imported = false;
     let mut items_imported = false;
     let mut summoners_imported = false;
@@ -176,61 +294,73 @@ async fn import_build_to_client(
 
     // Step 3: Import runes if available
     if let Some(mut rune_payload) = payload_response.rune_page_payload {
+        token.check()?;
         // Construct the singleton page name: "⚡{Champion} {Role}"
         let champion = payload_response.champion.as_deref().unwrap_or("Unknown");
         let role = payload_response.role.as_deref().unwrap_or("").to_uppercase();
         rune_payload.name = format!("{}{} {}", FOCUS_RUNE_PAGE_PREFIX, champion, role);
 
-        match create_rune_page(&connection, &rune_payload).await {
+        match client.create_rune_page(&rune_payload).await {
             Ok(()) => {
                 runes_imported = true;
                 messages.push(format!("Rune page '{}' imported", rune_payload.name));
-                #[cfg(debug_assertions)]
-                eprintln!("[import_build_to_client] Runes imported successfully");
+                log_debug!("[import_build_to_client] Runes imported successfully");
             }
             Err(e) => {
                 messages.push(format!("Failed to import runes: {}", e));
-                #[cfg(debug_assertions)]
-                eprintln!("[import_build_to_client] Failed to import runes: {}", e);
+                log_debug!("[import_build_to_client] Failed to import runes: {}", e);
             }
         }
     }
 
     // Step 4: Import item set if available
     if let Some(mut item_set_payload) = payload_response.item_set_payload {
+        token.check()?;
+        // Associate the set with the map of the current queue when the API leaves it open
+        if item_set_payload.associated_maps.is_empty() {
+            let queue_id = client
+                .gameflow_session()
+                .await
+                .ok()
+                .and_then(|session| session.game_data)
+                .and_then(|data| data.queue)
+                .map(|queue| queue.id)
+                .filter(|&id| id > 0);
+            if let Some(queue_id) = queue_id {
+                item_set_payload.associated_maps = vec![queues::map_id_for_queue(queue_id)];
+            }
+        }
+
         // Construct the singleton page name: "⚡{Champion} {Role}"
         let champion = payload_response.champion.as_deref().unwrap_or("Unknown");
         let role = payload_response.role.as_deref().unwrap_or("").to_uppercase();
         item_set_payload.title = format!("{}{} {}", FOCUS_ITEM_SET_PREFIX, champion, role);
 
-        match add_item_set(&connection, &item_set_payload).await {
+        match client.add_item_set(&item_set_payload).await {
             Ok(()) => {
                 items_imported = true;
                 messages.push(format!("Item set '{}' imported", item_set_payload.title));
-                #[cfg(debug_assertions)]
-                eprintln!("[import_build_to_client] Items imported successfully");
+                log_debug!("[import_build_to_client] Items imported successfully");
             }
             Err(e) => {
                 messages.push(format!("Failed to import items: {}", e));
-                #[cfg(debug_assertions)]
-                eprintln!("[import_build_to_client] Failed to import items: {}", e);
+                log_debug!("[import_build_to_client] Failed to import items: {}", e);
             }
         }
     }
 
     // Step 5: Import summoner spells if available (only works during champ select)
     if let Some(spells_payload) = payload_response.summoner_spells_payload {
-        match set_summoner_spells(&connection, &spells_payload).await {
+        token.check()?;
+        match client.set_summoner_spells(&spells_payload).await {
             Ok(()) => {
                 summoners_imported = true;
                 messages.push("Summoner spells set".to_string());
-                #[cfg(debug_assertions)]
-                eprintln!("[import_build_to_client] Summoner spells imported successfully");
+                log_debug!("[import_build_to_client] Summoner spells imported successfully");
             }
             Err(e) => {
                 // Don't add to messages if not in champ select - it's expected
-                #[cfg(debug_assertions)]
-                eprintln!("[import_build_to_client] Failed to set summoner spells: {}", e);
+                log_debug!("[import_build_to_client] Failed to set summoner spells: {}", e);
             }
         }
     }
@@ -248,44 +378,66 @@ async fn import_build_to_client(
         items_imported,
         summoners_imported,
         message,
+        queued: false,
     })
 }
 
-/// Fetch import payloads from FocusApi.
+/// Get the import payloads for a build from the configured provider.
 ///
-/// This function calls the external FocusApi with a POST request to get the
-/// rune page and item set payloads formatted for the League Client API.
-async fn fetch_import_payloads(
+/// The patch override of the settings is applied first. FocusApi responses
+/// go through the on-disk cache: a fresh cached payload skips FocusApi
+/// entirely; when FocusApi fails, an older cached payload for the same build
+/// is used instead of the error.
+async fn resolve_import_payloads(
+    app: &tauri::AppHandle,
     payload: &ImportPayloadRequest,
 ) -> Result<ImportPayloadResponse, CommandError> {
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(30))
-        .build()?;
-
-    let url = format!("{}/lol/import-payload", FOCUS_API_BASE_URL);
-
-    #[cfg(debug_assertions)]
-    eprintln!("[fetch_import_payloads] POST to: {}", url);
-
-    let response = client
-        .post(&url)
-        .header("X-API-Key", FOCUS_API_KEY)
-        .json(payload)
-        .send()
-        .await?;
-
-    if !response.status().is_success() {
-        let status = response.status();
-        let body = response.text().await.unwrap_or_default();
-        return Err(CommandError {
-            code: "API_ERROR".to_string(),
-            message: format!("FocusApi returned error {}: {}", status, body),
-        });
+    let payload = &build_providers::with_patch_override(app, payload);
+    let provider = build_providers::configured_provider(app);
+    if !provider.cacheable() {
+        log_debug!("[resolve_import_payloads] Using {}", provider.name());
+        let mut response = provider.fetch_payloads(payload).await?;
+        validate_payloads(&mut response)?;
+        return Ok(response);
     }
 
-    response.json().await.map_err(|e| CommandError {
-        code: "PARSE_ERROR".to_string(),
-        message: format!("Failed to parse FocusApi response: {}", e),
+    // Cached copies are checked like fresh ones: they may predate a rule
+    let cached = |max_age_secs| {
+        let mut cached = import_cache::lookup(app, payload, max_age_secs)?;
+        validate_payloads(&mut cached).ok().map(|()| cached)
+    };
+    if let Some(cached) = cached(import_cache::FRESH_TTL_SECS) {
+        log_debug!("[resolve_import_payloads] Using cached payload");
+        return Ok(cached);
+    }
+
+    match provider.fetch_payloads(payload).await {
+        Ok(mut response) => {
+            // A bad response is not cached and does not fall back to the stale copy
+            validate_payloads(&mut response)?;
+            if let Err(e) = import_cache::store(app, payload, &response) {
+                log_warn!("[resolve_import_payloads] Failed to cache payload: {}", e);
+            }
+            Ok(response)
+        }
+        Err(err) => cached(import_cache::STALE_TTL_SECS).ok_or(err),
+    }
+}
+
+/// Fix the stat shards, then reject a build response the League Client would refuse
+fn validate_payloads(response: &mut ImportPayloadResponse) -> Result<(), AppError> {
+    if let Some(rune_page) = response.rune_page_payload.as_mut() {
+        if rune_page.normalize_stat_shards() {
+            log_warn!(
+                "[resolve_import_payloads] Fixed the stat shards of '{}': {:?}",
+                rune_page.name,
+                rune_page.selected_perk_ids
+            );
+        }
+    }
+    response.validate().map_err(|(field, reason)| {
+        log_warn!("[resolve_import_payloads] Invalid payload: {}: {}", field, reason);
+        AppError::PayloadInvalid { field, reason }
     })
 }
 
@@ -298,6 +450,28 @@ async fn is_league_client_running() -> bool {
     find_lockfile().await.is_ok()
 }
 
+/// Interval between two lockfile checks while waiting for the client
+const CLIENT_WAIT_POLL: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Wait until the League Client is running, at most `timeout_secs`.
+///
+/// Returns false on timeout. Cancellable with `cancel_operation(operation_id)`,
+/// e.g. when the user leaves the screen waiting for the client.
+#[tauri::command]
+async fn wait_for_league_client(timeout_secs: u64, operation_id: Option<String>) -> Result<bool, CommandError> {
+    let operation = cancel::start(operation_id);
+    let wait = async {
+        loop {
+            if find_lockfile().await.is_ok() {
+                return true;
+            }
+            tokio::time::sleep(CLIENT_WAIT_POLL).await;
+        }
+    };
+    let timeout = std::time::Duration::from_secs(timeout_secs);
+    Ok(operation.run(tokio::time::timeout(timeout, wait)).await?.is_ok())
+}
+
 /// Set summoner spells directly in champion select.
 ///
 /// This is a lightweight command that only sets summoner spells without
@@ -305,16 +479,7 @@ async fn is_league_client_running() -> bool {
 #[tauri::command]
 async fn set_summoner_spells_cmd(spell1_id: i32, spell2_id: i32) -> Result<bool, CommandError> {
     // Step 1: Connect to League Client
-    let connection = find_lockfile().await.map_err(|e| match e {
-        LcuError::ClientNotRunning => CommandError {
-            code: "CLIENT_NOT_RUNNING".to_string(),
-            message: "League Client is not running".to_string(),
-        },
-        _ => CommandError {
-            code: "CONNECTION_ERROR".to_string(),
-            message: format!("Failed to connect to League Client: {}", e),
-        },
-    })?;
+    let connection = find_lockfile().await?;
 
     // Step 2: Set summoner spells
     let payload = SummonerSpellsPayload {
@@ -324,14 +489,28 @@ async fn set_summoner_spells_cmd(spell1_id: i32, spell2_id: i32) -> Result<bool,
 
     set_summoner_spells(&connection, &payload)
         .await
-        .map_err(|e| CommandError {
-            code: "LCU_ERROR".to_string(),
-            message: format!("{}", e),
-        })?;
+        .map_err(|e| AppError::Lcu(e.to_string()))?;
 
     Ok(true)
 }
 
+/// Create a rune page written by the user in the rune editor.
+///
+/// The page goes through the same checks as imported builds, then is created
+/// as it is: no FocusApp prefix, and no existing page is replaced.
+#[tauri::command]
+async fn create_custom_rune_page(mut payload: RunePagePayload) -> Result<bool, CommandError> {
+    payload.name = payload.name.trim().to_string();
+    payload
+        .validate_custom()
+        .map_err(|(field, reason)| AppError::RunePageInvalid { field, reason })?;
+
+    let connection = find_lockfile().await?;
+    lcu::create_custom_rune_page(&connection, &payload).await?;
+    log_info!("[create_custom_rune_page] Created '{}'", payload.name);
+    Ok(true)
+}
+
 /// Get the current champion select session.
 ///
 /// # Compliance Note
@@ -387,16 +566,7 @@ async fn get_current_summoner_cmd() -> Result<CurrentSummoner, CommandError> {
 /// This function runs before Tauri starts to catch early errors.
 fn initialize_app() -> Result<(), Box<dyn std::error::Error>> {
     // Set up panic hook in ALL builds — release builds are silent otherwise
-    panic::set_hook(Box::new(|panic_info| {
-        let msg = format!("=== FOCUSAPP PANIC ===\n{}\n", panic_info);
-        eprintln!("{}", msg);
-
-        // Write to a crash log file next to the executable
-        if let Ok(exe) = std::env::current_exe() {
-            let crash_log = exe.with_file_name("focusapp-crash.log");
-            let _ = std::fs::write(&crash_log, &msg);
-        }
-    }));
+    crash_logs::install_hook();
 
     // Log startup info in ALL builds — essential for diagnosing release crashes
     eprintln!("--- FocusApp Starting ---");
@@ -406,7 +576,7 @@ fn initialize_app() -> Result<(), Box<dyn std::error::Error>> {
     if let Ok(cwd) = std::env::current_dir() {
         eprintln!("CWD: {:?}", cwd);
     }
-    eprintln!("FOCUS_API_KEY embedded: {}", !FOCUS_API_KEY.is_empty());
+    eprintln!("FOCUS_API_KEY embedded: {}", focus_api::has_embedded_api_key());
 
     Ok(())
 }
@@ -421,24 +591,75 @@ fn main() {
         std::process::exit(1);
     }
 
+    // Options de la ligne de commande (valables pour cette session uniquement)
+    let cli_options = cli::init(std::env::args());
+    if let Some(level) = cli_options.log_level {
+        logging::set_level(level);
+    }
+
     eprintln!("--- BUILDING TAURI ---");
 
     // Crée l'instance du GameWatcher (partagée entre threads)
     let game_watcher = GameWatcher::new();
 
     // 3. Build and run Tauri application
-    let result = tauri::Builder::default()
-        .plugin(tauri_plugin_http::init())
+    let context = tauri::generate_context!();
+    let mut builder = tauri::Builder::default()
+        // En premier : un second lancement confie ses liens focusapp:// à l'instance ouverte
+        .plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| tray::show_main_window(app)))
+        .plugin(tauri_plugin_deep_link::init())
+        .plugin(tauri_plugin_http::init());
+    // Sans clé publique de signature, les mises à jour restent désactivées
+    if updater::is_enabled(context.config()) {
+        builder = builder.plugin(tauri_plugin_updater::Builder::new().build());
+    }
+    let result = builder
+        .plugin(tauri_plugin_dialog::init())
+        .plugin(autostart::plugin())
+        .plugin(tauri_plugin_notification::init())
         .manage(game_watcher.clone()) // Injecte le watcher dans l'état Tauri
+        .manage(overlay::OverlayEmitter::new())
+        .manage(spell_timers::SpellTimers::new())
+        .manage(prefetch::BuildPrefetcher::default())
+        .manage(cancel::InFlightImport::default())
+        .manage(deep_link::DeepLinkState::default())
+        // Icônes DDragon mises en cache localement (focus-asset://)
+        .register_asynchronous_uri_scheme_protocol(assets::ASSET_PROTOCOL, |ctx, request, responder| {
+            let app = ctx.app_handle().clone();
+            let path = request.uri().path().to_string();
+            tauri::async_runtime::spawn(async move {
+                responder.respond(assets::serve_asset(app, path).await);
+            });
+        })
         .invoke_handler(tauri::generate_handler![
             import_build_to_client,
+            cancel::cancel_import,
+            cancel::cancel_operation,
+            bulk_import::import_all_roles,
+            import_request::build_import_request,
+            auto_import::auto_import_build,
             is_league_client_running,
+            wait_for_league_client,
+            lcu_metrics::get_lcu_metrics,
             set_summoner_spells_cmd,
-            get_env_api_key,
+            create_custom_rune_page,
             get_champion_select_session_cmd,
+            champ_select_timer::get_champ_select_timer,
+            aram::get_aram_bench,
             // Gameflow monitoring commands
             get_gameflow_session_cmd,
             get_current_summoner_cmd,
+            // Static data
+            champions::get_ddragon_version,
+            champions::get_champion_art_urls,
+            champions::get_champion_abilities,
+            champions::get_champion_info,
+            champions::list_champions,
+            queues::get_queue_metadata,
+            roles::get_default_role,
+            roles::get_champion_roles,
+            static_data::verify_static_data,
+            snapshot::get_bundled_static_data,
             // NEW: Game Watcher commands
             get_game_state,
             start_game_watcher,
@@ -447,14 +668,151 @@ fn main() {
             // CS Overlay commands
             overlay::is_game_active,
             overlay::get_live_cs_stats,
+            overlay::get_active_player_puuid,
             overlay::show_cs_overlay,
             overlay::hide_cs_overlay,
             overlay::set_overlay_click_through,
             overlay::move_overlay,
-            overlay::emit_cs_update
+            overlay::emit_cs_update,
+            overlay::get_overlay_config,
+            overlay::set_overlay_config,
+            // Summoner spell timers (manual tracking)
+            spell_timers::mark_spell_used,
+            spell_timers::get_spell_timers,
+            spell_timers::clear_spell_timers,
+            // Objective respawn timers
+            objectives::get_objective_timers,
+            scoreboard::get_scoreboard_snapshot,
+            // Game session history
+            sessions::get_game_sessions,
+            sessions::get_cs_by_result,
+            sessions::get_session_summary,
+            daily_goals::get_streaks,
+            personal_bests::get_personal_bests,
+            game_reports::get_game_report,
+            game_reports::get_game_timeline,
+            trends::get_trends,
+            trends::get_champion_stats,
+            stats_export::export_stats_csv,
+            rank_benchmarks::get_cs_benchmark,
+            matchups::get_matchups,
+            build_diff::get_build_diff,
+            build_feedback::submit_build_feedback,
+            ban_suggestions::get_ban_suggestions,
+            // FocusApi (the API key never leaves the backend)
+            focus_api::fetch_tierlist,
+            focus_api::fetch_champion_build,
+            focus_api::fetch_items,
+            focus_api::fetch_player_profile,
+            focus_api::set_api_key,
+            focus_api::get_api_key_source,
+            focus_api::validate_api_key,
+            focus_api::clear_api_key,
+            focus_api::set_api_endpoint,
+            focus_api::get_api_status,
+            auth::login,
+            auth::logout,
+            auth::get_account_status,
+            cloud_sync::sync_now,
+            // Imports queued while offline
+            pending_imports::get_pending_imports,
+            pending_imports::process_pending_imports,
+            pending_imports::clear_pending_imports,
+            // Build prefetch during champion select
+            prefetch::prefetch_import_payload,
+            // User-authored build templates
+            build_templates::list_build_templates,
+            build_templates::get_build_template,
+            build_templates::save_build_template,
+            build_templates::delete_build_template,
+            build_templates::import_build_template,
+            // Shareable build codes
+            build_codes::export_build_code,
+            build_codes::import_build_code,
+            // Import history
+            import_history::get_import_history,
+            import_history::get_import_stats,
+            // Favorite champions and notes
+            favorites::get_favorite_champions,
+            favorites::set_favorite_champion,
+            favorites::get_champion_notes,
+            favorites::save_champion_note,
+            // focusapp:// deep links
+            deep_link::take_pending_deep_link,
+            // App updates
+            updater::get_latest_version,
+            updater::check_for_update,
+            updater::install_update,
+            // Logs
+            logging::set_log_level,
+            logging::get_log_file_path,
+            crash_logs::get_recent_crashes,
+            telemetry::get_telemetry_buffer,
+            data_export::export_all_data,
+            data_export::delete_all_data,
+            health::run_health_check,
+            proxy::set_proxy,
+            events::get_event_catalog,
+            // Settings
+            settings::get_settings,
+            settings::update_settings
         ])
         .setup(move |app| {
             eprintln!("--- SETUP PHASE --- commands registered");
+
+            // Données statiques embarquées (premier lancement hors ligne)
+            snapshot::install_bundled_champions();
+
+            // Base SQLite (historique des parties et des imports), créée et migrée au besoin
+            app.manage(db::Database::open(app.handle()));
+
+            // Clé FocusApi et session du compte stockées dans le trousseau du système
+            tokio::spawn(focus_api::load_stored_api_key());
+            tokio::spawn(auth::load_stored_session());
+
+            // Résout la version DDragon courante (icônes à jour après un patch)
+            tokio::spawn(champions::run_ddragon_version_refresher(app.handle().clone()));
+            tokio::spawn(queues::refresh_queue_metadata(app.handle().clone()));
+            tokio::spawn(roles::refresh_champion_roles(app.handle().clone()));
+
+            // Signale une nouvelle version publiée
+            tokio::spawn(updater::check_at_startup(app.handle().clone()));
+
+            // Réessaie les imports mis en file hors ligne
+            tokio::spawn(pending_imports::run_pending_import_worker(app.handle().clone()));
+
+            // Charge les paramètres (migrés si le fichier est ancien)
+            app.manage(settings::SettingsStore::load(app.handle()));
+
+            // Fichier de log, au niveau choisi dans les paramètres
+            logging::init(app.handle());
+            crash_logs::init(app.handle());
+            etag_cache::init(app.handle());
+            messages::init(app.handle());
+            proxy::init(app.handle());
+            focus_api::init(app.handle());
+            lcu::init(app.handle());
+
+            // Statistiques d'usage anonymes (uniquement si activées)
+            tokio::spawn(telemetry::run_telemetry_worker(app.handle().clone()));
+
+            // Fenêtre principale cachée au démarrage (affichée sauf lancement réduit ou --minimized)
+            autostart::init(app.handle());
+            let settings = app.state::<settings::SettingsStore>().get();
+            if !cli_options.minimized && !autostart::should_start_hidden(&settings, autostart::launched_at_login()) {
+                tray::show_main_window(app.handle());
+            }
+
+            // Charge la configuration de l'overlay sauvegardée
+            app.manage(overlay::OverlayConfigStore::load(app.handle()));
+
+            // Icône du tray (lit le paramètre d'auto-import)
+            if let Err(e) = tray::init(app.handle()) {
+                log_warn!("[Setup] Failed to create the tray icon: {}", e);
+            }
+
+            // Liens focusapp:// (site FocusApi) : lien du lancement et liens suivants
+            deep_link::init(app.handle());
             
             // Démarre le GameWatcher automatiquement au lancement
             let app_handle = app.handle().clone();
@@ -464,16 +822,16 @@ fn main() {
                 // Petit délai pour laisser l'app démarrer proprement
                 tokio::time::sleep(std::time::Duration::from_secs(1)).await;
                 watcher.start(app_handle).await;
-                eprintln!("[Setup] GameWatcher auto-started");
+                log_info!("[Setup] GameWatcher auto-started");
             });
             
             Ok(())
         })
-        .run(tauri::generate_context!());
+        .run(context);
 
     // 4. Handle Tauri errors
     if let Err(e) = result {
-        eprintln!("Tauri error: {:?}", e);
+        log_error!("Tauri error: {:?}", e);
         std::process::exit(1);
     }
 }
@@ -0,0 +1,120 @@
+//! Counter and matchup hints for champion select.
+//!
+//! FocusApi publishes, for each champion and role, its win rate against each
+//! lane opponent on the current patch. `get_matchups` turns it into the few
+//! champions it is strong and weak into, shown next to the import button.
+//! Opponents with too few games are left out so a handful of games cannot
+//! make a hint.
+//!
+//! Matchups are cached on disk per patch (`patch_cache`), like the rank
+//! benchmarks.
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::focus_api;
+use crate::patch_cache::PatchCache;
+
+/// Cache file, relative to the cache directory
+const MATCHUPS_CACHE_FILE: &str = "focus-api/matchups.json";
+
+/// Champions listed on each side
+const MAX_MATCHUPS: usize = 5;
+
+/// Fewest games for a matchup to be listed
+const MIN_MATCHUP_GAMES: u32 = 100;
+
+/// Win rate of a champion against one opponent
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Matchup {
+    pub champion: String,
+    pub win_rate: f64,
+    pub games: u32,
+}
+
+/// Result of `get_matchups`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Matchups {
+    pub champion: String,
+    pub role: String,
+    /// Best win rates first
+    pub strong_against: Vec<Matchup>,
+    /// Worst win rates first
+    pub weak_against: Vec<Matchup>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MatchupResponse {
+    matchups: Vec<Matchup>,
+}
+
+/// Matchups of the current patch, by "champion/role"
+static CACHE: PatchCache<Matchups> = PatchCache::on_disk(MATCHUPS_CACHE_FILE);
+
+fn cache_key(champion: &str, role: &str) -> String {
+    format!("{}/{}", champion.trim().to_lowercase(), role.trim().to_lowercase())
+}
+
+/// Split a FocusApi response into strong and weak matchups
+fn parse_matchups(champion: &str, role: &str, body: serde_json::Value) -> Result<Matchups, String> {
+    let response: MatchupResponse = serde_json::from_value(body).map_err(|e| format!("Invalid matchups: {}", e))?;
+    let mut matchups: Vec<Matchup> = response
+        .matchups
+        .into_iter()
+        .filter(|m| m.games >= MIN_MATCHUP_GAMES && (0.0..=1.0).contains(&m.win_rate))
+        .collect();
+    matchups.sort_by(|a, b| b.win_rate.total_cmp(&a.win_rate));
+
+    let strong_against = matchups.iter().filter(|m| m.win_rate > 0.5).take(MAX_MATCHUPS).cloned().collect();
+    let weak_against = matchups.iter().rev().filter(|m| m.win_rate < 0.5).take(MAX_MATCHUPS).cloned().collect();
+    Ok(Matchups {
+        champion: champion.to_string(),
+        role: role.to_lowercase(),
+        strong_against,
+        weak_against,
+    })
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the champions `champion` is strong and weak into in `role`, for the current patch.
+#[tauri::command]
+pub async fn get_matchups(app: AppHandle, champion: String, role: String) -> Result<Matchups, String> {
+    let key = cache_key(&champion, &role);
+    if let Some(cached) = CACHE.get(&app, &key) {
+        return Ok(cached);
+    }
+
+    let matchups = parse_matchups(&champion, &role, focus_api::fetch_matchups(&champion, &role).await.map_err(|e| e.to_string())?)?;
+    CACHE.insert(&app, key, matchups.clone())?;
+    Ok(matchups)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_matchups() {
+        let body = json!({
+            "matchups": [
+                { "champion": "Zed", "winRate": 0.46, "games": 900 },
+                { "champion": "Lux", "winRate": 0.55, "games": 1200 },
+                { "champion": "Annie", "winRate": 0.61, "games": 40 },
+                { "champion": "Syndra", "winRate": 0.52, "games": 300 },
+                { "champion": "Yasuo", "winRate": 0.44, "games": 700 }
+            ]
+        });
+        let matchups = parse_matchups("Ahri", "MID", body).unwrap();
+        let names = |list: &[Matchup]| list.iter().map(|m| m.champion.clone()).collect::<Vec<_>>();
+        assert_eq!(names(&matchups.strong_against), ["Lux", "Syndra"]);
+        assert_eq!(names(&matchups.weak_against), ["Yasuo", "Zed"]);
+        assert_eq!(matchups.role, "mid");
+        assert!(parse_matchups("Ahri", "mid", json!({})).is_err());
+    }
+}
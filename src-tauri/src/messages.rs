@@ -0,0 +1,180 @@
+//! User-facing error messages.
+//!
+//! `CommandError.code` stays machine-readable; the `message` sent to the
+//! frontend comes from this catalog, in the language chosen in the settings
+//! (`locale`). The technical text the error was built with is kept in
+//! `detail` for logs and bug reports. Codes missing from the catalog fall
+//! back to the generic `UNKNOWN_ERROR` message.
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU8, Ordering};
+use tauri::{AppHandle, Listener, Manager};
+
+use crate::events::SETTINGS_CHANGED_EVENT;
+use crate::settings::{AppSettings, SettingsStore};
+
+/// Code used when an error code has no catalog entry
+const UNKNOWN_ERROR: &str = "UNKNOWN_ERROR";
+
+/// Language of the messages shown to the user
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    En = 0,
+    Fr = 1,
+}
+
+/// Message catalog: (code, English, French), one entry per `AppError` code
+const CATALOG: &[(&str, &str, &str)] = &[
+    (
+        "CLIENT_NOT_RUNNING",
+        "The League Client is not running. Start it and try again.",
+        "Le client League of Legends n'est pas lancé. Lancez-le puis réessayez.",
+    ),
+    (
+        "LOCKFILE_NOT_FOUND",
+        "The League Client could not be found. Check that it is installed.",
+        "Le client League of Legends est introuvable. Vérifiez qu'il est installé.",
+    ),
+    (
+        "LOCKFILE_PARSE_ERROR",
+        "Could not read the League Client connection info. Restart the client.",
+        "Impossible de lire les informations de connexion du client. Redémarrez le client.",
+    ),
+    (
+        "LCU_UNREACHABLE",
+        "Could not connect to the League Client.",
+        "Impossible de se connecter au client League of Legends.",
+    ),
+    (
+        "LCU_ERROR",
+        "The League Client rejected the request.",
+        "Le client League of Legends a refusé la requête.",
+    ),
+    (
+        "LIVE_CLIENT_ERROR",
+        "Could not read the data of the game in progress.",
+        "Impossible de lire les données de la partie en cours.",
+    ),
+    (
+        "FOCUS_API_UNAUTHORIZED",
+        "The FocusApi key was rejected. Check it in the settings.",
+        "La clé FocusApi a été refusée. Vérifiez-la dans les paramètres.",
+    ),
+    (
+        "FOCUS_API_ERROR",
+        "The build service returned an error.",
+        "Le service de builds a renvoyé une erreur.",
+    ),
+    (
+        "RATE_LIMITED",
+        "Too many requests to the build service. Try again in a moment.",
+        "Trop de requêtes au service de builds. Réessayez dans un instant.",
+    ),
+    (
+        "NETWORK_ERROR",
+        "Network error. Check your connection and try again.",
+        "Erreur réseau. Vérifiez votre connexion puis réessayez.",
+    ),
+    (
+        "PARSE_ERROR",
+        "The build data could not be read.",
+        "Les données du build sont illisibles.",
+    ),
+    (
+        "PAYLOAD_INVALID",
+        "The build service returned a build the League Client would reject.",
+        "Le service de builds a renvoyé un build que le client League of Legends refuserait.",
+    ),
+    (
+        "RUNE_PAGE_INVALID",
+        "This rune page would be rejected by the League Client.",
+        "Le client League of Legends refuserait cette page de runes.",
+    ),
+    ("NOT_FOUND", "The requested item was not found.", "L'élément demandé est introuvable."),
+    (
+        "STORAGE_ERROR",
+        "Could not read or write FocusApp data on disk.",
+        "Impossible de lire ou d'écrire les données de FocusApp sur le disque.",
+    ),
+    (
+        "OVERLAY_ERROR",
+        "The CS overlay could not be updated.",
+        "Impossible de mettre à jour l'overlay CS.",
+    ),
+    ("CANCELLED", "The operation was cancelled.", "L'opération a été annulée."),
+    (
+        "SAFE_MODE",
+        "Safe mode is on: FocusApp does not change your runes, items or spells.",
+        "Le mode sans échec est activé : FocusApp ne modifie ni vos runes, ni vos objets, ni vos sorts.",
+    ),
+    (
+        UNKNOWN_ERROR,
+        "Something went wrong. Please try again.",
+        "Une erreur est survenue. Veuillez réessayer.",
+    ),
+];
+
+/// Current locale
+static LOCALE: AtomicU8 = AtomicU8::new(Locale::En as u8);
+
+pub fn set_locale(locale: Locale) {
+    LOCALE.store(locale as u8, Ordering::Relaxed);
+}
+
+pub fn locale() -> Locale {
+    match LOCALE.load(Ordering::Relaxed) {
+        1 => Locale::Fr,
+        _ => Locale::En,
+    }
+}
+
+/// Message for an error code in `locale`
+pub fn message_for(code: &str, locale: Locale) -> &'static str {
+    let entry = CATALOG
+        .iter()
+        .find(|(known, _, _)| *known == code)
+        .or_else(|| CATALOG.iter().find(|(known, _, _)| *known == UNKNOWN_ERROR))
+        .expect("the catalog has an UNKNOWN_ERROR entry");
+    match locale {
+        Locale::En => entry.1,
+        Locale::Fr => entry.2,
+    }
+}
+
+/// Message for an error code in the current locale
+pub fn localized(code: &str) -> &'static str {
+    message_for(code, locale())
+}
+
+/// Follow the locale saved in the settings
+pub fn init(app: &AppHandle) {
+    if let Some(store) = app.try_state::<SettingsStore>() {
+        set_locale(store.get().locale);
+    }
+    app.listen(SETTINGS_CHANGED_EVENT, |event| {
+        if let Ok(settings) = serde_json::from_str::<AppSettings>(event.payload()) {
+            set_locale(settings.locale);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_for() {
+        assert_eq!(
+            message_for("CLIENT_NOT_RUNNING", Locale::Fr),
+            "Le client League of Legends n'est pas lancé. Lancez-le puis réessayez."
+        );
+        assert_eq!(
+            message_for("SOMETHING_NEW", Locale::En),
+            "Something went wrong. Please try again."
+        );
+        // Every entry has both languages
+        assert!(CATALOG.iter().all(|(_, en, fr)| !en.is_empty() && !fr.is_empty()));
+    }
+}
@@ -0,0 +1,68 @@
+//! Fake League Client for the tests, served by `wiremock`.
+//!
+//! Holds the recorded fixtures (`tests/fixtures`) and the helpers that mount
+//! them on a `wiremock::MockServer` and point `LcuApi`/`LiveClientApi` at it,
+//! so a test can run the import flow or the watcher and check what was
+//! written to the client. `import_request` is the build the tests import,
+//! export or queue. `MockServer::reset` followed by new routes moves
+//! the fake client from one phase to the next.
+
+use std::future::Future;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, ResponseTemplate};
+
+use crate::client_api::LiveClient;
+use crate::lcu::LcuConnection;
+use crate::ImportPayloadRequest;
+
+pub use wiremock::MockServer;
+
+pub const LCU_RUNE_PAGES: &str = include_str!("../tests/fixtures/lcu_rune_pages.json");
+pub const LCU_CURRENT_SUMMONER: &str = include_str!("../tests/fixtures/lcu_current_summoner.json");
+pub const LCU_ITEM_SETS: &str = include_str!("../tests/fixtures/lcu_item_sets.json");
+pub const LCU_GAMEFLOW_SESSION: &str = include_str!("../tests/fixtures/lcu_gameflow_session.json");
+pub const LCU_CHAMP_SELECT_SESSION: &str = include_str!("../tests/fixtures/lcu_champ_select_session.json");
+pub const LIVE_ACTIVE_PLAYER: &str = include_str!("../tests/fixtures/live_activeplayer.json");
+pub const LIVE_GAME_STATS: &str = include_str!("../tests/fixtures/live_gamestats.json");
+pub const LIVE_EVENT_DATA: &str = include_str!("../tests/fixtures/live_eventdata.json");
+pub const IMPORT_PAYLOAD_REQUEST: &str = include_str!("../tests/fixtures/import_payload_request.json");
+
+/// A complete import request (Ahri mid, FocusApi source); tests set the fields they vary
+pub fn import_request() -> ImportPayloadRequest {
+    serde_json::from_str(IMPORT_PAYLOAD_REQUEST).expect("import request fixture")
+}
+
+/// Answer each `(method, path, status, body)` route with its status and JSON body
+/// (unknown routes get a 404)
+pub async fn mount(server: &MockServer, routes: &[(&str, &str, u16, &str)]) {
+    for &(route_method, route_path, status, body) in routes {
+        Mock::given(method(route_method))
+            .and(path(route_path))
+            .respond_with(ResponseTemplate::new(status).set_body_raw(body, "application/json"))
+            .mount(server)
+            .await;
+    }
+}
+
+/// LCU connection pointing at `server`
+pub fn lcu_connection(server: &MockServer) -> LcuConnection {
+    LcuConnection {
+        port: server.address().port(),
+        password: "mock".to_string(),
+        protocol: "http".to_string(),
+    }
+}
+
+/// Live Client Data API pointing at `server`
+pub fn live_client(server: &MockServer) -> LiveClient {
+    LiveClient::at(server.uri())
+}
+
+/// Run an async test body on a fresh runtime
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("test runtime")
+        .block_on(future)
+}
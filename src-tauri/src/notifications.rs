@@ -0,0 +1,115 @@
+//! Native notifications for game phase changes.
+//!
+//! Opt-in OS toasts for the moments a user tabbed out of the client cares
+//! about: a ready check popping, champion select starting, a build landing
+//! in the client, and the CS summary of the game that just ended. Each one
+//! has its own toggle under `notifications` in the settings, all off by
+//! default. Toasts are shown by `tauri-plugin-notification`.
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::sessions::{GameResult, GameSession};
+use crate::settings::SettingsStore;
+
+/// Per-event notification toggles
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct NotificationSettings {
+    pub ready_check: bool,
+    pub champ_select: bool,
+    pub build_imported: bool,
+    pub game_ended: bool,
+}
+
+/// Something worth a notification
+#[derive(Debug, Clone, PartialEq)]
+pub enum NotificationEvent {
+    ReadyCheck,
+    ChampSelect,
+    BuildImported { champion: String, role: String },
+    GameEnded(GameSession),
+}
+
+impl NotificationEvent {
+    fn enabled(&self, settings: &NotificationSettings) -> bool {
+        match self {
+            NotificationEvent::ReadyCheck => settings.ready_check,
+            NotificationEvent::ChampSelect => settings.champ_select,
+            NotificationEvent::BuildImported { .. } => settings.build_imported,
+            NotificationEvent::GameEnded(_) => settings.game_ended,
+        }
+    }
+
+    /// Title and body of the toast
+    fn message(&self) -> (String, String) {
+        match self {
+            NotificationEvent::ReadyCheck => ("Match found".to_string(), "Accept the ready check in the client".to_string()),
+            NotificationEvent::ChampSelect => ("Champion select".to_string(), "Champion select has started".to_string()),
+            NotificationEvent::BuildImported { champion, role } => (
+                "Build imported".to_string(),
+                format!("{} {} runes and items are ready", champion, role.to_uppercase()),
+            ),
+            NotificationEvent::GameEnded(session) => {
+                let title = match session.result {
+                    Some(GameResult::Win) => "Victory",
+                    Some(GameResult::Loss) => "Defeat",
+                    None => "Game ended",
+                };
+                let minutes = (session.duration_secs / 60.0).floor() as i64;
+                let seconds = (session.duration_secs % 60.0).floor() as i64;
+                (
+                    title.to_string(),
+                    format!(
+                        "{}: {} CS in {}:{:02} ({:.1} CS/min)",
+                        session.champion_name, session.final_cs, minutes, seconds, session.cs_per_minute
+                    ),
+                )
+            }
+        }
+    }
+}
+
+/// Show a notification if the user enabled it
+pub fn notify(app: &AppHandle, event: NotificationEvent) {
+    let Some(store) = app.try_state::<SettingsStore>() else {
+        return;
+    };
+    if !event.enabled(&store.get().notifications) {
+        return;
+    }
+
+    let (title, body) = event.message();
+    if let Err(e) = app.notification().builder().title(title).body(body).show() {
+        log_warn!("[notifications] Failed to show notification: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_game_ended_message() {
+        let event = NotificationEvent::GameEnded(GameSession {
+            champion_name: "Ahri".to_string(),
+            duration_secs: 1541.4,
+            final_cs: 185,
+            cs_per_minute: 7.2,
+            result: Some(GameResult::Win),
+            ..GameSession::default()
+        });
+        assert_eq!(
+            event.message(),
+            ("Victory".to_string(), "Ahri: 185 CS in 25:41 (7.2 CS/min)".to_string())
+        );
+
+        let settings = NotificationSettings {
+            game_ended: true,
+            ..NotificationSettings::default()
+        };
+        assert!(event.enabled(&settings));
+        assert!(!NotificationEvent::ReadyCheck.enabled(&settings));
+    }
+}
@@ -0,0 +1,200 @@
+//! Timers de réapparition des objectifs neutres
+//!
+//! Calcule les prochaines apparitions du Dragon, du Baron et du Héraut à partir
+//! du flux d'événements officiel de la Live Client Data API
+//! (`/liveclientdata/eventdata` : DragonKill, BaronKill, HeraldKill) et des
+//! intervalles de réapparition connus.
+//!
+//! Ces informations sont affichées à tous les joueurs par le jeu lui-même
+//! (annonces et timers de la carte) : aucun avantage compétitif.
+
+use serde::{Deserialize, Serialize};
+
+use crate::client_api::{LiveClient, LiveClientApi};
+use crate::game_watcher::LiveEvent;
+
+/// Première apparition du Dragon (5:00)
+const DRAGON_FIRST_SPAWN_SECS: f64 = 300.0;
+/// Réapparition du Dragon après un kill
+const DRAGON_RESPAWN_SECS: f64 = 300.0;
+/// Réapparition du Dragon Ancestral après un kill
+const ELDER_RESPAWN_SECS: f64 = 360.0;
+/// Première apparition du Baron Nashor (20:00)
+const BARON_FIRST_SPAWN_SECS: f64 = 1200.0;
+/// Réapparition du Baron après un kill
+const BARON_RESPAWN_SECS: f64 = 360.0;
+/// Apparition du Héraut (14:00) - une seule apparition par partie
+const HERALD_FIRST_SPAWN_SECS: f64 = 840.0;
+
+/// Objectif neutre suivi
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum Objective {
+    Dragon,
+    Baron,
+    Herald,
+}
+
+/// État du timer d'un objectif
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectiveTimer {
+    pub objective: Objective,
+    /// Temps de jeu (secondes) de la prochaine apparition, `None` si l'objectif
+    /// ne réapparaît plus (Héraut déjà tué)
+    pub next_spawn_time: Option<f64>,
+    /// Indique si l'objectif est actuellement en vie sur la carte
+    pub is_alive: bool,
+    /// Nombre de fois où l'objectif a été tué
+    pub kill_count: u32,
+    /// Type du prochain dragon si connu ("Elder" après un Dragon Ancestral)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_variant: Option<String>,
+}
+
+/// Timers des objectifs à un instant donné
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectiveTimers {
+    pub game_time: f64,
+    pub timers: Vec<ObjectiveTimer>,
+}
+
+/// Calcule les timers à partir des événements de la partie.
+///
+/// Le nombre d'âmes n'étant pas connu sans les équipes, le Dragon Ancestral
+/// n'est détecté qu'après un premier kill de type "Elder".
+pub fn compute_objective_timers(events: &[LiveEvent], game_time: f64) -> Vec<ObjectiveTimer> {
+    let kills = |name: &str| -> Vec<&LiveEvent> {
+        events.iter().filter(|e| e.event_name == name).collect()
+    };
+
+    let dragon_kills = kills("DragonKill");
+    let baron_kills = kills("BaronKill");
+    let herald_kills = kills("HeraldKill");
+
+    let last_dragon = dragon_kills.last();
+    let next_dragon = match last_dragon {
+        Some(kill) if kill.dragon_type.as_deref() == Some("Elder") => kill.event_time + ELDER_RESPAWN_SECS,
+        Some(kill) => kill.event_time + DRAGON_RESPAWN_SECS,
+        None => DRAGON_FIRST_SPAWN_SECS,
+    };
+    let next_dragon_variant = last_dragon
+        .filter(|kill| kill.dragon_type.as_deref() == Some("Elder"))
+        .map(|_| "Elder".to_string());
+
+    let next_baron = match baron_kills.last() {
+        Some(kill) => kill.event_time + BARON_RESPAWN_SECS,
+        None => BARON_FIRST_SPAWN_SECS,
+    };
+
+    let next_herald = if herald_kills.is_empty() {
+        Some(HERALD_FIRST_SPAWN_SECS)
+    } else {
+        None
+    };
+
+    vec![
+        ObjectiveTimer {
+            objective: Objective::Dragon,
+            next_spawn_time: Some(next_dragon),
+            is_alive: game_time >= next_dragon,
+            kill_count: dragon_kills.len() as u32,
+            next_variant: next_dragon_variant,
+        },
+        ObjectiveTimer {
+            objective: Objective::Baron,
+            next_spawn_time: Some(next_baron),
+            is_alive: game_time >= next_baron,
+            kill_count: baron_kills.len() as u32,
+            next_variant: None,
+        },
+        ObjectiveTimer {
+            objective: Objective::Herald,
+            next_spawn_time: next_herald,
+            // Le Héraut disparaît s'il n'est pas tué avant l'arrivée du Baron
+            is_alive: next_herald.is_some_and(|t| game_time >= t && game_time < BARON_FIRST_SPAWN_SECS),
+            kill_count: herald_kills.len() as u32,
+            next_variant: None,
+        },
+    ]
+}
+
+/// Récupère les événements de la partie et calcule les timers.
+pub async fn fetch_objective_timers() -> Result<ObjectiveTimers, String> {
+    let live = LiveClient::local();
+    let game_time = live.game_time().await?;
+    let events = live.events().await?;
+    Ok(ObjectiveTimers {
+        game_time,
+        timers: compute_objective_timers(&events, game_time),
+    })
+}
+
+// =============================================================================
+// COMMANDES TAURI
+// =============================================================================
+
+/// Commande Tauri : timers de réapparition des objectifs de la partie en cours
+#[tauri::command]
+pub async fn get_objective_timers() -> Result<Option<ObjectiveTimers>, String> {
+    Ok(fetch_objective_timers().await.ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(name: &str, time: f64, dragon_type: Option<&str>) -> LiveEvent {
+        LiveEvent {
+            event_name: name.to_string(),
+            event_time: time,
+            dragon_type: dragon_type.map(str::to_string),
+            ..LiveEvent::default()
+        }
+    }
+
+    fn timer(timers: &[ObjectiveTimer], objective: Objective) -> &ObjectiveTimer {
+        timers.iter().find(|t| t.objective == objective).unwrap()
+    }
+
+    #[test]
+    fn test_first_spawns_without_events() {
+        let timers = compute_objective_timers(&[], 60.0);
+
+        assert_eq!(timer(&timers, Objective::Dragon).next_spawn_time, Some(300.0));
+        assert_eq!(timer(&timers, Objective::Baron).next_spawn_time, Some(1200.0));
+        assert_eq!(timer(&timers, Objective::Herald).next_spawn_time, Some(840.0));
+        assert!(!timer(&timers, Objective::Dragon).is_alive);
+    }
+
+    #[test]
+    fn test_respawn_after_kills() {
+        let events = vec![
+            event("DragonKill", 400.0, Some("Fire")),
+            event("HeraldKill", 900.0, None),
+            event("BaronKill", 1300.0, None),
+        ];
+        let timers = compute_objective_timers(&events, 1310.0);
+
+        let dragon = timer(&timers, Objective::Dragon);
+        assert_eq!(dragon.next_spawn_time, Some(700.0));
+        assert!(dragon.is_alive);
+        assert_eq!(dragon.kill_count, 1);
+
+        assert_eq!(timer(&timers, Objective::Baron).next_spawn_time, Some(1660.0));
+        assert_eq!(timer(&timers, Objective::Herald).next_spawn_time, None);
+    }
+
+    #[test]
+    fn test_elder_respawn() {
+        let events = vec![event("DragonKill", 2000.0, Some("Elder"))];
+        let dragon = compute_objective_timers(&events, 2010.0)
+            .into_iter()
+            .find(|t| t.objective == Objective::Dragon)
+            .unwrap();
+
+        assert_eq!(dragon.next_spawn_time, Some(2360.0));
+        assert_eq!(dragon.next_variant.as_deref(), Some("Elder"));
+    }
+}
@@ -24,13 +24,97 @@
 //! =============================================================================
 
 use serde::{Deserialize, Serialize};
-use tauri::{AppHandle, Emitter, Manager};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+use crate::cs_goals::CsGoal;
+use crate::error::AppError;
+use crate::events::{AppEvent, CS_OVERLAY_UPDATE_EVENT};
+use crate::lcu::{find_lockfile, get_current_summoner, get_summoner_by_name};
+use crate::queues::{self, QueueCategory};
+use crate::{storage, CommandError};
 
 // Constante pour le port Live Client API
 const LIVE_CLIENT_API_PORT: u16 = 2999;
 
+/// Timeout des requetes de l'overlay vers le Live Client Data API
+const LIVE_CLIENT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Label de la fenetre overlay (voir tauri.conf.json)
+const OVERLAY_WINDOW_LABEL: &str = "cs-overlay";
+
+/// Fichier de sauvegarde de la configuration de l'overlay
+const OVERLAY_CONFIG_FILE: &str = "overlay-config.json";
+
+/// Duree d'un bloc du mode drill : les cibles repartent de zero a chaque bloc
+const DRILL_INTERVAL_SECS: u32 = 600;
+
+/// Cible CS/min par defaut en ARAM (pas de phase de lane, farm partage)
+const ARAM_DEFAULT_CS_TARGET: f64 = 4.5;
+
+/// Position verticale de l'overlay quand il est place en haut de l'ecran
+const OVERLAY_TOP_POSITION_Y: i32 = 10;
+
+/// Taille de police de reference de l'overlay (px)
+const DEFAULT_FONT_SIZE: u32 = 14;
+const MIN_FONT_SIZE: u32 = 10;
+const MAX_FONT_SIZE: u32 = 24;
+
+/// Palette de couleurs de l'overlay (en avance / en retard sur la cible)
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum OverlayPalette {
+    /// Vert -> rouge
+    #[default]
+    Default,
+    /// Bleu -> orange, lisible sans distinction rouge/vert (deuteranopie)
+    Deuteranopia,
+    /// Bleu -> violet, evite les rouges sombres (protanopie)
+    Protanopia,
+}
+
+/// Couleurs du delta CS/min, de la meilleure a la pire situation
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DeltaColors {
+    pub excellent: String,
+    pub good: String,
+    pub warning: String,
+    pub danger: String,
+    pub critical: String,
+}
+
+impl OverlayPalette {
+    pub fn colors(self) -> DeltaColors {
+        let [excellent, good, warning, danger, critical] = match self {
+            OverlayPalette::Default => ["#22c55e", "#84cc16", "#eab308", "#f97316", "#ef4444"],
+            // Palette Okabe-Ito
+            OverlayPalette::Deuteranopia => ["#0072b2", "#56b4e9", "#f0e442", "#e69f00", "#d55e00"],
+            OverlayPalette::Protanopia => ["#0072b2", "#56b4e9", "#f0e442", "#e69f00", "#cc79a7"],
+        };
+        DeltaColors {
+            excellent: excellent.to_string(),
+            good: good.to_string(),
+            warning: warning.to_string(),
+            danger: danger.to_string(),
+            critical: critical.to_string(),
+        }
+    }
+}
+
+/// Style de l'overlay envoye a la fenetre (`cs-overlay-style`)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OverlayStyle {
+    pub font_size: u32,
+    pub palette: OverlayPalette,
+    pub colors: DeltaColors,
+}
+
 /// Configuration de l'overlay sauvegardee
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
 pub struct OverlayConfig {
     pub position_x: i32,
     pub position_y: i32,
@@ -38,6 +122,20 @@ pub struct OverlayConfig {
     pub click_through: bool,
     pub role: String,
     pub rank: String,
+    /// Cible CS/min en ARAM (`None` = cibles CS desactivees en ARAM)
+    pub aram_cs_target: Option<f64>,
+    /// Deplace l'overlay en haut de l'ecran pendant les parties ARAM
+    pub aram_position_top: bool,
+    /// Affiche automatiquement l'overlay au debut d'une partie
+    pub auto_show: bool,
+    /// Categories de file dans lesquelles l'overlay est desactive
+    pub disabled_queue_categories: Vec<QueueCategory>,
+    /// Taille de police de reference (px)
+    pub font_size: u32,
+    /// Palette de couleurs (daltonisme)
+    pub palette: OverlayPalette,
+    /// Mode drill dans l'outil d'entrainement : cibles remises a zero toutes les 10 minutes
+    pub drill_mode: bool,
 }
 
 impl Default for OverlayConfig {
@@ -49,35 +147,422 @@ impl Default for OverlayConfig {
             click_through: true,
             role: "mid".to_string(),
             rank: "platinum".to_string(),
+            aram_cs_target: Some(ARAM_DEFAULT_CS_TARGET),
+            aram_position_top: false,
+            auto_show: true,
+            disabled_queue_categories: Vec::new(),
+            font_size: DEFAULT_FONT_SIZE,
+            palette: OverlayPalette::Default,
+            drill_mode: false,
+        }
+    }
+}
+
+impl OverlayConfig {
+    /// Ramene les valeurs hors bornes dans un intervalle utilisable
+    fn normalized(mut self) -> Self {
+        self.opacity = if self.opacity.is_finite() {
+            self.opacity.clamp(0.1, 1.0)
+        } else {
+            Self::default().opacity
+        };
+        self.role = self.role.trim().to_lowercase();
+        self.rank = self.rank.trim().to_lowercase();
+        self.aram_cs_target = self.aram_cs_target.filter(|t| t.is_finite() && *t > 0.0);
+        self.font_size = self.font_size.clamp(MIN_FONT_SIZE, MAX_FONT_SIZE);
+        self
+    }
+
+    /// Style a appliquer dans la fenetre overlay
+    pub fn style(&self) -> OverlayStyle {
+        OverlayStyle {
+            font_size: self.font_size,
+            palette: self.palette,
+            colors: self.palette.colors(),
+        }
+    }
+}
+
+/// Comportement de l'overlay selon la file de la partie en cours
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OverlayMode {
+    pub queue_id: i32,
+    pub category: QueueCategory,
+    pub is_aram: bool,
+    /// Overlay active pour cette file (voir `disabled_queue_categories`)
+    pub enabled: bool,
+    /// L'overlay doit etre affiche automatiquement (auto-show et file active)
+    pub auto_show: bool,
+    /// Afficher la comparaison a la cible CS/min
+    pub cs_target_enabled: bool,
+    /// Cible CS/min imposee par la file (`None` = benchmark du role)
+    pub target_cs_per_minute: Option<f64>,
+    /// Cibles CS par etape du role joue (vide = cible CS/min)
+    pub cs_goals: Vec<CsGoal>,
+    /// CS moyen par minute du rang et du role (FocusApi), reference sans cibles par etape
+    pub benchmark_curve: Vec<CsGoal>,
+    /// Partie d'entrainement (outil d'entrainement), exclue des statistiques
+    pub training: bool,
+    /// Duree des blocs du mode drill (`None` = pas de drill)
+    pub drill_interval_secs: Option<u32>,
+}
+
+impl Default for OverlayMode {
+    fn default() -> Self {
+        Self {
+            queue_id: 0,
+            category: QueueCategory::Other,
+            is_aram: false,
+            enabled: true,
+            auto_show: false,
+            cs_target_enabled: true,
+            target_cs_per_minute: None,
+            cs_goals: Vec::new(),
+            benchmark_curve: Vec::new(),
+            training: false,
+            drill_interval_secs: None,
+        }
+    }
+}
+
+impl OverlayMode {
+    /// Determine le comportement de l'overlay pour une file donnee
+    pub fn for_queue(queue_id: i32, game_mode: &str, config: &OverlayConfig) -> Self {
+        let category = QueueCategory::from_queue(queue_id, game_mode);
+        let enabled = !config.disabled_queue_categories.contains(&category);
+        let auto_show = enabled && config.auto_show;
+
+        if queues::is_practice_tool(game_mode) {
+            return Self {
+                queue_id,
+                category,
+                enabled,
+                auto_show,
+                training: true,
+                drill_interval_secs: config.drill_mode.then_some(DRILL_INTERVAL_SECS),
+                ..Self::default()
+            };
+        }
+
+        if category != QueueCategory::Aram {
+            return Self {
+                queue_id,
+                category,
+                enabled,
+                auto_show,
+                ..Self::default()
+            };
+        }
+
+        Self {
+            queue_id,
+            category,
+            is_aram: true,
+            enabled,
+            auto_show,
+            cs_target_enabled: config.aram_cs_target.is_some(),
+            target_cs_per_minute: config.aram_cs_target,
+            cs_goals: Vec::new(),
+            benchmark_curve: Vec::new(),
+            training: false,
+            drill_interval_secs: None,
         }
     }
 }
 
+/// Applique le mode de la file en cours a l'overlay (position + evenement).
+///
+/// Appele par le GameWatcher quand la partie demarre, avec le role de la
+/// selection de champion (sinon celui de la configuration). Les cibles CS
+/// par etape du role et la courbe du rang ne s'appliquent que si la file
+/// n'impose pas de cible.
+pub fn apply_queue_mode(app: &AppHandle, queue_id: i32, game_mode: &str, role: Option<&str>) -> OverlayMode {
+    let config = app
+        .try_state::<OverlayConfigStore>()
+        .map(|store| store.get())
+        .unwrap_or_default();
+    let mut mode = OverlayMode::for_queue(queue_id, game_mode, &config);
+    if mode.cs_target_enabled && mode.target_cs_per_minute.is_none() {
+        let settings = app
+            .try_state::<crate::settings::SettingsStore>()
+            .map(|store| store.get())
+            .unwrap_or_default();
+        let role = role.unwrap_or(&config.role);
+        mode.cs_goals = settings.cs_goals.goals_for(Some(role));
+        mode.benchmark_curve = crate::rank_benchmarks::cached_curve(app, &config.rank, role);
+    }
+    // --no-overlay : jamais d'affichage automatique pendant cette session
+    if crate::cli::options().no_overlay {
+        mode.auto_show = false;
+    }
+
+    if let Some(window) = app.get_webview_window(OVERLAY_WINDOW_LABEL) {
+        use tauri::PhysicalPosition;
+        let y = if mode.is_aram && config.aram_position_top {
+            OVERLAY_TOP_POSITION_Y
+        } else {
+            config.position_y
+        };
+        let _ = window.set_position(PhysicalPosition::new(config.position_x, y));
+    }
+
+    if let Some(emitter) = app.try_state::<OverlayEmitter>() {
+        emitter.set_mode(app, mode.clone());
+    }
+    mode
+}
+
+/// Configuration de l'overlay partagee dans l'etat Tauri.
+///
+/// Chargee depuis le disque au demarrage, sauvegardee a chaque modification.
+#[derive(Debug, Default)]
+pub struct OverlayConfigStore {
+    config: Mutex<OverlayConfig>,
+}
+
+impl OverlayConfigStore {
+    /// Charge la configuration sauvegardee (ou les valeurs par defaut)
+    pub fn load(app: &AppHandle) -> Self {
+        let config: OverlayConfig = storage::read_json(app, OVERLAY_CONFIG_FILE);
+        Self {
+            config: Mutex::new(config.normalized()),
+        }
+    }
+
+    /// Retourne une copie de la configuration courante
+    pub fn get(&self) -> OverlayConfig {
+        self.config
+            .lock()
+            .map(|config| config.clone())
+            .unwrap_or_default()
+    }
+
+    /// Remplace et sauvegarde la configuration
+    pub fn set(&self, app: &AppHandle, config: OverlayConfig) -> Result<OverlayConfig, String> {
+        let config = config.normalized();
+        storage::write_json(app, OVERLAY_CONFIG_FILE, &config)?;
+        let mut current = self.config.lock().map_err(|e| e.to_string())?;
+        *current = config.clone();
+        Ok(config)
+    }
+}
+
+/// Emetteur differentiel vers la fenetre overlay.
+///
+/// Garde en memoire, pour chaque evenement, l'etat connu de l'overlay
+/// (fusion de tous les payloads deja envoyes) et n'emet que les champs
+/// qui ont change. Si rien n'a change, aucun evenement n'est emis.
+///
+/// Cote overlay, chaque payload recu est donc un patch a fusionner
+/// dans l'etat courant.
+#[derive(Debug, Default)]
+pub struct OverlayEmitter {
+    sent: Mutex<HashMap<String, serde_json::Value>>,
+    /// Mode courant (file de la partie), renvoye a chaque affichage
+    mode: Mutex<OverlayMode>,
+}
+
+impl OverlayEmitter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Emet uniquement les champs modifies de `payload` vers l'overlay
+    /// (`cs-overlay-update`).
+    ///
+    /// Retourne `Ok(false)` si l'evenement a ete ignore (aucun changement).
+    pub fn emit_delta<S: Serialize>(&self, app: &AppHandle, payload: &S) -> Result<bool, String> {
+        let next = serde_json::to_value(payload).map_err(|e| e.to_string())?;
+
+        let patch = {
+            let mut sent = self.sent.lock().map_err(|e| e.to_string())?;
+            let known = sent
+                .entry(CS_OVERLAY_UPDATE_EVENT.to_string())
+                .or_insert(serde_json::Value::Null);
+            match diff_payload(known, &next) {
+                Some(patch) => {
+                    merge_payload(known, &patch);
+                    patch
+                }
+                None => return Ok(false),
+            }
+        };
+
+        AppEvent::CsOverlayUpdate(&patch).emit_to(app, OVERLAY_WINDOW_LABEL)?;
+        Ok(true)
+    }
+
+    /// Change le mode de l'overlay et l'envoie (`cs-overlay-mode`).
+    ///
+    /// Envoye a toutes les fenetres : la fenetre principale s'en sert pour
+    /// l'affichage automatique de l'overlay.
+    pub fn set_mode(&self, app: &AppHandle, mode: OverlayMode) {
+        if let Ok(mut current) = self.mode.lock() {
+            *current = mode.clone();
+        }
+        let _ = AppEvent::CsOverlayMode(&mode).emit(app);
+    }
+
+    /// Renvoie le mode courant a l'overlay (apres un reaffichage)
+    fn resend_mode(&self, app: &AppHandle) {
+        if let Ok(mode) = self.mode.lock() {
+            let _ = AppEvent::CsOverlayMode(&mode).emit_to(app, OVERLAY_WINDOW_LABEL);
+        }
+    }
+
+    /// Oublie l'etat connu de l'overlay : le prochain envoi sera complet.
+    ///
+    /// A appeler quand la fenetre est (re)affichee ou qu'une partie se termine.
+    pub fn reset(&self) {
+        if let Ok(mut sent) = self.sent.lock() {
+            sent.clear();
+        }
+    }
+}
+
+/// Calcule le patch a envoyer pour passer de `known` a `next`.
+///
+/// Seuls les champs presents dans `next` sont compares (pas de suppression) ;
+/// les objets imbriques sont compares recursivement.
+fn diff_payload(known: &serde_json::Value, next: &serde_json::Value) -> Option<serde_json::Value> {
+    match (known, next) {
+        (serde_json::Value::Object(known_map), serde_json::Value::Object(next_map)) => {
+            let mut patch = serde_json::Map::new();
+            for (key, value) in next_map {
+                match known_map.get(key) {
+                    Some(previous) => {
+                        if let Some(sub_patch) = diff_payload(previous, value) {
+                            patch.insert(key.clone(), sub_patch);
+                        }
+                    }
+                    None => {
+                        patch.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+            if patch.is_empty() {
+                None
+            } else {
+                Some(serde_json::Value::Object(patch))
+            }
+        }
+        _ if known == next => None,
+        _ => Some(next.clone()),
+    }
+}
+
+/// Applique un patch (produit par `diff_payload`) sur l'etat connu.
+fn merge_payload(known: &mut serde_json::Value, patch: &serde_json::Value) {
+    match (known, patch) {
+        (serde_json::Value::Object(known_map), serde_json::Value::Object(patch_map)) => {
+            for (key, value) in patch_map {
+                match known_map.get_mut(key) {
+                    Some(previous) => merge_payload(previous, value),
+                    None => {
+                        known_map.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (known, patch) => *known = patch.clone(),
+    }
+}
+
+/// Retourne la configuration complete de l'overlay.
+#[tauri::command]
+pub async fn get_overlay_config(
+    store: tauri::State<'_, OverlayConfigStore>,
+) -> Result<OverlayConfig, String> {
+    Ok(store.get())
+}
+
+/// Remplace la configuration de l'overlay, la sauvegarde et l'applique.
+///
+/// La position et le click-through sont appliques directement a la fenetre,
+/// puis la configuration est envoyee a l'overlay (`cs-overlay-config`).
+#[tauri::command]
+pub async fn set_overlay_config(
+    app: AppHandle,
+    store: tauri::State<'_, OverlayConfigStore>,
+    config: OverlayConfig,
+) -> Result<OverlayConfig, String> {
+    let config = store.set(&app, config)?;
+
+    if let Some(window) = app.get_webview_window(OVERLAY_WINDOW_LABEL) {
+        use tauri::PhysicalPosition;
+        window
+            .set_position(PhysicalPosition::new(config.position_x, config.position_y))
+            .map_err(|e| e.to_string())?;
+        window
+            .set_ignore_cursor_events(config.click_through)
+            .map_err(|e| e.to_string())?;
+    }
+
+    AppEvent::CsOverlayConfig(&config).emit_to(&app, OVERLAY_WINDOW_LABEL)?;
+    AppEvent::CsOverlayStyle(&config.style()).emit_to(&app, OVERLAY_WINDOW_LABEL)?;
+
+    Ok(config)
+}
+
+/// Fenetre de l'overlay
+fn overlay_window(app: &AppHandle) -> Result<tauri::WebviewWindow, AppError> {
+    app.get_webview_window(OVERLAY_WINDOW_LABEL)
+        .ok_or_else(|| AppError::Overlay("Overlay window not found".to_string()))
+}
+
+fn overlay_error(err: tauri::Error) -> AppError {
+    AppError::Overlay(err.to_string())
+}
+
 /// Affiche l'overlay CS.
 ///
 /// # Compliance Note
 /// Cette commande affiche simplement une fenetre d'information.
 /// Elle ne modifie pas le jeu et n'envoie aucun input.
 #[tauri::command]
-pub async fn show_cs_overlay(app: AppHandle) -> Result<(), String> {
-    if let Some(window) = app.get_webview_window("cs-overlay") {
-        window.show().map_err(|e| e.to_string())?;
-        // Ne pas prendre le focus pour ne pas interrompre le jeu
-        Ok(())
-    } else {
-        Err("Overlay window not found".to_string())
-    }
+pub async fn show_cs_overlay(
+    app: AppHandle,
+    emitter: tauri::State<'_, OverlayEmitter>,
+    store: tauri::State<'_, OverlayConfigStore>,
+) -> Result<(), CommandError> {
+    overlay_window(&app)?.show().map_err(overlay_error)?;
+    crate::telemetry::record(&app, crate::telemetry::Metric::OverlayShown);
+    // La fenetre doit recevoir un etat complet au prochain envoi
+    emitter.reset();
+    emitter.resend_mode(&app);
+    let _ = AppEvent::CsOverlayStyle(&store.get().style()).emit_to(&app, OVERLAY_WINDOW_LABEL);
+    // Ne pas prendre le focus pour ne pas interrompre le jeu
+    Ok(())
 }
 
 /// Cache l'overlay CS.
 #[tauri::command]
-pub async fn hide_cs_overlay(app: AppHandle) -> Result<(), String> {
-    if let Some(window) = app.get_webview_window("cs-overlay") {
+pub async fn hide_cs_overlay(app: AppHandle) -> Result<(), CommandError> {
+    overlay_window(&app)?.hide().map_err(overlay_error)?;
+    Ok(())
+}
+
+/// Affiche ou cache l'overlay CS (menu du tray). Retourne true s'il est affiche.
+pub fn toggle_overlay(app: &AppHandle) -> Result<bool, String> {
+    let window = app
+        .get_webview_window(OVERLAY_WINDOW_LABEL)
+        .ok_or("Overlay window not found")?;
+    if window.is_visible().map_err(|e| e.to_string())? {
         window.hide().map_err(|e| e.to_string())?;
-        Ok(())
-    } else {
-        Err("Overlay window not found".to_string())
+        return Ok(false);
     }
+
+    window.show().map_err(|e| e.to_string())?;
+    crate::telemetry::record(app, crate::telemetry::Metric::OverlayShown);
+    if let Some(emitter) = app.try_state::<OverlayEmitter>() {
+        emitter.reset();
+        emitter.resend_mode(app);
+    }
+    if let Some(store) = app.try_state::<OverlayConfigStore>() {
+        let _ = AppEvent::CsOverlayStyle(&store.get().style()).emit_to(app, OVERLAY_WINDOW_LABEL);
+    }
+    Ok(true)
 }
 
 /// Definit le mode click-through (les clics passent a travers la fenetre).
@@ -85,29 +570,21 @@ pub async fn hide_cs_overlay(app: AppHandle) -> Result<(), String> {
 /// Quand active, l'overlay devient completement non-interactif,
 /// permettant de cliquer sur le jeu en dessous.
 #[tauri::command]
-pub async fn set_overlay_click_through(app: AppHandle, enabled: bool) -> Result<(), String> {
-    if let Some(window) = app.get_webview_window("cs-overlay") {
-        window
-            .set_ignore_cursor_events(enabled)
-            .map_err(|e| e.to_string())?;
-        Ok(())
-    } else {
-        Err("Overlay window not found".to_string())
-    }
+pub async fn set_overlay_click_through(app: AppHandle, enabled: bool) -> Result<(), CommandError> {
+    overlay_window(&app)?
+        .set_ignore_cursor_events(enabled)
+        .map_err(overlay_error)?;
+    Ok(())
 }
 
 /// Deplace l'overlay a une nouvelle position.
 #[tauri::command]
-pub async fn move_overlay(app: AppHandle, x: i32, y: i32) -> Result<(), String> {
-    if let Some(window) = app.get_webview_window("cs-overlay") {
-        use tauri::PhysicalPosition;
-        window
-            .set_position(PhysicalPosition::new(x, y))
-            .map_err(|e| e.to_string())?;
-        Ok(())
-    } else {
-        Err("Overlay window not found".to_string())
-    }
+pub async fn move_overlay(app: AppHandle, x: i32, y: i32) -> Result<(), CommandError> {
+    use tauri::PhysicalPosition;
+    overlay_window(&app)?
+        .set_position(PhysicalPosition::new(x, y))
+        .map_err(overlay_error)?;
+    Ok(())
 }
 
 /// Verifie si une partie est en cours via la Live Client Data API.
@@ -123,40 +600,38 @@ pub async fn move_overlay(app: AppHandle, x: i32, y: i32) -> Result<(), String>
 /// Le port est bien 2999 (pas 29990 comme mentionné dans certaines docs obsolètes)
 #[tauri::command]
 pub async fn is_game_active() -> Result<bool, String> {
-    let client = reqwest::Client::builder()
-        .danger_accept_invalid_certs(true) // Certificat auto-signe de Riot (localhost uniquement)
-        .timeout(std::time::Duration::from_secs(2))
-        .build()
-        .map_err(|e| e.to_string())?;
+    // Client partage (certificat auto-signe de Riot, localhost uniquement)
+    let client = crate::http_clients::live_client().map_err(|e| e.to_string())?;
 
     let url = format!(
         "https://127.0.0.1:{}/liveclientdata/gamestats",
         LIVE_CLIENT_API_PORT
     );
 
-    match client.get(&url).send().await {
+    match client.get(&url).timeout(LIVE_CLIENT_TIMEOUT).send().await {
         Ok(response) => Ok(response.status().is_success()),
         Err(_) => Ok(false), // Pas de partie en cours ou API inaccessible
     }
 }
 
-/// Recupere le PUUID du joueur actif depuis le Live Client Data API.
+/// Recupere le PUUID du joueur actif.
+///
+/// Le Live Client Data API n'expose pas le PUUID : on recupere le Riot ID
+/// du joueur actif puis on le recoupe avec le LCU (invocateur connecte,
+/// sinon recherche par nom via /lol-summoner/v1/summoners).
 ///
 /// Necessaire pour appeler l'endpoint /live/cs-stats de FocusApi.
 #[tauri::command]
 pub async fn get_active_player_puuid() -> Result<Option<String>, String> {
-    let client = reqwest::Client::builder()
-        .danger_accept_invalid_certs(true)
-        .timeout(std::time::Duration::from_secs(2))
-        .build()
-        .map_err(|e| e.to_string())?;
+    let client = crate::http_clients::live_client().map_err(|e| e.to_string())?;
 
-    // D'abord on recupere le nom du joueur actif
-    let active_player_response = match client
-        .get("https://127.0.0.1:2999/liveclientdata/activeplayername")
-        .send()
-        .await
-    {
+    // D'abord on recupere le nom du joueur actif ("Nom#TAG")
+    let active_player_url = format!(
+        "https://127.0.0.1:{}/liveclientdata/activeplayername",
+        LIVE_CLIENT_API_PORT
+    );
+
+    let active_player_response = match client.get(&active_player_url).timeout(LIVE_CLIENT_TIMEOUT).send().await {
         Ok(resp) if resp.status().is_success() => resp,
         _ => return Ok(None),
     };
@@ -166,40 +641,24 @@ pub async fn get_active_player_puuid() -> Result<Option<String>, String> {
         .await
         .map_err(|e| e.to_string())?;
 
-    // Ensuite on recupere la liste des joueurs pour trouver le PUUID
-    let players_response = match client
-        .get("https://127.0.0.1:2999/liveclientdata/playerlist")
-        .send()
-        .await
-    {
-        Ok(resp) if resp.status().is_success() => resp,
-        _ => return Ok(None),
+    // Ensuite on recoupe avec le LCU
+    let connection = match find_lockfile().await {
+        Ok(conn) => conn,
+        Err(_) => return Ok(None),
     };
 
-    #[derive(Deserialize)]
-    struct PlayerInfo {
-        #[serde(rename = "riotIdGameName")]
-        riot_id_game_name: Option<String>,
-        #[serde(rename = "summonerName")]
-        summoner_name: String,
-        // Note: Le PUUID n'est pas directement expose par le Live Client API
-        // On devra utiliser une autre methode ou l'obtenir du LCU
+    // Cas nominal : le joueur actif est l'invocateur connecte au client
+    if let Ok(summoner) = get_current_summoner(&connection).await {
+        if summoner.matches_player_name(&active_player_name) {
+            return Ok(Some(summoner.puuid));
+        }
     }
 
-    let players: Vec<PlayerInfo> = players_response
-        .json()
-        .await
-        .map_err(|e| e.to_string())?;
-
-    // Trouver le joueur actif
-    let _active_player = players.iter().find(|p| {
-        p.summoner_name == active_player_name
-            || p.riot_id_game_name.as_ref() == Some(&active_player_name)
-    });
-
-    // Note: Le Live Client Data API ne fournit pas directement le PUUID
-    // On retourne None pour l'instant, le frontend utilisera le LCU pour l'obtenir
-    Ok(None)
+    // Sinon (spectateur, compte different...) : recherche par Riot ID
+    match get_summoner_by_name(&connection, &active_player_name).await {
+        Ok(summoner) if !summoner.puuid.is_empty() => Ok(Some(summoner.puuid)),
+        _ => Ok(None),
+    }
 }
 
 /// Recupere les stats CS en temps reel depuis le Live Client Data API local.
@@ -209,12 +668,9 @@ pub async fn get_active_player_puuid() -> Result<Option<String>, String> {
 /// GET https://127.0.0.1:2999/liveclientdata/activeplayer
 /// GET https://127.0.0.1:2999/liveclientdata/gamestats
 #[tauri::command]
-pub async fn get_live_cs_stats() -> Result<Option<LiveCsData>, String> {
-    let client = reqwest::Client::builder()
-        .danger_accept_invalid_certs(true) // Sécurisé : connexion localhost uniquement
-        .timeout(std::time::Duration::from_secs(2))
-        .build()
-        .map_err(|e| e.to_string())?;
+pub async fn get_live_cs_stats() -> Result<Option<LiveCsData>, CommandError> {
+    let live_client_error = |e: reqwest::Error| AppError::LiveClient(e.to_string());
+    let client = crate::http_clients::live_client().map_err(live_client_error)?; // Connexion localhost uniquement
 
     // Recuperer les stats du joueur actif
     let active_player_url = format!(
@@ -222,7 +678,7 @@ pub async fn get_live_cs_stats() -> Result<Option<LiveCsData>, String> {
         LIVE_CLIENT_API_PORT
     );
     
-    let active_player_response = match client.get(&active_player_url).send().await {
+    let active_player_response = match client.get(&active_player_url).timeout(LIVE_CLIENT_TIMEOUT).send().await {
         Ok(resp) if resp.status().is_success() => resp,
         _ => return Ok(None),
     };
@@ -230,7 +686,7 @@ pub async fn get_live_cs_stats() -> Result<Option<LiveCsData>, String> {
     let active_player: serde_json::Value = active_player_response
         .json()
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(live_client_error)?;
 
     // Recuperer le temps de jeu
     let game_stats_url = format!(
@@ -238,7 +694,7 @@ pub async fn get_live_cs_stats() -> Result<Option<LiveCsData>, String> {
         LIVE_CLIENT_API_PORT
     );
     
-    let game_stats_response = match client.get(&game_stats_url).send().await {
+    let game_stats_response = match client.get(&game_stats_url).timeout(LIVE_CLIENT_TIMEOUT).send().await {
         Ok(resp) if resp.status().is_success() => resp,
         _ => return Ok(None),
     };
@@ -246,7 +702,7 @@ pub async fn get_live_cs_stats() -> Result<Option<LiveCsData>, String> {
     let game_stats: serde_json::Value = game_stats_response
         .json()
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(live_client_error)?;
 
     // Extraire les donnees
     let cs = active_player
@@ -292,8 +748,138 @@ pub struct LiveCsData {
 }
 
 /// Envoie un evenement a la fenetre overlay pour mettre a jour les stats.
+///
+/// Seuls les champs modifies depuis le dernier envoi sont transmis ;
+/// l'evenement est ignore si rien n'a change.
 #[tauri::command]
-pub async fn emit_cs_update(app: AppHandle, stats: serde_json::Value) -> Result<(), String> {
-    app.emit("cs-overlay-update", stats)
-        .map_err(|e: tauri::Error| e.to_string())
+pub async fn emit_cs_update(
+    app: AppHandle,
+    emitter: tauri::State<'_, OverlayEmitter>,
+    stats: serde_json::Value,
+) -> Result<(), String> {
+    emitter.emit_delta(&app, &stats)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_overlay_config_normalized() {
+        let config = OverlayConfig {
+            opacity: 3.0,
+            role: " Mid ".to_string(),
+            ..OverlayConfig::default()
+        }
+        .normalized();
+
+        assert_eq!(config.opacity, 1.0);
+        assert_eq!(config.role, "mid");
+
+        let config = OverlayConfig {
+            font_size: 200,
+            ..OverlayConfig::default()
+        }
+        .normalized();
+        assert_eq!(config.font_size, MAX_FONT_SIZE);
+    }
+
+    #[test]
+    fn test_color_blind_palettes_avoid_red_green() {
+        let colors = OverlayPalette::Deuteranopia.colors();
+        assert_ne!(colors.excellent, OverlayPalette::Default.colors().excellent);
+        assert_ne!(colors.critical, OverlayPalette::Default.colors().critical);
+
+        let style = OverlayConfig {
+            palette: OverlayPalette::Protanopia,
+            ..OverlayConfig::default()
+        }
+        .style();
+        assert_eq!(style.colors, OverlayPalette::Protanopia.colors());
+    }
+
+    #[test]
+    fn test_overlay_config_partial_json_uses_defaults() {
+        let config: OverlayConfig = serde_json::from_str(r#"{ "role": "top" }"#).unwrap();
+
+        assert_eq!(config.role, "top");
+        assert_eq!(config.position_x, OverlayConfig::default().position_x);
+    }
+
+    #[test]
+    fn test_overlay_mode_for_aram() {
+        let config = OverlayConfig::default();
+
+        let mode = OverlayMode::for_queue(450, "ARAM", &config);
+        assert!(mode.is_aram);
+        assert_eq!(mode.target_cs_per_minute, Some(ARAM_DEFAULT_CS_TARGET));
+
+        let config = OverlayConfig {
+            aram_cs_target: None,
+            ..config
+        };
+        assert!(!OverlayMode::for_queue(450, "", &config).cs_target_enabled);
+
+        let ranked = OverlayMode::for_queue(420, "CLASSIC", &config);
+        assert!(!ranked.is_aram);
+        assert!(ranked.cs_target_enabled);
+    }
+
+    #[test]
+    fn test_overlay_mode_practice_tool() {
+        let config = OverlayConfig::default();
+        let mode = OverlayMode::for_queue(0, "PRACTICETOOL", &config);
+        assert!(mode.training);
+        assert_eq!(mode.drill_interval_secs, None);
+
+        let config = OverlayConfig {
+            drill_mode: true,
+            ..config
+        };
+        assert_eq!(OverlayMode::for_queue(0, "PRACTICETOOL", &config).drill_interval_secs, Some(DRILL_INTERVAL_SECS));
+        assert!(!OverlayMode::for_queue(0, "CLASSIC", &config).training);
+    }
+
+    #[test]
+    fn test_overlay_mode_disabled_queues() {
+        let config = OverlayConfig {
+            disabled_queue_categories: vec![QueueCategory::Aram, QueueCategory::Custom],
+            ..OverlayConfig::default()
+        };
+
+        let aram = OverlayMode::for_queue(450, "ARAM", &config);
+        assert!(!aram.enabled);
+        assert!(!aram.auto_show);
+
+        let ranked = OverlayMode::for_queue(420, "CLASSIC", &config);
+        assert!(ranked.enabled);
+        assert!(ranked.auto_show);
+    }
+
+    #[test]
+    fn test_diff_payload_only_changed_fields() {
+        let known = json!({ "currentCs": 42, "csPerMinute": 6.5, "role": "mid" });
+        let next = json!({ "currentCs": 43, "csPerMinute": 6.5, "role": "mid" });
+
+        assert_eq!(diff_payload(&known, &next), Some(json!({ "currentCs": 43 })));
+    }
+
+    #[test]
+    fn test_diff_payload_unchanged_is_skipped() {
+        let known = json!({ "currentCs": 42, "nested": { "a": 1 } });
+        let next = json!({ "nested": { "a": 1 } });
+
+        assert_eq!(diff_payload(&known, &next), None);
+    }
+
+    #[test]
+    fn test_merge_payload_keeps_previous_fields() {
+        let mut known = serde_json::Value::Null;
+        merge_payload(&mut known, &json!({ "currentCs": 10, "nested": { "a": 1 } }));
+        merge_payload(&mut known, &json!({ "nested": { "b": 2 } }));
+
+        assert_eq!(known, json!({ "currentCs": 10, "nested": { "a": 1, "b": 2 } }));
+    }
 }
@@ -0,0 +1,122 @@
+//! Per-patch caches of FocusApi statistics.
+//!
+//! Matchups, rank benchmarks and ban suggestions only hold for the patch
+//! they were computed on. A `PatchCache` keeps them by key for the current
+//! patch and drops them on the first access after a patch change. A cache
+//! created with `on_disk` is also written to a file of the cache directory
+//! and loaded from it on first use; `in_memory` ones start empty with each
+//! launch.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+use crate::{champions, storage};
+
+/// Entries of one patch, as stored on disk
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct PatchEntries<T> {
+    patch: String,
+    entries: BTreeMap<String, T>,
+}
+
+impl<T> Default for PatchEntries<T> {
+    fn default() -> Self {
+        Self {
+            patch: String::new(),
+            entries: BTreeMap::new(),
+        }
+    }
+}
+
+impl<T> PatchEntries<T> {
+    /// Drop the entries if they were cached on another patch
+    fn roll_over(&mut self, patch: String) {
+        if self.patch != patch {
+            *self = Self {
+                patch,
+                entries: BTreeMap::new(),
+            };
+        }
+    }
+}
+
+/// Values of the current patch, by key
+pub struct PatchCache<T> {
+    /// Cache file, relative to the cache directory (None = memory only)
+    file: Option<&'static str>,
+    /// Loaded on first use
+    entries: Mutex<Option<PatchEntries<T>>>,
+}
+
+impl<T: Clone + Serialize + DeserializeOwned> PatchCache<T> {
+    /// Cache kept in `file` of the cache directory
+    pub const fn on_disk(file: &'static str) -> Self {
+        Self {
+            file: Some(file),
+            entries: Mutex::new(None),
+        }
+    }
+
+    /// Cache lost when the app quits
+    pub const fn in_memory() -> Self {
+        Self {
+            file: None,
+            entries: Mutex::new(None),
+        }
+    }
+
+    fn load(&self, app: &AppHandle) -> PatchEntries<T> {
+        self.file
+            .and_then(|file| storage::cache_file_path(app, file).ok())
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    /// Run `f` on the entries of the current patch
+    fn with_entries<R>(&self, app: &AppHandle, f: impl FnOnce(&mut PatchEntries<T>) -> R) -> Option<R> {
+        let mut entries = self.entries.lock().ok()?;
+        let entries = entries.get_or_insert_with(|| self.load(app));
+        entries.roll_over(champions::patch_of(&champions::ddragon_version()));
+        Some(f(entries))
+    }
+
+    /// Cached value of `key` for the current patch
+    pub fn get(&self, app: &AppHandle, key: &str) -> Option<T> {
+        self.with_entries(app, |entries| entries.entries.get(key).cloned()).flatten()
+    }
+
+    /// Cache a value for the current patch (and save the file of an `on_disk` cache)
+    pub fn insert(&self, app: &AppHandle, key: String, value: T) -> Result<(), String> {
+        let saved = self.with_entries(app, |entries| {
+            entries.entries.insert(key, value);
+            let file = self.file?;
+            Some(serde_json::to_string(entries).map(|json| (file, json)).map_err(|e| e.to_string()))
+        });
+        if let Some((file, json)) = saved.flatten().transpose()? {
+            storage::write_atomic(&storage::cache_file_path(app, file)?, json.as_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roll_over() {
+        let json = r#"{"patch": "14.10", "entries": {"ahri/mid": 3}}"#;
+        let mut entries: PatchEntries<u32> = serde_json::from_str(json).unwrap();
+        entries.roll_over("14.10".to_string());
+        assert_eq!(entries.entries.get("ahri/mid"), Some(&3));
+
+        // A new patch starts empty
+        entries.roll_over("14.11".to_string());
+        assert_eq!((entries.patch.as_str(), entries.entries.len()), ("14.11", 0));
+    }
+}
@@ -0,0 +1,322 @@
+//! Imports queued while offline.
+//!
+//! When the League Client or FocusApi cannot be reached, a build the user
+//! asked to import is saved here instead of being lost. The queue is retried
+//! when connectivity returns (background check) or on demand with
+//! `process_pending_imports`. Queued imports go through `InFlightImport`
+//! like the others: they wait for an import in progress to finish, and
+//! `cancel_import` or a newer import cancels them (the cancelled build is
+//! dropped from the queue). Only one build is kept per champion and role:
+//! a newer request replaces the older one, since both would write the same
+//! rune page and item set. A build that could not be applied within
+//! `PENDING_IMPORT_TTL_SECS` is dropped: it was meant for a champion select
+//! long over, and would replace the pages the user has set up since.
+//!
+//! Every change of the queue is emitted as `pending-imports-changed`.
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+use crate::cancel::InFlightImport;
+use crate::events::AppEvent;
+use crate::lcu::{find_lockfile, ImportResult};
+use crate::sessions::now_unix_secs;
+use crate::error::AppError;
+use crate::{import_history, storage, CommandError, ImportPayloadRequest};
+
+/// File holding the queued imports
+const PENDING_IMPORTS_FILE: &str = "pending-imports.json";
+
+/// Maximum number of queued imports (oldest are dropped first)
+const MAX_PENDING_IMPORTS: usize = 20;
+
+/// Age after which a queued import is dropped instead of applied
+const PENDING_IMPORT_TTL_SECS: i64 = 10 * 60;
+
+/// Interval between connectivity checks while imports are queued
+const RETRY_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Serializes queue processing (manual and background)
+static PROCESSING: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+/// An import waiting for connectivity
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingImport {
+    /// Unix timestamp (seconds) at which the import was queued
+    pub queued_at: i64,
+    /// Error that caused the import to be queued
+    pub reason: String,
+    pub payload: ImportPayloadRequest,
+}
+
+/// Queued import as shown in the UI
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingImportSummary {
+    pub champion_key: String,
+    pub role: String,
+    pub queued_at: i64,
+    pub reason: String,
+}
+
+/// Payload of `pending-imports-changed` and `get_pending_imports`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingImportsState {
+    pub imports: Vec<PendingImportSummary>,
+    /// True while the queue is being processed
+    pub processing: bool,
+}
+
+/// Result of `process_pending_imports`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessPendingResult {
+    pub imported: Vec<ImportResult>,
+    /// Imports dropped because they failed for another reason than connectivity
+    pub failed: Vec<String>,
+    pub remaining: usize,
+}
+
+/// Whether an import error is a connectivity problem worth retrying later
+pub fn is_offline_error(error: &CommandError) -> bool {
+    AppError::is_offline_code(&error.code)
+}
+
+fn same_build(a: &ImportPayloadRequest, b: &ImportPayloadRequest) -> bool {
+    a.champion_key.eq_ignore_ascii_case(&b.champion_key) && a.role.eq_ignore_ascii_case(&b.role)
+}
+
+/// Drop the imports queued more than `PENDING_IMPORT_TTL_SECS` before `now`
+fn drop_expired(queue: &mut Vec<PendingImport>, now: i64) {
+    queue.retain(|import| {
+        let fresh = now - import.queued_at <= PENDING_IMPORT_TTL_SECS;
+        if !fresh {
+            log_info!(
+                "[pending_imports] Dropping expired import of {} ({})",
+                import.payload.champion_key, import.payload.role
+            );
+        }
+        fresh
+    });
+}
+
+/// The queued imports that have not expired
+fn load_queue(app: &AppHandle) -> Vec<PendingImport> {
+    let mut queue = storage::read_json(app, PENDING_IMPORTS_FILE);
+    drop_expired(&mut queue, now_unix_secs());
+    queue
+}
+
+fn save_queue(app: &AppHandle, queue: &[PendingImport], processing: bool) -> Result<(), String> {
+    storage::write_json(app, PENDING_IMPORTS_FILE, &queue)?;
+    let _ = AppEvent::PendingImportsChanged(&state_of(queue, processing)).emit(app);
+    Ok(())
+}
+
+fn state_of(queue: &[PendingImport], processing: bool) -> PendingImportsState {
+    PendingImportsState {
+        imports: queue
+            .iter()
+            .map(|import| PendingImportSummary {
+                champion_key: import.payload.champion_key.clone(),
+                role: import.payload.role.clone(),
+                queued_at: import.queued_at,
+                reason: import.reason.clone(),
+            })
+            .collect(),
+        processing,
+    }
+}
+
+/// Add an import to the queue, replacing a queued build for the same champion and role
+fn push_import(queue: &mut Vec<PendingImport>, import: PendingImport) {
+    queue.retain(|queued| !same_build(&queued.payload, &import.payload));
+    queue.push(import);
+    if queue.len() > MAX_PENDING_IMPORTS {
+        let excess = queue.len() - MAX_PENDING_IMPORTS;
+        queue.drain(..excess);
+    }
+}
+
+/// Queue an import that failed for lack of connectivity
+pub async fn enqueue(app: &AppHandle, payload: ImportPayloadRequest, reason: &CommandError) -> Result<(), String> {
+    let _guard = PROCESSING.lock().await;
+    let mut queue = load_queue(app);
+    push_import(
+        &mut queue,
+        PendingImport {
+            queued_at: now_unix_secs(),
+            reason: reason.message.clone(),
+            payload,
+        },
+    );
+    save_queue(app, &queue, false)
+}
+
+/// Result returned to the frontend for a queued import
+pub fn queued_result(reason: &CommandError) -> ImportResult {
+    ImportResult {
+        success: false,
+        runes_imported: false,
+        items_imported: false,
+        summoners_imported: false,
+        message: format!(
+            "Import queued, it will be applied if back online within {} minutes ({})",
+            PENDING_IMPORT_TTL_SECS / 60,
+            reason.message
+        ),
+        queued: true,
+    }
+}
+
+/// Import the queued builds in order.
+///
+/// Stops at the first connectivity error or cancellation, and while another
+/// import is in progress (the rest stays queued).
+pub async fn process_queue(app: &AppHandle) -> ProcessPendingResult {
+    let _guard = PROCESSING.lock().await;
+    let mut queue = load_queue(app);
+    let mut result = ProcessPendingResult {
+        imported: Vec::new(),
+        failed: Vec::new(),
+        remaining: queue.len(),
+    };
+    let Some(in_flight) = app.try_state::<InFlightImport>() else {
+        return result;
+    };
+    if queue.is_empty() {
+        return result;
+    }
+    let _ = AppEvent::PendingImportsChanged(&state_of(&queue, true)).emit(app);
+
+    while let Some(import) = queue.first().cloned() {
+        let Some(token) = in_flight.begin_if_idle() else {
+            break;
+        };
+        let import_result = crate::perform_import(app, &import.payload, &token).await;
+        in_flight.finish(&token);
+        if token.is_cancelled() {
+            log_info!("[pending_imports] Queued import of {} cancelled", import.payload.champion_key);
+            queue.remove(0);
+            break;
+        }
+        if import_result.as_ref().is_err_and(is_offline_error) {
+            break;
+        }
+        import_history::record_result(app, &import.payload, &import_result);
+        match import_result {
+            Ok(import_result) => result.imported.push(import_result),
+            Err(e) => result.failed.push(format!(
+                "{} ({}): {}",
+                import.payload.champion_key, import.payload.role, e.message
+            )),
+        }
+        queue.remove(0);
+    }
+
+    result.remaining = queue.len();
+    if let Err(e) = save_queue(app, &queue, false) {
+        log_warn!("[pending_imports] Failed to save queue: {}", e);
+    }
+    result
+}
+
+/// Retry queued imports whenever the League Client is reachable
+pub async fn run_pending_import_worker(app: AppHandle) {
+    loop {
+        tokio::time::sleep(RETRY_INTERVAL).await;
+        if load_queue(&app).is_empty() || find_lockfile().await.is_err() {
+            continue;
+        }
+        let result = process_queue(&app).await;
+        if !result.imported.is_empty() {
+            log_info!(
+                "[pending_imports] Applied {} queued import(s), {} remaining",
+                result.imported.len(),
+                result.remaining
+            );
+        }
+    }
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the imports waiting for connectivity.
+#[tauri::command]
+pub async fn get_pending_imports(app: AppHandle) -> Result<PendingImportsState, String> {
+    Ok(state_of(&load_queue(&app), PROCESSING.try_lock().is_err()))
+}
+
+/// Apply the queued imports now.
+#[tauri::command]
+pub async fn process_pending_imports(app: AppHandle) -> Result<ProcessPendingResult, String> {
+    Ok(process_queue(&app).await)
+}
+
+/// Drop every queued import.
+#[tauri::command]
+pub async fn clear_pending_imports(app: AppHandle) -> Result<(), String> {
+    let _guard = PROCESSING.lock().await;
+    save_queue(&app, &[], false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_server::import_request;
+
+    fn import(champion_key: &str, role: &str, queued_at: i64) -> PendingImport {
+        PendingImport {
+            queued_at,
+            reason: String::new(),
+            payload: ImportPayloadRequest {
+                champion_key: champion_key.to_string(),
+                role: role.to_string(),
+                ..import_request()
+            },
+        }
+    }
+
+    #[test]
+    fn test_push_import_replaces_same_build() {
+        let mut queue = Vec::new();
+        push_import(&mut queue, import("Ahri", "mid", 1));
+        push_import(&mut queue, import("Jinx", "adc", 2));
+        push_import(&mut queue, import("ahri", "MID", 3));
+
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue[0].payload.champion_key, "Jinx");
+        assert_eq!(queue[1].queued_at, 3);
+    }
+
+    #[test]
+    fn test_drop_expired() {
+        let now = 1_700_000_000;
+        let mut queue = vec![
+            import("Ahri", "mid", now - PENDING_IMPORT_TTL_SECS - 1),
+            import("Jinx", "adc", now - PENDING_IMPORT_TTL_SECS),
+            import("Zed", "mid", now),
+        ];
+        drop_expired(&mut queue, now);
+
+        let champions: Vec<&str> = queue.iter().map(|import| import.payload.champion_key.as_str()).collect();
+        assert_eq!(champions, ["Jinx", "Zed"]);
+    }
+
+    #[test]
+    fn test_is_offline_error() {
+        let error = |code: &str| CommandError {
+            code: code.to_string(),
+            message: String::new(),
+            retry_after_secs: None,
+        };
+        assert!(is_offline_error(&error("CLIENT_NOT_RUNNING")));
+        assert!(is_offline_error(&error("NETWORK_ERROR")));
+        assert!(!is_offline_error(&error("FOCUS_API_ERROR")));
+    }
+}
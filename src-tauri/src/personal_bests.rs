@@ -0,0 +1,254 @@
+//! Personal bests per champion and per role.
+//!
+//! The game watcher follows each live game with a `GameProgress` (CS at 10,
+//! 14 and 20 minutes, gold earned) and hands the finished game to
+//! `record_game`, which raises the bests of the champion and of the role
+//! played. `get_personal_bests` returns them, for display and for comparing a
+//! live game against them.
+//!
+//! The Live Client only exposes the current gold, so the gold earned is the
+//! sum of its increases between two polls (item sales count as income).
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tauri::AppHandle;
+
+use crate::db;
+
+/// Game time (seconds) of the first CS milestone
+const EARLY_STAGE_SECS: f64 = 600.0;
+
+/// Game time (seconds) of the second CS milestone
+const MID_STAGE_SECS: f64 = 1200.0;
+
+/// End of the laning phase (seconds), measured apart from the full-game CS/min
+const LANING_PHASE_SECS: f64 = 840.0;
+
+/// Longest gap between two polls for a milestone to be trusted
+const MAX_SAMPLE_GAP_SECS: f64 = 10.0;
+
+/// Games shorter than this (remakes, early surrenders) don't count for per-minute bests
+const MIN_GAME_SECS: f64 = 900.0;
+
+/// One personal best and the game it was set in
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PersonalBest {
+    pub value: f64,
+    pub game_id: i64,
+    /// Unix timestamp (seconds) at which the game ended
+    pub achieved_at: i64,
+}
+
+/// Personal bests of a champion or a role
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Bests {
+    pub cs_at_10: Option<PersonalBest>,
+    pub cs_at_20: Option<PersonalBest>,
+    pub cs_per_minute: Option<PersonalBest>,
+    pub gold_per_minute: Option<PersonalBest>,
+    /// Games counted
+    pub games: usize,
+}
+
+/// Personal bests of every champion and role
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PersonalBests {
+    /// By champion name
+    pub champions: BTreeMap<String, Bests>,
+    /// By role ("top", "jungle", "mid", "adc", "support")
+    pub roles: BTreeMap<String, Bests>,
+}
+
+/// A finished game, as measured by the watcher
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GameStats {
+    pub game_id: i64,
+    pub ended_at: i64,
+    pub champion_name: String,
+    /// Role from champion select, when known
+    pub role: Option<String>,
+    pub duration_secs: f64,
+    pub cs_at_10: Option<i32>,
+    pub cs_at_20: Option<i32>,
+    pub final_cs: i32,
+    /// None when the game was not followed from the start
+    pub gold_earned: Option<f64>,
+}
+
+impl GameStats {
+    fn per_minute(&self, total: f64) -> Option<f64> {
+        (self.duration_secs >= MIN_GAME_SECS).then(|| total / (self.duration_secs / 60.0))
+    }
+}
+
+/// Measures taken along a live game
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GameProgress {
+    pub cs_at_10: Option<i32>,
+    pub cs_at_14: Option<i32>,
+    pub cs_at_20: Option<i32>,
+    /// Game time of the first poll
+    first_time: Option<f64>,
+    last_time: f64,
+    last_gold: f64,
+    gold_earned: f64,
+}
+
+impl GameProgress {
+    /// Take into account one poll of the Live Client
+    pub fn observe(&mut self, game_time: f64, cs: i32, current_gold: f64) {
+        if self.first_time.is_none() {
+            self.first_time = Some(game_time);
+            self.last_time = game_time;
+            self.last_gold = current_gold;
+            self.gold_earned = current_gold;
+            return;
+        }
+
+        let trusted = game_time - self.last_time <= MAX_SAMPLE_GAP_SECS;
+        let stages = [
+            (EARLY_STAGE_SECS, &mut self.cs_at_10),
+            (LANING_PHASE_SECS, &mut self.cs_at_14),
+            (MID_STAGE_SECS, &mut self.cs_at_20),
+        ];
+        for (stage, slot) in stages {
+            if trusted && slot.is_none() && self.last_time < stage && game_time >= stage {
+                *slot = Some(cs);
+            }
+        }
+
+        if current_gold > self.last_gold {
+            self.gold_earned += current_gold - self.last_gold;
+        }
+        self.last_time = game_time;
+        self.last_gold = current_gold;
+    }
+
+    /// CS/min of the laning phase (first 14 minutes), if it was followed
+    pub fn laning_cs_per_minute(&self) -> Option<f64> {
+        self.cs_at_14.map(|cs| f64::from(cs) / (LANING_PHASE_SECS / 60.0))
+    }
+
+    /// Gold earned, if the game was followed from its first minute
+    pub fn gold_earned(&self) -> Option<f64> {
+        self.first_time.filter(|&t| t < 60.0).map(|_| self.gold_earned)
+    }
+}
+
+impl Bests {
+    /// Raise the bests beaten by `stats`; returns the names of the improved ones
+    fn update(&mut self, stats: &GameStats) -> Vec<&'static str> {
+        let candidates = [
+            ("csAt10", &mut self.cs_at_10, stats.cs_at_10.map(f64::from)),
+            ("csAt20", &mut self.cs_at_20, stats.cs_at_20.map(f64::from)),
+            ("csPerMinute", &mut self.cs_per_minute, stats.per_minute(stats.final_cs as f64)),
+            ("goldPerMinute", &mut self.gold_per_minute, stats.gold_earned.and_then(|g| stats.per_minute(g))),
+        ];
+
+        let mut improved = Vec::new();
+        for (name, best, value) in candidates {
+            let Some(value) = value else { continue };
+            if best.as_ref().is_none_or(|b| value > b.value) {
+                *best = Some(PersonalBest {
+                    value,
+                    game_id: stats.game_id,
+                    achieved_at: stats.ended_at,
+                });
+                improved.push(name);
+            }
+        }
+        self.games += 1;
+        improved
+    }
+}
+
+impl PersonalBests {
+    /// Count a finished game; returns the names of the improved bests of the champion
+    fn record(&mut self, stats: &GameStats) -> Vec<&'static str> {
+        if let Some(role) = &stats.role {
+            self.roles.entry(role.clone()).or_default().update(stats);
+        }
+        self.champions
+            .entry(stats.champion_name.clone())
+            .or_default()
+            .update(stats)
+    }
+}
+
+/// Load the personal bests
+pub fn load(app: &AppHandle) -> PersonalBests {
+    db::read(app, |connection| db::load_document(connection, db::PERSONAL_BESTS_DOCUMENT))
+}
+
+/// Update the personal bests with a finished game.
+///
+/// Returns the names of the champion bests that were beaten.
+pub fn record_game(app: &AppHandle, stats: &GameStats) -> Result<Vec<&'static str>, String> {
+    db::with(app, |connection| {
+        let mut bests: PersonalBests = db::load_document(connection, db::PERSONAL_BESTS_DOCUMENT)?;
+        let improved = bests.record(stats);
+        db::save_document(connection, db::PERSONAL_BESTS_DOCUMENT, &bests)?;
+        Ok(improved)
+    })
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the personal bests per champion and per role.
+#[tauri::command]
+pub async fn get_personal_bests(app: AppHandle) -> Result<PersonalBests, String> {
+    Ok(load(&app))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_personal_bests_from_progress() {
+        let mut progress = GameProgress::default();
+        progress.observe(1.0, 0, 500.0);
+        progress.observe(598.0, 70, 900.0);
+        progress.observe(600.5, 72, 400.0);
+        progress.observe(839.0, 97, 400.0);
+        progress.observe(841.0, 98, 400.0);
+        progress.observe(1_199.0, 150, 1_000.0);
+        progress.observe(1_201.0, 151, 1_100.0);
+        assert_eq!((progress.cs_at_10, progress.cs_at_20), (Some(72), Some(151)));
+        assert_eq!(progress.laning_cs_per_minute(), Some(7.0));
+        assert_eq!(progress.gold_earned(), Some(500.0 + 400.0 + 600.0 + 100.0));
+
+        // Started in the middle of the game: no milestone, no gold
+        let mut late = GameProgress::default();
+        late.observe(700.0, 80, 300.0);
+        late.observe(1_500.0, 180, 300.0);
+        assert_eq!((late.cs_at_10, late.cs_at_20, late.gold_earned()), (None, None, None));
+
+        let game = |game_id, final_cs, duration_secs| GameStats {
+            game_id,
+            champion_name: "Ahri".to_string(),
+            role: Some("mid".to_string()),
+            duration_secs,
+            cs_at_10: progress.cs_at_10,
+            final_cs,
+            gold_earned: Some(12_000.0),
+            ..Default::default()
+        };
+        let mut bests = PersonalBests::default();
+        assert_eq!(bests.record(&game(1, 240, 1_800.0)), vec!["csAt10", "csPerMinute", "goldPerMinute"]);
+        // A remake only counts for the milestones it reached
+        assert!(bests.record(&game(2, 100, 300.0)).is_empty());
+        assert_eq!(bests.record(&game(3, 270, 1_800.0)), vec!["csPerMinute"]);
+
+        let ahri = &bests.champions["Ahri"];
+        assert_eq!(ahri.games, 3);
+        assert_eq!(ahri.cs_per_minute.as_ref().map(|b| (b.value, b.game_id)), Some((9.0, 3)));
+        assert_eq!(ahri.gold_per_minute.as_ref().map(|b| b.value), Some(400.0));
+        assert_eq!(bests.roles["mid"].games, 3);
+    }
+}
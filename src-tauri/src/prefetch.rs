@@ -0,0 +1,234 @@
+//! Build prefetch during champion select.
+//!
+//! The game watcher reports the champion the local player hovers or locks
+//! (`champ-select-pick-changed`, with the likely role). The frontend, which
+//! owns the build format, answers with the import request for that build and
+//! `prefetch_import_payload` fetches the FocusApi payload into the import
+//! cache without applying anything. The user's Import click then hits the
+//! cache. A prefetch still running when the pick changes is cancelled.
+
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::async_runtime::JoinHandle;
+use tauri::AppHandle;
+
+use crate::build_providers::{self, BuildProvider};
+use crate::events::AppEvent;
+use crate::lcu::ChampionSelectSession;
+use crate::{cancel, champions, import_cache, roles, ImportPayloadRequest};
+
+/// Champion hovered or locked by the local player
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChampSelectPick {
+    pub champion_id: i64,
+    /// DDragon key (e.g., "MonkeyKing"), when known
+    pub champion_key: Option<String>,
+    /// Role used for the build: assigned position, or the champion's main role
+    pub role: String,
+    /// False while the champion is only hovered
+    pub locked: bool,
+}
+
+/// Map an LCU assigned position to a build role
+pub(crate) fn role_for_position(position: &str) -> Option<&'static str> {
+    match position.to_uppercase().as_str() {
+        "TOP" => Some("top"),
+        "JUNGLE" => Some("jungle"),
+        "MIDDLE" => Some("mid"),
+        "BOTTOM" => Some("adc"),
+        "UTILITY" => Some("support"),
+        _ => None,
+    }
+}
+
+/// Champion currently hovered or locked by the local player
+pub fn local_pick(session: &ChampionSelectSession) -> Option<ChampSelectPick> {
+    let cell_id = session.local_player_cell_id?;
+    if session.is_spectating {
+        return None;
+    }
+
+    let (champion_id, locked) = session.champion_of(cell_id);
+    if champion_id <= 0 {
+        return None;
+    }
+    let member = session.my_team.iter().flatten().find(|member| member.cell_id == cell_id);
+
+    let champion_key = champions::ddragon_key_for_id(champion_id);
+    let role = member
+        .and_then(|m| m.assigned_position.as_deref())
+        .and_then(role_for_position)
+        .map(str::to_string)
+        .unwrap_or_else(|| roles::default_role(champion_id, champion_key.as_deref()));
+
+    Some(ChampSelectPick {
+        champion_id,
+        champion_key,
+        role,
+        locked,
+    })
+}
+
+/// Prefetch state shared in the Tauri state
+#[derive(Default)]
+pub struct BuildPrefetcher {
+    /// Pick reported by the watcher, and the prefetch running for it
+    current: Mutex<(Option<ChampSelectPick>, Option<JoinHandle<()>>)>,
+}
+
+/// Whether an import request is for `pick` (the frontend may not know the champion ID)
+fn is_for_pick(payload: &ImportPayloadRequest, pick: &ChampSelectPick) -> bool {
+    payload.champion_id == pick.champion_id
+        || pick
+            .champion_key
+            .as_deref()
+            .is_some_and(|key| key.eq_ignore_ascii_case(payload.champion_key.trim()))
+}
+
+impl BuildPrefetcher {
+    /// Record the local player's new pick (None when leaving champion select).
+    ///
+    /// Cancels the running prefetch when the champion changed, and tells the
+    /// frontend when the champion or its lock changed.
+    pub fn set_pick(&self, app: &AppHandle, pick: Option<&ChampSelectPick>) {
+        let Ok(mut current) = self.current.lock() else {
+            return;
+        };
+        if current.0.as_ref() == pick {
+            return;
+        }
+        let champion_id = pick.map(|p| p.champion_id);
+        // Same champion (e.g., hover then lock): keep the running prefetch
+        if current.0.as_ref().map(|p| p.champion_id) != champion_id {
+            if let Some(task) = current.1.take() {
+                task.abort();
+            }
+        }
+        current.0 = pick.cloned();
+        drop(current);
+
+        if let Some(pick) = pick {
+            let _ = AppEvent::ChampSelectPickChanged(pick).emit(app);
+        }
+    }
+
+    /// Fetch the payload of `payload` into the import cache in the background.
+    ///
+    /// Returns false (nothing started) if the request is not for the current pick.
+    fn start(&self, app: AppHandle, payload: ImportPayloadRequest, operation_id: Option<String>) -> bool {
+        let Ok(mut current) = self.current.lock() else {
+            return false;
+        };
+        if !current.0.as_ref().is_some_and(|pick| is_for_pick(&payload, pick)) {
+            return false;
+        }
+        // Local providers answer instantly, nothing to prefetch
+        let provider = build_providers::configured_provider(&app);
+        if !provider.cacheable() {
+            return false;
+        }
+
+        if let Some(task) = current.1.take() {
+            task.abort();
+        }
+        // Same patch, hence same cache key, as the import
+        let payload = build_providers::with_patch_override(&app, &payload);
+        let operation = cancel::start(operation_id);
+        current.1 = Some(tauri::async_runtime::spawn(async move {
+            if import_cache::lookup(&app, &payload, import_cache::FRESH_TTL_SECS).is_some() {
+                return;
+            }
+            let Ok(result) = operation.run(provider.fetch_payloads(&payload)).await else {
+                log_debug!("[prefetch] Prefetch cancelled");
+                return;
+            };
+            match result {
+                // Cached as the import would: with the stat shards fixed
+                Ok(mut response) => {
+                    if crate::validate_payloads(&mut response).is_err() {
+                        log_warn!("[prefetch] Invalid payload not cached");
+                    } else if let Err(e) = import_cache::store(&app, &payload, &response) {
+                        log_warn!("[prefetch] Failed to cache payload: {}", e);
+                    }
+                }
+                Err(e) => log_warn!("[prefetch] Prefetch failed: {}", e.message),
+            }
+        }));
+        true
+    }
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Prefetch the FocusApi payload of a build without importing it.
+///
+/// Ignored (returns false) when the player's pick has changed in the meantime
+/// or when the configured build provider is local. Cancellable with
+/// `cancel_operation(operation_id)`.
+#[tauri::command]
+pub async fn prefetch_import_payload(
+    app: AppHandle,
+    prefetcher: tauri::State<'_, BuildPrefetcher>,
+    payload: ImportPayloadRequest,
+    operation_id: Option<String>,
+) -> Result<bool, String> {
+    Ok(prefetcher.start(app, payload, operation_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(actions: serde_json::Value, position: &str) -> ChampionSelectSession {
+        serde_json::from_value(serde_json::json!({
+            "localPlayerCellId": 2,
+            "actions": actions,
+            "myTeam": [
+                { "cellId": 1, "championId": 22, "assignedPosition": "BOTTOM" },
+                { "cellId": 2, "championId": 0, "assignedPosition": position }
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_local_pick() {
+        let hover = session(
+            serde_json::json!([[
+                { "actorCellId": 2, "championId": 64, "completed": false, "isInProgress": true, "id": 1, "type": "ban" },
+                { "actorCellId": 2, "championId": 103, "completed": false, "isInProgress": true, "id": 2, "type": "pick" }
+            ]]),
+            "MIDDLE",
+        );
+        let pick = local_pick(&hover).unwrap();
+        assert_eq!(pick.champion_id, 103);
+        assert_eq!(pick.role, "mid");
+        assert!(!pick.locked);
+
+        // Nothing hovered yet
+        let empty = session(
+            serde_json::json!([[{ "actorCellId": 2, "championId": 0, "completed": false, "id": 2, "type": "pick" }]]),
+            "TOP",
+        );
+        assert_eq!(local_pick(&empty), None);
+
+        // Hovered before the player's turn: prefetched already
+        let early = session(
+            serde_json::json!([[{ "actorCellId": 2, "championId": 103, "completed": false, "isInProgress": false, "id": 2, "type": "pick" }]]),
+            "MIDDLE",
+        );
+        assert_eq!(local_pick(&early).map(|pick| (pick.champion_id, pick.locked)), Some((103, false)));
+        let mut intent = empty.clone();
+        intent.my_team.as_mut().unwrap()[1].champion_pick_intent = 103;
+        assert_eq!(local_pick(&intent).map(|pick| pick.champion_id), Some(103));
+
+        let locked = session(
+            serde_json::json!([[{ "actorCellId": 2, "championId": 103, "completed": true, "id": 2, "type": "pick" }]]),
+            "MIDDLE",
+        );
+        assert!(local_pick(&locked).unwrap().locked);
+    }
+}
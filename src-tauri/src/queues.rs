@@ -0,0 +1,342 @@
+//! Queue classification and metadata.
+//!
+//! Maps the queue IDs reported by the LCU gameflow session to broad
+//! categories used by the overlay and the game history, and to display
+//! names and map IDs (used to associate item sets with the right map).
+//!
+//! A bundled table covers the common queues; it is completed at startup
+//! from Riot's static `queues.json` / `maps.json`, cached on disk so the
+//! last known data stays available offline.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{LazyLock, RwLock};
+use std::time::Duration;
+use tauri::AppHandle;
+
+use crate::storage;
+
+/// Ranked Summoner's Rift queues (Solo/Duo, Flex)
+const RANKED_QUEUE_IDS: &[i32] = &[420, 440];
+
+/// Normal Summoner's Rift queues (Draft, Blind, Quickplay, Swiftplay)
+const NORMAL_QUEUE_IDS: &[i32] = &[400, 430, 480, 490];
+
+/// ARAM queues (ARAM, Butcher's Bridge, ARAM Clash, ARAM Mayhem)
+const ARAM_QUEUE_IDS: &[i32] = &[100, 450, 720, 2400];
+
+/// Arena queues (2v2v2v2, 16 players)
+const ARENA_QUEUE_IDS: &[i32] = &[1700, 1710];
+
+/// Co-op vs AI queues
+const COOP_VS_AI_QUEUE_IDS: &[i32] = &[830, 840, 850, 870, 880, 890];
+
+/// Summoner's Rift
+pub const SUMMONERS_RIFT_MAP_ID: i32 = 11;
+/// Howling Abyss (ARAM)
+pub const HOWLING_ABYSS_MAP_ID: i32 = 12;
+/// Butcher's Bridge (ARAM event map)
+const BUTCHERS_BRIDGE_MAP_ID: i32 = 14;
+/// Rings of Wrath (Arena)
+const RINGS_OF_WRATH_MAP_ID: i32 = 30;
+
+/// Bundled queue metadata: (queue ID, map ID, display name)
+const BUNDLED_QUEUES: &[(i32, i32, &str)] = &[
+    (0, SUMMONERS_RIFT_MAP_ID, "Custom"),
+    (100, BUTCHERS_BRIDGE_MAP_ID, "ARAM (Butcher's Bridge)"),
+    (400, SUMMONERS_RIFT_MAP_ID, "Normal Draft"),
+    (420, SUMMONERS_RIFT_MAP_ID, "Ranked Solo/Duo"),
+    (430, SUMMONERS_RIFT_MAP_ID, "Normal Blind"),
+    (440, SUMMONERS_RIFT_MAP_ID, "Ranked Flex"),
+    (450, HOWLING_ABYSS_MAP_ID, "ARAM"),
+    (480, SUMMONERS_RIFT_MAP_ID, "Swiftplay"),
+    (490, SUMMONERS_RIFT_MAP_ID, "Quickplay"),
+    (700, SUMMONERS_RIFT_MAP_ID, "Clash"),
+    (720, HOWLING_ABYSS_MAP_ID, "ARAM Clash"),
+    (830, SUMMONERS_RIFT_MAP_ID, "Co-op vs AI (Intro)"),
+    (840, SUMMONERS_RIFT_MAP_ID, "Co-op vs AI (Beginner)"),
+    (850, SUMMONERS_RIFT_MAP_ID, "Co-op vs AI (Intermediate)"),
+    (870, SUMMONERS_RIFT_MAP_ID, "Co-op vs AI (Intro)"),
+    (880, SUMMONERS_RIFT_MAP_ID, "Co-op vs AI (Beginner)"),
+    (890, SUMMONERS_RIFT_MAP_ID, "Co-op vs AI (Intermediate)"),
+    (900, SUMMONERS_RIFT_MAP_ID, "ARURF"),
+    (1020, SUMMONERS_RIFT_MAP_ID, "One for All"),
+    (1700, RINGS_OF_WRATH_MAP_ID, "Arena"),
+    (1710, RINGS_OF_WRATH_MAP_ID, "Arena (16 players)"),
+    (1900, SUMMONERS_RIFT_MAP_ID, "URF"),
+    (2400, HOWLING_ABYSS_MAP_ID, "ARAM Mayhem"),
+];
+
+const QUEUES_URL: &str = "https://static.developer.riotgames.com/docs/lol/queues.json";
+const MAPS_URL: &str = "https://static.developer.riotgames.com/docs/lol/maps.json";
+pub(crate) const QUEUES_CACHE_FILE: &str = "static/queues.json";
+pub(crate) const MAPS_CACHE_FILE: &str = "static/maps.json";
+
+/// Broad category of a queue
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum QueueCategory {
+    Ranked,
+    Normal,
+    Aram,
+    Arena,
+    CoopVsAi,
+    /// Custom games and Practice Tool
+    Custom,
+    /// Rotating game modes (URF, One for All, ...)
+    Other,
+}
+
+impl QueueCategory {
+    /// Classify a queue from its ID and game mode.
+    ///
+    /// Custom games have no queue (ID 0 or -1 depending on the client).
+    pub fn from_queue(queue_id: i32, game_mode: &str) -> Self {
+        if RANKED_QUEUE_IDS.contains(&queue_id) {
+            QueueCategory::Ranked
+        } else if NORMAL_QUEUE_IDS.contains(&queue_id) {
+            QueueCategory::Normal
+        } else if ARAM_QUEUE_IDS.contains(&queue_id) || game_mode.eq_ignore_ascii_case("ARAM") {
+            QueueCategory::Aram
+        } else if ARENA_QUEUE_IDS.contains(&queue_id) || game_mode.eq_ignore_ascii_case("CHERRY") {
+            QueueCategory::Arena
+        } else if COOP_VS_AI_QUEUE_IDS.contains(&queue_id) {
+            QueueCategory::CoopVsAi
+        } else if queue_id <= 0 || game_mode.eq_ignore_ascii_case("PRACTICETOOL") {
+            QueueCategory::Custom
+        } else {
+            QueueCategory::Other
+        }
+    }
+}
+
+// =============================================================================
+// QUEUE METADATA
+// =============================================================================
+
+/// Display metadata of a queue
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueMetadata {
+    pub queue_id: i32,
+    pub map_id: i32,
+    pub name: String,
+    pub category: QueueCategory,
+}
+
+/// Entry of Riot's static `queues.json`
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StaticQueue {
+    queue_id: i32,
+    #[serde(default)]
+    map: String,
+    #[serde(default)]
+    description: Option<String>,
+}
+
+/// Entry of Riot's static `maps.json`
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StaticMap {
+    map_id: i32,
+    map_name: String,
+}
+
+/// Queue metadata loaded from the static endpoints (None until loaded)
+static REMOTE_QUEUES: LazyLock<RwLock<Option<HashMap<i32, QueueMetadata>>>> =
+    LazyLock::new(|| RwLock::new(None));
+
+fn bundled_queue(queue_id: i32) -> Option<QueueMetadata> {
+    BUNDLED_QUEUES
+        .iter()
+        .find(|(id, _, _)| *id == queue_id)
+        .map(|&(queue_id, map_id, name)| QueueMetadata {
+            queue_id,
+            map_id,
+            name: name.to_string(),
+            category: QueueCategory::from_queue(queue_id, ""),
+        })
+}
+
+/// Resolve the metadata of a queue.
+///
+/// Bundled names take precedence (they are shorter than Riot's descriptions);
+/// the static endpoints cover rotating and newly added queues.
+pub fn queue_metadata(queue_id: i32) -> Option<QueueMetadata> {
+    bundled_queue(queue_id).or_else(|| {
+        REMOTE_QUEUES
+            .read()
+            .ok()?
+            .as_ref()?
+            .get(&queue_id)
+            .cloned()
+    })
+}
+
+/// Practice Tool games (no queue, reported by their game mode)
+pub fn is_practice_tool(game_mode: &str) -> bool {
+    game_mode.eq_ignore_ascii_case("PRACTICETOOL")
+}
+
+/// Map on which a queue is played (Summoner's Rift when unknown)
+pub fn map_id_for_queue(queue_id: i32) -> i32 {
+    queue_metadata(queue_id)
+        .map(|q| q.map_id)
+        .unwrap_or(SUMMONERS_RIFT_MAP_ID)
+}
+
+/// Join Riot's static `queues.json` and `maps.json` into queue metadata.
+///
+/// `queues.json` only names the map, and several map IDs share a name
+/// (older Summoner's Rift versions), so the highest ID wins.
+fn parse_static_metadata(queues_json: &str, maps_json: &str) -> Result<HashMap<i32, QueueMetadata>, String> {
+    let queues: Vec<StaticQueue> = serde_json::from_str(queues_json).map_err(|e| e.to_string())?;
+    let maps: Vec<StaticMap> = serde_json::from_str(maps_json).map_err(|e| e.to_string())?;
+
+    let mut map_ids: HashMap<&str, i32> = HashMap::new();
+    for map in &maps {
+        let id = map_ids.entry(map.map_name.as_str()).or_insert(map.map_id);
+        *id = (*id).max(map.map_id);
+    }
+
+    Ok(queues
+        .into_iter()
+        .map(|queue| {
+            let name = queue
+                .description
+                .map(|d| d.trim_end_matches(" games").trim().to_string())
+                .filter(|d| !d.is_empty())
+                .unwrap_or_else(|| format!("Queue {}", queue.queue_id));
+            let metadata = QueueMetadata {
+                queue_id: queue.queue_id,
+                map_id: map_ids
+                    .get(queue.map.as_str())
+                    .copied()
+                    .unwrap_or(SUMMONERS_RIFT_MAP_ID),
+                name,
+                category: QueueCategory::from_queue(queue.queue_id, ""),
+            };
+            (queue.queue_id, metadata)
+        })
+        .collect())
+}
+
+/// Check that a cached `queues.json` can be parsed
+pub(crate) fn validate_queues_json(json: &str) -> Result<(), String> {
+    serde_json::from_str::<Vec<StaticQueue>>(json)
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// Check that a cached `maps.json` can be parsed
+pub(crate) fn validate_maps_json(json: &str) -> Result<(), String> {
+    serde_json::from_str::<Vec<StaticMap>>(json)
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+async fn download_text(client: &reqwest::Client, url: &str) -> Result<String, String> {
+    client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?
+        .error_for_status()
+        .map_err(|e| e.to_string())?
+        .text()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Load the static queue metadata, refreshing the disk cache when online.
+pub async fn refresh_queue_metadata(app: AppHandle) {
+    let queues_path = storage::cache_file_path(&app, QUEUES_CACHE_FILE);
+    let maps_path = storage::cache_file_path(&app, MAPS_CACHE_FILE);
+    let (Ok(queues_path), Ok(maps_path)) = (queues_path, maps_path) else {
+        return;
+    };
+
+    let downloaded = async {
+        let client = crate::proxy::remote_client()
+            .timeout(Duration::from_secs(15))
+            .build()
+            .map_err(|e| e.to_string())?;
+        let queues = download_text(&client, QUEUES_URL).await?;
+        let maps = download_text(&client, MAPS_URL).await?;
+        let metadata = parse_static_metadata(&queues, &maps)?;
+        storage::write_atomic(&queues_path, queues.as_bytes())?;
+        storage::write_atomic(&maps_path, maps.as_bytes())?;
+        Ok::<_, String>(metadata)
+    }
+    .await;
+
+    let metadata = match downloaded {
+        Ok(metadata) => metadata,
+        Err(e) => {
+            log_info!("[queues] Static data unavailable, using disk cache: {}", e);
+            let cached = std::fs::read_to_string(&queues_path)
+                .and_then(|queues| Ok((queues, std::fs::read_to_string(&maps_path)?)));
+            match cached.map_err(|e| e.to_string()).and_then(|(q, m)| parse_static_metadata(&q, &m)) {
+                Ok(metadata) => metadata,
+                Err(_) => return,
+            }
+        }
+    };
+
+    if let Ok(mut remote) = REMOTE_QUEUES.write() {
+        *remote = Some(metadata);
+    }
+}
+
+/// Get the display name and map of a queue.
+#[tauri::command]
+pub async fn get_queue_metadata(queue_id: i32) -> Result<Option<QueueMetadata>, String> {
+    Ok(queue_metadata(queue_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_queue_categories() {
+        assert_eq!(QueueCategory::from_queue(420, "CLASSIC"), QueueCategory::Ranked);
+        assert_eq!(QueueCategory::from_queue(400, "CLASSIC"), QueueCategory::Normal);
+        assert_eq!(QueueCategory::from_queue(450, "ARAM"), QueueCategory::Aram);
+        assert_eq!(QueueCategory::from_queue(1700, "CHERRY"), QueueCategory::Arena);
+        assert_eq!(QueueCategory::from_queue(-1, "PRACTICETOOL"), QueueCategory::Custom);
+        assert_eq!(QueueCategory::from_queue(0, "CLASSIC"), QueueCategory::Custom);
+        assert_eq!(QueueCategory::from_queue(900, "URF"), QueueCategory::Other);
+    }
+
+    #[test]
+    fn test_bundled_queue_metadata() {
+        let aram = queue_metadata(450).unwrap();
+        assert_eq!(aram.name, "ARAM");
+        assert_eq!(aram.map_id, HOWLING_ABYSS_MAP_ID);
+        assert_eq!(aram.category, QueueCategory::Aram);
+
+        assert_eq!(map_id_for_queue(420), SUMMONERS_RIFT_MAP_ID);
+        assert_eq!(map_id_for_queue(1700), 30);
+    }
+
+    #[test]
+    fn test_parse_static_metadata() {
+        let queues = r#"[
+            { "queueId": 450, "map": "Howling Abyss", "description": "5v5 ARAM games", "notes": null },
+            { "queueId": 1300, "map": "Summoner's Rift", "description": null, "notes": null }
+        ]"#;
+        let maps = r#"[
+            { "mapId": 1, "mapName": "Summoner's Rift", "notes": "Original Summer variant" },
+            { "mapId": 11, "mapName": "Summoner's Rift", "notes": "Current Version" },
+            { "mapId": 12, "mapName": "Howling Abyss", "notes": "ARAM map" }
+        ]"#;
+
+        let metadata = parse_static_metadata(queues, maps).unwrap();
+        assert_eq!(metadata[&450].name, "5v5 ARAM");
+        assert_eq!(metadata[&450].map_id, 12);
+        assert_eq!(metadata[&1300].name, "Queue 1300");
+        assert_eq!(metadata[&1300].map_id, SUMMONERS_RIFT_MAP_ID);
+    }
+}
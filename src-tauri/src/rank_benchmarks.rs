@@ -0,0 +1,136 @@
+//! Average CS curves per rank and role, from FocusApi.
+//!
+//! FocusApi publishes, for each rank and role, the average CS at each minute
+//! of the current patch. The curve of the rank chosen in the overlay and of
+//! the role played replaces the static CS/min numbers as the comparison
+//! baseline: the overlay colors its delta against it when the user set no
+//! stage goals, and post-game reports measure farming gaps against it.
+//!
+//! Curves are cached on disk per patch (a new patch drops them). The game
+//! watcher prefetches the curve during champion select so it is available
+//! when the game starts without waiting for the network.
+
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+use crate::cs_goals::CsGoal;
+use crate::focus_api;
+use crate::patch_cache::PatchCache;
+
+/// Cache file, relative to the cache directory
+const BENCHMARKS_CACHE_FILE: &str = "focus-api/cs-benchmarks.json";
+
+/// A point of the FocusApi curve (average CS, not rounded)
+#[derive(Debug, Deserialize)]
+struct BenchmarkPoint {
+    minute: u32,
+    cs: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct BenchmarkResponse {
+    curve: Vec<BenchmarkPoint>,
+}
+
+/// Curves of the current patch, by "rank/role"
+static CACHE: PatchCache<Vec<CsGoal>> = PatchCache::on_disk(BENCHMARKS_CACHE_FILE);
+
+/// Curves being downloaded, by key
+static IN_FLIGHT: Mutex<Option<HashSet<String>>> = Mutex::new(None);
+
+fn cache_key(rank: &str, role: &str) -> String {
+    format!("{}/{}", rank.trim().to_lowercase(), role.trim().to_lowercase())
+}
+
+/// Parse a FocusApi curve, by increasing minute
+fn parse_curve(body: serde_json::Value) -> Result<Vec<CsGoal>, String> {
+    let response: BenchmarkResponse = serde_json::from_value(body).map_err(|e| format!("Invalid CS benchmark: {}", e))?;
+    let mut curve: Vec<CsGoal> = response
+        .curve
+        .into_iter()
+        .filter(|point| point.minute > 0 && point.cs.is_finite() && point.cs >= 0.0)
+        .map(|point| CsGoal {
+            minute: point.minute,
+            cs: point.cs.round() as u32,
+        })
+        .collect();
+    curve.sort_by_key(|point| point.minute);
+    curve.dedup_by_key(|point| point.minute);
+    Ok(curve)
+}
+
+/// Cached curve of a rank and role (empty when not downloaded yet)
+pub fn cached_curve(app: &AppHandle, rank: &str, role: &str) -> Vec<CsGoal> {
+    CACHE.get(app, &cache_key(rank, role)).unwrap_or_default()
+}
+
+/// Download the curve of a rank and role and cache it for the current patch
+async fn fetch_curve(app: &AppHandle, rank: &str, role: &str) -> Result<Vec<CsGoal>, String> {
+    let curve = parse_curve(focus_api::fetch_cs_benchmark(rank, role).await.map_err(|e| e.to_string())?)?;
+    CACHE.insert(app, cache_key(rank, role), curve.clone())?;
+    Ok(curve)
+}
+
+/// Download the curve in the background if it is not cached yet
+pub fn prefetch(app: &AppHandle, rank: &str, role: &str) {
+    if !cached_curve(app, rank, role).is_empty() {
+        return;
+    }
+    let key = cache_key(rank, role);
+    let Ok(mut in_flight) = IN_FLIGHT.lock() else {
+        return;
+    };
+    if !in_flight.get_or_insert_with(HashSet::new).insert(key.clone()) {
+        return;
+    }
+    drop(in_flight);
+
+    let app = app.clone();
+    let (rank, role) = (rank.to_string(), role.to_string());
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = fetch_curve(&app, &rank, &role).await {
+            log_debug!("[rank_benchmarks] {} not available: {}", key, e);
+        }
+        if let Ok(mut in_flight) = IN_FLIGHT.lock() {
+            if let Some(keys) = in_flight.as_mut() {
+                keys.remove(&key);
+            }
+        }
+    });
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the average CS per minute of a rank and role for the current patch.
+#[tauri::command]
+pub async fn get_cs_benchmark(app: AppHandle, rank: String, role: String) -> Result<Vec<CsGoal>, String> {
+    let cached = cached_curve(&app, &rank, &role);
+    if !cached.is_empty() {
+        return Ok(cached);
+    }
+    fetch_curve(&app, &rank, &role).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_curve() {
+        let body = json!({
+            "patch": "14.10",
+            "curve": [{ "minute": 2, "cs": 13.6 }, { "minute": 1, "cs": 5.2 }, { "minute": 0, "cs": 0 }]
+        });
+        assert_eq!(
+            parse_curve(body).unwrap(),
+            vec![CsGoal { minute: 1, cs: 5 }, CsGoal { minute: 2, cs: 14 }]
+        );
+        assert!(parse_curve(json!({ "error": "unknown rank" })).is_err());
+        assert_eq!(cache_key(" Diamond ", "MID"), "diamond/mid");
+    }
+}
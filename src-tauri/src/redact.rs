@@ -0,0 +1,143 @@
+//! Secret redaction.
+//!
+//! The League Client lockfile password, the Authorization headers built from
+//! it and the FocusApi key must never end up in the log file, a crash file
+//! or an error shown in the UI. Secrets are registered here as soon as they
+//! are read, and `redact` replaces every occurrence of them, plus anything
+//! that looks like a Basic/Bearer credential, before text leaves the
+//! backend. The logging layer and `CommandError` serialization both go
+//! through it.
+
+use base64::Engine;
+use std::sync::RwLock;
+
+/// Replacement text
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Shorter values are not registered (they would mask ordinary text)
+const MIN_SECRET_LEN: usize = 6;
+
+/// Bound on the rotating secrets (lockfile passwords change every client
+/// launch, account tokens on every refresh)
+const MAX_ROTATING_SECRETS: usize = 32;
+
+/// Markers followed by a credential token
+const CREDENTIAL_MARKERS: &[&str] = &["basic ", "bearer "];
+
+/// Secrets kept for the whole session (API keys, signing secret, passwords)
+static SECRETS: RwLock<Vec<String>> = RwLock::new(Vec::new());
+
+/// Secrets replaced as they rotate, oldest dropped first
+static ROTATING_SECRETS: RwLock<Vec<String>> = RwLock::new(Vec::new());
+
+fn add_secret(registry: &RwLock<Vec<String>>, secret: &str, max_len: Option<usize>) {
+    let secret = secret.trim();
+    if secret.len() < MIN_SECRET_LEN {
+        return;
+    }
+    let Ok(mut secrets) = registry.write() else {
+        return;
+    };
+    if secrets.iter().any(|known| known == secret) {
+        return;
+    }
+    if max_len.is_some_and(|max_len| secrets.len() >= max_len) {
+        secrets.remove(0);
+    }
+    secrets.push(secret.to_string());
+}
+
+/// Register a value that must never be shown, for the whole session
+pub fn register_secret(secret: &str) {
+    add_secret(&SECRETS, secret, None);
+}
+
+/// Register a short-lived value that must never be shown (only the last
+/// `MAX_ROTATING_SECRETS` are kept)
+pub fn register_rotating_secret(secret: &str) {
+    add_secret(&ROTATING_SECRETS, secret, Some(MAX_ROTATING_SECRETS));
+}
+
+/// Register the lockfile password and the Basic credential derived from it
+pub fn register_lcu_password(password: &str) {
+    register_rotating_secret(password);
+    register_rotating_secret(&base64::engine::general_purpose::STANDARD.encode(format!("riot:{}", password)));
+}
+
+/// Characters of a Basic/Bearer token
+fn is_token_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '+' | '/' | '=' | '-' | '_' | '.' | '~')
+}
+
+/// Mask the token following each "Basic " / "Bearer " marker
+fn redact_credentials(text: &str) -> String {
+    let lower = text.to_ascii_lowercase();
+    let mut result = String::with_capacity(text.len());
+    let mut position = 0;
+
+    while let Some((start, marker)) = CREDENTIAL_MARKERS
+        .iter()
+        .filter_map(|marker| lower[position..].find(marker).map(|i| (position + i, marker)))
+        .min_by_key(|(start, _)| *start)
+    {
+        let token_start = start + marker.len();
+        let token_len = text[token_start..]
+            .find(|c: char| !is_token_char(c))
+            .unwrap_or(text.len() - token_start);
+        result.push_str(&text[position..token_start]);
+        if token_len > 0 {
+            result.push_str(REDACTED);
+        }
+        position = token_start + token_len;
+    }
+    result.push_str(&text[position..]);
+    result
+}
+
+/// Remove every registered secret and credential from `text`
+pub fn redact(text: &str) -> String {
+    let mut text = redact_credentials(text);
+    for registry in [&SECRETS, &ROTATING_SECRETS] {
+        let Ok(secrets) = registry.read() else {
+            continue;
+        };
+        for secret in secrets.iter() {
+            if text.contains(secret.as_str()) {
+                text = text.replace(secret.as_str(), REDACTED);
+            }
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact() {
+        register_lcu_password("lockfile-pass-123");
+        register_secret("abc");
+
+        assert_eq!(
+            redact("lockfile: LeagueClient:1:2:lockfile-pass-123:https"),
+            format!("lockfile: LeagueClient:1:2:{}:https", REDACTED)
+        );
+        assert_eq!(
+            redact("header Authorization: Basic cmlvdDp4eXo= sent"),
+            format!("header Authorization: Basic {} sent", REDACTED)
+        );
+        assert_eq!(redact("BEARER tok.en-1, done"), format!("BEARER {}, done", REDACTED));
+        // Too short to be registered
+        assert_eq!(redact("abc basic"), "abc basic");
+
+        // Many client launches later, the session-long secrets are still masked
+        register_secret("embedded-api-key");
+        for launch in 0..MAX_ROTATING_SECRETS {
+            register_lcu_password(&format!("password-{}", launch));
+        }
+        assert_eq!(redact("key embedded-api-key"), format!("key {}", REDACTED));
+        assert_eq!(redact("password-0"), "password-0");
+        assert_eq!(redact("password-31"), REDACTED);
+    }
+}
@@ -0,0 +1,149 @@
+//! Re-import prompt when the champion changes after an import.
+//!
+//! A successful import during champion select is recorded with `record`. If
+//! the local player then ends up on another champion (trade, reroll, bench
+//! swap), the build in the client is for the wrong champion: on the next
+//! champion select poll `on_champ_select` sends `reimport-recommended` with
+//! both champions, once per new champion, so the UI can offer a corrected
+//! import in one click.
+
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+use crate::events::AppEvent;
+use crate::lcu::ChampionSelectSession;
+use crate::prefetch::{self, ChampSelectPick};
+use crate::ImportPayloadRequest;
+
+/// Champion of the last import
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ImportedChampion {
+    /// 0 when the frontend did not send it
+    champion_id: i64,
+    champion_key: String,
+}
+
+impl ImportedChampion {
+    fn is(&self, pick: &ChampSelectPick) -> bool {
+        (self.champion_id > 0 && self.champion_id == pick.champion_id)
+            || pick
+                .champion_key
+                .as_deref()
+                .is_some_and(|key| key.eq_ignore_ascii_case(&self.champion_key))
+    }
+}
+
+/// `reimport-recommended` payload
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReimportRecommended {
+    /// Champion of the imported build (as sent with the import)
+    pub previous_champion_id: i64,
+    pub previous_champion_key: String,
+    pub champion_id: i64,
+    /// DDragon key (e.g., "MonkeyKing"), when known
+    pub champion_key: Option<String>,
+    /// Role used for the build of the new champion
+    pub role: String,
+}
+
+/// Last import of this champion select, and the champion last recommended
+#[derive(Debug, Default)]
+struct ReimportState {
+    imported: Option<ImportedChampion>,
+    recommended_champion_id: Option<i64>,
+}
+
+static STATE: Mutex<ReimportState> = Mutex::new(ReimportState {
+    imported: None,
+    recommended_champion_id: None,
+});
+
+/// Recommendation for the local player's pick, if it is not the imported champion
+fn recommendation(state: &ReimportState, pick: &ChampSelectPick) -> Option<ReimportRecommended> {
+    let imported = state.imported.as_ref()?;
+    if !pick.locked || imported.is(pick) || state.recommended_champion_id == Some(pick.champion_id) {
+        return None;
+    }
+    Some(ReimportRecommended {
+        previous_champion_id: imported.champion_id,
+        previous_champion_key: imported.champion_key.clone(),
+        champion_id: pick.champion_id,
+        champion_key: pick.champion_key.clone(),
+        role: pick.role.clone(),
+    })
+}
+
+/// Remember the champion of a successful import
+pub fn record(payload: &ImportPayloadRequest) {
+    if let Ok(mut state) = STATE.lock() {
+        state.imported = Some(ImportedChampion {
+            champion_id: payload.champion_id,
+            champion_key: payload.champion_key.trim().to_string(),
+        });
+        state.recommended_champion_id = None;
+    }
+}
+
+/// Send `reimport-recommended` when the locked champion is not the imported one
+pub fn on_champ_select(app: &AppHandle, session: &ChampionSelectSession) {
+    let Some(pick) = prefetch::local_pick(session) else {
+        return;
+    };
+    let Ok(mut state) = STATE.lock() else {
+        return;
+    };
+    let Some(recommended) = recommendation(&state, &pick) else {
+        return;
+    };
+    log_info!(
+        "[reimport] {} build imported, now playing {}",
+        recommended.previous_champion_key,
+        recommended.champion_id
+    );
+    if let Err(e) = AppEvent::ReimportRecommended(&recommended).emit(app) {
+        log_debug!("[reimport] {}", e);
+    }
+    state.recommended_champion_id = Some(pick.champion_id);
+}
+
+/// Forget the import (entering or leaving champion select)
+pub fn reset() {
+    if let Ok(mut state) = STATE.lock() {
+        *state = ReimportState::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recommendation() {
+        let pick = |champion_id: i64, champion_key: &str, locked| ChampSelectPick {
+            champion_id,
+            champion_key: Some(champion_key.to_string()),
+            role: "mid".to_string(),
+            locked,
+        };
+        let mut state = ReimportState::default();
+        assert_eq!(recommendation(&state, &pick(103, "Ahri", true)), None);
+
+        // Imported without the champion ID: the key identifies it
+        state.imported = Some(ImportedChampion {
+            champion_id: 0,
+            champion_key: "ahri".to_string(),
+        });
+        assert_eq!(recommendation(&state, &pick(103, "Ahri", true)), None);
+        assert_eq!(recommendation(&state, &pick(64, "LeeSin", false)), None);
+
+        let recommended = recommendation(&state, &pick(64, "LeeSin", true)).unwrap();
+        assert_eq!((recommended.previous_champion_key.as_str(), recommended.champion_id), ("ahri", 64));
+
+        // Sent once per champion
+        state.recommended_champion_id = Some(64);
+        assert_eq!(recommendation(&state, &pick(64, "LeeSin", true)), None);
+        assert!(recommendation(&state, &pick(22, "Ashe", true)).is_some());
+    }
+}
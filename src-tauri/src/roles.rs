@@ -0,0 +1,391 @@
+//! Default roles per champion.
+//!
+//! Used when champion select gives no assigned position (blind pick, custom
+//! games) so that the build request still targets a sensible role.
+//!
+//! A bundled champion -> roles table is shipped with the app; at startup it
+//! is refreshed from Meraki Analytics' per-position play rates, cached on
+//! disk so the last known data stays available offline.
+
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::{LazyLock, RwLock};
+use std::time::Duration;
+use tauri::AppHandle;
+
+use crate::champions;
+use crate::storage;
+
+/// Role used when nothing is known about the champion
+const FALLBACK_ROLE: &str = "mid";
+
+/// Minimum play rate (%) for a position to count as one of a champion's roles
+const MIN_ROLE_PLAY_RATE: f64 = 10.0;
+
+const CHAMPION_RATES_URL: &str =
+    "https://cdn.merakianalytics.com/riot/lol/resources/latest/en-US/championrates.json";
+pub(crate) const CHAMPION_RATES_CACHE_FILE: &str = "static/championrates.json";
+
+/// Bundled roles per DDragon key, most played first
+const BUNDLED_ROLES: &[(&str, &[&str])] = &[
+    ("Aatrox", &["top"]),
+    ("Ahri", &["mid"]),
+    ("Akali", &["mid", "top"]),
+    ("Akshan", &["mid", "top"]),
+    ("Alistar", &["support"]),
+    ("Ambessa", &["top"]),
+    ("Amumu", &["jungle", "support"]),
+    ("Anivia", &["mid"]),
+    ("Annie", &["mid", "support"]),
+    ("Aphelios", &["adc"]),
+    ("Ashe", &["adc", "support"]),
+    ("AurelionSol", &["mid"]),
+    ("Aurora", &["mid", "top"]),
+    ("Azir", &["mid"]),
+    ("Bard", &["support"]),
+    ("Belveth", &["jungle"]),
+    ("Blitzcrank", &["support"]),
+    ("Brand", &["support", "jungle"]),
+    ("Braum", &["support"]),
+    ("Briar", &["jungle"]),
+    ("Caitlyn", &["adc"]),
+    ("Camille", &["top"]),
+    ("Cassiopeia", &["mid"]),
+    ("Chogath", &["top"]),
+    ("Corki", &["mid"]),
+    ("Darius", &["top"]),
+    ("Diana", &["jungle", "mid"]),
+    ("DrMundo", &["top"]),
+    ("Draven", &["adc"]),
+    ("Ekko", &["jungle", "mid"]),
+    ("Elise", &["jungle"]),
+    ("Evelynn", &["jungle"]),
+    ("Ezreal", &["adc"]),
+    ("Fiddlesticks", &["jungle"]),
+    ("Fiora", &["top"]),
+    ("Fizz", &["mid"]),
+    ("Galio", &["mid", "support"]),
+    ("Gangplank", &["top"]),
+    ("Garen", &["top"]),
+    ("Gnar", &["top"]),
+    ("Gragas", &["jungle", "top"]),
+    ("Graves", &["jungle"]),
+    ("Gwen", &["top"]),
+    ("Hecarim", &["jungle"]),
+    ("Heimerdinger", &["mid", "support"]),
+    ("Hwei", &["mid", "support"]),
+    ("Illaoi", &["top"]),
+    ("Irelia", &["top", "mid"]),
+    ("Ivern", &["jungle"]),
+    ("Janna", &["support"]),
+    ("JarvanIV", &["jungle"]),
+    ("Jax", &["top", "jungle"]),
+    ("Jayce", &["top", "mid"]),
+    ("Jhin", &["adc"]),
+    ("Jinx", &["adc"]),
+    ("Kaisa", &["adc"]),
+    ("Kalista", &["adc"]),
+    ("Karma", &["support"]),
+    ("Karthus", &["jungle"]),
+    ("Kassadin", &["mid"]),
+    ("Katarina", &["mid"]),
+    ("Kayle", &["top"]),
+    ("Kayn", &["jungle"]),
+    ("Kennen", &["top"]),
+    ("Khazix", &["jungle"]),
+    ("Kindred", &["jungle"]),
+    ("Kled", &["top"]),
+    ("KogMaw", &["adc"]),
+    ("KSante", &["top"]),
+    ("Leblanc", &["mid"]),
+    ("LeeSin", &["jungle"]),
+    ("Leona", &["support"]),
+    ("Lillia", &["jungle"]),
+    ("Lissandra", &["mid"]),
+    ("Lucian", &["adc", "mid"]),
+    ("Lulu", &["support"]),
+    ("Lux", &["support", "mid"]),
+    ("Malphite", &["top"]),
+    ("Malzahar", &["mid"]),
+    ("Maokai", &["support", "jungle"]),
+    ("MasterYi", &["jungle"]),
+    ("Mel", &["mid", "support"]),
+    ("Milio", &["support"]),
+    ("MissFortune", &["adc"]),
+    ("MonkeyKing", &["jungle", "top"]),
+    ("Mordekaiser", &["top"]),
+    ("Morgana", &["support"]),
+    ("Naafiri", &["mid"]),
+    ("Nami", &["support"]),
+    ("Nasus", &["top"]),
+    ("Nautilus", &["support"]),
+    ("Neeko", &["mid", "support"]),
+    ("Nidalee", &["jungle"]),
+    ("Nilah", &["adc"]),
+    ("Nocturne", &["jungle"]),
+    ("Nunu", &["jungle"]),
+    ("Olaf", &["top", "jungle"]),
+    ("Orianna", &["mid"]),
+    ("Ornn", &["top"]),
+    ("Pantheon", &["support", "top"]),
+    ("Poppy", &["jungle", "top"]),
+    ("Pyke", &["support"]),
+    ("Qiyana", &["mid"]),
+    ("Quinn", &["top"]),
+    ("Rakan", &["support"]),
+    ("Rammus", &["jungle"]),
+    ("RekSai", &["jungle"]),
+    ("Rell", &["support"]),
+    ("Renata", &["support"]),
+    ("Renekton", &["top"]),
+    ("Rengar", &["jungle"]),
+    ("Riven", &["top"]),
+    ("Rumble", &["top"]),
+    ("Ryze", &["mid"]),
+    ("Samira", &["adc"]),
+    ("Sejuani", &["jungle"]),
+    ("Senna", &["support", "adc"]),
+    ("Seraphine", &["support", "adc"]),
+    ("Sett", &["top"]),
+    ("Shaco", &["jungle"]),
+    ("Shen", &["top"]),
+    ("Shyvana", &["jungle"]),
+    ("Singed", &["top"]),
+    ("Sion", &["top"]),
+    ("Sivir", &["adc"]),
+    ("Skarner", &["jungle"]),
+    ("Smolder", &["adc"]),
+    ("Sona", &["support"]),
+    ("Soraka", &["support"]),
+    ("Swain", &["support", "mid"]),
+    ("Sylas", &["mid"]),
+    ("Syndra", &["mid"]),
+    ("TahmKench", &["top", "support"]),
+    ("Taliyah", &["jungle", "mid"]),
+    ("Talon", &["mid", "jungle"]),
+    ("Taric", &["support"]),
+    ("Teemo", &["top"]),
+    ("Thresh", &["support"]),
+    ("Tristana", &["adc", "mid"]),
+    ("Trundle", &["top", "jungle"]),
+    ("Tryndamere", &["top"]),
+    ("TwistedFate", &["mid"]),
+    ("Twitch", &["adc"]),
+    ("Udyr", &["jungle"]),
+    ("Urgot", &["top"]),
+    ("Varus", &["adc"]),
+    ("Vayne", &["adc", "top"]),
+    ("Veigar", &["mid"]),
+    ("Velkoz", &["support", "mid"]),
+    ("Vex", &["mid"]),
+    ("Vi", &["jungle"]),
+    ("Viego", &["jungle"]),
+    ("Viktor", &["mid"]),
+    ("Vladimir", &["mid", "top"]),
+    ("Volibear", &["top", "jungle"]),
+    ("Warwick", &["jungle", "top"]),
+    ("Xayah", &["adc"]),
+    ("Xerath", &["support", "mid"]),
+    ("XinZhao", &["jungle"]),
+    ("Yasuo", &["mid", "top"]),
+    ("Yone", &["mid", "top"]),
+    ("Yorick", &["top"]),
+    ("Yuumi", &["support"]),
+    ("Zac", &["jungle"]),
+    ("Zed", &["mid"]),
+    ("Zeri", &["adc"]),
+    ("Ziggs", &["adc", "mid"]),
+    ("Zilean", &["support"]),
+    ("Zoe", &["mid"]),
+    ("Zyra", &["support"]),
+];
+
+/// `championrates.json`: champion ID -> position -> rates
+#[derive(Deserialize)]
+struct ChampionRatesFile {
+    data: HashMap<String, HashMap<String, PositionRates>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PositionRates {
+    #[serde(default)]
+    play_rate: f64,
+}
+
+/// Roles per champion ID, most played first
+type RolesById = HashMap<i64, Vec<String>>;
+
+/// Roles from the refreshed play rates (None until loaded)
+static REMOTE_ROLES: LazyLock<RwLock<Option<RolesById>>> = LazyLock::new(|| RwLock::new(None));
+
+/// Convert a Meraki position ("TOP", "MIDDLE", "BOTTOM", ...) to our role names
+fn role_from_position(position: &str) -> Option<&'static str> {
+    match position {
+        "TOP" => Some("top"),
+        "JUNGLE" => Some("jungle"),
+        "MIDDLE" => Some("mid"),
+        "BOTTOM" => Some("adc"),
+        "UTILITY" => Some("support"),
+        _ => None,
+    }
+}
+
+/// Parse `championrates.json` into roles per champion, most played first.
+pub(crate) fn parse_champion_rates(json: &str) -> Result<RolesById, String> {
+    let file: ChampionRatesFile = serde_json::from_str(json).map_err(|e| e.to_string())?;
+
+    Ok(file
+        .data
+        .into_iter()
+        .filter_map(|(champion_id, positions)| {
+            let mut rates: Vec<(&'static str, f64)> = positions
+                .iter()
+                .filter_map(|(position, rates)| Some((role_from_position(position)?, rates.play_rate)))
+                .collect();
+            rates.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+            // Always keep the main role, even for rarely played champions
+            let roles: Vec<String> = rates
+                .iter()
+                .enumerate()
+                .filter(|(i, (_, rate))| *i == 0 || *rate >= MIN_ROLE_PLAY_RATE)
+                .map(|(_, (role, _))| role.to_string())
+                .collect();
+
+            if roles.is_empty() {
+                return None;
+            }
+            Some((champion_id.parse().ok()?, roles))
+        })
+        .collect())
+}
+
+fn bundled_roles(ddragon_key: &str) -> Option<Vec<String>> {
+    BUNDLED_ROLES
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(ddragon_key))
+        .map(|(_, roles)| roles.iter().map(|r| r.to_string()).collect())
+}
+
+/// Roles of a champion, most played first (empty if unknown).
+pub fn champion_roles(champion_id: i64, ddragon_key: Option<&str>) -> Vec<String> {
+    let remote = REMOTE_ROLES
+        .read()
+        .ok()
+        .and_then(|roles| roles.as_ref()?.get(&champion_id).cloned());
+    if let Some(roles) = remote {
+        return roles;
+    }
+
+    let key = ddragon_key
+        .map(str::to_string)
+        .or_else(|| champions::ddragon_key_for_id(champion_id));
+    key.and_then(|key| bundled_roles(&key)).unwrap_or_default()
+}
+
+/// Main role of a champion, "mid" if unknown.
+pub fn default_role(champion_id: i64, ddragon_key: Option<&str>) -> String {
+    champion_roles(champion_id, ddragon_key)
+        .into_iter()
+        .next()
+        .unwrap_or_else(|| FALLBACK_ROLE.to_string())
+}
+
+/// Load the play-rate based roles, refreshing the disk cache when online.
+pub async fn refresh_champion_roles(app: AppHandle) {
+    let Ok(path) = storage::cache_file_path(&app, CHAMPION_RATES_CACHE_FILE) else {
+        return;
+    };
+
+    let downloaded = async {
+        let client = crate::proxy::remote_client()
+            .timeout(Duration::from_secs(15))
+            .build()
+            .map_err(|e| e.to_string())?;
+        let json = client
+            .get(CHAMPION_RATES_URL)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?
+            .error_for_status()
+            .map_err(|e| e.to_string())?
+            .text()
+            .await
+            .map_err(|e| e.to_string())?;
+        let roles = parse_champion_rates(&json)?;
+        storage::write_atomic(&path, json.as_bytes())?;
+        Ok::<_, String>(roles)
+    }
+    .await;
+
+    let roles = match downloaded {
+        Ok(roles) => roles,
+        Err(e) => {
+            log_info!("[roles] Champion rates unavailable, using disk cache: {}", e);
+            match std::fs::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|json| parse_champion_rates(&json))
+            {
+                Ok(roles) => roles,
+                Err(_) => return,
+            }
+        }
+    };
+
+    if let Ok(mut remote) = REMOTE_ROLES.write() {
+        *remote = Some(roles);
+    }
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the default role of a champion (used when no position is assigned).
+#[tauri::command]
+pub async fn get_default_role(champion_id: i64, champion_key: Option<String>) -> Result<String, String> {
+    Ok(default_role(champion_id, champion_key.as_deref()))
+}
+
+/// Get all the usual roles of a champion, most played first.
+#[tauri::command]
+pub async fn get_champion_roles(champion_id: i64, champion_key: Option<String>) -> Result<Vec<String>, String> {
+    Ok(champion_roles(champion_id, champion_key.as_deref()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundled_default_role() {
+        assert_eq!(default_role(0, Some("LeeSin")), "jungle");
+        assert_eq!(default_role(0, Some("thresh")), "support");
+        assert_eq!(default_role(0, Some("Unknown")), FALLBACK_ROLE);
+    }
+
+    #[test]
+    fn test_parse_champion_rates() {
+        let json = r#"{
+            "data": {
+                "64": {
+                    "TOP": { "playRate": 2.1 },
+                    "JUNGLE": { "playRate": 95.0 },
+                    "MIDDLE": { "playRate": 0.8 },
+                    "BOTTOM": { "playRate": 0.0 },
+                    "UTILITY": { "playRate": 2.1 }
+                },
+                "555": {
+                    "MIDDLE": { "playRate": 12.0 },
+                    "UTILITY": { "playRate": 85.0 }
+                }
+            },
+            "patch": "15.1"
+        }"#;
+
+        let roles = parse_champion_rates(json).unwrap();
+        assert_eq!(roles[&64], vec!["jungle"]);
+        assert_eq!(roles[&555], vec!["support", "mid"]);
+    }
+}
@@ -0,0 +1,138 @@
+//! Tableau des scores à la demande
+//!
+//! Expose la liste complète des joueurs de la partie en cours
+//! (champions, objets, scores) telle que fournie par l'endpoint officiel
+//! `/liveclientdata/playerlist`. Ce sont les mêmes informations que le
+//! tableau des scores du jeu (touche Tab) : aucun avantage compétitif.
+
+use serde::{Deserialize, Serialize};
+
+use crate::client_api::{LiveClient, LiveClientApi};
+use crate::game_watcher::{create_ingame_http_client, LIVE_CLIENT_PORT};
+
+/// Objet dans l'inventaire d'un joueur
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct PlayerItem {
+    #[serde(rename(deserialize = "itemID"), default)]
+    pub item_id: i64,
+    #[serde(rename(deserialize = "displayName"), default)]
+    pub display_name: String,
+    #[serde(default)]
+    pub count: i32,
+    #[serde(default)]
+    pub slot: i32,
+}
+
+/// Scores d'un joueur
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerScores {
+    #[serde(default)]
+    pub kills: i32,
+    #[serde(default)]
+    pub deaths: i32,
+    #[serde(default)]
+    pub assists: i32,
+    #[serde(default)]
+    pub creep_score: i32,
+    #[serde(default)]
+    pub ward_score: f64,
+}
+
+/// Joueur de la partie en cours (entrée de `/liveclientdata/playerlist`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LivePlayer {
+    #[serde(default)]
+    pub champion_name: String,
+    #[serde(default)]
+    pub riot_id: String,
+    #[serde(default)]
+    pub summoner_name: String,
+    /// "ORDER" (bleu) ou "CHAOS" (rouge)
+    #[serde(default)]
+    pub team: String,
+    /// "TOP", "JUNGLE", "MIDDLE", "BOTTOM", "UTILITY" ou vide
+    #[serde(default)]
+    pub position: String,
+    #[serde(default)]
+    pub level: i32,
+    #[serde(default)]
+    pub is_dead: bool,
+    #[serde(default)]
+    pub respawn_timer: f64,
+    #[serde(default)]
+    pub items: Vec<PlayerItem>,
+    #[serde(default)]
+    pub scores: PlayerScores,
+}
+
+/// Photo du tableau des scores à un instant donné
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ScoreboardSnapshot {
+    pub game_time: f64,
+    pub players: Vec<LivePlayer>,
+}
+
+/// Récupère la liste des joueurs de la partie en cours
+pub(crate) async fn fetch_player_list() -> Result<Vec<LivePlayer>, String> {
+    let client = create_ingame_http_client()?;
+    let url = format!(
+        "https://127.0.0.1:{}/liveclientdata/playerlist",
+        LIVE_CLIENT_PORT
+    );
+
+    let response = client.get(&url).send().await.map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err("Live Client API not available".to_string());
+    }
+
+    response.json().await.map_err(|e| e.to_string())
+}
+
+/// Commande Tauri : tableau des scores complet de la partie en cours
+///
+/// Retourne `None` hors partie.
+#[tauri::command]
+pub async fn get_scoreboard_snapshot() -> Result<Option<ScoreboardSnapshot>, String> {
+    let players = match fetch_player_list().await {
+        Ok(players) => players,
+        Err(_) => return Ok(None),
+    };
+    let game_time = LiveClient::local().game_time().await.unwrap_or(0.0);
+
+    Ok(Some(ScoreboardSnapshot { game_time, players }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_player_list() {
+        let json = r#"[{
+            "championName": "Ahri",
+            "riotId": "Focus#EUW",
+            "summonerName": "Focus#EUW",
+            "team": "ORDER",
+            "position": "MIDDLE",
+            "level": 11,
+            "isDead": true,
+            "respawnTimer": 21.5,
+            "items": [{ "itemID": 6655, "displayName": "Luden's Companion", "count": 1, "slot": 0, "price": 2850 }],
+            "scores": { "kills": 5, "deaths": 2, "assists": 7, "creepScore": 150, "wardScore": 12.0 },
+            "summonerSpells": {}
+        }]"#;
+
+        let players: Vec<LivePlayer> = serde_json::from_str(json).unwrap();
+        assert_eq!(players.len(), 1);
+        assert_eq!(players[0].champion_name, "Ahri");
+        assert_eq!(players[0].items[0].item_id, 6655);
+        assert_eq!(players[0].scores.creep_score, 150);
+
+        let serialized = serde_json::to_value(&players[0]).unwrap();
+        assert_eq!(serialized["items"][0]["itemId"], 6655);
+    }
+}
@@ -0,0 +1,333 @@
+//! Game session history.
+//!
+//! A session record is written by the game watcher when a live game ends
+//! (final CS, duration, champion) and completed with the game result once it
+//! is known, either from the `GameEnd` live event or from the LCU
+//! end-of-game stats block, which also gives the exact gold earned and the
+//! player's share of their team's gold, and the combat figures (KDA,
+//! damage share, kill participation).
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tauri::AppHandle;
+
+use crate::db;
+use crate::game_reports::CombatStats;
+
+/// Maximum number of sessions kept (oldest are dropped first)
+const MAX_SESSIONS: usize = 500;
+
+/// Outcome of a game for the local player
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum GameResult {
+    Win,
+    Loss,
+}
+
+impl GameResult {
+    /// Parse the `Result` field of the `GameEnd` live event ("Win" / "Lose")
+    pub fn from_live_event(value: &str) -> Option<Self> {
+        match value {
+            "Win" => Some(GameResult::Win),
+            "Lose" | "Loss" => Some(GameResult::Loss),
+            _ => None,
+        }
+    }
+}
+
+/// A finished game as recorded by the watcher
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GameSession {
+    /// LCU game ID (0 when it could not be resolved)
+    pub game_id: i64,
+    /// Queue ID from the gameflow session (e.g., 420 for ranked solo)
+    pub queue_id: i32,
+    pub champion_name: String,
+    /// Role assigned in champion select, when known
+    #[serde(default)]
+    pub role: Option<String>,
+    /// Unix timestamp (seconds) at which the game ended
+    pub ended_at: i64,
+    pub duration_secs: f64,
+    pub final_cs: i32,
+    pub cs_per_minute: f64,
+    /// CS/min over the laning phase (first 14 minutes), None when it was not followed
+    #[serde(default)]
+    pub laning_cs_per_minute: Option<f64>,
+    #[serde(default)]
+    pub result: Option<GameResult>,
+    /// Gold earned per minute, estimated from the live gold then set from the end-of-game
+    /// stats (None when neither is known)
+    #[serde(default)]
+    pub gold_per_minute: Option<f64>,
+    /// Share of the team's gold earned by the player, from the end-of-game stats
+    #[serde(default)]
+    pub gold_share: Option<f64>,
+    /// KDA, damage share and kill participation, from the end-of-game stats
+    #[serde(default)]
+    pub combat: Option<CombatStats>,
+    /// Practice Tool game: kept in the history, left out of the stats
+    #[serde(default)]
+    pub training: bool,
+}
+
+/// CS/min comparison between wins and losses
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ResultCsComparison {
+    pub wins: usize,
+    pub losses: usize,
+    pub avg_cs_per_minute_wins: f64,
+    pub avg_cs_per_minute_losses: f64,
+}
+
+/// Aggregates of the games played in one day or week
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PeriodSummary {
+    /// Unix timestamp (seconds) of the local midnight starting the period
+    pub start: i64,
+    pub games: usize,
+    pub wins: usize,
+    pub losses: usize,
+    pub avg_cs_per_minute: f64,
+    /// Share of wins among the games with a known result (None if there are none)
+    pub win_rate: Option<f64>,
+}
+
+/// Result of `get_session_summary`, most recent period first
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionSummary {
+    pub days: Vec<PeriodSummary>,
+    /// Weeks start on Monday
+    pub weeks: Vec<PeriodSummary>,
+}
+
+/// Seconds in a day
+const DAY_SECS: i64 = 86_400;
+
+/// Current Unix timestamp in seconds
+pub fn now_unix_secs() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64
+}
+
+/// Load all recorded sessions (oldest first)
+pub fn load_sessions(app: &AppHandle) -> Vec<GameSession> {
+    db::read(app, |connection| db::load_rows(connection, db::GAME_SESSIONS))
+}
+
+/// Load the sessions counted in the stats (Practice Tool games left out)
+pub fn load_competitive_sessions(app: &AppHandle) -> Vec<GameSession> {
+    let mut sessions = load_sessions(app);
+    sessions.retain(|s| !s.training);
+    sessions
+}
+
+/// Change a recorded session in place; `Ok(false)` if no session matches the game ID
+fn update_session(app: &AppHandle, game_id: i64, update: impl FnOnce(&mut GameSession)) -> Result<bool, String> {
+    db::with(app, |connection| db::update_game(connection, db::GAME_SESSIONS, game_id, update))
+}
+
+/// Append a finished game to the history.
+///
+/// If a record with the same non-zero game ID exists, it is replaced.
+pub fn record_session(app: &AppHandle, session: GameSession) -> Result<(), String> {
+    db::with(app, |connection| {
+        db::save_game(connection, db::GAME_SESSIONS, session.game_id, &session, MAX_SESSIONS)
+    })
+}
+
+/// Attach a game result to an already recorded session.
+///
+/// Returns `Ok(false)` if no session matches the game ID.
+pub fn set_session_result(app: &AppHandle, game_id: i64, result: GameResult) -> Result<bool, String> {
+    update_session(app, game_id, |session| session.result = Some(result))
+}
+
+/// Attach the end-of-game gold figures to an already recorded session.
+///
+/// Returns `Ok(false)` if no session matches the game ID.
+pub fn set_session_gold(app: &AppHandle, game_id: i64, gold_earned: f64, gold_share: Option<f64>) -> Result<bool, String> {
+    update_session(app, game_id, |session| {
+        if session.duration_secs > 0.0 {
+            session.gold_per_minute = Some(gold_earned / (session.duration_secs / 60.0));
+        }
+        session.gold_share = gold_share;
+    })
+}
+
+/// Attach the end-of-game combat figures to an already recorded session.
+///
+/// Returns `Ok(false)` if no session matches the game ID.
+pub fn set_session_combat(app: &AppHandle, game_id: i64, combat: CombatStats) -> Result<bool, String> {
+    update_session(app, game_id, |session| session.combat = Some(combat))
+}
+
+/// Compare average CS/min in wins and losses
+pub fn compare_cs_by_result(sessions: &[GameSession]) -> ResultCsComparison {
+    let average = |result: GameResult| -> (usize, f64) {
+        let values: Vec<f64> = sessions
+            .iter()
+            .filter(|s| s.result == Some(result))
+            .map(|s| s.cs_per_minute)
+            .collect();
+        if values.is_empty() {
+            (0, 0.0)
+        } else {
+            (values.len(), values.iter().sum::<f64>() / values.len() as f64)
+        }
+    };
+
+    let (wins, avg_cs_per_minute_wins) = average(GameResult::Win);
+    let (losses, avg_cs_per_minute_losses) = average(GameResult::Loss);
+
+    ResultCsComparison {
+        wins,
+        losses,
+        avg_cs_per_minute_wins,
+        avg_cs_per_minute_losses,
+    }
+}
+
+/// Index of the local day a session ended in (days since 1970-01-01)
+pub(crate) fn local_day(ended_at: i64, utc_offset_secs: i64) -> i64 {
+    (ended_at + utc_offset_secs).div_euclid(DAY_SECS)
+}
+
+/// First day (Monday) of the week holding `day` (1970-01-01 was a Thursday)
+fn week_start(day: i64) -> i64 {
+    day - (day + 3).rem_euclid(7)
+}
+
+/// Aggregate sessions per period; `period_of` maps an end time to the first day of its period
+fn summarize_periods(sessions: &[GameSession], utc_offset_secs: i64, period_of: impl Fn(i64) -> i64) -> Vec<PeriodSummary> {
+    let mut periods: BTreeMap<i64, Vec<&GameSession>> = BTreeMap::new();
+    for session in sessions {
+        let day = period_of(local_day(session.ended_at, utc_offset_secs));
+        periods.entry(day).or_default().push(session);
+    }
+
+    periods
+        .into_iter()
+        .rev()
+        .map(|(day, games)| {
+            let wins = games.iter().filter(|s| s.result == Some(GameResult::Win)).count();
+            let losses = games.iter().filter(|s| s.result == Some(GameResult::Loss)).count();
+            let decided = wins + losses;
+            PeriodSummary {
+                start: day * DAY_SECS - utc_offset_secs,
+                games: games.len(),
+                wins,
+                losses,
+                avg_cs_per_minute: games.iter().map(|s| s.cs_per_minute).sum::<f64>() / games.len() as f64,
+                win_rate: (decided > 0).then(|| wins as f64 / decided as f64),
+            }
+        })
+        .collect()
+}
+
+/// Per-day and per-week aggregates, in the local time given by `utc_offset_secs`
+pub fn summarize_sessions(sessions: &[GameSession], utc_offset_secs: i64) -> SessionSummary {
+    SessionSummary {
+        days: summarize_periods(sessions, utc_offset_secs, |day| day),
+        weeks: summarize_periods(sessions, utc_offset_secs, week_start),
+    }
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the recorded game sessions, most recent first.
+#[tauri::command]
+pub async fn get_game_sessions(app: AppHandle) -> Result<Vec<GameSession>, String> {
+    let mut sessions = load_sessions(&app);
+    sessions.reverse();
+    Ok(sessions)
+}
+
+/// Compare CS/min between won and lost games.
+#[tauri::command]
+pub async fn get_cs_by_result(app: AppHandle) -> Result<ResultCsComparison, String> {
+    Ok(compare_cs_by_result(&load_competitive_sessions(&app)))
+}
+
+/// Get the games played, average CS/min and win rate per day and per week.
+///
+/// `utc_offset_minutes` is the local offset from UTC (e.g., 120 for UTC+2),
+/// used to split days at local midnight. Defaults to UTC.
+#[tauri::command]
+pub async fn get_session_summary(app: AppHandle, utc_offset_minutes: Option<i32>) -> Result<SessionSummary, String> {
+    let offset = i64::from(utc_offset_minutes.unwrap_or(0)) * 60;
+    Ok(summarize_sessions(&load_competitive_sessions(&app), offset))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(cs_per_minute: f64, result: Option<GameResult>) -> GameSession {
+        GameSession {
+            cs_per_minute,
+            result,
+            ..GameSession::default()
+        }
+    }
+
+    #[test]
+    fn test_result_from_live_event() {
+        assert_eq!(GameResult::from_live_event("Win"), Some(GameResult::Win));
+        assert_eq!(GameResult::from_live_event("Lose"), Some(GameResult::Loss));
+        assert_eq!(GameResult::from_live_event("Unknown"), None);
+    }
+
+    #[test]
+    fn test_compare_cs_by_result() {
+        let sessions = vec![
+            session(8.0, Some(GameResult::Win)),
+            session(6.0, Some(GameResult::Win)),
+            session(5.0, Some(GameResult::Loss)),
+            session(9.0, None),
+        ];
+
+        let comparison = compare_cs_by_result(&sessions);
+        assert_eq!(comparison.wins, 2);
+        assert_eq!(comparison.losses, 1);
+        assert_eq!(comparison.avg_cs_per_minute_wins, 7.0);
+        assert_eq!(comparison.avg_cs_per_minute_losses, 5.0);
+    }
+
+    #[test]
+    fn test_summarize_sessions() {
+        let ended = |ended_at, cs_per_minute, result| GameSession {
+            ended_at,
+            ..session(cs_per_minute, result)
+        };
+        // 2024-01-01 (Monday) 23:30 UTC, i.e. 2024-01-02 00:30 at UTC+1
+        let monday = 1_704_151_800;
+        let sessions = vec![
+            ended(monday - 3 * DAY_SECS, 4.0, Some(GameResult::Loss)),
+            ended(monday, 6.0, Some(GameResult::Win)),
+            ended(monday + 600, 8.0, None),
+        ];
+
+        let utc = summarize_sessions(&sessions, 0);
+        assert_eq!(utc.days.len(), 2);
+        assert_eq!(utc.days[0].start, 1_704_067_200);
+        assert_eq!((utc.days[0].games, utc.days[0].avg_cs_per_minute), (2, 7.0));
+        assert_eq!(utc.days[0].win_rate, Some(1.0));
+        assert_eq!(utc.weeks.len(), 2);
+
+        // At UTC+1 the monday games fall on tuesday, still the same week
+        let local = summarize_sessions(&sessions, 3600);
+        assert_eq!(local.days[0].start, 1_704_150_000);
+        assert_eq!(local.weeks[0].start, 1_704_063_600);
+        assert_eq!(local.weeks[1].win_rate, Some(0.0));
+    }
+}
@@ -0,0 +1,264 @@
+//! Application settings.
+//!
+//! Settings are stored as JSON in the app config directory and shared through
+//! the Tauri state. Each file carries a `schemaVersion`; older files are
+//! migrated step by step on load so a renamed or reshaped field never resets
+//! the user's preferences. Every change is broadcast to all windows with the
+//! `settings-changed` event.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+use crate::auto_import::AutoImportRules;
+use crate::build_providers::{BuildPatch, BuildSource};
+use crate::cs_goals::CsGoalSettings;
+use crate::daily_goals::DailyGoal;
+use crate::events::AppEvent;
+use crate::logging::LogLevel;
+use crate::messages::Locale;
+use crate::notifications::NotificationSettings;
+use crate::storage;
+
+/// File holding the settings
+const SETTINGS_FILE: &str = "settings.json";
+
+/// Version of the settings format written by this build
+pub const CURRENT_SCHEMA_VERSION: u32 = 1;
+
+/// Migration steps: `MIGRATIONS[n]` upgrades a version `n` file to `n + 1`
+const MIGRATIONS: &[fn(&mut Map<String, Value>)] = &[migrate_v0_to_v1];
+
+/// Summoner spell key holding Flash
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum FlashKey {
+    D,
+    F,
+}
+
+/// User settings
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AppSettings {
+    pub schema_version: u32,
+    /// Import the build automatically during champion select
+    pub auto_import: bool,
+    /// Queues and ranked policy of the automatic import
+    pub auto_import_rules: AutoImportRules,
+    /// Preferred Flash key, swapped into imported builds (None = keep the build order)
+    pub flash_key: Option<FlashKey>,
+    /// Where import payloads come from (FocusApi by default)
+    pub build_source: BuildSource,
+    /// Patch of the imported builds (the current one by default)
+    pub build_patch: BuildPatch,
+    /// Launch FocusApp when the user logs in
+    pub autostart: bool,
+    /// Stay in the tray when launched at login
+    pub start_minimized: bool,
+    /// OS notifications for game phase changes (all off by default)
+    pub notifications: NotificationSettings,
+    /// Verbosity of the log file (raised to `debug` when investigating an issue)
+    pub log_level: LogLevel,
+    /// Language of the error messages
+    pub locale: Locale,
+    /// Send anonymous usage counters to FocusApi (opt-in)
+    pub telemetry: bool,
+    /// Proxy of remote requests (None = system proxy settings)
+    pub proxy_url: Option<String>,
+    /// Custom FocusApi URL (self-hosted servers)
+    pub focus_api_url: Option<String>,
+    /// League Client lockfile to use instead of the default install locations
+    pub lockfile_path: Option<String>,
+    /// Read-only mode: the overlay and stats keep working, the client is never modified
+    pub safe_mode: bool,
+    /// CS targets per game stage, globally or per role
+    pub cs_goals: CsGoalSettings,
+    /// Goals to meet every day, with their streaks
+    pub daily_goals: Vec<DailyGoal>,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            schema_version: CURRENT_SCHEMA_VERSION,
+            auto_import: false,
+            auto_import_rules: AutoImportRules::default(),
+            flash_key: None,
+            build_source: BuildSource::FocusApi,
+            build_patch: BuildPatch::Current,
+            autostart: false,
+            start_minimized: false,
+            notifications: NotificationSettings::default(),
+            log_level: LogLevel::default(),
+            locale: Locale::default(),
+            telemetry: false,
+            proxy_url: None,
+            focus_api_url: None,
+            lockfile_path: None,
+            safe_mode: false,
+            cs_goals: CsGoalSettings::default(),
+            daily_goals: Vec::new(),
+        }
+    }
+}
+
+/// Files written before `schemaVersion` existed have the same fields
+fn migrate_v0_to_v1(_settings: &mut Map<String, Value>) {}
+
+/// Upgrade raw settings to the current schema.
+///
+/// Files written by a newer build are left untouched: unknown fields are
+/// ignored and missing ones take their default value.
+fn migrate(mut raw: Value) -> Value {
+    let Some(settings) = raw.as_object_mut() else {
+        return Value::Object(Map::new());
+    };
+
+    let mut version = settings
+        .get("schemaVersion")
+        .and_then(Value::as_u64)
+        .unwrap_or(0) as usize;
+
+    while let Some(step) = MIGRATIONS.get(version) {
+        step(settings);
+        version += 1;
+        settings.insert("schemaVersion".to_string(), Value::from(version));
+    }
+
+    raw
+}
+
+/// Parse a settings file, migrating it if needed
+fn parse_settings(raw: Value) -> AppSettings {
+    serde_json::from_value(migrate(raw)).unwrap_or_else(|e| {
+        log_warn!("[settings] Ignoring unreadable settings: {}", e);
+        AppSettings::default()
+    })
+}
+
+/// Apply a partial update (only the fields present in `patch` change)
+fn apply_patch(current: &AppSettings, patch: Value) -> Result<AppSettings, String> {
+    let Value::Object(patch) = patch else {
+        return Err("Settings update must be an object".to_string());
+    };
+
+    let mut merged = serde_json::to_value(current).map_err(|e| e.to_string())?;
+    if let Some(fields) = merged.as_object_mut() {
+        for (key, value) in patch {
+            if key != "schemaVersion" {
+                fields.insert(key, value);
+            }
+        }
+    }
+
+    serde_json::from_value(merged).map_err(|e| format!("Invalid settings: {}", e))
+}
+
+/// Settings shared in the Tauri state.
+///
+/// Loaded from disk at startup, saved on every change.
+#[derive(Debug, Default)]
+pub struct SettingsStore {
+    settings: Mutex<AppSettings>,
+}
+
+impl SettingsStore {
+    /// Load the saved settings, migrating and rewriting older files
+    pub fn load(app: &AppHandle) -> Self {
+        let raw: Option<Value> = storage::read_json(app, SETTINGS_FILE);
+        let migrated = raw
+            .as_ref()
+            .and_then(|raw| raw.get("schemaVersion"))
+            .and_then(Value::as_u64)
+            .is_none_or(|version| version < CURRENT_SCHEMA_VERSION as u64);
+
+        let settings = raw.map(parse_settings).unwrap_or_default();
+        if migrated {
+            if let Err(e) = storage::write_json(app, SETTINGS_FILE, &settings) {
+                log_warn!("[settings] Failed to save migrated settings: {}", e);
+            }
+        }
+
+        Self {
+            settings: Mutex::new(settings),
+        }
+    }
+
+    /// Return a copy of the current settings
+    pub fn get(&self) -> AppSettings {
+        self.settings
+            .lock()
+            .map(|settings| settings.clone())
+            .unwrap_or_default()
+    }
+
+    /// Apply a partial update, save it and notify every window
+    pub fn update(&self, app: &AppHandle, patch: Value) -> Result<AppSettings, String> {
+        let mut current = self.settings.lock().map_err(|e| e.to_string())?;
+        let settings = apply_patch(&current, patch)?;
+        if settings == *current {
+            return Ok(settings);
+        }
+
+        storage::write_json(app, SETTINGS_FILE, &settings)?;
+        *current = settings.clone();
+        drop(current);
+
+        let _ = AppEvent::SettingsChanged(&settings).emit(app);
+        Ok(settings)
+    }
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the current settings.
+#[tauri::command]
+pub async fn get_settings(store: tauri::State<'_, SettingsStore>) -> Result<AppSettings, String> {
+    Ok(store.get())
+}
+
+/// Update some settings and return the full result.
+///
+/// Only the fields present in `settings` change; the new settings are also
+/// broadcast with the `settings-changed` event.
+#[tauri::command]
+pub async fn update_settings(
+    app: AppHandle,
+    store: tauri::State<'_, SettingsStore>,
+    settings: Value,
+) -> Result<AppSettings, String> {
+    store.update(&app, settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_migrate_unversioned_settings() {
+        let settings = parse_settings(json!({ "autoImport": true }));
+        assert_eq!(settings.schema_version, CURRENT_SCHEMA_VERSION);
+        assert!(settings.auto_import);
+        assert_eq!(settings.flash_key, None);
+
+        // Not an object (corrupted file)
+        assert_eq!(parse_settings(json!([1, 2])), AppSettings::default());
+    }
+
+    #[test]
+    fn test_apply_patch() {
+        let current = AppSettings::default();
+
+        let updated = apply_patch(&current, json!({ "flashKey": "F", "schemaVersion": 0 })).unwrap();
+        assert_eq!(updated.flash_key, Some(FlashKey::F));
+        assert_eq!(updated.schema_version, CURRENT_SCHEMA_VERSION);
+        assert!(!updated.auto_import);
+
+        assert!(apply_patch(&current, json!({ "autoImport": "yes" })).is_err());
+        assert!(apply_patch(&current, json!(true)).is_err());
+    }
+}
@@ -0,0 +1,114 @@
+//! Bundled offline static data.
+//!
+//! A gzip-compressed snapshot of DDragon's champion, item and rune data is
+//! embedded in the binary (regenerated with `scripts/update_static_snapshot.py`)
+//! so that names and icons resolve on first run without network access. Live
+//! DDragon data replaces it as soon as it has been loaded.
+
+use flate2::read::GzDecoder;
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::sync::LazyLock;
+
+use crate::champions::{self, ChampionDataset};
+
+static SNAPSHOT_GZ: &[u8] = include_bytes!("../resources/static-snapshot.json.gz");
+
+/// Item entry of the snapshot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotItem {
+    pub id: i64,
+    pub name: String,
+}
+
+/// Rune (or rune style) entry of the snapshot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotRune {
+    pub id: i64,
+    pub name: String,
+    /// Path relative to `https://ddragon.leagueoflegends.com/cdn/img/`
+    pub icon: String,
+}
+
+/// Item and rune data returned to the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundledStaticData {
+    pub version: String,
+    pub items: Vec<SnapshotItem>,
+    pub runes: Vec<SnapshotRune>,
+}
+
+#[derive(Deserialize)]
+struct SnapshotFile {
+    version: String,
+    /// Same shape as DDragon's `champion.json`
+    champions: serde_json::Value,
+    items: Vec<SnapshotItem>,
+    runes: Vec<SnapshotRune>,
+}
+
+/// Decompressed snapshot, decoded once
+static SNAPSHOT: LazyLock<Result<(ChampionDataset, BundledStaticData), String>> = LazyLock::new(|| {
+    let mut json = String::new();
+    GzDecoder::new(SNAPSHOT_GZ)
+        .read_to_string(&mut json)
+        .map_err(|e| e.to_string())?;
+    decode_snapshot(&json)
+});
+
+fn decode_snapshot(json: &str) -> Result<(ChampionDataset, BundledStaticData), String> {
+    let file: SnapshotFile = serde_json::from_str(json).map_err(|e| e.to_string())?;
+    let champions = ChampionDataset::from_json(&file.champions.to_string())?;
+    Ok((
+        champions,
+        BundledStaticData {
+            version: file.version,
+            items: file.items,
+            runes: file.runes,
+        },
+    ))
+}
+
+/// Use the bundled champions until live DDragon data is loaded.
+pub fn install_bundled_champions() {
+    match SNAPSHOT.as_ref() {
+        Ok((champions, _)) => champions::install_bundled_dataset(champions.clone()),
+        Err(e) => log_warn!("[snapshot] Failed to decode bundled static data: {}", e),
+    }
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the bundled item and rune data (offline fallback for the frontend).
+#[tauri::command]
+pub async fn get_bundled_static_data() -> Result<BundledStaticData, String> {
+    SNAPSHOT
+        .as_ref()
+        .map(|(_, data)| data.clone())
+        .map_err(|e| e.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundled_snapshot_decodes() {
+        let (champions, data) = SNAPSHOT.as_ref().unwrap();
+
+        assert_eq!(champions.find("wukong").unwrap().ddragon_key, "MonkeyKing");
+        assert_eq!(champions.find_by_id(145).unwrap().name, "Kai'Sa");
+        assert!(data.items.iter().any(|item| item.id == 3031));
+        assert!(data.runes.iter().any(|rune| rune.id == 8010));
+
+        // Every entry can be displayed
+        assert!(champions.champions().iter().all(|champion| !champion.name.is_empty()));
+        assert!(data.items.iter().all(|item| !item.name.is_empty()));
+        assert!(data.runes.iter().all(|rune| !rune.name.is_empty() && !rune.icon.is_empty()));
+    }
+}
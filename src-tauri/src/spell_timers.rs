@@ -0,0 +1,242 @@
+//! Suivi manuel des sorts d'invocateur adverses
+//!
+//! =============================================================================
+//! CONFORMITE RIOT GAMES
+//! =============================================================================
+//!
+//! Ce module ne lit AUCUNE donnée du jeu : les timers sont déclenchés
+//! uniquement par un clic de l'utilisateur dans l'interface de FocusApp
+//! (« Flash utilisé »). Le backend se contente de calculer l'expiration
+//! à partir des temps de recharge statiques et d'émettre un compte à rebours.
+//!
+//! - Aucun input envoyé au jeu
+//! - Aucune détection automatique de l'utilisation des sorts
+//! - Équivalent à un chronomètre manuel
+//!
+//! =============================================================================
+
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tauri::AppHandle;
+use tokio::time::{interval, Duration};
+
+use crate::events::AppEvent;
+
+/// Intervalle d'émission du compte à rebours
+const COUNTDOWN_TICK_MS: u64 = 1000;
+
+/// Données statiques d'un sort d'invocateur
+struct SummonerSpellData {
+    id: i32,
+    name: &'static str,
+    /// Temps de recharge de base en secondes (sans haste)
+    cooldown_secs: f64,
+}
+
+/// Temps de recharge de base des sorts d'invocateur (IDs identiques au LCU)
+const SUMMONER_SPELLS: &[SummonerSpellData] = &[
+    SummonerSpellData { id: 1, name: "Cleanse", cooldown_secs: 240.0 },
+    SummonerSpellData { id: 3, name: "Exhaust", cooldown_secs: 240.0 },
+    SummonerSpellData { id: 4, name: "Flash", cooldown_secs: 300.0 },
+    SummonerSpellData { id: 6, name: "Ghost", cooldown_secs: 240.0 },
+    SummonerSpellData { id: 7, name: "Heal", cooldown_secs: 240.0 },
+    SummonerSpellData { id: 11, name: "Smite", cooldown_secs: 90.0 },
+    SummonerSpellData { id: 12, name: "Teleport", cooldown_secs: 360.0 },
+    SummonerSpellData { id: 13, name: "Clarity", cooldown_secs: 240.0 },
+    SummonerSpellData { id: 14, name: "Ignite", cooldown_secs: 180.0 },
+    SummonerSpellData { id: 21, name: "Barrier", cooldown_secs: 180.0 },
+    SummonerSpellData { id: 32, name: "Mark", cooldown_secs: 80.0 },
+];
+
+fn find_spell(spell_id: i32) -> Option<&'static SummonerSpellData> {
+    SUMMONER_SPELLS.iter().find(|s| s.id == spell_id)
+}
+
+/// ID LCU d'un sort d'invocateur à partir de son nom anglais ("Flash" -> 4)
+pub fn spell_id_by_name(name: &str) -> Option<i64> {
+    SUMMONER_SPELLS
+        .iter()
+        .find(|s| s.name.eq_ignore_ascii_case(name.trim()))
+        .map(|s| i64::from(s.id))
+}
+
+/// Timer actif pour un sort d'un joueur adverse
+#[derive(Debug, Clone)]
+struct SpellTimer {
+    cell: i64,
+    spell_id: i32,
+    expires_at: Instant,
+}
+
+/// État d'un timer envoyé au frontend
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SpellTimerStatus {
+    /// Identifiant du joueur adverse (cellId de la champ select)
+    pub cell: i64,
+    pub spell_id: i32,
+    pub spell_name: String,
+    pub cooldown_secs: f64,
+    /// Secondes restantes avant que le sort soit de nouveau disponible
+    pub remaining_secs: f64,
+}
+
+impl SpellTimer {
+    fn status_at(&self, now: Instant) -> SpellTimerStatus {
+        let data = find_spell(self.spell_id);
+        SpellTimerStatus {
+            cell: self.cell,
+            spell_id: self.spell_id,
+            spell_name: data.map(|d| d.name).unwrap_or("Unknown").to_string(),
+            cooldown_secs: data.map(|d| d.cooldown_secs).unwrap_or(0.0),
+            remaining_secs: self.expires_at.saturating_duration_since(now).as_secs_f64(),
+        }
+    }
+}
+
+/// Gestionnaire des timers de sorts (partagé dans l'état Tauri)
+#[derive(Debug, Default, Clone)]
+pub struct SpellTimers {
+    timers: Arc<Mutex<Vec<SpellTimer>>>,
+    /// Indique si la tâche de compte à rebours tourne
+    ticking: Arc<AtomicBool>,
+}
+
+impl SpellTimers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Démarre (ou redémarre) le timer d'un sort
+    fn mark_used(&self, cell: i64, spell_id: i32, now: Instant) -> Result<SpellTimerStatus, String> {
+        let data = find_spell(spell_id).ok_or_else(|| format!("Unknown summoner spell: {}", spell_id))?;
+        let timer = SpellTimer {
+            cell,
+            spell_id,
+            expires_at: now + Duration::from_secs_f64(data.cooldown_secs),
+        };
+
+        let mut timers = self.timers.lock().map_err(|e| e.to_string())?;
+        timers.retain(|t| !(t.cell == cell && t.spell_id == spell_id));
+        timers.push(timer.clone());
+        Ok(timer.status_at(now))
+    }
+
+    /// Retourne les timers actifs et oublie ceux qui ont expiré
+    fn statuses_at(&self, now: Instant) -> Vec<SpellTimerStatus> {
+        let mut timers = match self.timers.lock() {
+            Ok(timers) => timers,
+            Err(_) => return Vec::new(),
+        };
+        timers.retain(|t| t.expires_at > now);
+        timers.iter().map(|t| t.status_at(now)).collect()
+    }
+
+    /// Supprime tous les timers (fin de partie)
+    pub fn clear(&self) {
+        if let Ok(mut timers) = self.timers.lock() {
+            timers.clear();
+        }
+    }
+
+    /// Lance la tâche d'émission du compte à rebours si elle ne tourne pas déjà.
+    ///
+    /// La tâche s'arrête d'elle-même quand plus aucun timer n'est actif,
+    /// après avoir émis une liste vide.
+    fn ensure_ticking(&self, app: AppHandle) {
+        if self.ticking.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        let timers = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = interval(Duration::from_millis(COUNTDOWN_TICK_MS));
+            loop {
+                ticker.tick().await;
+                let statuses = timers.statuses_at(Instant::now());
+                let _ = AppEvent::SpellTimersUpdate(&statuses).emit(&app);
+                if statuses.is_empty() {
+                    break;
+                }
+            }
+            timers.ticking.store(false, Ordering::SeqCst);
+        });
+    }
+}
+
+// =============================================================================
+// COMMANDES TAURI
+// =============================================================================
+
+/// Commande Tauri : l'utilisateur signale qu'un adversaire a utilisé un sort
+///
+/// Déclenchée UNIQUEMENT par un clic dans l'interface de FocusApp.
+/// Un nouveau clic sur le même sort redémarre son timer.
+#[tauri::command]
+pub async fn mark_spell_used(
+    app: AppHandle,
+    timers: tauri::State<'_, SpellTimers>,
+    cell: i64,
+    spell: i32,
+) -> Result<SpellTimerStatus, String> {
+    let status = timers.mark_used(cell, spell, Instant::now())?;
+    timers.ensure_ticking(app);
+    Ok(status)
+}
+
+/// Commande Tauri : retourne les timers de sorts actifs
+#[tauri::command]
+pub async fn get_spell_timers(
+    timers: tauri::State<'_, SpellTimers>,
+) -> Result<Vec<SpellTimerStatus>, String> {
+    Ok(timers.statuses_at(Instant::now()))
+}
+
+/// Commande Tauri : supprime tous les timers de sorts
+#[tauri::command]
+pub async fn clear_spell_timers(
+    app: AppHandle,
+    timers: tauri::State<'_, SpellTimers>,
+) -> Result<(), String> {
+    timers.clear();
+    let _ = AppEvent::SpellTimersUpdate(&[]).emit(&app);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mark_used_uses_static_cooldown() {
+        let timers = SpellTimers::new();
+        let now = Instant::now();
+
+        let status = timers.mark_used(3, 4, now).unwrap();
+        assert_eq!(status.spell_name, "Flash");
+        assert_eq!(status.remaining_secs, 300.0);
+    }
+
+    #[test]
+    fn test_mark_used_unknown_spell() {
+        let timers = SpellTimers::new();
+        assert!(timers.mark_used(3, 999, Instant::now()).is_err());
+    }
+
+    #[test]
+    fn test_remark_restarts_and_expired_are_dropped() {
+        let timers = SpellTimers::new();
+        let now = Instant::now();
+
+        timers.mark_used(1, 14, now).unwrap();
+        timers.mark_used(1, 14, now + Duration::from_secs(60)).unwrap();
+        timers.mark_used(2, 32, now).unwrap();
+
+        let statuses = timers.statuses_at(now + Duration::from_secs(120));
+        assert_eq!(statuses.len(), 1);
+        assert_eq!(statuses[0].spell_id, 14);
+        assert_eq!(statuses[0].remaining_secs, 120.0);
+    }
+}
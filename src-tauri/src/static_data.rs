@@ -0,0 +1,231 @@
+//! Static data cache integrity.
+//!
+//! Validates the files cached on disk by the champion, queue and role modules
+//! (presence, parseability and patch version) and repairs broken ones by
+//! deleting and re-downloading them. A half-written or stale cache would
+//! otherwise silently break champion resolution until the next patch.
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::champions::{self, ChampionDataset};
+use crate::queues;
+use crate::roles;
+use crate::storage;
+
+/// State of a cached file
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum CacheStatus {
+    Ok,
+    Missing,
+    /// The file exists but cannot be parsed (e.g., interrupted write)
+    Corrupt,
+    /// The file is valid but belongs to another patch
+    VersionMismatch,
+}
+
+/// Result of the check of one cached file
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheCheck {
+    /// Path relative to the cache directory
+    pub file: String,
+    pub status: CacheStatus,
+    /// Whether the file was successfully re-downloaded
+    pub repaired: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Report returned by `verify_static_data`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StaticDataReport {
+    /// DDragon version the caches were checked against
+    pub version: String,
+    /// True when every file is valid (initially or after repair)
+    pub healthy: bool,
+    pub checks: Vec<CacheCheck>,
+}
+
+/// Files cached by the static data modules
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CachedFile {
+    ChampionFull,
+    Queues,
+    Maps,
+    ChampionRates,
+    CDragonChampions,
+}
+
+const CACHED_FILES: &[CachedFile] = &[
+    CachedFile::ChampionFull,
+    CachedFile::Queues,
+    CachedFile::Maps,
+    CachedFile::ChampionRates,
+    CachedFile::CDragonChampions,
+];
+
+impl CachedFile {
+    fn relative_path(self, version: &str) -> String {
+        match self {
+            CachedFile::ChampionFull => champions::champion_cache_path(version),
+            CachedFile::Queues => queues::QUEUES_CACHE_FILE.to_string(),
+            CachedFile::Maps => queues::MAPS_CACHE_FILE.to_string(),
+            CachedFile::ChampionRates => roles::CHAMPION_RATES_CACHE_FILE.to_string(),
+            CachedFile::CDragonChampions => champions::CDRAGON_CACHE_FILE.to_string(),
+        }
+    }
+
+    /// The CommunityDragon cache only exists if DDragon once lagged behind
+    fn required(self) -> bool {
+        self != CachedFile::CDragonChampions
+    }
+
+    /// Validate the contents of the file
+    fn validate(self, json: &str, version: &str) -> Result<(), (CacheStatus, String)> {
+        let corrupt = |e: String| (CacheStatus::Corrupt, e);
+        match self {
+            CachedFile::ChampionFull => {
+                let dataset = ChampionDataset::from_json(json).map_err(corrupt)?;
+                if dataset.version != version {
+                    return Err((
+                        CacheStatus::VersionMismatch,
+                        format!("expected {}, found {}", version, dataset.version),
+                    ));
+                }
+                Ok(())
+            }
+            CachedFile::Queues => queues::validate_queues_json(json).map_err(corrupt),
+            CachedFile::Maps => queues::validate_maps_json(json).map_err(corrupt),
+            CachedFile::ChampionRates => roles::parse_champion_rates(json).map(|_| ()).map_err(corrupt),
+            CachedFile::CDragonChampions => ChampionDataset::from_cdragon_summary(json).map(|_| ()).map_err(corrupt),
+        }
+    }
+
+    /// Re-download the file through the module that owns it
+    async fn repair(self, app: &AppHandle, version: &str) {
+        let result = match self {
+            CachedFile::ChampionFull => champions::ensure_champion_dataset(app, version).await,
+            CachedFile::Queues | CachedFile::Maps => {
+                queues::refresh_queue_metadata(app.clone()).await;
+                Ok(())
+            }
+            CachedFile::ChampionRates => {
+                roles::refresh_champion_roles(app.clone()).await;
+                Ok(())
+            }
+            CachedFile::CDragonChampions => champions::ensure_cdragon_champions(app).await,
+        };
+        if let Err(e) = result {
+            log_warn!("[static_data] Failed to repair {:?}: {}", self, e);
+        }
+    }
+}
+
+/// Check one file; returns None for optional files that are absent.
+fn check_file(app: &AppHandle, file: CachedFile, version: &str) -> Option<(CacheStatus, Option<String>)> {
+    let path = match storage::cache_file_path(app, &file.relative_path(version)) {
+        Ok(path) => path,
+        Err(e) => return Some((CacheStatus::Missing, Some(e))),
+    };
+
+    match std::fs::read_to_string(&path) {
+        Ok(json) => match file.validate(&json, version) {
+            Ok(()) => Some((CacheStatus::Ok, None)),
+            Err((status, error)) => Some((status, Some(error))),
+        },
+        Err(_) if !file.required() => None,
+        Err(e) => Some((CacheStatus::Missing, Some(e.to_string()))),
+    }
+}
+
+/// Check the cached files without repairing them (health check)
+pub fn inspect_static_data(app: &AppHandle) -> StaticDataReport {
+    let version = champions::ddragon_version();
+    let checks: Vec<CacheCheck> = CACHED_FILES
+        .iter()
+        .filter_map(|&file| {
+            check_file(app, file, &version).map(|(status, error)| CacheCheck {
+                file: file.relative_path(&version),
+                status,
+                repaired: false,
+                error,
+            })
+        })
+        .collect();
+
+    StaticDataReport {
+        healthy: checks.iter().all(|c| c.status == CacheStatus::Ok),
+        version,
+        checks,
+    }
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Validate the cached static data files and repair the broken ones.
+#[tauri::command]
+pub async fn verify_static_data(app: AppHandle) -> Result<StaticDataReport, String> {
+    let version = champions::ddragon_version();
+    let mut checks = Vec::new();
+
+    for &file in CACHED_FILES {
+        let Some((status, error)) = check_file(&app, file, &version) else {
+            continue;
+        };
+
+        let mut check = CacheCheck {
+            file: file.relative_path(&version),
+            status,
+            repaired: false,
+            error,
+        };
+
+        if status != CacheStatus::Ok {
+            if let Ok(path) = storage::cache_file_path(&app, &check.file) {
+                let _ = std::fs::remove_file(path);
+            }
+            file.repair(&app, &version).await;
+            check.repaired = matches!(check_file(&app, file, &version), Some((CacheStatus::Ok, _)));
+        }
+
+        checks.push(check);
+    }
+
+    let healthy = checks.iter().all(|c| c.status == CacheStatus::Ok || c.repaired);
+    Ok(StaticDataReport {
+        version,
+        healthy,
+        checks,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_champion_full() {
+        let json = r#"{ "version": "15.1.1", "data": { "Ahri": { "id": "Ahri", "key": "103", "name": "Ahri" } } }"#;
+
+        assert!(CachedFile::ChampionFull.validate(json, "15.1.1").is_ok());
+
+        let (status, _) = CachedFile::ChampionFull.validate(json, "15.2.1").unwrap_err();
+        assert_eq!(status, CacheStatus::VersionMismatch);
+
+        // Truncated file (interrupted write)
+        let (status, _) = CachedFile::ChampionFull.validate(&json[..40], "15.1.1").unwrap_err();
+        assert_eq!(status, CacheStatus::Corrupt);
+    }
+
+    #[test]
+    fn test_validate_queue_files() {
+        assert!(CachedFile::Queues.validate(r#"[{ "queueId": 420, "map": "Summoner's Rift" }]"#, "").is_ok());
+        assert!(CachedFile::Maps.validate(r#"[{ "mapId": 11, "mapName": "Summoner's Rift" }]"#, "").is_ok());
+        assert!(CachedFile::Maps.validate("{}", "").is_err());
+    }
+}
@@ -0,0 +1,138 @@
+//! Export of the game sessions to CSV.
+//!
+//! `export_stats_csv` writes one row per recorded game (date, champion,
+//! role, queue, duration, CS, CS/min, result, Practice Tool flag) for the users who track their
+//! progress in a spreadsheet. Dates are UTC in ISO 8601 and the columns use
+//! plain values (seconds, numbers with a dot) so any spreadsheet can parse
+//! them.
+
+use std::path::PathBuf;
+use tauri::AppHandle;
+
+use crate::data_export::{civil_date, export_dir};
+use crate::queues;
+use crate::sessions::{self, now_unix_secs, GameResult, GameSession};
+use crate::storage;
+use crate::trends::{self, TrendWindow};
+
+/// Header row of the export
+const CSV_HEADER: &str = "date,champion,role,queue,duration_secs,cs,cs_per_minute,result,training";
+
+/// UTC date and time of a Unix timestamp ("2024-01-01T23:30:00Z")
+fn iso_date_time(timestamp: i64) -> String {
+    let (year, month, day) = civil_date(timestamp.div_euclid(86_400));
+    let secs = timestamp.rem_euclid(86_400);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        secs / 3600,
+        (secs % 3600) / 60,
+        secs % 60
+    )
+}
+
+/// Quote a field when it holds a separator, a quote or a line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// One row of the export
+fn csv_row(session: &GameSession) -> String {
+    let queue = queues::queue_metadata(session.queue_id)
+        .map(|queue| queue.name)
+        .unwrap_or_else(|| session.queue_id.to_string());
+    let result = match session.result {
+        Some(GameResult::Win) => "win",
+        Some(GameResult::Loss) => "loss",
+        None => "",
+    };
+    [
+        iso_date_time(session.ended_at),
+        csv_field(&session.champion_name),
+        csv_field(session.role.as_deref().unwrap_or_default()),
+        csv_field(&queue),
+        format!("{:.0}", session.duration_secs),
+        session.final_cs.to_string(),
+        format!("{:.2}", session.cs_per_minute),
+        result.to_string(),
+        session.training.to_string(),
+    ]
+    .join(",")
+}
+
+/// CSV of `sessions`, header included
+fn sessions_csv(sessions: &[&GameSession]) -> String {
+    let mut csv = String::from(CSV_HEADER);
+    csv.push('\n');
+    for session in sessions {
+        csv.push_str(&csv_row(session));
+        csv.push('\n');
+    }
+    csv
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Write the games of `range` (all of them by default) to a CSV file and return its path.
+///
+/// `range` is `{ "lastGames": 20 }` or `{ "lastDays": 30 }`; `path` defaults
+/// to `focusapp-stats-<timestamp>.csv` in the Downloads folder.
+#[tauri::command]
+pub async fn export_stats_csv(app: AppHandle, range: Option<TrendWindow>, path: Option<String>) -> Result<String, String> {
+    let timestamp = now_unix_secs();
+    let all = sessions::load_sessions(&app);
+    let selected = trends::select_sessions(&all, range.unwrap_or(TrendWindow::LastGames(usize::MAX)), None, timestamp);
+
+    let path = match path.filter(|p| !p.trim().is_empty()) {
+        Some(path) => PathBuf::from(path),
+        None => export_dir(&app)?.join(format!("focusapp-stats-{}.csv", timestamp)),
+    };
+    storage::write_atomic(&path, sessions_csv(&selected).as_bytes())?;
+
+    log_info!("[stats_export] Exported {} game(s) to {:?}", selected.len(), path);
+    Ok(path.to_string_lossy().into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sessions_csv() {
+        let session = GameSession {
+            queue_id: 420,
+            champion_name: "Kai'Sa, \"the\" Daughter".to_string(),
+            role: Some("adc".to_string()),
+            ended_at: 1_704_151_800,
+            duration_secs: 1_541.4,
+            final_cs: 185,
+            cs_per_minute: 7.2,
+            result: Some(GameResult::Win),
+            ..GameSession::default()
+        };
+        let unknown = GameSession {
+            queue_id: 9_999,
+            ..GameSession::default()
+        };
+
+        let csv = sessions_csv(&[&session, &unknown]);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], CSV_HEADER);
+        assert_eq!(
+            lines[1],
+            format!(
+                "2024-01-01T23:30:00Z,\"Kai'Sa, \"\"the\"\" Daughter\",adc,{},1541,185,7.20,win,false",
+                queues::queue_metadata(420).unwrap().name
+            )
+        );
+        assert_eq!(lines[2], "1970-01-01T00:00:00Z,,,9999,0,0,0.00,,false");
+    }
+}
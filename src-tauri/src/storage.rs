@@ -0,0 +1,64 @@
+//! Local JSON persistence helpers.
+//!
+//! User data lives in the application config directory
+//! (`%APPDATA%/com.focusapp.frontend` on Windows); downloaded static data
+//! lives in the application cache directory. Writes go through a
+//! temporary file followed by a rename so that a crash mid-write never
+//! leaves a truncated file behind.
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+/// Resolve the full path of a data file in the app config directory.
+pub fn data_file_path(app: &AppHandle, file_name: &str) -> Result<PathBuf, String> {
+    let dir = app.path().app_config_dir().map_err(|e| e.to_string())?;
+    Ok(dir.join(file_name))
+}
+
+/// Resolve the full path of a file in the app cache directory.
+pub fn cache_file_path(app: &AppHandle, relative_path: &str) -> Result<PathBuf, String> {
+    let dir = app.path().app_cache_dir().map_err(|e| e.to_string())?;
+    Ok(dir.join(relative_path))
+}
+
+/// Atomically write raw bytes to `path`, creating parent directories.
+pub fn write_atomic(path: &Path, contents: &[u8]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+
+    let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(".tmp");
+    let tmp_path = path.with_file_name(tmp_name);
+
+    std::fs::write(&tmp_path, contents).map_err(|e| e.to_string())?;
+    std::fs::rename(&tmp_path, path).map_err(|e| e.to_string())
+}
+
+/// Read a JSON file from the app config directory.
+///
+/// Returns `T::default()` when the file does not exist or cannot be parsed,
+/// so a corrupted file never prevents the app from starting.
+pub fn read_json<T: DeserializeOwned + Default>(app: &AppHandle, file_name: &str) -> T {
+    let path = match data_file_path(app, file_name) {
+        Ok(path) => path,
+        Err(_) => return T::default(),
+    };
+
+    match std::fs::read_to_string(&path) {
+        Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+            log_warn!("[storage] Ignoring unreadable {:?}: {}", path, e);
+            T::default()
+        }),
+        Err(_) => T::default(),
+    }
+}
+
+/// Atomically write a JSON file to the app config directory.
+pub fn write_json<T: Serialize>(app: &AppHandle, file_name: &str, value: &T) -> Result<(), String> {
+    let path = data_file_path(app, file_name)?;
+    let contents = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
+    write_atomic(&path, contents.as_bytes())
+}
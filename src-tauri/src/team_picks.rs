@@ -0,0 +1,175 @@
+//! Team composition during champion select.
+//!
+//! On each champion select poll the game watcher hands the session to
+//! `on_champ_select`, which works out the champion each member of the local
+//! player's team hovers or locked and sends the whole team with the
+//! `team-picks-changed` event when anything changed. The frontend shows the
+//! evolving comp next to the build suggestions.
+//!
+//! The local player's assigned position is also sent on its own, with
+//! `assigned-position`, as soon as the session shows it, so the role
+//! selector and the build requests use the real lane.
+
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+use crate::events::AppEvent;
+use crate::lcu::ChampionSelectSession;
+use crate::{champions, prefetch};
+
+/// Champion of one member of the local player's team
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TeammatePick {
+    pub cell_id: i64,
+    /// 0 while nothing is hovered
+    pub champion_id: i64,
+    /// DDragon key (e.g., "MonkeyKing"), when known
+    pub champion_key: Option<String>,
+    /// Assigned position as sent by the client (e.g., "UTILITY")
+    pub assigned_position: Option<String>,
+    /// Build role of the position ("support"), when assigned
+    pub role: Option<String>,
+    /// False while the champion is only hovered
+    pub locked: bool,
+    pub is_local_player: bool,
+}
+
+/// `team-picks-changed` payload
+#[derive(Debug, Clone, Default, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TeamPicks {
+    /// In cell order
+    pub picks: Vec<TeammatePick>,
+}
+
+/// `assigned-position` payload
+#[derive(Debug, Clone, Default, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AssignedPosition {
+    /// As sent by the client (e.g., "UTILITY"); None in blind pick
+    pub position: Option<String>,
+    /// Build role of the position ("support")
+    pub role: Option<String>,
+}
+
+/// Team last sent (None outside champion select)
+static LAST_SENT: Mutex<Option<TeamPicks>> = Mutex::new(None);
+
+/// Position last sent (None outside champion select)
+static LAST_POSITION: Mutex<Option<AssignedPosition>> = Mutex::new(None);
+
+/// Picks of the local player's team
+fn team_picks(session: &ChampionSelectSession) -> TeamPicks {
+    let mut picks: Vec<TeammatePick> = session
+        .my_team
+        .iter()
+        .flatten()
+        .map(|member| {
+            let (champion_id, locked) = session.champion_of(member.cell_id);
+            TeammatePick {
+                cell_id: member.cell_id,
+                champion_id,
+                champion_key: (champion_id > 0).then(|| champions::ddragon_key_for_id(champion_id)).flatten(),
+                assigned_position: member.assigned_position.clone().filter(|p| !p.is_empty()),
+                role: member
+                    .assigned_position
+                    .as_deref()
+                    .and_then(prefetch::role_for_position)
+                    .map(str::to_string),
+                locked,
+                is_local_player: session.local_player_cell_id == Some(member.cell_id),
+            }
+        })
+        .collect();
+    picks.sort_by_key(|pick| pick.cell_id);
+    TeamPicks { picks }
+}
+
+/// Assigned position of the local player (None until the session shows them)
+fn assigned_position(picks: &TeamPicks) -> Option<AssignedPosition> {
+    let local = picks.picks.iter().find(|pick| pick.is_local_player)?;
+    Some(AssignedPosition {
+        position: local.assigned_position.clone(),
+        role: local.role.clone(),
+    })
+}
+
+/// Send the local player's position the first time it is known, or when it changed
+fn send_position(app: &AppHandle, position: AssignedPosition) {
+    let Ok(mut last) = LAST_POSITION.lock() else {
+        return;
+    };
+    if last.as_ref() == Some(&position) {
+        return;
+    }
+    log_debug!("[team_picks] Assigned position: {:?}", position.position);
+    if let Err(e) = AppEvent::AssignedPosition(&position).emit(app) {
+        log_debug!("[team_picks] {}", e);
+    }
+    *last = Some(position);
+}
+
+/// Send the team when a hover or lock changed
+pub fn on_champ_select(app: &AppHandle, session: &ChampionSelectSession) {
+    let picks = team_picks(session);
+    if let Some(position) = assigned_position(&picks) {
+        send_position(app, position);
+    }
+    let Ok(mut last) = LAST_SENT.lock() else {
+        return;
+    };
+    if last.as_ref() == Some(&picks) {
+        return;
+    }
+    if let Err(e) = AppEvent::TeamPicksChanged(&picks).emit(app) {
+        log_debug!("[team_picks] {}", e);
+    }
+    *last = Some(picks);
+}
+
+/// Forget the last team and position (leaving champion select)
+pub fn reset() {
+    if let Ok(mut last) = LAST_SENT.lock() {
+        *last = None;
+    }
+    if let Ok(mut last) = LAST_POSITION.lock() {
+        *last = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_team_picks() {
+        let session: ChampionSelectSession = serde_json::from_value(json!({
+            "localPlayerCellId": 1,
+            "actions": [[
+                { "id": 1, "actorCellId": 0, "championId": 22, "completed": true, "type": "pick" },
+                { "id": 2, "actorCellId": 1, "championId": 103, "completed": false, "isInProgress": true, "type": "pick" },
+                { "id": 3, "actorCellId": 2, "championId": 0, "completed": false, "type": "pick" }
+            ]],
+            "myTeam": [
+                { "cellId": 2, "championId": 0, "championPickIntent": 64, "assignedPosition": "JUNGLE" },
+                { "cellId": 0, "championId": 22, "assignedPosition": "BOTTOM" },
+                { "cellId": 1, "championId": 103, "assignedPosition": "" }
+            ]
+        }))
+        .unwrap();
+
+        let picks = team_picks(&session).picks;
+        let summary: Vec<(i64, i64, bool)> = picks.iter().map(|p| (p.cell_id, p.champion_id, p.locked)).collect();
+        assert_eq!(summary, [(0, 22, true), (1, 103, false), (2, 64, false)]);
+        assert_eq!(picks[0].role.as_deref(), Some("adc"));
+        assert_eq!(picks[1].assigned_position, None);
+        assert!(picks[1].is_local_player);
+
+        // Blind pick: known, without a position
+        let position = assigned_position(&team_picks(&session)).unwrap();
+        assert_eq!(position, AssignedPosition::default());
+    }
+}
@@ -0,0 +1,217 @@
+//! Anonymous usage metrics (opt-in).
+//!
+//! Off unless the user enables `telemetry` in the settings. Only counters are
+//! kept: how many imports succeeded, failed or were queued, how many times
+//! the CS overlay was shown, and which error codes were returned. No summoner
+//! name, champion, Riot ID or message is ever recorded. Counters are
+//! buffered in `telemetry.json` and sent to FocusApi in batches; the buffer is
+//! cleared once sent, or as soon as the user turns telemetry off.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Listener, Manager};
+
+use crate::sessions::now_unix_secs;
+use crate::events::SETTINGS_CHANGED_EVENT;
+use crate::settings::{AppSettings, SettingsStore};
+use crate::{focus_api, storage};
+
+/// File holding the counters not sent yet
+const TELEMETRY_FILE: &str = "telemetry.json";
+
+/// FocusApi endpoint receiving the batches
+const TELEMETRY_PATH: &str = "/telemetry";
+
+/// Interval between two batches
+const SEND_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Longest error code accepted as a counter name
+const MAX_CODE_LEN: usize = 40;
+
+/// Serializes read-modify-write of the buffer
+static BUFFER_LOCK: Mutex<()> = Mutex::new(());
+
+/// Something counted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric<'a> {
+    ImportSucceeded,
+    ImportFailed,
+    ImportQueued,
+    OverlayShown,
+    /// `CommandError.code` returned to the frontend
+    Error(&'a str),
+}
+
+impl Metric<'_> {
+    /// Counter name, None for values that are not plain error codes
+    fn key(&self) -> Option<String> {
+        match self {
+            Metric::ImportSucceeded => Some("import.success".to_string()),
+            Metric::ImportFailed => Some("import.failed".to_string()),
+            Metric::ImportQueued => Some("import.queued".to_string()),
+            Metric::OverlayShown => Some("overlay.shown".to_string()),
+            Metric::Error(code) => {
+                let valid = !code.is_empty()
+                    && code.len() <= MAX_CODE_LEN
+                    && code.chars().all(|c| c.is_ascii_uppercase() || c == '_');
+                valid.then(|| format!("error.{}", code))
+            }
+        }
+    }
+}
+
+/// Counters waiting to be sent
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TelemetryBuffer {
+    /// Unix timestamp (seconds) of the first counted event
+    pub since: i64,
+    pub counters: BTreeMap<String, u64>,
+}
+
+impl TelemetryBuffer {
+    fn add(&mut self, key: String, now: i64) {
+        if self.counters.is_empty() {
+            self.since = now;
+        }
+        *self.counters.entry(key).or_insert(0) += 1;
+    }
+}
+
+/// Batch sent to FocusApi
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TelemetryBatch<'a> {
+    app_version: &'a str,
+    os: &'a str,
+    since: i64,
+    until: i64,
+    counters: &'a BTreeMap<String, u64>,
+}
+
+fn enabled(app: &AppHandle) -> bool {
+    app.try_state::<SettingsStore>()
+        .is_some_and(|store| store.get().telemetry)
+}
+
+fn load_buffer(app: &AppHandle) -> TelemetryBuffer {
+    storage::read_json(app, TELEMETRY_FILE)
+}
+
+fn save_buffer(app: &AppHandle, buffer: &TelemetryBuffer) {
+    if let Err(e) = storage::write_json(app, TELEMETRY_FILE, buffer) {
+        log_warn!("[telemetry] Failed to save counters: {}", e);
+    }
+}
+
+/// Count an event if the user opted in
+pub fn record(app: &AppHandle, metric: Metric) {
+    if !enabled(app) {
+        return;
+    }
+    let Some(key) = metric.key() else {
+        return;
+    };
+    let _guard = BUFFER_LOCK.lock();
+    let mut buffer = load_buffer(app);
+    buffer.add(key, now_unix_secs());
+    save_buffer(app, &buffer);
+}
+
+/// Drop the counters not sent yet
+fn clear(app: &AppHandle) {
+    let _guard = BUFFER_LOCK.lock();
+    if !load_buffer(app).counters.is_empty() {
+        save_buffer(app, &TelemetryBuffer::default());
+    }
+}
+
+/// Send the buffered counters, cleared on success
+async fn send_batch(app: &AppHandle) -> Result<(), String> {
+    let buffer = load_buffer(app);
+    if buffer.counters.is_empty() {
+        return Ok(());
+    }
+
+    let batch = TelemetryBatch {
+        app_version: env!("CARGO_PKG_VERSION"),
+        os: std::env::consts::OS,
+        since: buffer.since,
+        until: now_unix_secs(),
+        counters: &buffer.counters,
+    };
+    let response = focus_api::request_json(reqwest::Method::POST, TELEMETRY_PATH, &batch)
+        .map_err(|e| e.to_string())?
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("HTTP {}", response.status()));
+    }
+
+    // Events counted while the batch was in flight stay for the next one
+    let _guard = BUFFER_LOCK.lock();
+    let mut current = load_buffer(app);
+    for (key, count) in &buffer.counters {
+        if let Some(value) = current.counters.get_mut(key) {
+            *value = value.saturating_sub(*count);
+        }
+    }
+    current.counters.retain(|_, count| *count > 0);
+    save_buffer(app, &current);
+    Ok(())
+}
+
+/// Send batches periodically and forget the counters when telemetry is disabled
+pub async fn run_telemetry_worker(app: AppHandle) {
+    let listener_app = app.clone();
+    app.listen(SETTINGS_CHANGED_EVENT, move |event| {
+        if let Ok(settings) = serde_json::from_str::<AppSettings>(event.payload()) {
+            if !settings.telemetry {
+                clear(&listener_app);
+            }
+        }
+    });
+
+    loop {
+        tokio::time::sleep(SEND_INTERVAL).await;
+        if !enabled(&app) {
+            continue;
+        }
+        if let Err(e) = send_batch(&app).await {
+            log_info!("[telemetry] Batch not sent: {}", e);
+        }
+    }
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the counters waiting to be sent (exactly what the next batch contains).
+#[tauri::command]
+pub async fn get_telemetry_buffer(app: AppHandle) -> Result<TelemetryBuffer, String> {
+    Ok(load_buffer(&app))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metric_keys() {
+        assert_eq!(Metric::ImportSucceeded.key().as_deref(), Some("import.success"));
+        assert_eq!(Metric::Error("CLIENT_NOT_RUNNING").key().as_deref(), Some("error.CLIENT_NOT_RUNNING"));
+        // Anything that could carry user data is dropped
+        assert_eq!(Metric::Error("Faker#KR1").key(), None);
+        assert_eq!(Metric::Error("").key(), None);
+
+        let mut buffer = TelemetryBuffer::default();
+        buffer.add("import.success".to_string(), 100);
+        buffer.add("import.success".to_string(), 200);
+        assert_eq!(buffer.since, 100);
+        assert_eq!(buffer.counters["import.success"], 2);
+    }
+}
@@ -0,0 +1,178 @@
+//! System tray icon.
+//!
+//! FocusApp mostly runs in the background next to League, so the tray shows
+//! the connection state (client closed, connected, in game) in its tooltip
+//! and first menu line, and gives quick access to the CS overlay, the
+//! auto-import setting, the main window and Quit. Left click opens the main
+//! window.
+
+use serde_json::json;
+use std::sync::Mutex;
+use tauri::menu::{CheckMenuItem, Menu, MenuEvent, MenuItem, PredefinedMenuItem};
+use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
+use tauri::{AppHandle, Listener, Manager, Wry};
+
+use crate::game_watcher::GameState;
+use crate::overlay;
+use crate::events::SETTINGS_CHANGED_EVENT;
+use crate::settings::{AppSettings, SettingsStore};
+
+/// ID of the tray icon
+const TRAY_ID: &str = "focusapp";
+
+const MENU_TOGGLE_OVERLAY: &str = "toggle-overlay";
+const MENU_AUTO_IMPORT: &str = "auto-import";
+const MENU_OPEN: &str = "open";
+const MENU_QUIT: &str = "quit";
+
+/// Connection state shown in the tray
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrayStatus {
+    ClientClosed,
+    Connected,
+    InGame,
+}
+
+impl TrayStatus {
+    pub fn of(state: &GameState) -> Self {
+        match state {
+            GameState::ClientClosed | GameState::Error { .. } => TrayStatus::ClientClosed,
+            GameState::GameStart | GameState::InProgress { .. } => TrayStatus::InGame,
+            _ => TrayStatus::Connected,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            TrayStatus::ClientClosed => "League Client closed",
+            TrayStatus::Connected => "Connected to the League Client",
+            TrayStatus::InGame => "In game",
+        }
+    }
+}
+
+/// Tray status and the menu line showing it
+pub struct TrayState {
+    status_item: MenuItem<Wry>,
+    status: Mutex<TrayStatus>,
+}
+
+/// Bring the main window to front
+pub fn show_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+fn on_menu_event(app: &AppHandle, event: MenuEvent) {
+    match event.id.as_ref() {
+        MENU_TOGGLE_OVERLAY => {
+            if let Err(e) = overlay::toggle_overlay(app) {
+                log_warn!("[tray] Failed to toggle the overlay: {}", e);
+            }
+        }
+        MENU_AUTO_IMPORT => {
+            let Some(store) = app.try_state::<SettingsStore>() else {
+                return;
+            };
+            let enabled = !store.get().auto_import;
+            // The frontend follows the change through `settings-changed`
+            if let Err(e) = store.update(app, json!({ "autoImport": enabled })) {
+                log_warn!("[tray] Failed to toggle auto-import: {}", e);
+            }
+        }
+        MENU_OPEN => show_main_window(app),
+        MENU_QUIT => app.exit(0),
+        _ => {}
+    }
+}
+
+/// Create the tray icon (after the settings are loaded)
+pub fn init(app: &AppHandle) -> tauri::Result<()> {
+    let status = TrayStatus::ClientClosed;
+    let auto_import = app
+        .try_state::<SettingsStore>()
+        .is_some_and(|store| store.get().auto_import);
+
+    let status_item = MenuItem::with_id(app, "status", status.label(), false, None::<&str>)?;
+    let auto_import_item = CheckMenuItem::with_id(app, MENU_AUTO_IMPORT, "Auto-import builds", true, auto_import, None::<&str>)?;
+    let menu = Menu::with_items(
+        app,
+        &[
+            &status_item,
+            &PredefinedMenuItem::separator(app)?,
+            &MenuItem::with_id(app, MENU_TOGGLE_OVERLAY, "Show / hide CS overlay", true, None::<&str>)?,
+            &auto_import_item,
+            &PredefinedMenuItem::separator(app)?,
+            &MenuItem::with_id(app, MENU_OPEN, "Open FocusApp", true, None::<&str>)?,
+            &MenuItem::with_id(app, MENU_QUIT, "Quit", true, None::<&str>)?,
+        ],
+    )?;
+
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip(format!("FocusApp - {}", status.label()))
+        .menu(&menu)
+        .show_menu_on_left_click(false)
+        .on_menu_event(on_menu_event)
+        .on_tray_icon_event(|tray, event| {
+            if let TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                ..
+            } = event
+            {
+                show_main_window(tray.app_handle());
+            }
+        });
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    builder.build(app)?;
+
+    // Keep the check mark in sync when auto-import is toggled in the app
+    app.listen(SETTINGS_CHANGED_EVENT, move |event| {
+        if let Ok(settings) = serde_json::from_str::<AppSettings>(event.payload()) {
+            let _ = auto_import_item.set_checked(settings.auto_import);
+        }
+    });
+
+    app.manage(TrayState {
+        status_item,
+        status: Mutex::new(status),
+    });
+    Ok(())
+}
+
+/// Show the game state in the tray (only touches the tray when the status changes)
+pub fn set_status(app: &AppHandle, state: &GameState) {
+    let Some(tray_state) = app.try_state::<TrayState>() else {
+        return;
+    };
+    let status = TrayStatus::of(state);
+    let Ok(mut current) = tray_state.status.lock() else {
+        return;
+    };
+    if *current == status {
+        return;
+    }
+    *current = status;
+
+    let _ = tray_state.status_item.set_text(status.label());
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        let _ = tray.set_tooltip(Some(format!("FocusApp - {}", status.label())));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tray_status() {
+        assert_eq!(TrayStatus::of(&GameState::ClientClosed), TrayStatus::ClientClosed);
+        assert_eq!(TrayStatus::of(&GameState::ChampSelect { champion_id: Some(103) }), TrayStatus::Connected);
+        assert_eq!(TrayStatus::of(&GameState::InProgress { game_data: None }), TrayStatus::InGame);
+    }
+}
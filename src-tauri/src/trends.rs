@@ -0,0 +1,272 @@
+//! Historical trends of the recorded sessions, for the charts.
+//!
+//! `get_trends` selects the sessions of a window (the last N games or the
+//! last N days, optionally for one champion) and returns one point per game
+//! with its CS/min, laning-phase CS/min, gold/min and gold share and the rolling averages and win rate over
+//! the previous `ROLLING_GAMES` games, already smoothed so the frontend only
+//! has to draw them.
+//!
+//! `get_champion_stats` sums the sessions up per champion (averages over all
+//! games and over the last `ROLLING_GAMES`), to show which champions the
+//! player farms well on.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tauri::AppHandle;
+
+use crate::sessions::{self, GameResult, GameSession};
+
+/// Games averaged by each point of the rolling curves
+const ROLLING_GAMES: usize = 5;
+
+/// Seconds in a day
+const DAY_SECS: i64 = 86_400;
+
+/// Sessions covered by a trend
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum TrendWindow {
+    /// The N most recent games
+    LastGames(usize),
+    /// The games of the last N days
+    LastDays(u32),
+}
+
+impl Default for TrendWindow {
+    fn default() -> Self {
+        TrendWindow::LastGames(20)
+    }
+}
+
+/// One game of a trend
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TrendPoint {
+    pub game_id: i64,
+    pub ended_at: i64,
+    pub champion_name: String,
+    pub cs_per_minute: f64,
+    pub laning_cs_per_minute: Option<f64>,
+    pub gold_per_minute: Option<f64>,
+    /// Share of the team's gold
+    pub gold_share: Option<f64>,
+    /// Averages over this game and the previous ones (up to `ROLLING_GAMES`)
+    pub rolling_cs_per_minute: f64,
+    pub rolling_laning_cs_per_minute: Option<f64>,
+    pub rolling_gold_per_minute: Option<f64>,
+    pub rolling_gold_share: Option<f64>,
+    /// Share of wins among the rolling games with a known result
+    pub rolling_win_rate: Option<f64>,
+}
+
+/// Result of `get_trends`, oldest game first
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Trends {
+    pub points: Vec<TrendPoint>,
+    pub avg_cs_per_minute: f64,
+    pub avg_laning_cs_per_minute: Option<f64>,
+    pub avg_gold_per_minute: Option<f64>,
+    pub avg_gold_share: Option<f64>,
+    pub win_rate: Option<f64>,
+}
+
+/// CS averages of one champion
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChampionStats {
+    pub champion_name: String,
+    pub games: usize,
+    pub avg_cs_per_minute: f64,
+    /// Average over the last `ROLLING_GAMES` games
+    pub recent_cs_per_minute: f64,
+    pub best_cs_per_minute: f64,
+    pub avg_laning_cs_per_minute: Option<f64>,
+    pub avg_final_cs: f64,
+    pub avg_gold_per_minute: Option<f64>,
+    pub avg_gold_share: Option<f64>,
+    pub win_rate: Option<f64>,
+    /// Unix timestamp (seconds) of the last game
+    pub last_played: i64,
+}
+
+/// Average of the values that are known
+fn average(values: impl Iterator<Item = Option<f64>>) -> Option<f64> {
+    let (sum, count) = values.flatten().fold((0.0, 0), |(sum, count), v| (sum + v, count + 1));
+    (count > 0).then(|| sum / count as f64)
+}
+
+/// Share of wins among the games with a known result
+fn win_rate(games: &[&GameSession]) -> Option<f64> {
+    average(games.iter().map(|s| {
+        s.result.map(|result| if result == GameResult::Win { 1.0 } else { 0.0 })
+    }))
+}
+
+/// Sessions (oldest first) within `window`, optionally of one champion
+pub fn select_sessions<'a>(
+    sessions: &'a [GameSession],
+    window: TrendWindow,
+    champion: Option<&str>,
+    now: i64,
+) -> Vec<&'a GameSession> {
+    let mut games: Vec<&GameSession> = sessions
+        .iter()
+        .filter(|s| champion.is_none_or(|c| s.champion_name.eq_ignore_ascii_case(c)))
+        .collect();
+    match window {
+        TrendWindow::LastGames(count) => {
+            let skip = games.len().saturating_sub(count);
+            games.drain(..skip);
+        }
+        TrendWindow::LastDays(days) => {
+            let since = now - i64::from(days) * DAY_SECS;
+            games.retain(|s| s.ended_at >= since);
+        }
+    }
+    games
+}
+
+/// Trends of `sessions` (oldest first) within `window`
+pub fn compute_trends(sessions: &[GameSession], window: TrendWindow, champion: Option<&str>, now: i64) -> Trends {
+    let games = select_sessions(sessions, window, champion, now);
+
+    let points = games
+        .iter()
+        .enumerate()
+        .map(|(i, session)| {
+            let rolling = &games[(i + 1).saturating_sub(ROLLING_GAMES)..=i];
+            TrendPoint {
+                game_id: session.game_id,
+                ended_at: session.ended_at,
+                champion_name: session.champion_name.clone(),
+                cs_per_minute: session.cs_per_minute,
+                laning_cs_per_minute: session.laning_cs_per_minute,
+                gold_per_minute: session.gold_per_minute,
+                gold_share: session.gold_share,
+                rolling_cs_per_minute: average(rolling.iter().map(|s| Some(s.cs_per_minute))).unwrap_or_default(),
+                rolling_laning_cs_per_minute: average(rolling.iter().map(|s| s.laning_cs_per_minute)),
+                rolling_gold_per_minute: average(rolling.iter().map(|s| s.gold_per_minute)),
+                rolling_gold_share: average(rolling.iter().map(|s| s.gold_share)),
+                rolling_win_rate: win_rate(rolling),
+            }
+        })
+        .collect();
+
+    Trends {
+        points,
+        avg_cs_per_minute: average(games.iter().map(|s| Some(s.cs_per_minute))).unwrap_or_default(),
+        avg_laning_cs_per_minute: average(games.iter().map(|s| s.laning_cs_per_minute)),
+        avg_gold_per_minute: average(games.iter().map(|s| s.gold_per_minute)),
+        avg_gold_share: average(games.iter().map(|s| s.gold_share)),
+        win_rate: win_rate(&games),
+    }
+}
+
+/// Per-champion averages of `sessions` (oldest first), most played first
+pub fn champion_stats(sessions: &[GameSession], champion: Option<&str>) -> Vec<ChampionStats> {
+    let mut by_champion: BTreeMap<String, Vec<&GameSession>> = BTreeMap::new();
+    for session in sessions {
+        if champion.is_none_or(|c| session.champion_name.eq_ignore_ascii_case(c)) {
+            by_champion.entry(session.champion_name.clone()).or_default().push(session);
+        }
+    }
+
+    let mut stats: Vec<ChampionStats> = by_champion
+        .into_iter()
+        .map(|(champion_name, games)| {
+            let recent = &games[games.len().saturating_sub(ROLLING_GAMES)..];
+            ChampionStats {
+                champion_name,
+                games: games.len(),
+                avg_cs_per_minute: average(games.iter().map(|s| Some(s.cs_per_minute))).unwrap_or_default(),
+                recent_cs_per_minute: average(recent.iter().map(|s| Some(s.cs_per_minute))).unwrap_or_default(),
+                best_cs_per_minute: games.iter().map(|s| s.cs_per_minute).fold(0.0, f64::max),
+                avg_laning_cs_per_minute: average(games.iter().map(|s| s.laning_cs_per_minute)),
+                avg_final_cs: average(games.iter().map(|s| Some(f64::from(s.final_cs)))).unwrap_or_default(),
+                avg_gold_per_minute: average(games.iter().map(|s| s.gold_per_minute)),
+                avg_gold_share: average(games.iter().map(|s| s.gold_share)),
+                win_rate: win_rate(&games),
+                last_played: games.iter().map(|s| s.ended_at).max().unwrap_or_default(),
+            }
+        })
+        .collect();
+    stats.sort_by(|a, b| b.games.cmp(&a.games).then_with(|| a.champion_name.cmp(&b.champion_name)));
+    stats
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the CS/min, gold/min and win rate trends of a window of games.
+///
+/// `range` is `{ "lastGames": 20 }` (default) or `{ "lastDays": 30 }`;
+/// `champion` keeps only the games of that champion.
+#[tauri::command]
+pub async fn get_trends(app: AppHandle, range: Option<TrendWindow>, champion: Option<String>) -> Result<Trends, String> {
+    Ok(compute_trends(
+        &sessions::load_competitive_sessions(&app),
+        range.unwrap_or_default(),
+        champion.as_deref(),
+        sessions::now_unix_secs(),
+    ))
+}
+
+/// Get the CS averages per champion, most played first (only `champion` if given).
+#[tauri::command]
+pub async fn get_champion_stats(app: AppHandle, champion: Option<String>) -> Result<Vec<ChampionStats>, String> {
+    Ok(champion_stats(&sessions::load_competitive_sessions(&app), champion.as_deref()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compute_trends() {
+        let session = |ended_at, champion: &str, cs_per_minute, result| GameSession {
+            ended_at,
+            champion_name: champion.to_string(),
+            cs_per_minute,
+            result,
+            gold_per_minute: (champion == "Ahri").then_some(400.0),
+            gold_share: (champion == "Zed").then_some(0.25),
+            laning_cs_per_minute: Some(cs_per_minute + 1.0),
+            ..GameSession::default()
+        };
+        let sessions: Vec<GameSession> = (0..8)
+            .map(|i| {
+                let result = if i % 2 == 0 { GameResult::Win } else { GameResult::Loss };
+                let champion = if i < 6 { "Ahri" } else { "Zed" };
+                session(i * DAY_SECS, champion, i as f64, Some(result))
+            })
+            .collect();
+        let now = 7 * DAY_SECS;
+
+        let last = compute_trends(&sessions, TrendWindow::LastGames(6), None, now);
+        assert_eq!(last.points.len(), 6);
+        assert_eq!(last.points[0].cs_per_minute, 2.0);
+        assert_eq!(last.points[5].rolling_cs_per_minute, 5.0);
+        assert_eq!(last.points[5].rolling_laning_cs_per_minute, Some(6.0));
+        assert_eq!(last.points[5].rolling_gold_per_minute, Some(400.0));
+        assert_eq!(last.win_rate, Some(0.5));
+
+        let ahri = compute_trends(&sessions, TrendWindow::LastDays(3), Some("ahri"), now);
+        assert_eq!(ahri.points.len(), 2);
+        assert_eq!(ahri.avg_cs_per_minute, 4.5);
+        assert_eq!(ahri.points[0].rolling_win_rate, Some(1.0));
+
+        let champions = champion_stats(&sessions, None);
+        assert_eq!(champions.iter().map(|c| c.champion_name.as_str()).collect::<Vec<_>>(), ["Ahri", "Zed"]);
+        assert_eq!((champions[0].games, champions[0].avg_cs_per_minute), (6, 2.5));
+        assert_eq!((champions[0].recent_cs_per_minute, champions[0].best_cs_per_minute), (3.0, 5.0));
+        assert_eq!(champions[1].avg_gold_per_minute, None);
+        assert_eq!((champions[0].avg_gold_share, champions[1].avg_gold_share), (None, Some(0.25)));
+        assert_eq!(last.points[5].rolling_gold_share, Some(0.25));
+        assert_eq!(champion_stats(&sessions, Some("zed")).len(), 1);
+
+        let parsed: TrendWindow = serde_json::from_str(r#"{ "lastDays": 30 }"#).unwrap();
+        assert_eq!(parsed, TrendWindow::LastDays(30));
+    }
+}
@@ -0,0 +1,271 @@
+//! Application updates.
+//!
+//! A lightweight check runs once at startup and emits `update-available`
+//! when a newer release is out, so the UI can show a banner. It and
+//! `get_latest_version` read the latest GitHub release directly, so they
+//! work even when the updater itself can't run.
+//!
+//! Updates go through `tauri-plugin-updater`: each GitHub release carries a
+//! `latest.json` manifest and installers signed with the FocusApp minisign
+//! key. `install_update` downloads the installer (progress is emitted as
+//! `update-download-progress`); the plugin checks its signature before
+//! running it, so an installer that was not signed with that key is never
+//! run.
+//!
+//! The updater is only enabled once `plugins.updater` in `tauri.conf.json`
+//! holds the public half of that key (with `bundle.createUpdaterArtifacts`,
+//! so releases are signed). Until then the plugin is not registered and
+//! updates are never installable.
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tauri::AppHandle;
+use tauri_plugin_updater::{Update, UpdaterExt};
+
+use crate::events::AppEvent;
+
+/// Latest published release of the app
+const LATEST_RELEASE_URL: &str = "https://api.github.com/repos/mhommet/FocusAPP/releases/latest";
+
+/// Delay before the startup check (lets the app finish loading)
+const STARTUP_CHECK_DELAY: Duration = Duration::from_secs(10);
+
+/// Timeout of the release check (the download itself has none)
+const CHECK_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Minimum interval between two progress events, in bytes
+const PROGRESS_STEP: u64 = 256 * 1024;
+
+/// Latest release, as returned by the GitHub API
+#[derive(Debug, Clone, Deserialize)]
+struct Release {
+    tag_name: String,
+    #[serde(default)]
+    body: Option<String>,
+    #[serde(default)]
+    published_at: Option<String>,
+}
+
+/// Result of `check_for_update`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateInfo {
+    pub available: bool,
+    pub current_version: String,
+    pub latest_version: String,
+    pub notes: Option<String>,
+    pub published_at: Option<String>,
+    /// Whether `install_update` can install it (signed installer published for this platform)
+    pub installable: bool,
+}
+
+/// Result of `get_latest_version` and payload of `update-available`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatestVersion {
+    pub current_version: String,
+    pub latest_version: String,
+    pub update_available: bool,
+}
+
+/// Payload of `update-download-progress`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateProgress {
+    pub downloaded: u64,
+    /// 0 when unknown
+    pub total: u64,
+}
+
+/// Whether `config` has an updater public key (the plugin is only registered then)
+pub fn is_enabled(config: &tauri::Config) -> bool {
+    config
+        .plugins
+        .0
+        .get("updater")
+        .and_then(|updater| updater.get("pubkey"))
+        .and_then(|pubkey| pubkey.as_str())
+        .is_some_and(|pubkey| !pubkey.trim().is_empty())
+}
+
+/// Newer signed release for this platform (None when up to date)
+async fn find_update(app: &AppHandle) -> Result<Option<Update>, String> {
+    if !is_enabled(app.config()) {
+        return Err("Automatic updates are not available in this build".to_string());
+    }
+    let mut builder = app.updater_builder().timeout(CHECK_TIMEOUT);
+    if let Some(proxy) = crate::proxy::proxy_url() {
+        builder = builder.proxy(proxy);
+    }
+    let updater = builder.build().map_err(|e| format!("Update check failed: {}", e))?;
+    updater.check().await.map_err(|e| format!("Update check failed: {}", e))
+}
+
+/// Parse a release tag ("v1.6.7" or "1.6.7")
+fn parse_version(tag: &str) -> Option<semver::Version> {
+    semver::Version::parse(tag.trim().trim_start_matches(['v', 'V'])).ok()
+}
+
+async fn fetch_latest_release() -> Result<Release, String> {
+    let client = crate::http_clients::remote().map_err(|e| format!("Update check failed: {}", e))?;
+    let response = client
+        .get(LATEST_RELEASE_URL)
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", concat!("FocusApp/", env!("CARGO_PKG_VERSION")))
+        .timeout(CHECK_TIMEOUT)
+        .send()
+        .await
+        .map_err(|e| format!("Update check failed: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Update check failed: HTTP {}", response.status()));
+    }
+    response.json().await.map_err(|e| format!("Invalid release data: {}", e))
+}
+
+fn release_version(release: &Release) -> Result<semver::Version, String> {
+    parse_version(&release.tag_name).ok_or_else(|| format!("Invalid release version: {}", release.tag_name))
+}
+
+/// Compare the running version with the latest release
+async fn latest_version(app: &AppHandle) -> Result<LatestVersion, String> {
+    let current = app.package_info().version.clone();
+    let latest = release_version(&fetch_latest_release().await?)?;
+    Ok(LatestVersion {
+        current_version: current.to_string(),
+        latest_version: latest.to_string(),
+        update_available: latest > current,
+    })
+}
+
+/// Tell the frontend once at startup if a newer release is out
+pub async fn check_at_startup(app: AppHandle) {
+    tokio::time::sleep(STARTUP_CHECK_DELAY).await;
+    match latest_version(&app).await {
+        Ok(version) if version.update_available => {
+            let _ = AppEvent::UpdateAvailable(&version).emit(&app);
+        }
+        Ok(_) => {}
+        Err(e) => log_info!("[updater] {}", e),
+    }
+}
+
+/// Emits `update-download-progress` every `PROGRESS_STEP` bytes
+struct ProgressReporter {
+    downloaded: u64,
+    last_emitted: u64,
+}
+
+impl ProgressReporter {
+    /// Count a chunk; returns the progress to emit, if any
+    fn on_chunk(&mut self, chunk_len: usize, total: Option<u64>) -> Option<UpdateProgress> {
+        self.downloaded += chunk_len as u64;
+        let total = total.unwrap_or_default();
+        if self.downloaded - self.last_emitted < PROGRESS_STEP && self.downloaded != total {
+            return None;
+        }
+        self.last_emitted = self.downloaded;
+        Some(UpdateProgress {
+            downloaded: self.downloaded,
+            total,
+        })
+    }
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Compare the running version with the latest published release.
+#[tauri::command]
+pub async fn get_latest_version(app: AppHandle) -> Result<LatestVersion, String> {
+    latest_version(&app).await
+}
+
+/// Check whether a newer FocusApp release is available (with release notes).
+#[tauri::command]
+pub async fn check_for_update(app: AppHandle) -> Result<UpdateInfo, String> {
+    let current = app.package_info().version.clone();
+    let release = fetch_latest_release().await?;
+    let latest = release_version(&release)?;
+    let available = latest > current;
+
+    // Installable only if the updater finds a signed installer for this platform
+    let installable = available && matches!(find_update(&app).await, Ok(Some(_)));
+
+    Ok(UpdateInfo {
+        available,
+        current_version: current.to_string(),
+        latest_version: latest.to_string(),
+        notes: release.body,
+        published_at: release.published_at,
+        installable,
+    })
+}
+
+/// Download, verify and install the latest release, then restart.
+#[tauri::command]
+pub async fn install_update(app: AppHandle) -> Result<(), String> {
+    let Some(update) = find_update(&app).await? else {
+        return Err("FocusApp is already up to date".to_string());
+    };
+
+    let mut progress = ProgressReporter {
+        downloaded: 0,
+        last_emitted: 0,
+    };
+    update
+        .download_and_install(
+            |chunk_len, total| {
+                if let Some(progress) = progress.on_chunk(chunk_len, total) {
+                    let _ = AppEvent::UpdateDownloadProgress(&progress).emit(&app);
+                }
+            },
+            || log_info!("[updater] Update downloaded and verified"),
+        )
+        .await
+        .map_err(|e| format!("Update failed: {}", e))?;
+
+    log_info!("[updater] Update to {} installed, restarting", update.version);
+    app.restart()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(parse_version("v1.6.7"), Some(semver::Version::new(1, 6, 7)));
+        assert!(parse_version("1.10.0").unwrap() > parse_version("v1.9.3").unwrap());
+        assert_eq!(parse_version("latest"), None);
+    }
+
+    #[test]
+    fn test_is_enabled() {
+        let mut config = tauri::Config::default();
+        assert!(!is_enabled(&config));
+
+        config.plugins.0.insert("updater".to_string(), serde_json::json!({ "pubkey": " " }));
+        assert!(!is_enabled(&config));
+
+        config.plugins.0.insert("updater".to_string(), serde_json::json!({ "pubkey": "RWQ1dGVzdA==" }));
+        assert!(is_enabled(&config));
+    }
+
+    #[test]
+    fn test_progress_reporter() {
+        let mut progress = ProgressReporter {
+            downloaded: 0,
+            last_emitted: 0,
+        };
+        let total = Some(3 * PROGRESS_STEP);
+        assert!(progress.on_chunk(1024, total).is_none());
+        assert_eq!(progress.on_chunk(PROGRESS_STEP as usize, total).unwrap().downloaded, PROGRESS_STEP + 1024);
+        assert!(progress.on_chunk(1024, total).is_none());
+        assert!(progress.on_chunk(PROGRESS_STEP as usize, total).is_some());
+
+        // The last chunk is always reported
+        let last = progress.on_chunk((PROGRESS_STEP - 2048) as usize, total).unwrap();
+        assert_eq!((last.downloaded, last.total), (3 * PROGRESS_STEP, 3 * PROGRESS_STEP));
+    }
+}
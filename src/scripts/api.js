@@ -18,77 +18,79 @@ const { fetch } = window.__TAURI__.http;
 // CONFIGURATION
 // =============================================================================
 
-const API_BASE_URL = "https://api.hommet.ch/api/v1";
 const DDRAGON_BASE_URL = "https://ddragon.leagueoflegends.com";
-const DEFAULT_TIMEOUT = 30; // seconds
 const RETRY_COUNT = 3;
-let API_KEY = "";
 
-async function initApi() {
-  if (window.__TAURI__) {
-    try {
-      // Appelle la fonction Rust qu'on vient de créer
-      API_KEY = await window.__TAURI__.core.invoke("get_env_api_key");
-      console.log("🔑 API Key loaded successfully");
-    } catch (e) {
-      console.error("Failed to load API Key:", e);
-      API_KEY = "dev_key_fallback";
-    }
-  }
-}
-
-initApi();
+// Codes of the command errors that retrying right away can't fix: cancelled
+// with cancel_operation, or FocusApi quota used up (see error.rs)
+const NO_RETRY_CODES = ["CANCELLED", "RATE_LIMITED"];
 
 // Cache for DDragon version
 let cachedDDragonVersion = "14.10.1";
 
+// The backend emits this when a new patch's static data has been loaded
+if (window.__TAURI__?.event) {
+  window.__TAURI__.event.listen("static-data-updated", (event) => {
+    cachedDDragonVersion = event.payload.version;
+    console.log("[DDragon] Static data updated to", cachedDDragonVersion);
+    window.dispatchEvent(new CustomEvent("static-data-updated", { detail: event.payload }));
+  });
+}
+
 // =============================================================================
 // GENERIC API WRAPPER
 // =============================================================================
 
+/**
+ * Turn a command error (`{ code, message, detail, retryAfterSecs }`) into an Error.
+ *
+ * @param {*} rawError - Value the invoke was rejected with
+ * @returns {Error} Error with the backend's `code`, `detail` and `retryAfterSecs`
+ */
+function toError(rawError) {
+  if (rawError instanceof Error) {
+    return rawError;
+  }
+  if (rawError && typeof rawError === "object" && rawError.code) {
+    return Object.assign(new Error(rawError.message), {
+      code: rawError.code,
+      detail: rawError.detail,
+      retryAfterSecs: rawError.retryAfterSecs,
+    });
+  }
+  return new Error(String(rawError));
+}
+
 /**
  * Generic API call wrapper with retry logic and error handling.
+ * Requests are made by the backend, which holds the API key (see focus_api.rs).
  *
- * @param {string} endpoint - API endpoint (without base URL)
- * @param {Object} options - Fetch options
+ * @param {string} command - FocusApi Tauri command (e.g., "fetch_tierlist")
+ * @param {Object} args - Command arguments
  * @param {number} retries - Number of retry attempts
  * @returns {Promise<Object>} Parsed JSON response
- * @throws {Error} On API error after all retries
+ * @throws {Error} On API error after all retries (with the command error's `code`)
  */
-async function apiCall(endpoint, options = {}, retries = RETRY_COUNT) {
-  const url = `${API_BASE_URL}${endpoint}`;
-
+async function apiCall(command, args = {}, retries = RETRY_COUNT) {
   for (let attempt = 0; attempt < retries; attempt++) {
     try {
-      const response = await fetch(url, {
-        method: options.method || "GET",
-        timeout: { secs: options.timeout || DEFAULT_TIMEOUT, nanos: 0 },
-        headers: {
-          "Content-Type": "application/json",
-          Accept: "application/json",
-          "X-API-Key": API_KEY,
-          ...options.headers,
-        },
-      });
+      return await window.__TAURI__.core.invoke(command, args);
+    } catch (rawError) {
+      const error = toError(rawError);
 
-      if (!response.ok) {
-        const errorText = await response.text();
-        if (response.status === 401) {
-          throw new Error("API Key invalid - check configuration");
-        }
-        throw new Error(`API Error ${response.status}: ${errorText}`);
+      // Cancelled with cancelOperation() or superseded by a newer request, or
+      // quota used up (retrying now would fail the same way): no retry
+      if (NO_RETRY_CODES.includes(error.code)) {
+        throw error;
       }
-
-      return await response.json();
-    } catch (error) {
       console.warn(
-        `[API] Attempt ${attempt + 1}/${retries} failed for ${endpoint}:`,
-        error.message,
+        `[API] Attempt ${attempt + 1}/${retries} failed for ${command}:`,
+        error.detail || error.message,
       );
 
       if (attempt === retries - 1) {
         console.error(
-          `[API] Failed ${endpoint} after ${retries} attempts:`,
+          `[API] Failed ${command} after ${retries} attempts:`,
           error,
         );
         throw error;
@@ -155,6 +157,21 @@ export async function getDDragonVersion() {
  * @returns {Promise<Array>} List of champion objects
  */
 export async function getChampionList() {
+  // The backend resolves champions from DDragon with offline fallbacks
+  try {
+    const champions = await window.__TAURI__.core.invoke("list_champions");
+    if (champions.length > 0) {
+      return champions.map((champ) => ({
+        id: champ.ddragonKey,
+        key: String(champ.championId),
+        name: champ.name,
+        image: getAssetUrl("champion", `${champ.ddragonKey}.png`, champ.iconUrl),
+      }));
+    }
+  } catch (error) {
+    console.warn("[Champions] Backend champion list unavailable:", error);
+  }
+
   try {
     const version = await getDDragonVersion();
     const data = await ddragonCall(`/cdn/${version}/data/en_US/champion.json`);
@@ -163,7 +180,11 @@ export async function getChampionList() {
       id: champ.id,
       key: champ.key,
       name: champ.name,
-      image: `${DDRAGON_BASE_URL}/cdn/${version}/img/champion/${champ.image.full}`,
+      image: getAssetUrl(
+        "champion",
+        champ.image.full,
+        `${DDRAGON_BASE_URL}/cdn/${version}/img/champion/${champ.image.full}`
+      ),
     }));
 
     // Sort alphabetically
@@ -188,10 +209,8 @@ export async function getChampionList() {
  * @returns {Promise<Object>} Tier list data
  */
 export async function getTierlist(role = null) {
-  const params = role ? `?role=${role.toLowerCase()}` : "";
-
   try {
-    const data = await apiCall(`/tierlist${params}`);
+    const data = await apiCall("fetch_tierlist", { role: role ? role.toLowerCase() : null });
     return formatTierlistResponse(data, role);
   } catch (error) {
     console.error("[API] Tierlist error:", error);
@@ -309,7 +328,11 @@ function formatTierlistResponse(data, filteredRole = null) {
         roles: roles,
         roles_str: displayRole,
         performance_score: champ.performance_score || 0,
-        image: `${DDRAGON_BASE_URL}/cdn/${cachedDDragonVersion}/img/champion/${championName}.png`,
+        image: getAssetUrl(
+          "champion",
+          `${championName}.png`,
+          `${DDRAGON_BASE_URL}/cdn/${cachedDDragonVersion}/img/champion/${championName}.png`
+        ),
       };
 
       formattedTiers[tier].push(formattedEntry);
@@ -371,12 +394,12 @@ export async function getChampionBuild(
     roleNormalized = "bottom";
   }
 
-  const params = forceRefresh ? "?force_refresh=true" : "";
-
   try {
-    const data = await apiCall(
-      `/build/${champNormalized}/${roleNormalized}${params}`,
-    );
+    const data = await apiCall("fetch_champion_build", {
+      champion: champNormalized,
+      role: roleNormalized,
+      forceRefresh,
+    });
     return formatBuildResponse(data, championName, role);
   } catch (error) {
     console.error("[API] Build error:", error);
@@ -384,6 +407,28 @@ export async function getChampionBuild(
   }
 }
 
+/**
+ * Turn a FocusApi build into the request sent to import_build_to_client.
+ * The backend picks the runes, shards, items and spells, so a single import
+ * and import_all_roles import the same build.
+ *
+ * @param {Object} apiData - Raw fetch_champion_build response (build.apiData)
+ * @param {string} champion - Champion name or key
+ * @param {string} role - Role of the build
+ * @param {string|null} patch - Patch of the build (null for the current one)
+ * @param {number[]} summonerSpells - Spell IDs in the order shown (empty to keep the build's)
+ * @returns {Promise<Object>} The import request
+ */
+export async function buildImportRequest(apiData, champion, role, patch = null, summonerSpells = []) {
+  return window.__TAURI__.core.invoke("build_import_request", {
+    build: apiData || {},
+    champion,
+    role,
+    patch,
+    summonerSpells,
+  });
+}
+
 /**
  * Format build response for frontend display.
  * Transforms API response format to what the frontend renderBuild() expects.
@@ -589,6 +634,8 @@ function formatBuildResponse(data, champion, role) {
     games: games,
     cached: cached,
     cache_age_hours: cacheAgeHours,
+    // Raw API response, turned into the import request by the backend
+    apiData: data,
   };
 }
 
@@ -638,6 +685,55 @@ function makeErrorResponse(errorMsg, champion, role) {
   };
 }
 
+/**
+ * Compare a build with the same build of the previous patch.
+ *
+ * @param {Object} payload - Same request as an import (patch "current" = the live patch)
+ * @returns {Promise<Object>} { championKey, role, fromPatch, toPatch, unchanged, changes:
+ *   { primaryStyle, subStyle, runesAdded, runesRemoved, itemsAdded, itemsRemoved, spells } }
+ */
+export async function getBuildDiff(payload) {
+  return window.__TAURI__.core.invoke("get_build_diff", { payload });
+}
+
+/**
+ * Rate an imported build (1 to 5) on FocusApi.
+ *
+ * @param {string} buildId - FocusApi build ID
+ * @param {number} rating - 1 to 5
+ * @param {boolean|null} won - Game result (null = unknown)
+ * @param {number|null} gameId - Game to take the result from when won is null
+ */
+export async function submitBuildFeedback(buildId, rating, won = null, gameId = null) {
+  return window.__TAURI__.core.invoke("submit_build_feedback", { buildId, rating, won, gameId });
+}
+
+// =============================================================================
+// MATCHUPS API
+// =============================================================================
+
+/**
+ * Get the champions a champion is strong and weak into for the current patch (FocusApi, cached).
+ *
+ * @param {string} champion - Champion name
+ * @param {string} role - e.g. "mid"
+ * @returns {Promise<Object>} { champion, role, strongAgainst, weakAgainst } with { champion, winRate, games }
+ */
+export async function getMatchups(champion, role) {
+  return window.__TAURI__.core.invoke("get_matchups", { champion, role });
+}
+
+/**
+ * Get the recommended bans of a role for the current patch (FocusApi).
+ * During the player's ban turn the backend also sends them with `ban-suggestions`.
+ *
+ * @param {string|null} role - e.g. "mid" (null = every role)
+ * @returns {Promise<Object>} { role, suggestions: [{ championId, champion, banRate, winRate }] }
+ */
+export async function getBanSuggestions(role = null) {
+  return window.__TAURI__.core.invoke("get_ban_suggestions", { role });
+}
+
 // =============================================================================
 // ITEMS API
 // =============================================================================
@@ -649,9 +745,7 @@ function makeErrorResponse(errorMsg, champion, role) {
  * @returns {Promise<Array>} List of item objects
  */
 export async function getItemsData(refresh = false) {
-  const params = refresh ? "?refresh=true" : "";
-
-  const data = await apiCall(`/items${params}`);
+  const data = await apiCall("fetch_items", { refresh });
 
   // API returns { items: [...], version: "15.x.x", total: N }
   if (!data || !Array.isArray(data.items)) {
@@ -668,10 +762,559 @@ export async function getItemsData(refresh = false) {
   return { items: data.items, version: data.version || cachedDDragonVersion };
 }
 
+// =============================================================================
+// API KEY
+// =============================================================================
+
+/**
+ * Store the FocusApi key in the OS credential store (empty string = remove it).
+ * The key is kept by the backend and never read back.
+ *
+ * @param {string} key - API key
+ * @returns {Promise<string>} Source of the key now in use ("keyring", "embedded" or "none")
+ */
+export async function setApiKey(key) {
+  return window.__TAURI__.core.invoke("set_api_key", { key });
+}
+
+/**
+ * Test an API key against FocusApi without storing it.
+ *
+ * @param {string|null} key - Key to test (null = the key in use)
+ * @returns {Promise<Object>} { valid, status, message }
+ */
+export async function validateApiKey(key = null) {
+  return window.__TAURI__.core.invoke("validate_api_key", { key });
+}
+
+/**
+ * Remove the stored FocusApi key.
+ *
+ * @returns {Promise<string>} Source of the key now in use
+ */
+export async function clearApiKey() {
+  return window.__TAURI__.core.invoke("clear_api_key");
+}
+
+/**
+ * Select the FocusApi server.
+ *
+ * @param {string|null} url - Custom API URL (self-hosted server), null for api.hommet.ch
+ * @returns {Promise<string>} API URL now in use
+ */
+export async function setApiEndpoint(url = null) {
+  return window.__TAURI__.core.invoke("set_api_endpoint", { url });
+}
+
+// =============================================================================
+// FOCUSAPI ACCOUNT
+// =============================================================================
+
+/**
+ * Log in to a FocusApi account (personalized builds, higher rate limits).
+ * The tokens are kept by the backend; the password is not stored.
+ *
+ * @param {string} username - Account username or email
+ * @param {string} password - Account password
+ * @returns {Promise<Object>} { loggedIn, username }
+ */
+export async function login(username, password) {
+  return window.__TAURI__.core.invoke("login", { username, password });
+}
+
+/**
+ * Log out of the FocusApi account.
+ *
+ * @returns {Promise<Object>} { loggedIn, username }
+ */
+export async function logout() {
+  return window.__TAURI__.core.invoke("logout");
+}
+
+/**
+ * Get whether a FocusApi account is logged in.
+ *
+ * @returns {Promise<Object>} { loggedIn, username }
+ */
+export async function getAccountStatus() {
+  return window.__TAURI__.core.invoke("get_account_status");
+}
+
+/**
+ * Sync build templates and settings with the logged-in FocusApi account.
+ * When a setting changed differently on both sides, nothing is written and
+ * the conflicts are returned: ask the user, then call again with the choices.
+ *
+ * @param {Object|null} choices - Side kept per conflicting setting key ("local" or "remote")
+ * @returns {Promise<Object>} { status, conflicts: [{ key, local, remote }] }
+ */
+export async function syncNow(choices = null) {
+  return window.__TAURI__.core.invoke("sync_now", { choices });
+}
+
+// =============================================================================
+// BUILD TEMPLATES
+// =============================================================================
+
+/**
+ * List the user's build templates.
+ *
+ * @param {string|null} championKey - Only templates for this champion
+ * @param {string|null} role - Only templates for this role
+ * @returns {Promise<Array>} Templates { id, createdAt, updatedAt, build }
+ */
+export async function listBuildTemplates(championKey = null, role = null) {
+  return window.__TAURI__.core.invoke("list_build_templates", { championKey, role });
+}
+
+/**
+ * Create or update a build template.
+ *
+ * @param {Object} build - Build in the import request format
+ * @param {string|null} id - Template to update (null = new template)
+ * @returns {Promise<Object>} Saved template
+ */
+export async function saveBuildTemplate(build, id = null) {
+  return window.__TAURI__.core.invoke("save_build_template", { id, build });
+}
+
+/**
+ * Delete a build template.
+ *
+ * @param {string} id - Template ID
+ * @returns {Promise<boolean>} False if the template did not exist
+ */
+export async function deleteBuildTemplate(id) {
+  return window.__TAURI__.core.invoke("delete_build_template", { id });
+}
+
+/**
+ * Import a build template into the League Client.
+ *
+ * @param {string} id - Template ID
+ * @returns {Promise<Object>} Import result (same as FocusApi builds)
+ */
+export async function importBuildTemplate(id) {
+  return window.__TAURI__.core.invoke("import_build_template", { id });
+}
+
+// =============================================================================
+// CHAMPION SELECT
+// =============================================================================
+
+/**
+ * Get the time left in the current champion select phase.
+ *
+ * @returns {Promise<Object>} { phase, remainingMs, totalMs, isInfinite, lowTime }
+ *   lowTime: too little time left to review the build before the pick locks
+ */
+export async function getChampSelectTimer() {
+  return window.__TAURI__.core.invoke("get_champ_select_timer");
+}
+
+/**
+ * Get the champions on the ARAM bench, to compare their builds before a swap.
+ *
+ * @returns {Promise<Object>} { enabled, champions: [{ championId, championKey, isPriority }], rerollsRemaining }
+ */
+export async function getAramBench() {
+  return window.__TAURI__.core.invoke("get_aram_bench");
+}
+
+// =============================================================================
+// IMPORT
+// =============================================================================
+
+/**
+ * Cancel the build import in progress before it writes to the client.
+ * Starting a new import cancels the previous one automatically.
+ *
+ * @returns {Promise<boolean>} False when no import was running
+ */
+export async function cancelImport() {
+  return window.__TAURI__.core.invoke("cancel_import");
+}
+
+/**
+ * Create a rune page from the rune editor, kept next to the user's pages
+ * (no FocusApp prefix, no page replaced).
+ *
+ * @param {Object} payload - { name, primaryStyleId, subStyleId, selectedPerkIds: [9 perk IDs], current }
+ * @returns {Promise<boolean>} Rejects with code RUNE_PAGE_INVALID and the offending field
+ */
+export async function createCustomRunePage(payload) {
+  return window.__TAURI__.core.invoke("create_custom_rune_page", { payload });
+}
+
+/**
+ * Cancel a long-running command started with an `operationId` (FocusApi
+ * fetches, build prefetch, waitForLeagueClient). Starting a command with
+ * the id of one still running cancels the older one.
+ *
+ * @param {string} id - Operation ID given to the command
+ * @returns {Promise<boolean>} False when no operation with this ID is running
+ */
+export async function cancelOperation(id) {
+  return window.__TAURI__.core.invoke("cancel_operation", { id });
+}
+
+/**
+ * Wait until the League Client is running.
+ *
+ * @param {number} timeoutSecs - Maximum wait
+ * @param {string|null} operationId - ID to cancel the wait with cancelOperation()
+ * @returns {Promise<boolean>} False on timeout
+ */
+export async function waitForLeagueClient(timeoutSecs, operationId = null) {
+  return window.__TAURI__.core.invoke("wait_for_league_client", { timeoutSecs, operationId });
+}
+
+// =============================================================================
+// BUILD CODES
+// =============================================================================
+
+/**
+ * Encode a build as a short code that can be shared in chat.
+ *
+ * @param {Object} build - Build in the import request format
+ * @returns {Promise<string>} Build code
+ */
+export async function exportBuildCode(build) {
+  return window.__TAURI__.core.invoke("export_build_code", { build });
+}
+
+/**
+ * Decode a shared build code.
+ *
+ * @param {string} code - Build code
+ * @returns {Promise<Object>} Build in the import request format
+ */
+export async function importBuildCode(code) {
+  return window.__TAURI__.core.invoke("import_build_code", { code });
+}
+
+// =============================================================================
+// IMPORT HISTORY
+// =============================================================================
+
+/**
+ * Get the builds imported into the client, most recent first.
+ *
+ * @param {number|null} limit - Maximum number of records
+ * @returns {Promise<Array>} Records { timestamp, championKey, role, patch, source, outcome, message }
+ */
+export async function getImportHistory(limit = null) {
+  return window.__TAURI__.core.invoke("get_import_history", { limit });
+}
+
+/**
+ * Get import statistics.
+ *
+ * @returns {Promise<Object>} { total, successes, failures, queued, failureRate, mostImported }
+ */
+export async function getImportStats() {
+  return window.__TAURI__.core.invoke("get_import_stats");
+}
+
+// =============================================================================
+// FAVORITES AND NOTES
+// =============================================================================
+
+/**
+ * Get the favorite champions, in the order they were added.
+ *
+ * @returns {Promise<string[]>} Champion keys
+ */
+export async function getFavoriteChampions() {
+  return window.__TAURI__.core.invoke("get_favorite_champions");
+}
+
+/**
+ * Add a champion to the favorites, or remove it.
+ *
+ * @param {string} championKey - Champion key (e.g., "Ahri")
+ * @param {boolean} favorite - false to remove it
+ * @returns {Promise<string[]>} The updated favorites
+ */
+export async function setFavoriteChampion(championKey, favorite) {
+  return window.__TAURI__.core.invoke("set_favorite_champion", { championKey, favorite });
+}
+
+/**
+ * Get the notes written about a champion.
+ *
+ * @param {string} championKey - Champion key (e.g., "Ahri")
+ * @returns {Promise<Object[]>} { championKey, role, text, updatedAt }, general note first
+ */
+export async function getChampionNotes(championKey) {
+  return window.__TAURI__.core.invoke("get_champion_notes", { championKey });
+}
+
+/**
+ * Save the note of a champion. An empty text deletes it.
+ *
+ * @param {string} championKey - Champion key (e.g., "Ahri")
+ * @param {string|null} role - Role of the note, null for every role
+ * @param {string} text - Note text
+ * @returns {Promise<Object|null>} The saved note, null when deleted
+ */
+export async function saveChampionNote(championKey, role, text) {
+  return window.__TAURI__.core.invoke("save_champion_note", { championKey, role, text });
+}
+
+// =============================================================================
+// GAME SESSIONS
+// =============================================================================
+
+/**
+ * Get the games played, average CS/min and win rate per day and per week,
+ * most recent first. Days are split at local midnight.
+ *
+ * @returns {Promise<Object>} { days, weeks } of { start, games, wins, losses, avgCsPerMinute, winRate }
+ */
+export async function getSessionSummary() {
+  return window.__TAURI__.core.invoke("get_session_summary", {
+    utcOffsetMinutes: -new Date().getTimezoneOffset(),
+  });
+}
+
+/**
+ * Get the streak and today's progress of each daily goal (`dailyGoals` setting).
+ * Listen to `goal-achieved` to celebrate a goal met during the session.
+ *
+ * @returns {Promise<Array>} { goal, currentStreak, bestStreak, metToday, todayGames, todayValue }
+ */
+export async function getStreaks() {
+  return window.__TAURI__.core.invoke("get_streaks", {
+    utcOffsetMinutes: -new Date().getTimezoneOffset(),
+  });
+}
+
+/**
+ * Get the personal bests (CS@10, CS@20, CS/min, gold/min) per champion and per role.
+ *
+ * @returns {Promise<Object>} { champions, roles } mapping names to { csAt10, csAt20, csPerMinute, goldPerMinute, games }
+ */
+export async function getPersonalBests() {
+  return window.__TAURI__.core.invoke("get_personal_bests");
+}
+
+/**
+ * Get the post-game CS report of a game.
+ *
+ * @param {number} gameId - LCU game ID of the session
+ * @returns {Promise<Object|null>} { gameId, championName, durationSecs, finalCs, curve, farmingGaps, deathDroughts }
+ */
+export async function getGameReport(gameId) {
+  return window.__TAURI__.core.invoke("get_game_report", { gameId });
+}
+
+/**
+ * Get the recorded timeline of a finished game, for the playback view.
+ *
+ * @param {number} gameId - LCU game ID
+ * @returns {Promise<Object|null>} { gameId, durationSecs, samples: [{ gameTime, cs, gold }], markers: [{ gameTime, kind, name, detail }] }
+ */
+export async function getGameTimeline(gameId) {
+  return window.__TAURI__.core.invoke("get_game_timeline", { gameId });
+}
+
+/**
+ * Get the CS/min, gold/min and win rate trends of recent games, oldest first.
+ *
+ * @param {Object|null} range - { lastGames: 20 } (default) or { lastDays: 30 }
+ * @param {string|null} champion - Keep only the games of this champion
+ * @returns {Promise<Object>} { points, avgCsPerMinute, avgLaningCsPerMinute, avgGoldPerMinute, avgGoldShare, winRate }
+ */
+export async function getTrends(range = null, champion = null) {
+  return window.__TAURI__.core.invoke("get_trends", { range, champion });
+}
+
+/**
+ * Get the CS averages per champion, most played first.
+ *
+ * @param {string|null} champion - Only this champion
+ * @returns {Promise<Array>} { championName, games, avgCsPerMinute, recentCsPerMinute, bestCsPerMinute, avgLaningCsPerMinute, avgFinalCs, avgGoldPerMinute, avgGoldShare, winRate, lastPlayed }
+ */
+export async function getChampionStats(champion = null) {
+  return window.__TAURI__.core.invoke("get_champion_stats", { champion });
+}
+
+/**
+ * Write the recorded games to a CSV file and return its path.
+ *
+ * @param {Object|null} range - { lastGames: 20 } or { lastDays: 30 } (all games by default)
+ * @param {string|null} path - Destination (Downloads folder by default)
+ * @returns {Promise<string>} Path of the CSV file
+ */
+export async function exportStatsCsv(range = null, path = null) {
+  return window.__TAURI__.core.invoke("export_stats_csv", { range, path });
+}
+
+/**
+ * Get the average CS per minute of a rank and role for the current patch (FocusApi, cached).
+ *
+ * @param {string} rank - e.g. "diamond"
+ * @param {string} role - e.g. "mid"
+ * @returns {Promise<Array>} Points { minute, cs }
+ */
+export async function getCsBenchmark(rank, role) {
+  return window.__TAURI__.core.invoke("get_cs_benchmark", { rank, role });
+}
+
+// =============================================================================
+// APP UPDATES
+// =============================================================================
+
+/**
+ * Check whether a newer FocusApp release is available.
+ *
+ * @returns {Promise<Object>} { available, currentVersion, latestVersion, notes, publishedAt, installable }
+ */
+export async function checkForUpdate() {
+  return window.__TAURI__.core.invoke("check_for_update");
+}
+
+/**
+ * Download and run the installer of the latest release (the app quits).
+ *
+ * @param {Function|null} onProgress - Called with { downloaded, total } during the download
+ * @returns {Promise<void>}
+ */
+export async function installUpdate(onProgress = null) {
+  const unlisten = onProgress
+    ? await window.__TAURI__.event.listen("update-download-progress", (event) => onProgress(event.payload))
+    : null;
+  try {
+    await window.__TAURI__.core.invoke("install_update");
+  } finally {
+    if (unlisten) unlisten();
+  }
+}
+
+// =============================================================================
+// LOGS
+// =============================================================================
+
+/**
+ * Change the backend log level (saved in the settings).
+ *
+ * @param {string} level - "error", "warn", "info" or "debug"
+ * @returns {Promise<string>} Level now in use
+ */
+export async function setLogLevel(level) {
+  return window.__TAURI__.core.invoke("set_log_level", { level });
+}
+
+/**
+ * Get the path of the log file to attach to a bug report.
+ *
+ * @returns {Promise<string>} Absolute path of the log file
+ */
+export async function getLogFilePath() {
+  return window.__TAURI__.core.invoke("get_log_file_path");
+}
+
+/**
+ * List the crash logs written by previous sessions, newest first.
+ *
+ * @returns {Promise<Array<{fileName: string, crashedAt: number, message: string, path: string}>>}
+ */
+export async function getRecentCrashes() {
+  return window.__TAURI__.core.invoke("get_recent_crashes");
+}
+
+// =============================================================================
+// PROXY
+// =============================================================================
+
+/**
+ * Send remote requests (FocusApi, DDragon, updates) through a proxy.
+ *
+ * @param {string|null} url - "http://", "https://", "socks5://" or "socks5h://" URL;
+ *   empty to use the system proxy settings
+ * @returns {Promise<string|null>} Proxy now in use
+ */
+export async function setProxy(url) {
+  return window.__TAURI__.core.invoke("set_proxy", { url });
+}
+
+// =============================================================================
+// USAGE METRICS
+// =============================================================================
+
+/**
+ * Get the anonymous usage counters waiting to be sent (opt-in with the
+ * `telemetry` setting).
+ *
+ * @returns {Promise<Object>} { since, counters: { "import.success": 3, ... } }
+ */
+export async function getTelemetryBuffer() {
+  return window.__TAURI__.core.invoke("get_telemetry_buffer");
+}
+
+// =============================================================================
+// LOCAL DATA
+// =============================================================================
+
+/**
+ * Export everything FocusApp stores locally to a zip archive.
+ *
+ * The destination is chosen in a save dialog.
+ *
+ * @returns {Promise<string|null>} Path of the written archive (null if the dialog was cancelled)
+ */
+export async function exportAllData() {
+  return window.__TAURI__.core.invoke("export_all_data");
+}
+
+/**
+ * Delete every local file, the stored API key and the account session, then restart the app.
+ *
+ * @returns {Promise<void>}
+ */
+export async function deleteAllData() {
+  return window.__TAURI__.core.invoke("delete_all_data");
+}
+
+// =============================================================================
+// EVENTS
+// =============================================================================
+
+/**
+ * Get the version and names of the events the backend emits (see events.rs).
+ *
+ * @returns {Promise<{version: number, events: string[]}>}
+ */
+export async function getEventCatalog() {
+  return window.__TAURI__.core.invoke("get_event_catalog");
+}
+
 // =============================================================================
 // HEALTH CHECK
 // =============================================================================
 
+/**
+ * Get the latency and failure counters of each League Client endpoint since launch.
+ *
+ * @returns {Promise<{since: number, endpoints: Array<{endpoint: string, requests: number,
+ *   failures: number, httpErrors: number, avgMs: number, maxMs: number, lastMs: number}>}>}
+ */
+export async function getLcuMetrics() {
+  return window.__TAURI__.core.invoke("get_lcu_metrics");
+}
+
+/**
+ * Get the state of the build service (checked at most once a minute), to warn
+ * before an import fails.
+ *
+ * @param {boolean} refresh - Check again even if the last check is recent
+ * @returns {Promise<Object>} { state: "operational"|"degraded"|"down", message, latencyMs, checkedAt }
+ */
+export async function getApiStatus(refresh = false) {
+  return window.__TAURI__.core.invoke("get_api_status", { refresh });
+}
+
 /**
  * Check if the backend API is running.
  *
@@ -690,6 +1333,17 @@ export async function checkHealth() {
   }
 }
 
+/**
+ * Check the League Client, the Live Client API, FocusApi and the static
+ * data cache in parallel.
+ *
+ * @returns {Promise<Object>} { checkedAt, healthy, lcu, liveClient, focusApi, staticData },
+ *   each integration being { status: "ok" | "unavailable" | "error", latencyMs, message }
+ */
+export async function runHealthCheck() {
+  return window.__TAURI__.core.invoke("run_health_check");
+}
+
 /**
  * Verify backend connection with retries.
  *
@@ -985,11 +1639,24 @@ function getRuneName(runeId) {
   return RUNE_NAMES[parseInt(runeId)] || `Rune ${runeId}`;
 }
 
+/**
+ * Get the URL of an icon served from the local cache (focus-asset://).
+ *
+ * The backend downloads the icon from DDragon on first use and serves it
+ * from disk afterwards. Falls back to `fallbackUrl` outside of Tauri.
+ */
+export function getAssetUrl(kind, file, fallbackUrl) {
+  if (window.__TAURI__?.core?.convertFileSrc) {
+    return window.__TAURI__.core.convertFileSrc(`${kind}/${file}`, "focus-asset");
+  }
+  return fallbackUrl;
+}
+
 /**
  * Get item image URL.
  */
 function getItemImageUrl(itemId, version) {
-  return `${DDRAGON_BASE_URL}/cdn/${version}/img/item/${itemId}.png`;
+  return getAssetUrl("item", `${itemId}.png`, `${DDRAGON_BASE_URL}/cdn/${version}/img/item/${itemId}.png`);
 }
 
 /**
@@ -14,6 +14,7 @@ import {
     getDDragonVersion,
     getTierlist,
     getChampionBuild,
+    buildImportRequest,
     getItemsData,
     getChampionList,
     verifyBackendConnection,
@@ -90,6 +91,13 @@ let backendConnected = false;
 /** @type {number} Build request ID counter - used to ignore stale responses */
 let buildRequestId = 0;
 
+// =============================================================================
+// SETTINGS STATE
+// =============================================================================
+
+/** @type {Object} Settings persisted by the backend (see settings.rs) */
+let appSettings = { autoImport: false, flashKey: null };
+
 // =============================================================================
 // AUTO-IMPORT STATE
 // =============================================================================
@@ -103,6 +111,9 @@ let autoImportPollInterval = null;
 /** @type {string|null} Last detected champion ID to prevent duplicate imports */
 let lastAutoImportedChampion = null;
 
+/** @type {number|null} Champion last offered a build after a swap (one prompt per champion) */
+let lastOfferedChampion = null;
+
 /** @type {boolean} Whether we're currently in champion select */
 let inChampionSelect = false;
 
@@ -267,6 +278,8 @@ async function init() {
     const version = getAppVersion();
     document.getElementById('version').innerText = version;
 
+    await loadSettings();
+
     // Verify backend connection
     backendConnected = await verifyBackendConnection();
 
@@ -280,9 +293,75 @@ async function init() {
     // Load champions for global search (in background)
     loadGlobalSearchChampions();
 
+    initBuildPrefetch();
+    initAssignedPosition();
+    initChampionChanges();
+    initReimportPrompt();
+    initDeepLinks();
+    initUpdateBanner();
+
     refreshTierList();
 }
 
+/**
+ * Load the settings from the backend and keep them in sync.
+ * Preferences saved in localStorage by older versions are moved to the settings file.
+ * @returns {Promise<void>}
+ */
+async function loadSettings() {
+    try {
+        appSettings = await window.__TAURI__.core.invoke('get_settings');
+
+        const legacy = {};
+        const legacyAutoImport = localStorage.getItem('focusapp_autoimport');
+        if (legacyAutoImport !== null) {
+            legacy.autoImport = legacyAutoImport === 'true';
+        }
+        const legacyFlashSlot = localStorage.getItem('focusapp_flash_slot');
+        if (legacyFlashSlot !== null) {
+            legacy.flashKey = legacyFlashSlot === '0' ? 'D' : 'F';
+        }
+        if (Object.keys(legacy).length > 0) {
+            appSettings = await updateSettings(legacy);
+            localStorage.removeItem('focusapp_autoimport');
+            localStorage.removeItem('focusapp_flash_slot');
+        }
+
+        await window.__TAURI__.event.listen('settings-changed', (event) => {
+            appSettings = event.payload;
+            // Auto-import can also be toggled from the tray menu
+            if ((appSettings.autoImport === true) !== autoImportEnabled) {
+                toggleAutoImport(appSettings.autoImport === true);
+            }
+        });
+
+        // Imports queued while offline are applied once the client is reachable
+        let pendingImportCount = (await window.__TAURI__.core.invoke('get_pending_imports')).imports.length;
+        let pendingImportsProcessing = false;
+        await window.__TAURI__.event.listen('pending-imports-changed', (event) => {
+            const { imports, processing } = event.payload;
+            if (pendingImportsProcessing && !processing && imports.length < pendingImportCount) {
+                showToast('Queued builds imported', 'success');
+            }
+            pendingImportsProcessing = processing;
+            if (processing) return;
+            pendingImportCount = imports.length;
+        });
+    } catch (error) {
+        console.warn('[Settings] Failed to load settings:', error);
+    }
+}
+
+/**
+ * Save some settings (only the given fields change).
+ * @param {Object} changes - Settings fields to update
+ * @returns {Promise<Object>} Full updated settings
+ */
+async function updateSettings(changes) {
+    appSettings = await window.__TAURI__.core.invoke('update_settings', { settings: changes });
+    return appSettings;
+}
+
 /**
  * Show backend error banner.
  */
@@ -1512,7 +1591,7 @@ async function importBuildToClient() {
         console.log('[Import] Tauri API available, building payload...');
 
         // Build the full payload for FocusApi /lol/import-payload
-        const payload = buildImportPayload(currentBuild);
+        const payload = await buildImportPayload(currentBuild);
 
         console.log('[Import] Payload built:', JSON.stringify(payload, null, 2));
 
@@ -1544,6 +1623,8 @@ async function importBuildToClient() {
                 ? `${parts.join(', ')} imported!`
                 : 'Build imported successfully!';
             showToast(message, 'success');
+        } else if (result.queued) {
+            showToast(result.message, 'info');
         } else {
             showToast(result.message || 'Import failed', 'error');
         }
@@ -1556,22 +1637,21 @@ async function importBuildToClient() {
         console.error('[Import] Error stringified:', JSON.stringify(error, null, 2));
         console.error('[Import] Error keys:', error ? Object.keys(error) : 'null');
 
+        // Superseded by a newer import (champion re-picked) or cancelled by the user
+        if (error?.code === 'CANCELLED') {
+            console.log('[Import] Import cancelled');
+            return;
+        }
+
         // Handle specific error codes from the Tauri command
         let errorMessage = 'Failed to import build';
 
-        // Tauri errors come as objects with code and message
+        // Tauri errors come as objects with code, a localized message and the technical detail
         if (typeof error === 'object' && error !== null) {
-            if (error.code === 'CLIENT_NOT_RUNNING') {
-                errorMessage = 'League Client not running. Start the client first!';
-            } else if (error.code === 'API_ERROR' || error.code === 'HTTP_ERROR') {
-                errorMessage = error.message || 'API error';
-            } else if (error.code === 'PARSE_ERROR') {
-                errorMessage = 'Invalid response from server';
-            } else if (error.message) {
-                errorMessage = error.message;
-            } else {
-                errorMessage = JSON.stringify(error);
+            if (error.detail) {
+                console.error('[Import] Error detail:', error.code, error.detail);
             }
+            errorMessage = error.message || JSON.stringify(error);
         } else if (typeof error === 'string') {
             errorMessage = error;
         }
@@ -1607,10 +1687,9 @@ function isFlash(spell) {
 function applySummonerPreference(build) {
     if (!build || !build.summoners || build.summoners.length !== 2) return;
 
-    const savedFlashSlot = localStorage.getItem('focusapp_flash_slot');
-    if (savedFlashSlot === null) return; // No preference saved yet
+    if (!appSettings.flashKey) return; // No preference saved yet
 
-    const preferredSlot = parseInt(savedFlashSlot, 10); // 0 = D, 1 = F
+    const preferredSlot = appSettings.flashKey === 'D' ? 0 : 1;
     const currentFlashIndex = build.summoners.findIndex(s => isFlash(s));
 
     if (currentFlashIndex === -1) return; // No Flash in this build
@@ -1641,7 +1720,8 @@ async function swapSummoners() {
     // Save Flash slot preference
     const flashIndex = currentBuild.summoners.findIndex(s => isFlash(s));
     if (flashIndex !== -1) {
-        localStorage.setItem('focusapp_flash_slot', String(flashIndex));
+        updateSettings({ flashKey: flashIndex === 0 ? 'D' : 'F' })
+            .catch(error => console.warn('[Summoners] Failed to save preference:', error));
         console.log(`[Summoners] Saved preference: Flash on ${flashIndex === 0 ? 'D' : 'F'}`);
     }
 
@@ -1673,12 +1753,13 @@ async function swapSummoners() {
 
 /**
  * Build the import payload from the current build data.
- * Transforms the frontend build structure into the FocusApi request format.
+ * The backend turns the FocusApi build into the request format; the role and
+ * the summoner spell order come from the UI.
  *
  * @param {Object} build - The current build data
- * @returns {Object} The payload for FocusApi /lol/import-payload
+ * @returns {Promise<Object>} The payload for FocusApi /lol/import-payload
  */
-function buildImportPayload(build) {
+async function buildImportPayload(build) {
     // Get role from build, UI selector, or default to 'mid' (most common fallback)
     let role = build.role || document.getElementById('role-select')?.value || 'default';
 
@@ -1689,90 +1770,12 @@ function buildImportPayload(build) {
         console.log('[Import] No role detected, using fallback:', role);
     }
 
-    // Extract rune tree IDs from keystone (primary) and first secondary rune
-    // Keystone IDs: 8000 (Precision), 8100 (Domination), 8200 (Sorcery), 8300 (Inspiration), 8400 (Resolve)
-    const keystoneId = build.runes?.keystone || 0;
-    const primaryTreeId = Math.floor(keystoneId / 100) * 100; // e.g., 8010 -> 8000
-
-    // Get secondary tree from first secondary rune
-    const firstSecondaryRune = build.runes?.secondary?.[0];
-    const secondaryTreeId = firstSecondaryRune ? Math.floor(firstSecondaryRune.id / 100) * 100 : 8200;
-
-    // Extract primary rune IDs (keystone + 3 primary runes)
-    const primaryRuneIds = [
-        keystoneId,
-        ...(build.runes?.primary || []).map(r => r.id || r)
-    ].filter(id => id);
-
-    // Extract secondary rune IDs (2 runes)
-    const secondaryRuneIds = (build.runes?.secondary || [])
-        .map(r => r.id || r)
-        .filter(id => id);
-
-    // Extract stat shards (must be exactly 3: Offense, Flex, Defense)
-    // Fallback IDs: 5008 = Adaptive Force, 5002 = Armor
-    const defaultShards = [5008, 5008, 5002];
-    const extractedShards = (build.runes?.shards || build.stat_shards || [])
-        .map(s => s.id || s)
-        .filter(id => id);
-    // Ensure exactly 3 shards by padding with defaults if needed
-    const runeShards = [
-        extractedShards[0] || defaultShards[0],
-        extractedShards[1] || defaultShards[1],
-        extractedShards[2] || defaultShards[2]
-    ];
-
-    // Extract item IDs (V2 API - single build array in purchase order)
-    const itemsStarting = (build.items?.starting || [])
-        .map(i => parseInt(i.id || i, 10))
-        .filter(id => !isNaN(id));
-
-    // Build items: first 3 = core, rest = situational (for import payload)
-    const allBuildItems = (build.items?.build || [])
-        .map(i => parseInt(i.id || i, 10))
-        .filter(id => !isNaN(id));
-
-    const itemsCore = allBuildItems.slice(0, 3);
-    const itemsSituational = allBuildItems.slice(3);
-
-    const bootsId = build.items?.boots
-        ? parseInt(build.items.boots.id || build.items.boots, 10)
-        : null;
-
-    // Extract summoner spells
+    // Summoner spells as shown (Flash preference and manual swaps applied)
     const summonerSpells = (build.summoners || [])
         .map(s => s.id || s)
         .filter(id => id);
 
-    // Get champion ID from the champion list if available
-    const championData = allChampions.find(c =>
-        c.name?.toLowerCase() === build.champion?.toLowerCase() ||
-        c.id?.toLowerCase() === build.champion?.toLowerCase()
-    );
-    const championId = championData?.key ? parseInt(championData.key, 10) : 0;
-
-    return {
-        boots: bootsId,
-        champion_id: championId,
-        champion_key: build.champion,
-        items_core: itemsCore,
-        items_situational: itemsSituational,
-        items_starting: itemsStarting,
-        patch: build.patch || "current",
-        role: role,
-        rune_shards: runeShards,
-        runes_primary: {
-            rune_ids: primaryRuneIds,
-            tree_id: primaryTreeId
-        },
-        runes_secondary: {
-            rune_ids: secondaryRuneIds,
-            tree_id: secondaryTreeId
-        },
-        source: "FocusApp",
-        summoner_spells: summonerSpells,
-        title: `${build.champion} ${role.toUpperCase()}`
-    };
+    return buildImportRequest(build.apiData, build.champion, role, build.patch || null, summonerSpells);
 }
 
 /**
@@ -1840,9 +1843,10 @@ function toggleAutoImport(enabled) {
     console.log(`[AutoImport] toggleAutoImport called with: ${enabled}`);
     autoImportEnabled = enabled;
 
-    // Save preference to localStorage
-    localStorage.setItem('focusapp_autoimport', enabled ? 'true' : 'false');
-    console.log(`[AutoImport] Saved to localStorage: ${enabled}`);
+    // Save preference to the settings file
+    updateSettings({ autoImport: enabled })
+        .then(() => console.log(`[AutoImport] Saved to settings: ${enabled}`))
+        .catch(error => console.warn('[AutoImport] Failed to save preference:', error));
 
     // Update UI toggle state
     const toggle = document.getElementById('auto-import-toggle');
@@ -1942,19 +1946,13 @@ async function checkChampionSelectAndImport() {
 
         inChampionSelect = true;
 
-        // Find local player's pick
-        const myActions = session.actions?.flat()?.filter(
-            (a) => a.actorCellId === session.localPlayerCellId && a.type === 'pick'
-        ) || [];
-
-        const myPick = myActions.find((a) => a.championId && a.championId > 0 && a.completed);
-
-        if (!myPick) {
-            // No champion picked yet
+        // Local player's pick, resolved by the backend from the actions
+        if (!session.isLocked || !session.localChampionId) {
+            // No champion locked yet (only hovered)
             return;
         }
 
-        const championId = myPick.championId;
+        const championId = session.localChampionId;
 
         // Check if we already imported for this champion
         if (lastAutoImportedChampion === championId) {
@@ -1964,13 +1962,19 @@ async function checkChampionSelectAndImport() {
         console.log(`[AutoImport] Champion picked: ${championId}`);
 
         // Detect role from assigned position
-        let role = 'mid'; // Default fallback
+        let role = null;
         const myTeam = session.myTeam || [];
         const myCell = myTeam.find((p) => p.cellId === session.localPlayerCellId);
         if (myCell && myCell.assignedPosition) {
             role = normalizeRole(myCell.assignedPosition);
         }
 
+        // No assigned position (blind pick): use the champion's main role
+        if (!role) {
+            role = await window.__TAURI__.core.invoke('get_default_role', { championId })
+                .catch(() => 'mid');
+        }
+
         // Get champion name from ID
         const championName = await getChampionNameFromId(championId);
         if (!championName) {
@@ -2099,11 +2103,11 @@ async function getChampionNameFromId(championId) {
         if (champ) return champ.id; // Return the normalized ID (e.g., "LeeSin")
     }
 
-    // Fallback: fetch champion list
-    const { getChampionList } = await import('./api.js');
-    const champions = await getChampionList();
-    const champ = champions.find((c) => parseInt(c.key) === championId);
-    return champ ? champ.id : null;
+    // Fallback: backend champion data
+    const champ = await window.__TAURI__.core.invoke('get_champion_info', {
+        idOrName: String(championId)
+    }).catch(() => null);
+    return champ ? champ.ddragonKey : null;
 }
 
 /**
@@ -2135,33 +2139,231 @@ async function autoImportBuild(championName, role) {
         selectedChampionName = championName;
 
         // Build import payload
-        const payload = buildImportPayload(build);
+        const payload = await buildImportPayload(build);
 
-        // Import to client
-        const result = await window.__TAURI__.core.invoke('import_build_to_client', {
-            payload: payload
-        });
+        // Import to client (the backend applies the auto-import rules of the settings)
+        const { invoke } = window.__TAURI__.core;
+        let outcome = await invoke('auto_import_build', { payload, confirmed: false });
+        if (outcome.decision.status === 'needsConfirmation') {
+            if (!confirm(`Ranked game: import the ${championName} build automatically?`)) {
+                return;
+            }
+            outcome = await invoke('auto_import_build', { payload, confirmed: true });
+        }
+        if (outcome.decision.status !== 'allowed') {
+            console.log(`[AutoImport] Skipped for ${championName}: ${outcome.decision.status}`);
+            return;
+        }
+        const result = outcome.result;
 
         if (result.success) {
             const roleDisplay = role.charAt(0).toUpperCase() + role.slice(1);
             showToast(`✓ ${championName} ${roleDisplay} build imported!`, 'success');
             console.log(`[AutoImport] Success: ${championName} ${role}`);
+        } else if (result.queued) {
+            showToast(result.message, 'info');
         } else {
             showToast(`Import failed: ${result.message}`, 'error');
         }
 
     } catch (error) {
+        if (error?.code === 'CANCELLED') {
+            console.log(`[AutoImport] Import of ${championName} cancelled by a newer one`);
+            return;
+        }
         console.error('[AutoImport] Error:', error);
         showToast(`Auto-import failed: ${error.message}`, 'error');
     }
 }
 
+/**
+ * Import the builds of every viable role of the selected champion.
+ * Covers fill players before queueing: one "Focus: <Champion> <ROLE>" item set
+ * per role, and optionally one rune page per role (see bulk_import.rs).
+ *
+ * @param {boolean} [includeRunePages=false] - Also create one rune page per role
+ * @returns {Promise<void>}
+ */
+async function importAllRolesToClient(includeRunePages = false) {
+    const championName = currentBuild?.champion || selectedChampionName;
+    if (!championName) {
+        showToast('No build selected. Please select a champion first.', 'error');
+        return;
+    }
+
+    try {
+        // The backend looks up the roles and fetches each role's build
+        const result = await window.__TAURI__.core.invoke('import_all_roles', {
+            champion: championName,
+            includeRunePages
+        });
+        console.log('[BulkImport] Result:', result);
+        showToast(result.message, result.success ? 'success' : 'error');
+    } catch (error) {
+        console.error('[BulkImport] Error:', error.detail || error);
+        showToast(error.message || 'Failed to import builds', 'error');
+    }
+}
+
+/**
+ * Prefetch the build of the champion hovered or locked in champion select.
+ * The backend fetches the FocusApi payload in the background (see prefetch.rs)
+ * so a later Import click is near-instant. Nothing is imported here.
+ */
+function initBuildPrefetch() {
+    if (!window.__TAURI__?.event) return;
+
+    let prefetchedChampionId = null;
+    window.__TAURI__.event.listen('champ-select-pick-changed', async (event) => {
+        const { championId, championKey, role, locked } = event.payload;
+        // Hover then lock of the same champion: already prefetched
+        if (locked && championId === prefetchedChampionId) return;
+        prefetchedChampionId = championId;
+        try {
+            const championName = championKey || await getChampionNameFromId(championId);
+            if (!championName) return;
+
+            const build = await getChampionBuild(championName, role);
+            if (!build || !build.success) return;
+
+            // Same request as the import itself, so the cached payload matches
+            applySummonerPreference(build);
+            const payload = await buildImportPayload(build);
+            payload.champion_id = payload.champion_id || championId;
+
+            const started = await window.__TAURI__.core.invoke('prefetch_import_payload', { payload });
+            console.log(`[Prefetch] ${championName} ${role}: ${started ? 'started' : 'skipped (pick changed)'}`);
+        } catch (error) {
+            console.debug('[Prefetch] Failed:', error);
+        }
+    });
+}
+
+/**
+ * Pre-select the role the local player was assigned when champion select starts.
+ * Blind pick (no assigned position) leaves the selector as it is.
+ */
+function initAssignedPosition() {
+    if (!window.__TAURI__?.event) return;
+
+    window.__TAURI__.event.listen('assigned-position', (event) => {
+        const { role } = event.payload;
+        const select = document.getElementById('role-select');
+        if (!role || !select) return;
+        if ([...select.options].some((option) => option.value === role)) {
+            select.value = role;
+            console.log(`[ChampSelect] Assigned role: ${role}`);
+        }
+    });
+}
+
+/**
+ * Follow ARAM rerolls, bench swaps and trades (see aram.rs).
+ * With auto-import on, the new champion's build is imported right away;
+ * otherwise the user is offered to open it.
+ */
+function initChampionChanges() {
+    if (!window.__TAURI__?.event) return;
+
+    window.__TAURI__.event.listen('champion-changed', async (event) => {
+        const { championId, championKey, role, reason } = event.payload;
+        try {
+            const championName = championKey || await getChampionNameFromId(championId);
+            if (!championName) return;
+            console.log(`[ChampSelect] Champion changed (${reason}): ${championName}`);
+
+            if (autoImportEnabled) {
+                // Already imported by the auto-import poll
+                if (lastAutoImportedChampion === championId) return;
+                lastAutoImportedChampion = championId;
+                await autoImportBuild(championName, role);
+                return;
+            }
+            if (lastOfferedChampion === championId) return;
+            lastOfferedChampion = championId;
+            if (confirm(`Your champion is now ${capitalizeChampionName(championName)}.\nOpen its ${role.toUpperCase()} build?`)) {
+                await navigateToBuildForChampion(championName, role);
+            }
+        } catch (error) {
+            console.error('[ChampSelect] Re-import failed:', error);
+        }
+    });
+}
+
+/**
+ * Offer to replace the imported build when the champion changed afterwards
+ * (trade, reroll; see reimport.rs). Auto-import already re-imports every newly
+ * locked champion, so nothing is asked while it is on.
+ */
+function initReimportPrompt() {
+    if (!window.__TAURI__?.event) return;
+
+    window.__TAURI__.event.listen('reimport-recommended', async (event) => {
+        const { previousChampionKey, championId, championKey, role } = event.payload;
+        if (autoImportEnabled || lastOfferedChampion === championId) return;
+        lastOfferedChampion = championId;
+        try {
+            const championName = championKey || await getChampionNameFromId(championId);
+            if (!championName) return;
+            const previous = capitalizeChampionName(previousChampionKey);
+            const current = capitalizeChampionName(championName);
+            if (!confirm(`The ${previous} build is in the client, but you now play ${current}.\nImport the ${current} ${role.toUpperCase()} build instead?`)) {
+                return;
+            }
+            await navigateToBuildForChampion(championName, role);
+            await importBuildToClient();
+        } catch (error) {
+            console.error('[ChampSelect] Re-import failed:', error);
+        }
+    });
+    // Leaving champion select: the next one can offer the same champions again
+    window.__TAURI__.event.listen('game-state-changed', (event) => {
+        if (event.payload?.champSelect === undefined) lastOfferedChampion = null;
+    });
+}
+
+/**
+ * Open the builds requested by focusapp:// links (FocusApi website).
+ * The link is only taken once, whether it arrived before or after the listener.
+ */
+function initDeepLinks() {
+    if (!window.__TAURI__?.event) return;
+
+    const openPendingLink = async () => {
+        const link = await window.__TAURI__.core.invoke('take_pending_deep_link');
+        if (!link) return;
+
+        const role = link.role || getDefaultRolesForChampion(link.champion)[0];
+        const label = `${capitalizeChampionName(link.champion)} ${role.toUpperCase()}`;
+        if (!confirm(`Open the ${label} build from the FocusApi website?\nYou can review it before importing it.`)) {
+            return;
+        }
+        await navigateToBuildForChampion(link.champion, role);
+    };
+
+    window.__TAURI__.event.listen('deep-link-import', () => {
+        openPendingLink().catch(error => console.error('[DeepLink] Failed:', error));
+    });
+    openPendingLink().catch(error => console.error('[DeepLink] Failed:', error));
+}
+
+/**
+ * Tell the user when a newer FocusApp release is published (checked once at startup).
+ */
+function initUpdateBanner() {
+    if (!window.__TAURI__?.event) return;
+
+    window.__TAURI__.event.listen('update-available', (event) => {
+        const { currentVersion, latestVersion } = event.payload;
+        showToast(`FocusApp ${latestVersion} is available (you have ${currentVersion})`, 'info');
+    });
+}
+
 /**
  * Initialize auto-import from saved preference.
  */
 function initAutoImport() {
-    const saved = localStorage.getItem('focusapp_autoimport');
-    const enabled = saved === 'true';
+    const enabled = appSettings.autoImport === true;
 
     // Set initial state without triggering listener yet
     autoImportEnabled = enabled;
@@ -2933,35 +3135,12 @@ async function searchPlayer(gameName, tagLine, forceRefresh = false) {
     try {
         console.log(`[PlayerSearch] Searching for ${gameName}#${tagLine}${forceRefresh ? ' (force refresh)' : ''}`);
 
-        // Call the backend API using Tauri HTTP plugin
-        let API_KEY = "dev_key_fallback";
-        try {
-            if (window.__TAURI__?.core?.invoke) {
-                API_KEY = await window.__TAURI__.core.invoke("get_env_api_key");
-            }
-        } catch (e) {
-            console.warn('[PlayerSearch] Could not get API key:', e);
-        }
-
-        // Build URL with optional refresh parameter
-        const baseUrl = `https://api.hommet.ch/api/v1/player/${encodeURIComponent(gameName)}/${encodeURIComponent(tagLine)}`;
-        const url = forceRefresh ? `${baseUrl}?refresh=true` : baseUrl;
-
-        const response = await window.__TAURI__.http.fetch(url, {
-            method: "GET",
-            headers: {
-              Accept: "application/json",
-              "X-API-Key": API_KEY,
-            },
-          },
-        );
-
-
-        if (!response.ok) {
-            throw new Error(`Player not found (${response.status})`);
-        }
-
-        const data = await response.json();
+        // The backend calls FocusApi (the API key stays in Rust)
+        const data = await window.__TAURI__.core.invoke('fetch_player_profile', {
+            gameName,
+            tagLine,
+            refresh: forceRefresh,
+        });
         console.log('[PlayerSearch] Player data:', data);
 
         // Render the player profile page
@@ -3034,34 +3213,15 @@ async function refreshPlayerProfile() {
 
         showToast('Fetching fresh data from Riot...', 'info');
 
-        // Call the backend API with refresh=true
-        let API_KEY = "dev_key_fallback";
-        try {
-            if (window.__TAURI__?.core?.invoke) {
-                API_KEY = await window.__TAURI__.core.invoke("get_env_api_key");
-            }
-        } catch (e) {
-            console.warn('[PlayerRefresh] Could not get API key:', e);
-        }
-
         const { gameName, tagLine } = lastPlayerSearch;
-        const url = `https://api.hommet.ch/api/v1/player/${encodeURIComponent(gameName)}/${encodeURIComponent(tagLine)}?refresh=true`;
 
         console.log(`[PlayerRefresh] Force refreshing ${gameName}#${tagLine}`);
 
-        const response = await window.__TAURI__.http.fetch(url, {
-            method: "GET",
-            headers: {
-                Accept: "application/json",
-                "X-API-Key": API_KEY,
-            },
+        const data = await window.__TAURI__.core.invoke('fetch_player_profile', {
+            gameName,
+            tagLine,
+            refresh: true,
         });
-
-        if (!response.ok) {
-            throw new Error(`Failed to refresh (${response.status})`);
-        }
-
-        const data = await response.json();
         console.log('[PlayerRefresh] Fresh data received:', data);
 
         // Update the profile with new data
@@ -3070,7 +3230,7 @@ async function refreshPlayerProfile() {
 
     } catch (error) {
         console.error('[PlayerRefresh] Error:', error);
-        showToast(`Failed to refresh: ${error.message}`, 'error');
+        showToast(`Failed to refresh: ${error.message || error}`, 'error');
     } finally {
         // Restore button state
         if (refreshBtn) {
@@ -3743,6 +3903,7 @@ window.applyFilters = applyFilters;
 window.filterItems = filterItems;
 window.retryBackendConnection = retryBackendConnection;
 window.importBuildToClient = importBuildToClient;
+window.importAllRolesToClient = importAllRolesToClient;
 window.swapSummoners = swapSummoners;
 window.updateImportButtonState = updateImportButtonState;
 window.toggleAutoImport = toggleAutoImport;
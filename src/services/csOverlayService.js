@@ -43,21 +43,24 @@ const statsListeners = [];
  * Initialise le service d'overlay
  */
 async function initCsOverlay() {
-    // Ecoute les mises a jour CS depuis le Rust
-    await getListen()('cs-overlay-update', (event) => {
-        if (event.payload && event.payload.gameData) {
-            updateStats(event.payload.gameData);
+    // Les mises a jour CS (`cs-overlay-update`) ne sont envoyees qu'a la
+    // fenetre d'overlay ; ici les stats viennent du GameWatcher (csUpdated)
+
+    // Affichage automatique : le backend decide selon la file de la partie
+    // (overlay desactive en ARAM, Arena, customs... selon les reglages)
+    await getListen()('cs-overlay-mode', async (event) => {
+        const mode = event.payload || {};
+        if (mode.autoShow && config.autoShow) {
+            console.log('[CS Overlay] Game started, showing overlay');
+            await showOverlay();
+        } else if (!mode.enabled) {
+            console.log('[CS Overlay] Overlay disabled for this queue');
+            await hideOverlay();
         }
     });
 
     // S'abonne aux evenements du GameWatcher
     if (window.GameWatcherService) {
-        window.GameWatcherService.on('gameStarted', async (state) => {
-            console.log('[CS Overlay] Game started, showing overlay');
-            if (config.autoShow) {
-                await showOverlay();
-            }
-        });
 
         window.GameWatcherService.on('gameEnded', async () => {
             console.log('[CS Overlay] Game ended, hiding overlay');
@@ -297,13 +297,16 @@ async function checkChampionSelect() {
 
         // Find local player's champion from actions
         const championId = findLocalPlayerChampion(session, localCellId);
-        const role = findLocalPlayerRole(session, localCellId);
 
         // Only proceed if champion is actually selected (ID > 0)
         if (!championId || championId <= 0) {
             return;
         }
 
+        // No assigned position (blind pick): use the champion's main role
+        const role = findLocalPlayerRole(session, localCellId)
+            ?? await invoke('get_default_role', { championId }).catch(() => 'mid');
+
         // Detect if champion changed
         if (championId !== state.lastDetectedChampionId) {
             state.lastDetectedChampionId = championId;
@@ -371,7 +374,7 @@ function findLocalPlayerChampion(session, cellId) {
  *
  * @param {Object} session - Champion select session
  * @param {number} cellId - Local player's cell ID
- * @returns {string} Normalized role ('top', 'jungle', 'mid', 'adc', 'support')
+ * @returns {string|null} Normalized role ('top', 'jungle', 'mid', 'adc', 'support'), null if none is assigned
  */
 function findLocalPlayerRole(session, cellId) {
     if (session.myTeam && Array.isArray(session.myTeam)) {
@@ -381,8 +384,8 @@ function findLocalPlayerRole(session, cellId) {
         }
     }
 
-    // Default to mid if role not detected (e.g., blind pick)
-    return 'mid';
+    // No assigned position (e.g., blind pick)
+    return null;
 }
 
 /**
@@ -536,7 +539,8 @@ async function importBuildDirect(championName, role) {
 
     const result = await invoke('import_build_to_client', { payload });
 
-    if (!result.success) {
+    // A queued import (client or FocusApi offline) is applied later by the backend
+    if (!result.success && !result.queued) {
         throw new Error(result.message || 'Import failed');
     }
 
@@ -20,9 +20,23 @@ const getTauriFetch = () => {
 
 const DDRAGON_BASE_URL = "https://ddragon.leagueoflegends.com";
 
+// CommunityDragon mirrors the client files and is up to date on patch day
+const CDRAGON_GAME_DATA_URL = "https://raw.communitydragon.org/latest/plugins/rcp-be-lol-game-data/global/default";
+
 // Cache for DDragon version
 let cachedVersion = null;
 
+/**
+ * Get the URL of an icon served from the local cache (focus-asset://),
+ * or `fallbackUrl` outside of Tauri.
+ */
+const getAssetUrl = (kind, file, fallbackUrl) => {
+    if (window.__TAURI__ && window.__TAURI__.core && window.__TAURI__.core.convertFileSrc) {
+        return window.__TAURI__.core.convertFileSrc(`${kind}/${file}`, 'focus-asset');
+    }
+    return fallbackUrl;
+};
+
 /**
  * GOLD VALUES - Copied from old Python app (Patch 16.1)
  * These are the gold values per stat point used to calculate efficiency
@@ -202,7 +216,7 @@ async function fetchAllItems() {
                 raw_stats: itemData.stats || {},
                 goldEfficiency: goldEfficiency,
                 efficiency: goldEfficiency,
-                image: `${DDRAGON_BASE_URL}/cdn/${version}/img/item/${itemData.image.full}`,
+                image: getAssetUrl('item', itemData.image.full, `${DDRAGON_BASE_URL}/cdn/${version}/img/item/${itemData.image.full}`),
                 tags: itemData.tags || [],
                 category: category,
                 stat_type: statTypes[0] || null,
@@ -217,7 +231,106 @@ async function fetchAllItems() {
 
     } catch (error) {
         console.error('[Items] Fetch error:', error);
-        return { success: false, error: error.message, items: [], version: version };
+        return fetchItemsFromCommunityDragon(version, error);
+    }
+}
+
+/**
+ * Fallback when DDragon is unavailable or not yet published for the patch.
+ * CommunityDragon has no stat values, so gold efficiency is not computed.
+ * @param {string} version - DDragon version reported to the caller
+ * @param {Error} ddragonError - Error that triggered the fallback
+ * @returns {Promise<Object>} Result object with success, items array, and version
+ */
+async function fetchItemsFromCommunityDragon(version, ddragonError) {
+    try {
+        const tauriFetch = getTauriFetch();
+        const response = await tauriFetch(`${CDRAGON_GAME_DATA_URL}/v1/items.json`, {
+            method: 'GET',
+            timeout: { secs: 15, nanos: 0 }
+        });
+
+        if (!response.ok) {
+            throw new Error(`HTTP error! status: ${response.status}`);
+        }
+
+        const data = await response.json();
+        const items = data
+            .filter(item => item.inStore !== false && item.priceTotal > 0)
+            .map(item => {
+                let category = 'basic';
+                if (item.priceTotal >= 2500) {
+                    category = 'legendary';
+                } else if (item.priceTotal >= 1000) {
+                    category = 'epic';
+                }
+
+                // "/lol-game-data/assets/ASSETS/Items/Icons2D/x.png" -> ".../assets/items/icons2d/x.png"
+                const iconPath = (item.iconPath || '').replace('/lol-game-data/assets/', '').toLowerCase();
+
+                return {
+                    id: item.id,
+                    name: item.name,
+                    description: item.description || "",
+                    price: item.priceTotal,
+                    gold: item.priceTotal,
+                    stats: {},
+                    raw_stats: {},
+                    goldEfficiency: null,
+                    efficiency: null,
+                    image: `${CDRAGON_GAME_DATA_URL}/${iconPath}`,
+                    tags: item.categories || [],
+                    category: category,
+                    stat_type: null,
+                    stat_types: [],
+                    into: (item.to || []).map(String),
+                    from: (item.from || []).map(String)
+                };
+            });
+
+        console.log(`[Items] Loaded ${items.length} items from CommunityDragon (DDragon fallback)`);
+        return { success: true, items: items, version: version, source: 'communitydragon' };
+
+    } catch (error) {
+        console.error('[Items] CommunityDragon fallback failed:', error);
+        return fetchBundledItems(version, ddragonError);
+    }
+}
+
+/**
+ * Last resort when offline: item names from the snapshot embedded in the app.
+ * @param {string} version - DDragon version reported to the caller
+ * @param {Error} ddragonError - Error that triggered the fallback
+ * @returns {Promise<Object>} Result object with success, items array, and version
+ */
+async function fetchBundledItems(version, ddragonError) {
+    try {
+        const snapshot = await window.__TAURI__.core.invoke('get_bundled_static_data');
+        const items = snapshot.items.map(item => ({
+            id: item.id,
+            name: item.name,
+            description: "",
+            price: 0,
+            gold: 0,
+            stats: {},
+            raw_stats: {},
+            goldEfficiency: null,
+            efficiency: null,
+            image: getItemImageUrl(item.id, snapshot.version),
+            tags: [],
+            category: 'basic',
+            stat_type: null,
+            stat_types: [],
+            into: [],
+            from: []
+        }));
+
+        console.log(`[Items] Loaded ${items.length} items from the bundled snapshot`);
+        return { success: true, items: items, version: snapshot.version, source: 'bundled' };
+
+    } catch (error) {
+        console.error('[Items] Bundled snapshot unavailable:', error);
+        return { success: false, error: ddragonError.message, items: [], version: version };
     }
 }
 
@@ -242,7 +355,7 @@ async function getItemById(itemId) {
  */
 function getItemImageUrl(itemId, version = null) {
     const v = version || cachedVersion || "14.10.1";
-    return `${DDRAGON_BASE_URL}/cdn/${v}/img/item/${itemId}.png`;
+    return getAssetUrl('item', `${itemId}.png`, `${DDRAGON_BASE_URL}/cdn/${v}/img/item/${itemId}.png`);
 }
 
 /**